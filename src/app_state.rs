@@ -2,7 +2,10 @@
 
 use std::sync::Arc;
 
-use crate::{dynamic_flutter_windows_dll_loader::FlutterDll, bindings::windows::FlutterDesktopViewControllerRef};
+use crate::{
+    WindowSizeConstraints, bindings::windows::FlutterDesktopViewControllerRef,
+    dynamic_flutter_windows_dll_loader::FlutterDll,
+};
 use windows::Win32::Foundation::HWND;
 
 /// Holds the long‐lived handles needed to manage the Flutter view.
@@ -13,4 +16,8 @@ pub struct AppState {
     /// The HWND of the child window where Flutter renders its content.
     pub child_hwnd: HWND,
     pub dll: Arc<FlutterDll>,
+    /// Whether theme and accessibility broadcasts are passed to `child_hwnd`.
+    pub forward_system_settings: bool,
+    /// Size limits applied on `WM_GETMINMAXINFO`.
+    pub size_constraints: WindowSizeConstraints,
 }
@@ -5,6 +5,29 @@ pub const FlutterEngineResult_kInvalidLibraryVersion: FlutterEngineResult = 1;
 pub const FlutterEngineResult_kInvalidArguments: FlutterEngineResult = 2;
 pub const FlutterEngineResult_kInternalInconsistency: FlutterEngineResult = 3;
 pub type FlutterEngineResult = ::std::os::raw::c_int;
+#[doc = " Indicate there is a running accessibility service which is changing the\n interaction model of the device."]
+pub const FlutterAccessibilityFeature_kFlutterAccessibilityFeatureAccessibleNavigation: FlutterAccessibilityFeature =
+    1;
+#[doc = " Indicate the platform is inverting the colors of the application."]
+pub const FlutterAccessibilityFeature_kFlutterAccessibilityFeatureInvertColors: FlutterAccessibilityFeature =
+    2;
+#[doc = " Request that animations be disabled or simplified."]
+pub const FlutterAccessibilityFeature_kFlutterAccessibilityFeatureDisableAnimations: FlutterAccessibilityFeature =
+    4;
+#[doc = " Request that text be rendered at a bold font weight."]
+pub const FlutterAccessibilityFeature_kFlutterAccessibilityFeatureBoldText: FlutterAccessibilityFeature =
+    8;
+#[doc = " Request that certain animations be simplified and parallax effects\n removed."]
+pub const FlutterAccessibilityFeature_kFlutterAccessibilityFeatureReduceMotion: FlutterAccessibilityFeature =
+    16;
+#[doc = " Request that UI be rendered with darker colors."]
+pub const FlutterAccessibilityFeature_kFlutterAccessibilityFeatureHighContrast: FlutterAccessibilityFeature =
+    32;
+#[doc = " Request to show on/off labels inside switches."]
+pub const FlutterAccessibilityFeature_kFlutterAccessibilityFeatureOnOffSwitchLabels: FlutterAccessibilityFeature =
+    64;
+#[doc = " Additional accessibility features that may be enabled by the platform.\n Must match the `AccessibilityFeatures` enum in window.dart."]
+pub type FlutterAccessibilityFeature = ::std::os::raw::c_int;
 pub const FlutterRendererType_kOpenGL: FlutterRendererType = 0;
 pub const FlutterRendererType_kSoftware: FlutterRendererType = 1;
 #[doc = " Metal is only supported on Darwin platforms (macOS / iOS).\n iOS version >= 10.0 (device), 13.0 (simulator)\n macOS version >= 10.14"]
@@ -3102,6 +3125,13 @@ unsafe extern "C" {
         enabled: bool,
     ) -> FlutterEngineResult;
 }
+unsafe extern "C" {
+    #[doc = " @brief      Sets additional accessibility features.\n\n @param[in]  engine     A running engine instance\n @param[in]  features   The accessibility features to set.\n\n @return     The result of the call.\n"]
+    pub fn FlutterEngineUpdateAccessibilityFeatures(
+        engine: FlutterEngine,
+        features: FlutterAccessibilityFeature,
+    ) -> FlutterEngineResult;
+}
 unsafe extern "C" {
     #[doc = " @brief      Notify the engine that a vsync event occurred. A baton passed to\n             the platform via the vsync callback must be returned. This call\n             must be made on the thread on which the call to\n             `FlutterEngineRun` was made.\n\n @see        FlutterEngineGetCurrentTime()\n\n @attention  That frame timepoints are in nanoseconds.\n\n @attention  The system monotonic clock is used as the timebase.\n\n @param[in]  engine.                  A running engine instance.\n @param[in]  baton                    The baton supplied by the engine.\n @param[in]  frame_start_time_nanos   The point at which the vsync event\n                                      occurred or will occur. If the time\n                                      point is in the future, the engine will\n                                      wait till that point to begin its frame\n                                      workload.\n @param[in]  frame_target_time_nanos  The point at which the embedder\n                                      anticipates the next vsync to occur.\n                                      This is a hint the engine uses to\n                                      schedule Dart VM garbage collection in\n                                      periods in which the various threads\n                                      are most likely to be idle. For\n                                      example, for a 60Hz display, embedders\n                                      should add 16.6 * 1e6 to the frame time\n                                      field.\n\n @return     The result of the call.\n"]
     pub fn FlutterEngineOnVsync(
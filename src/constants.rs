@@ -9,6 +9,10 @@ pub const DEFAULT_WINDOW_WIDTH: i32 = 1280;
 /// Default height (in pixels) for the main application window.
 pub const DEFAULT_WINDOW_HEIGHT: i32 = 720;
 
+/// Default minimum window size (in pixels). Smaller windows leave no usable UI.
+pub const DEFAULT_MIN_WINDOW_WIDTH: i32 = 200;
+pub const DEFAULT_MIN_WINDOW_HEIGHT: i32 = 150;
+
 /// Title text for the main application window.
 pub const WINDOW_TITLE: PCWSTR = w!("Flutter Rust App");
 
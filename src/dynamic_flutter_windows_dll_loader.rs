@@ -2,7 +2,7 @@ use crate::bindings::windows as b;
 use anyhow::{Context, Result};
 use libloading::{Library, Symbol};
 use once_cell::sync::Lazy;
-use std::{collections::HashMap, path::{Path, PathBuf}, sync::{Arc, Mutex}};
+use std::{collections::HashMap, ffi::c_void, path::{Path, PathBuf}, sync::{Arc, Mutex}};
 use anyhow::anyhow;
 
 #[derive(Debug)]
@@ -64,6 +64,17 @@ pub struct FlutterDll {
         Symbol<'static, unsafe extern "C" fn(b::FlutterDesktopViewControllerRef)>,
     pub FlutterDesktopViewGetHWND:
         Symbol<'static, unsafe extern "C" fn(b::FlutterDesktopViewRef) -> b::HWND>,
+    /// Optional: not exported by older engines.
+    pub FlutterDesktopEngineSetNextFrameCallback: Option<
+        Symbol<
+            'static,
+            unsafe extern "C" fn(
+                b::FlutterDesktopEngineRef,
+                Option<unsafe extern "C" fn(*mut c_void)>,
+                *mut c_void,
+            ),
+        >,
+    >,
 }
 
 static DLL_CACHE: Lazy<Mutex<HashMap<PathBuf, Arc<FlutterDll>>>> =
@@ -118,6 +129,9 @@ impl FlutterDll {
             let FlutterDesktopViewGetHWND = lib_static
                 .get(b"FlutterDesktopViewGetHWND\0")
                 .context("Missing symbol: FlutterDesktopViewGetHWND")?;
+            let FlutterDesktopEngineSetNextFrameCallback = lib_static
+                .get(b"FlutterDesktopEngineSetNextFrameCallback\0")
+                .ok();
 
             Ok(FlutterDll {
                 _lib: lib_static,
@@ -131,6 +145,7 @@ impl FlutterDll {
                 FlutterDesktopViewControllerHandleTopLevelWindowProc,
                 FlutterDesktopViewControllerDestroy,
                 FlutterDesktopViewGetHWND,
+                FlutterDesktopEngineSetNextFrameCallback,
             })
         }
     }
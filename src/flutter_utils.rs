@@ -1,6 +1,6 @@
 use crate::{
     bindings::windows, constants, dynamic_flutter_windows_dll_loader::FlutterDll, path_utils,
-    win32_utils,
+    software_renderer::api::FlutterEmbedderError, win32_utils,
 };
 use ::windows::Win32::{Foundation::HWND, System::Com::CoUninitialize};
 use log::{error, info};
@@ -60,13 +60,14 @@ pub fn create_flutter_engine(dll: &Arc<FlutterDll>) -> FlutterDesktopEngineRef {
     engine
 }
 
-/// Like `create_flutter_engine`, but with explicit asset/ICU/AOT paths.
+/// Like `create_flutter_engine`, but with explicit asset/ICU/AOT paths. Returns
+/// an error instead of aborting; COM is left to the caller.
 pub fn create_flutter_engine_with_paths(
     assets_path: Vec<u16>,
     icu_data_path: Vec<u16>,
     aot_library_path: Vec<u16>,
     dll: &Arc<FlutterDll>,
-) -> FlutterDesktopEngineRef {
+) -> Result<FlutterDesktopEngineRef, FlutterEmbedderError> {
     // Prepare the same args as before...
     let args_ptrs: Vec<*const i8> = constants::DART_ENTRYPOINT_ARGS
         .iter()
@@ -95,64 +96,62 @@ pub fn create_flutter_engine_with_paths(
 
     let engine = unsafe { (dll.FlutterDesktopEngineCreate)(props) };
     if engine.is_null() {
-        // cleanup & abort
-        unsafe { CoUninitialize() };
-        win32_utils::panic_with_error("FlutterDesktopEngineCreate failed");
+        error!("[Flutter Utils] Engine creation failed");
+        return Err(FlutterEmbedderError::InitializationFailed(
+            "FlutterDesktopEngineCreate failed".to_string(),
+        ));
     }
-    engine
+    Ok(engine)
 }
 
 /// Creates a Flutter view controller of the given size for the specified engine.
-/// Panics (after cleanup) on failure.
+/// On failure the engine is destroyed and an error returned.
 pub fn create_flutter_view_controller(
     engine: FlutterDesktopEngineRef,
     width: i32,
     height: i32,
     dll: &Arc<FlutterDll>,
-) -> FlutterDesktopViewControllerRef {
+) -> Result<FlutterDesktopViewControllerRef, FlutterEmbedderError> {
     info!("[Flutter Utils] Creating view controller");
     let controller = unsafe { (dll.FlutterDesktopViewControllerCreate)(width, height, engine) };
     if controller.is_null() {
         error!("[Flutter Utils] View controller creation failed");
-        unsafe {
-            (dll.FlutterDesktopEngineDestroy)(engine);
-            CoUninitialize();
-        }
-        win32_utils::panic_with_error("FlutterDesktopViewControllerCreate failed");
+        unsafe { (dll.FlutterDesktopEngineDestroy)(engine) };
+        return Err(FlutterEmbedderError::InitializationFailed(
+            "FlutterDesktopViewControllerCreate failed".to_string(),
+        ));
     }
     info!("[Flutter Utils] View controller created");
-    controller
+    Ok(controller)
 }
 
 /// Retrieves the Flutter view and underlying HWND from a view controller.
-/// On failure, cleans up and aborts.
+/// On failure the controller (and with it the engine) is destroyed and an
+/// error returned.
 pub fn get_flutter_view_and_hwnd(
     controller: FlutterDesktopViewControllerRef,
     dll: &Arc<FlutterDll>,
-) -> (FlutterDesktopViewRef, HWND) {
+) -> Result<(FlutterDesktopViewRef, HWND), FlutterEmbedderError> {
+    let fail = |what: &str| {
+        unsafe { (dll.FlutterDesktopViewControllerDestroy)(controller) };
+        Err(FlutterEmbedderError::InitializationFailed(format!("{what} failed")))
+    };
+
     info!("[Flutter Utils] Obtaining Flutter view");
     let view = unsafe { (dll.FlutterDesktopViewControllerGetView)(controller) };
     if view.is_null() {
         error!("[Flutter Utils] Failed to get view");
-        unsafe {
-            (dll.FlutterDesktopViewControllerDestroy)(controller);
-            CoUninitialize();
-        }
-        win32_utils::panic_with_error("FlutterDesktopViewControllerGetView failed");
+        return fail("FlutterDesktopViewControllerGetView");
     }
 
     info!("[Flutter Utils] Obtaining HWND from view");
     let raw_handle_as_isize = unsafe { (dll.FlutterDesktopViewGetHWND)(view) };
     if raw_handle_as_isize.is_null() {
         error!("[Flutter Utils] View returned null HWND");
-        unsafe {
-            (dll.FlutterDesktopViewControllerDestroy)(controller);
-            CoUninitialize();
-        }
-        win32_utils::panic_with_error("FlutterDesktopViewGetHWND failed");
+        return fail("FlutterDesktopViewGetHWND");
     }
 
     let hwnd: HWND = HWND(raw_handle_as_isize as *mut c_void);
     info!("[Flutter Utils] Flutter child HWND = {hwnd:?}");
-    (view, hwnd)
+    Ok((view, hwnd))
 }
@@ -10,7 +10,10 @@
 //!
 //! Spin up a top-level Flutter window in its own HWND. This is the one-call path
 //! and needs no graphics knowledge. See [`init_flutter_window`] and
-//! [`init_flutter_window_from_dir`].
+//! [`init_flutter_window_from_dir`], or
+//! [`init_flutter_window_with_plugin_loading`] to register plugins after the
+//! window is shown. [`init_flutter_window_with_options`] exposes every knob.
+//! [`try_init_flutter_window_from_dir`] returns startup errors instead of exiting.
 //!
 //! ```no_run
 //! use flutter_rust_windows_embedder::init_flutter_window;
@@ -41,10 +44,11 @@
 #![allow(static_mut_refs)]
 #![cfg_attr(target_os = "windows", windows_subsystem = "windows")]
 
+use ::windows::Win32::Foundation::HWND;
 use ::windows::Win32::System::Com::{COINIT_APARTMENTTHREADED, CoInitializeEx, CoUninitialize};
-use ::windows::Win32::UI::WindowsAndMessaging::{SW_SHOWNORMAL, SetForegroundWindow, ShowWindow};
 use env_logger::{Builder, Env};
 use log::{LevelFilter, error, info};
+use software_renderer::api::FlutterEmbedderError;
 use std::path::PathBuf;
 use std::sync::Once;
 
@@ -59,6 +63,81 @@ pub mod software_renderer;
 mod win32_utils;
 static LOGGER_INIT: Once = Once::new();
 
+/// When the standalone window registers plugin DLLs with the engine.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PluginLoading {
+    /// Discover, load and register every plugin before the window is created.
+    #[default]
+    Synchronous,
+    /// Show the window first. Plugin DLLs are discovered and loaded on a worker
+    /// thread, then registered on the platform thread from the message loop.
+    /// Dart code that calls a plugin channel before registration finishes gets
+    /// a `MissingPluginException`.
+    Deferred,
+}
+
+/// Minimum and optional maximum outer size of the standalone window, in
+/// physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowSizeConstraints {
+    pub min: (i32, i32),
+    /// `None` leaves the maximum to Windows.
+    pub max: Option<(i32, i32)>,
+}
+
+impl Default for WindowSizeConstraints {
+    fn default() -> Self {
+        Self {
+            min: (
+                constants::DEFAULT_MIN_WINDOW_WIDTH,
+                constants::DEFAULT_MIN_WINDOW_HEIGHT,
+            ),
+            max: None,
+        }
+    }
+}
+
+impl WindowSizeConstraints {
+    /// Whether the minimum is at least 1x1 and the maximum, if set, is not
+    /// smaller than the minimum.
+    pub fn is_valid(&self) -> bool {
+        let (min_w, min_h) = self.min;
+        min_w > 0
+            && min_h > 0
+            && self
+                .max
+                .is_none_or(|(max_w, max_h)| max_w >= min_w && max_h >= min_h)
+    }
+
+    /// `size` moved into the limits.
+    pub fn clamp(&self, (width, height): (i32, i32)) -> (i32, i32) {
+        let (max_w, max_h) = self.max.unwrap_or((i32::MAX, i32::MAX));
+        (width.clamp(self.min.0, max_w), height.clamp(self.min.1, max_h))
+    }
+}
+
+/// Startup options for the standalone window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WindowOptions {
+    pub plugin_loading: PluginLoading,
+    /// Keep the window hidden until the engine has drawn its first frame, so it
+    /// appears already painted instead of flashing white. Falls back to showing
+    /// it after a short timeout, or immediately on engines without
+    /// `FlutterDesktopEngineSetNextFrameCallback`.
+    pub show_on_first_frame: bool,
+    /// Pass `WM_SETTINGCHANGE` and `WM_THEMECHANGED` on to the Flutter view.
+    /// Windows only sends them to top-level windows, so without this the UI
+    /// keeps the theme and accessibility settings it started with.
+    pub forward_system_settings: bool,
+    /// Size limits enforced while the user resizes the window. Defaults to a
+    /// 200x150 minimum and no maximum.
+    pub size_constraints: WindowSizeConstraints,
+}
+
+/// Called on the platform thread once plugin registration finishes, with the
+/// number of plugins registered or the error that stopped it.
+pub type PluginsRegisteredCallback = Box<dyn FnOnce(Result<usize, String>) + Send + 'static>;
+
 /// Init logging for the enviroument debug filtered.
 /// Calling this more than once is fine as it is already handled to be a Once call.
 pub fn init_logging() {
@@ -70,6 +149,21 @@ pub fn init_logging() {
     });
 }
 
+/// Changes the size limits of a standalone window created by this crate and
+/// resizes it into them if needed. Safe to call from any thread; the change is
+/// applied on the window's own thread.
+///
+/// Returns `false` if `min`/`max` are invalid (see
+/// [`WindowSizeConstraints::is_valid`]) or `hwnd` isn't one of our windows.
+pub fn set_window_size_constraints(
+    hwnd: HWND,
+    min: (i32, i32),
+    max: Option<(i32, i32)>,
+) -> bool {
+    let constraints = WindowSizeConstraints { min, max };
+    constraints.is_valid() && win32_utils::set_window_size_constraints(hwnd, constraints)
+}
+
 /// Bootstraps a Flutter-powered window from the *default* DLL directory.
 ///
 /// 1. Initializes COM (STA).  
@@ -91,35 +185,131 @@ pub fn init_flutter_window() {
 /// # Parameters
 /// - `data_dir`: optional root path of your release bundle.
 ///
-/// # Panics
-/// Panics if any required asset is missing or engine/view creation fails.
+/// # Exits
+/// Exits the process if any required asset is missing or engine/view creation
+/// fails. See [`try_init_flutter_window_from_dir`] for a variant that returns
+/// the error.
 pub fn init_flutter_window_from_dir(data_dir: Option<PathBuf>) {
+    init_flutter_window_with_plugin_loading(data_dir, PluginLoading::Synchronous, None)
+}
+
+/// Like [`init_flutter_window_from_dir`], but lets you choose when plugins are
+/// registered and get notified when that is done.
+///
+/// With [`PluginLoading::Deferred`] the window is shown before any plugin DLL
+/// is touched, which hides slow plugin initialization from startup. A plugin
+/// failure is reported through `on_registered` instead of exiting the process.
+///
+/// # Parameters
+/// - `data_dir`: optional root path of your release bundle.
+/// - `plugin_loading`: synchronous (default behavior) or deferred registration.
+/// - `on_registered`: optional callback, run on the platform thread.
+///
+/// # Exits
+/// Exits the process if any required asset is missing or engine/view creation
+/// fails.
+pub fn init_flutter_window_with_plugin_loading(
+    data_dir: Option<PathBuf>,
+    plugin_loading: PluginLoading,
+    on_registered: Option<PluginsRegisteredCallback>,
+) {
+    let options = WindowOptions {
+        plugin_loading,
+        ..WindowOptions::default()
+    };
+    init_flutter_window_with_options(data_dir, options, on_registered)
+}
+
+/// Bootstraps the standalone window with explicit [`WindowOptions`].
+///
+/// # Parameters
+/// - `data_dir`: optional root path of your release bundle.
+/// - `options`: plugin loading mode and when to show the window.
+/// - `on_registered`: optional callback, run on the platform thread once
+///   plugins are registered.
+///
+/// # Exits
+/// Logs the error and exits the process if any required asset is missing or
+/// engine/view creation fails. Use [`try_init_flutter_window_from_dir`] to get
+/// the error back instead.
+pub fn init_flutter_window_with_options(
+    data_dir: Option<PathBuf>,
+    options: WindowOptions,
+    on_registered: Option<PluginsRegisteredCallback>,
+) {
+    if let Err(e) = try_init_flutter_window_with_options(data_dir, options, on_registered) {
+        error!("Flutter window failed to start: {e}");
+        std::process::exit(1);
+    }
+}
+
+/// Like [`init_flutter_window_from_dir`], but returns an error instead of
+/// exiting the process, for hosts that must stay alive when the bundle is
+/// broken.
+///
+/// Missing assets are reported as [`FlutterEmbedderError::AssetMissing`]; a
+/// `flutter_windows.dll` that won't load, a failure to create the engine or
+/// view, and plugin registration failures as
+/// [`FlutterEmbedderError::InitializationFailed`]. COM is uninitialized and
+/// the engine destroyed before an error is returned. Blocks in the message
+/// loop and returns `Ok(())` once the window is closed.
+pub fn try_init_flutter_window_from_dir(
+    data_dir: Option<PathBuf>,
+) -> Result<(), FlutterEmbedderError> {
+    try_init_flutter_window_with_options(data_dir, WindowOptions::default(), None)
+}
+
+fn try_init_flutter_window_with_options(
+    data_dir: Option<PathBuf>,
+    options: WindowOptions,
+    on_registered: Option<PluginsRegisteredCallback>,
+) -> Result<(), FlutterEmbedderError> {
     init_logging();
 
     // --- COM init (STA) ---
-    unsafe {
-        let hresult_result = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
-        hresult_result.ok().unwrap_or_else(|e| {
+    unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) }
+        .ok()
+        .map_err(|e| {
             error!("COM initialization failed: {e:?}");
-            std::process::exit(1);
-        });
-    }
+            FlutterEmbedderError::InitializationFailed(format!("COM initialization failed: {e}"))
+        })?;
     info!("COM initialized (STA)");
 
+    let result = run_flutter_window(data_dir, options, on_registered);
+
+    unsafe {
+        CoUninitialize();
+    }
+    info!("Application exiting");
+    result
+}
+
+/// Everything between COM init and uninit, so every error path still reaches
+/// `CoUninitialize`.
+fn run_flutter_window(
+    data_dir: Option<PathBuf>,
+    options: WindowOptions,
+    on_registered: Option<PluginsRegisteredCallback>,
+) -> Result<(), FlutterEmbedderError> {
+    let WindowOptions {
+        plugin_loading,
+        show_on_first_frame,
+        forward_system_settings,
+        size_constraints,
+    } = options;
+
     // 1) Resolve Flutter asset paths
-    let (assets, icu, aot) = match data_dir.as_ref() {
-        Some(dir) => path_utils::get_flutter_build_paths_from(dir),
-        None => path_utils::get_flutter_build_paths(),
-    };
+    let bundle_dir = data_dir.clone().unwrap_or_else(path_utils::dll_directory);
+    let (assets, icu, aot) = path_utils::try_get_flutter_build_paths_with_icu(&bundle_dir, None)?;
 
     let dir_ref = data_dir.as_deref();
-    let dll =
-        dynamic_flutter_windows_dll_loader::FlutterDll::get_for(dir_ref).unwrap_or_else(|e| {
-            error!(
-                "Failed to load flutter_windows.dll from `{dir_ref:?}`: {e:?}"
-            );
-            std::process::exit(1);
-        });
+    let dll = dynamic_flutter_windows_dll_loader::FlutterDll::get_for(dir_ref).map_err(|e| {
+        error!("Failed to load flutter_windows.dll from `{dir_ref:?}`: {e:?}");
+        FlutterEmbedderError::InitializationFailed(format!(
+            "failed to load flutter_windows.dll from `{}`: {e}",
+            bundle_dir.display()
+        ))
+    })?;
 
     info!(
         "Loaded flutter_windows.dll from `{}`",
@@ -129,7 +319,7 @@ pub fn init_flutter_window_from_dir(data_dir: Option<PathBuf>) {
     );
 
     // 2) Create the engine with explicit paths
-    let engine = flutter_utils::create_flutter_engine_with_paths(assets, icu, aot, &dll);
+    let engine = flutter_utils::create_flutter_engine_with_paths(assets, icu, aot, &dll)?;
     info!("Flutter engine created");
 
     // 3) Create the view controller
@@ -138,7 +328,7 @@ pub fn init_flutter_window_from_dir(data_dir: Option<PathBuf>) {
         constants::DEFAULT_WINDOW_WIDTH,
         constants::DEFAULT_WINDOW_HEIGHT,
         &dll,
-    );
+    )?;
     info!(
         "Flutter view controller created ({}×{})",
         constants::DEFAULT_WINDOW_WIDTH,
@@ -146,24 +336,38 @@ pub fn init_flutter_window_from_dir(data_dir: Option<PathBuf>) {
     );
 
     // 4) Register plugins from the same directory
-    let binding = path_utils::dll_directory();
-    let plugin_dir = data_dir.as_ref().unwrap_or(&binding);
-    plugin_loader::load_and_register_plugins(plugin_dir, engine, Some(&dll)).unwrap_or_else(|e| {
-        error!(
-            "Plugin load failed from `{}`: {:?}",
-            plugin_dir.display(),
-            e
-        );
-        std::process::exit(1);
-    });
-    info!("All plugins registered from `{}`", plugin_dir.display());
+    let plugin_dir = &bundle_dir;
+    let mut on_registered = on_registered;
+    if plugin_loading == PluginLoading::Synchronous {
+        let count = match plugin_loader::load_and_register_plugins(plugin_dir, engine, Some(&dll)) {
+            Ok(count) => count,
+            Err(e) => {
+                error!(
+                    "Plugin load failed from `{}`: {:?}",
+                    plugin_dir.display(),
+                    e
+                );
+                unsafe { (dll.FlutterDesktopViewControllerDestroy)(controller) };
+                return Err(FlutterEmbedderError::InitializationFailed(format!(
+                    "plugin load failed from `{}`: {e}",
+                    plugin_dir.display()
+                )));
+            }
+        };
+        info!("All plugins registered from `{}`", plugin_dir.display());
+        if let Some(callback) = on_registered.take() {
+            callback(Ok(count));
+        }
+    }
 
     // 5) Embed Flutter’s HWND in a Win32 window
-    let (_view, flutter_child_hwnd) = flutter_utils::get_flutter_view_and_hwnd(controller, &dll);
+    let (_view, flutter_child_hwnd) = flutter_utils::get_flutter_view_and_hwnd(controller, &dll)?;
     let state = Box::new(app_state::AppState {
         controller,
         child_hwnd: flutter_child_hwnd,
         dll: dll.clone(),
+        forward_system_settings,
+        size_constraints,
     });
     let state_ptr = Box::into_raw(state);
 
@@ -171,18 +375,26 @@ pub fn init_flutter_window_from_dir(data_dir: Option<PathBuf>) {
     let parent_hwnd = win32_utils::create_main_window(state_ptr);
     win32_utils::set_flutter_window_as_child(parent_hwnd, flutter_child_hwnd);
 
-    // 6) Show and enter the message loop
-    unsafe {
-        let _ = ShowWindow(parent_hwnd, SW_SHOWNORMAL);
-        let _ = SetForegroundWindow(parent_hwnd);
+    // 6) Show (now or after the first frame) and enter the message loop
+    if show_on_first_frame
+        && win32_utils::show_main_window_on_first_frame(parent_hwnd, engine, &dll)
+    {
+        info!("Main window will be shown after the first frame");
+    } else {
+        win32_utils::show_main_window(parent_hwnd);
+    }
+
+    if plugin_loading == PluginLoading::Deferred {
+        info!("Loading plugins from `{}` in the background", plugin_dir.display());
+        plugin_loader::spawn_deferred_registration(
+            plugin_dir.clone(),
+            parent_hwnd,
+            win32_utils::WM_PLUGINS_PREPARED,
+            on_registered,
+        );
     }
-    info!("Main window shown");
 
     win32_utils::run_message_loop(parent_hwnd, state_ptr);
     info!("Message loop exited");
-
-    unsafe {
-        CoUninitialize();
-    }
-    info!("Application exiting");
+    Ok(())
 }
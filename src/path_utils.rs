@@ -24,6 +24,12 @@ use windows::{
     core::PCWSTR,
 };
 
+use crate::software_renderer::api::FlutterEmbedderError;
+
+/// `(assets_path, icu_data_path, aot_library_path)` as NUL-terminated UTF-16.
+/// The AOT path is empty when the bundle has no `app.so`.
+pub type FlutterBuildPaths = (Vec<u16>, Vec<u16>, Vec<u16>);
+
 /// Returns `(assets_path, icu_data_path, aot_library_path)` by inspecting
 /// the folder where this DLL (or EXE) is located.
 /// This assumes the flutter app was build with flutter build xxxx --debug or release
@@ -42,8 +48,30 @@ pub fn get_flutter_build_paths() -> (Vec<u16>, Vec<u16>, Vec<u16>) {
 /// Panics if `flutter_assets` or `icudtl.dat` are not found under `root_dir/data/`;
 /// but if `app.so` is missing, falls back to JIT mode (returns an empty AOT path).
 pub fn get_flutter_build_paths_from(root_dir: &Path) -> (Vec<u16>, Vec<u16>, Vec<u16>) {
+    get_flutter_build_paths_with_icu(root_dir, None)
+}
+
+/// Like `get_flutter_build_paths_from()`, but uses `icu_override` instead of
+/// the bundle's own `icudtl.dat` when given, so several bundles can share one
+/// ICU file. The bundle then doesn't need an `icudtl.dat` of its own.
+/// Panics if the chosen ICU file doesn't exist.
+pub fn get_flutter_build_paths_with_icu(
+    root_dir: &Path,
+    icu_override: Option<&Path>,
+) -> (Vec<u16>, Vec<u16>, Vec<u16>) {
+    try_get_flutter_build_paths_with_icu(root_dir, icu_override)
+        .unwrap_or_else(|e| panic!("[Path Utils] {e}"))
+}
+
+/// Like `get_flutter_build_paths_with_icu()`, but returns
+/// `FlutterEmbedderError::AssetMissing` instead of panicking when
+/// `flutter_assets` or the ICU file is missing.
+pub fn try_get_flutter_build_paths_with_icu(
+    root_dir: &Path,
+    icu_override: Option<&Path>,
+) -> Result<FlutterBuildPaths, FlutterEmbedderError> {
     let assets_dir: PathBuf;
-    let icu_file: PathBuf;
+    let mut icu_file: PathBuf;
     let aot_lib: PathBuf;
 
     let assemble_assets_dir = root_dir.join("flutter_assets");
@@ -60,24 +88,29 @@ pub fn get_flutter_build_paths_from(root_dir: &Path) -> (Vec<u16>, Vec<u16>, Vec
         aot_lib = data_dir.join("app.so");
     }
 
+    if let Some(icu) = icu_override {
+        info!("[Path Utils] Using ICU data override `{}`", icu.display());
+        icu_file = icu.to_path_buf();
+    }
+
     debug!("[Path Utils] Using asset root: `{}`", assets_dir.display());
 
     // 1) flutter_assets must exist
     if !assets_dir.is_dir() {
         error!("[Path Utils] Missing directory `{}`", assets_dir.display());
-        panic!(
-            "[Path Utils] Missing `flutter_assets` at `{}`",
-            assets_dir.display()
-        );
+        return Err(FlutterEmbedderError::AssetMissing {
+            which: "flutter_assets",
+            path: assets_dir,
+        });
     }
 
     // 2) icudtl.dat must exist
     if !icu_file.is_file() {
         error!("[Path Utils] Missing file `{}`", icu_file.display());
-        panic!(
-            "[Path Utils] Missing `icudtl.dat` at `{}`",
-            icu_file.display()
-        );
+        return Err(FlutterEmbedderError::AssetMissing {
+            which: "icudtl.dat",
+            path: icu_file,
+        });
     }
 
     // 3) app.so (AOT lib) is optional — fall back to JIT if missing
@@ -100,7 +133,54 @@ pub fn get_flutter_build_paths_from(root_dir: &Path) -> (Vec<u16>, Vec<u16>, Vec
     }
 
     info!("[Path Utils] Resolved Flutter asset paths successfully");
-    (to_wide(&assets_dir), to_wide(&icu_file), aot_path_vec)
+    Ok((to_wide(&assets_dir), to_wide(&icu_file), aot_path_vec))
+}
+
+/// Lists what a bundle at `root_dir` lacks to start an overlay: `flutter_assets`
+/// and `icudtl.dat` (in either the `assemble` or the `build` layout) and
+/// `flutter_engine.dll`. With `icu_override`, the bundle's own ICU file is not
+/// required. An empty result means the bundle is complete.
+pub fn missing_bundle_files(root_dir: &Path, icu_override: Option<&Path>) -> Vec<&'static str> {
+    let data_dir = if root_dir.join("flutter_assets").is_dir() {
+        root_dir.to_path_buf()
+    } else {
+        root_dir.join("data")
+    };
+    let mut missing = Vec::new();
+    if !data_dir.join("flutter_assets").is_dir() {
+        missing.push("flutter_assets");
+    }
+    let icu_present = match icu_override {
+        Some(icu) => icu.is_file(),
+        None => data_dir.join("icudtl.dat").is_file(),
+    };
+    if !icu_present {
+        missing.push("icudtl.dat");
+    }
+    if !root_dir.join("flutter_engine.dll").is_file() {
+        missing.push("flutter_engine.dll");
+    }
+    missing
+}
+
+/// Returns the first of `dirs` that is a complete bundle (see
+/// `missing_bundle_files()`). On failure the error lists, per directory, what
+/// was missing.
+pub fn resolve_bundle_dir(dirs: &[PathBuf], icu_override: Option<&Path>) -> Result<PathBuf, String> {
+    let mut report = Vec::with_capacity(dirs.len());
+    for dir in dirs {
+        let missing = missing_bundle_files(dir, icu_override);
+        if missing.is_empty() {
+            info!("[Path Utils] Using Flutter bundle `{}`", dir.display());
+            return Ok(dir.clone());
+        }
+        debug!("[Path Utils] Skipping `{}`: missing {missing:?}", dir.display());
+        report.push(format!("`{}`: missing {}", dir.display(), missing.join(", ")));
+    }
+    if report.is_empty() {
+        return Err("no bundle directories given".to_string());
+    }
+    Err(format!("no complete Flutter bundle found; {}", report.join("; ")))
 }
 
 /// Displays the standard Windows “Select Folder” dialog.
@@ -145,13 +225,14 @@ pub fn dll_directory() -> PathBuf {
 
 /// Returns (assets, icu, aot) as Vec<u16> with trailing NULs stripped on conversion.
 /// Panics if assets or icu missing; returns empty Vec for aot if missing.
+/// `icu_override` replaces the bundle's `icudtl.dat`, see
+/// `get_flutter_build_paths_with_icu()`.
 pub fn load_flutter_build_paths(
     data_dir: Option<PathBuf>,
+    icu_override: Option<&Path>,
 ) -> (OsString, OsString, Option<OsString>) {
-    let (assets_w, icu_w, aot_w) = match data_dir.as_ref() {
-        Some(dir) => get_flutter_build_paths_from(dir),
-        None => get_flutter_build_paths(),
-    };
+    let root_dir = data_dir.unwrap_or_else(dll_directory);
+    let (assets_w, icu_w, aot_w) = get_flutter_build_paths_with_icu(&root_dir, icu_override);
     let strip = |mut v: Vec<u16>| {
         if v.last() == Some(&0) {
             v.pop();
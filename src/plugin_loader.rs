@@ -25,6 +25,12 @@
 //!    once *per engine* (so you can create multiple engines without
 //!    double‑registering the same plugin into one engine).
 //!
+//! 4. **spawn_deferred_registration**  
+//!    Optional split of step 3 for faster startup: discovery and
+//!    `LoadLibrary` run on a worker thread, then a message is posted to
+//!    the main window so the registrar calls happen on the platform
+//!    thread, as the engine requires.
+//!
 
 use anyhow::{Context, Result};
 use goblin::Object;
@@ -36,8 +42,13 @@ use std::{
     path::{Path, PathBuf},
     sync::{Arc, Mutex, OnceLock},
 };
+use windows::Win32::{
+    Foundation::{HWND, LPARAM, WPARAM},
+    UI::WindowsAndMessaging::PostMessageW,
+};
 
 use crate::{
+    PluginsRegisteredCallback,
     dynamic_flutter_windows_dll_loader::FlutterDll,
     bindings::windows::{FlutterDesktopEngineRef, FlutterDesktopPluginRegistrarRef},
 };
@@ -83,73 +94,184 @@ fn discover_plugins(release_dir: &Path) -> Result<Vec<(PathBuf, Vec<String>)>> {
     Ok(out)
 }
 
-/// Load the specified DLL and invoke each `xxxRegisterWithRegistrar`
-/// symbol, passing in the given `registrar`. Leaks the Library.
-fn load_and_register(
-    dll: &Path,
-    symbols: &[String],
+/// A plugin DLL that has been loaded but not yet registered with an engine.
+pub(crate) struct PreparedPlugin {
+    name: String,
+    path: PathBuf,
+    library: Library,
+    symbols: Vec<String>,
+}
+
+/// Result of the worker-thread half of a deferred registration, handed to the
+/// platform thread through `PostMessageW`.
+pub(crate) struct DeferredPlugins {
+    release_dir: PathBuf,
+    prepared: Result<Vec<PreparedPlugin>>,
+    on_registered: Option<PluginsRegisteredCallback>,
+}
+
+/// Discover every plugin DLL in `release_dir` and load it, without touching
+/// any engine. Safe to call off the platform thread.
+fn prepare_plugins(release_dir: &Path) -> Result<Vec<PreparedPlugin>> {
+    let plugins = discover_plugins(release_dir)
+        .with_context(|| format!("discovering plugins in `{}`", release_dir.display()))?;
+
+    let mut out = Vec::with_capacity(plugins.len());
+    for (path, symbols) in plugins {
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_string();
+        let library =
+            unsafe { Library::new(&path).with_context(|| format!("loading {}", path.display()))? };
+        out.push(PreparedPlugin {
+            name,
+            path,
+            library,
+            symbols,
+        });
+    }
+    Ok(out)
+}
+
+/// Invoke each `xxxRegisterWithRegistrar` symbol of an already loaded
+/// plugin, passing in the given `registrar`. Leaks the Library.
+fn register_plugin(
+    plugin: PreparedPlugin,
     registrar: FlutterDesktopPluginRegistrarRef,
 ) -> Result<()> {
-    let lib = unsafe { Library::new(dll).with_context(|| format!("loading {}", dll.display()))? };
-    for sym in symbols {
+    for sym in &plugin.symbols {
         let cname = CString::new(sym.as_str()).unwrap();
         let func: Symbol<unsafe extern "C" fn(FlutterDesktopPluginRegistrarRef)> = unsafe {
-            lib.get(cname.as_bytes_with_nul())
+            plugin
+                .library
+                .get(cname.as_bytes_with_nul())
                 .with_context(|| format!("symbol {sym}"))?
         };
         unsafe { func(registrar) };
     }
     // Keep the DLL loaded
-    std::mem::forget(lib);
+    std::mem::forget(plugin.library);
     Ok(())
 }
 
-/// Discover every plugin DLL in `release_dir` and register it with
-/// the Flutter engine identified by `engine`.  Each plugin DLL
-/// (by its file‐stem name) runs *once* per engine.  
-pub fn load_and_register_plugins(
-    release_dir: &Path,
+/// Register prepared plugins with `engine`, skipping any already registered
+/// into it. Must run on the platform thread. Returns how many were registered.
+fn register_prepared_plugins(
+    plugins: Vec<PreparedPlugin>,
     engine: FlutterDesktopEngineRef,
     dll: Option<&Arc<FlutterDll>>,
-) -> Result<()> {
+) -> Result<usize> {
     // Get (or create) this engine's seen‐set
     let mut map = registered_map().lock().unwrap();
     let seen = map.entry(engine as usize).or_default();
+    let mut registered = 0;
 
-    // Find all candidate plugin DLLs
-    let plugins = discover_plugins(release_dir)
-        .with_context(|| format!("discovering plugins in `{}`", release_dir.display()))?;
-
-    for (dll_path, symbols) in plugins {
-        let plugin_name = dll_path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_string();
-
+    for plugin in plugins {
         // Skip if we've already registered that plugin into *this* engine
-        if !seen.insert(plugin_name.clone()) {
+        if !seen.insert(plugin.name.clone()) {
             log::debug!(
-                "[Plugin Loader] skipping `{plugin_name}` (already registered)"
+                "[Plugin Loader] skipping `{}` (already registered)",
+                plugin.name
             );
             continue;
         }
 
         log::info!(
             "[Plugin Loader] registering plugin `{}` from `{}`",
-            plugin_name,
-            dll_path.display()
+            plugin.name,
+            plugin.path.display()
         );
 
         // Grab the registrar from the Flutter engine
-        let cname = std::ffi::CString::new(plugin_name).unwrap();
+        let cname = std::ffi::CString::new(plugin.name.as_str()).unwrap();
         let name_ptr = cname.as_ptr() as *const u8;
         let registrar: FlutterDesktopPluginRegistrarRef =
             unsafe { (dll.unwrap().FlutterDesktopEngineGetPluginRegistrar)(engine, name_ptr) };
 
-        // Load & invoke registration routines
-        load_and_register(&dll_path, &symbols, registrar)?;
+        // Invoke registration routines
+        register_plugin(plugin, registrar)?;
+        registered += 1;
     }
 
-    Ok(())
+    Ok(registered)
+}
+
+/// Discover every plugin DLL in `release_dir` and register it with
+/// the Flutter engine identified by `engine`.  Each plugin DLL
+/// (by its file‐stem name) runs *once* per engine.  
+pub fn load_and_register_plugins(
+    release_dir: &Path,
+    engine: FlutterDesktopEngineRef,
+    dll: Option<&Arc<FlutterDll>>,
+) -> Result<usize> {
+    let plugins = prepare_plugins(release_dir)?;
+    register_prepared_plugins(plugins, engine, dll)
+}
+
+/// Start discovering and loading the plugins in `release_dir` on a worker
+/// thread. When done, `msg` is posted to `hwnd` with a `Box<DeferredPlugins>`
+/// in `lparam`; the window procedure passes it to
+/// [`complete_deferred_registration`].
+pub(crate) fn spawn_deferred_registration(
+    release_dir: PathBuf,
+    hwnd: HWND,
+    msg: u32,
+    on_registered: Option<PluginsRegisteredCallback>,
+) {
+    // HWND is not Send; the raw value is valid on any thread.
+    let hwnd_raw = hwnd.0 as isize;
+    std::thread::spawn(move || {
+        let prepared = prepare_plugins(&release_dir);
+        let payload = Box::into_raw(Box::new(DeferredPlugins {
+            release_dir,
+            prepared,
+            on_registered,
+        }));
+        let posted = unsafe {
+            PostMessageW(
+                Some(HWND(hwnd_raw as *mut _)),
+                msg,
+                WPARAM(0),
+                LPARAM(payload as isize),
+            )
+        };
+        if let Err(e) = posted {
+            // Window is gone; nothing left to register into.
+            log::warn!("[Plugin Loader] could not hand prepared plugins to the window: {e:?}");
+            drop(unsafe { Box::from_raw(payload) });
+        }
+    });
+}
+
+/// Platform-thread half of a deferred registration: registers what the worker
+/// loaded and reports the outcome to the completion callback.
+pub(crate) fn complete_deferred_registration(
+    payload: DeferredPlugins,
+    engine: FlutterDesktopEngineRef,
+    dll: &Arc<FlutterDll>,
+) {
+    let DeferredPlugins {
+        release_dir,
+        prepared,
+        on_registered,
+    } = payload;
+
+    let outcome =
+        prepared.and_then(|plugins| register_prepared_plugins(plugins, engine, Some(dll)));
+    match &outcome {
+        Ok(count) => log::info!(
+            "[Plugin Loader] {count} deferred plugin(s) registered from `{}`",
+            release_dir.display()
+        ),
+        Err(e) => log::error!(
+            "[Plugin Loader] deferred plugin load failed from `{}`: {e:?}",
+            release_dir.display()
+        ),
+    }
+
+    if let Some(callback) = on_registered {
+        callback(outcome.map_err(|e| format!("{e:?}")));
+    }
 }
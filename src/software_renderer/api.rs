@@ -4,32 +4,68 @@ use crate::bindings::embedder::{
 use crate::software_renderer::d3d11_compositor::primitive_3d_renderer::{
     BlendMode, PrimitiveOptions, PrimitiveType, Vertex3D,
 };
+use crate::software_renderer::d3d11_compositor::debug_font::DEBUG_FONT_ID;
+use crate::software_renderer::d3d11_compositor::effects::EffectConfig;
+use crate::software_renderer::d3d11_compositor::post_processing_renderer::AddressMode;
+use crate::software_renderer::d3d11_compositor::shader_manifest::{
+    ShaderManifestEntry, ShaderSource,
+};
 use crate::software_renderer::d3d11_compositor::text_3d_renderer::{
     FontAtlas, GlyphInfo, TexturedVertex3D,
 };
+use crate::software_renderer::d3d11_compositor::text_presets::{
+    TextStyle3D, generate_text_vertices_aligned,
+};
+use crate::software_renderer::gl_renderer::angle_interop::AngleOptions;
+use crate::software_renderer::multiview::view_surface::SyncMode;
 use crate::software_renderer::overlay::d3d::{
-    create_compositing_texture, create_srv, create_texture,
+    ExportTexture, TextureShareOptions, create_compositing_texture, create_srv, create_texture,
 };
-use crate::software_renderer::overlay::engine::update_flutter_window_metrics;
+use crate::software_renderer::overlay::engine::{
+    MAX_OVERLAY_DIMENSION, update_flutter_window_metrics,
+};
+use crate::software_renderer::overlays_manager_api::buffer_pool::PixelBufferPool;
+use crate::software_renderer::overlay::fade::OverlayFade;
 use crate::software_renderer::overlay::init::{self as internal_embedder_init};
 
-use crate::software_renderer::overlay::input::{handle_pointer_event, handle_set_cursor};
-use crate::software_renderer::overlay::keyevents::handle_keyboard_event;
+use crate::software_renderer::overlay::ime::handle_ime_event;
+use crate::software_renderer::overlay::input::{
+    InputTransform, cancel_pressed_buttons, cancel_touch_contacts, end_trackpad_gesture,
+    handle_pointer_event, handle_set_cursor, unrotate_about, unscale_about,
+};
+use crate::software_renderer::overlay::keyevents::{
+    handle_keyboard_event, inject_key_event, release_pressed_keys,
+};
+use crate::software_renderer::overlay::semantics_handler::{
+    HoverDebounce, ProcessedSemanticsNode, SemanticsThrottle,
+};
+use crate::software_renderer::overlay::snapshot::{
+    read_texture_bgra, read_texture_rgba, render_composited, save_rgba_png,
+};
+use crate::software_renderer::overlay::textinput::EditingState;
 // Re-export so `FlutterOverlay` is reachable as a public type under this module
 // (its inherent `impl` and all public methods live in this file). Without this,
 // the type is only visible through the private `overlay` module and cannot be
 // named or linked from public docs.
 pub use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
-use crate::software_renderer::overlay::platform_message_callback::send_platform_message;
+use crate::software_renderer::overlay::overlay_impl::PendingPlatformMessage;
+use crate::software_renderer::overlay::platform_message_callback::{
+    json_method_response, mc_encode_error_envelope, send_platform_message,
+    send_platform_message_with_response,
+};
+use crate::software_renderer::overlay::restoration::{RESTORATION_CHANNEL, encode_push_call};
 use crate::software_renderer::ticker::spawn::start_task_runner;
 use crate::software_renderer::ticker::ticker::tick;
-use log::{error, info, warn};
+use log::{debug, error, info, warn};
+use serde_json::Value;
+use std::collections::VecDeque;
 use std::ffi::CString;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 
-use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::Foundation::{HANDLE, HWND, LPARAM, LRESULT, WPARAM};
 use windows::Win32::Graphics::Direct3D11::{
     D3D11_BOX, ID3D11Device, ID3D11DeviceContext, ID3D11SamplerState, ID3D11ShaderResourceView,
     ID3D11Texture2D,
@@ -55,6 +91,260 @@ pub struct OverlayCreateParams {
     pub dart_entrypoint_args: Option<Vec<String>>,
     /// Optional engine command-line arguments.
     pub engine_args: Option<Vec<String>>,
+    /// Optional `icudtl.dat` to use instead of the one in `flutter_data_dir`.
+    /// Lets several overlays share a single ICU file.
+    pub icu_override: Option<PathBuf>,
+    /// Runtime mode to start the engine in. `None` infers it from the bundle:
+    /// release when `app.so` is present, debug (JIT) otherwise.
+    pub runtime_mode: Option<RuntimeMode>,
+    /// Typed engine switches, appended to `engine_args` after filtering out
+    /// the ones the runtime mode doesn't support.
+    pub engine_flags: Option<EngineFlags>,
+    /// COM apartment the task-runner thread joins at start.
+    pub task_runner_com: TaskRunnerComApartment,
+    /// Whether the engine renders on the overlay's task-runner thread (`true`)
+    /// or on its own internal raster thread (`false`).
+    pub custom_render_task_runner: bool,
+    /// Optional ANGLE display attributes for the OpenGL renderer.
+    pub angle_options: AngleOptions,
+    /// Renderer to use. `None` tries OpenGL and falls back to software;
+    /// `Some(OpenGL)` fails instead of falling back; `Some(Software)` never
+    /// touches ANGLE.
+    pub preferred_renderer: Option<RendererType>,
+    /// Explicit asset, ICU and AOT paths. `None` derives them from
+    /// `flutter_data_dir`. Either way the engine and ANGLE DLLs are still
+    /// loaded from `flutter_data_dir`, and `icu_override` is ignored when set.
+    pub flutter_paths: Option<FlutterPaths>,
+}
+
+/// Paths to a Flutter bundle's parts, for bundles that don't keep
+/// `flutter_assets`, `icudtl.dat` and `app.so` in the standard layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlutterPaths {
+    /// The `flutter_assets` directory.
+    pub assets: PathBuf,
+    /// The `icudtl.dat` file.
+    pub icu: PathBuf,
+    /// The AOT `app.so`. `None` runs the Dart code from `kernel_blob.bin` (JIT).
+    pub aot: Option<PathBuf>,
+}
+
+impl FlutterPaths {
+    /// Checks that every path exists. The error names the first missing one.
+    pub fn validate(&self) -> Result<(), FlutterEmbedderError> {
+        let missing = |which: &str, path: &Path| {
+            Err(FlutterEmbedderError::InitializationFailed(format!(
+                "{which} not found at `{}`",
+                path.display()
+            )))
+        };
+        if !self.assets.is_dir() {
+            return missing("flutter_assets", &self.assets);
+        }
+        if !self.icu.is_file() {
+            return missing("icudtl.dat", &self.icu);
+        }
+        if let Some(aot) = self.aot.as_deref()
+            && !aot.is_file()
+        {
+            return missing("app.so", aot);
+        }
+        Ok(())
+    }
+}
+
+/// Flutter runtime mode of an overlay.
+///
+/// The mode must match the `flutter_engine.dll` the bundle ships with; the
+/// embedder only selects the Dart code and engine switches, it can't turn a
+/// release engine into a profile one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeMode {
+    /// JIT from `kernel_blob.bin`. `app.so` is ignored even if present.
+    Debug,
+    /// AOT with Dart profiling and an unbounded timeline buffer, for DevTools.
+    /// Requires `app.so`.
+    Profile,
+    /// AOT, no extra switches. Requires `app.so`.
+    Release,
+}
+
+impl RuntimeMode {
+    /// Engine switches added on top of the user's `engine_args` for this mode.
+    pub fn engine_switches(self) -> &'static [&'static str] {
+        match self {
+            RuntimeMode::Debug | RuntimeMode::Release => &[],
+            RuntimeMode::Profile => &["--enable-dart-profiling", "--endless-trace-buffer"],
+        }
+    }
+
+    /// Whether the mode runs AOT-compiled Dart code.
+    pub fn uses_aot(self) -> bool {
+        !matches!(self, RuntimeMode::Debug)
+    }
+}
+
+/// COM apartment the task-runner thread initializes before running engine tasks.
+///
+/// Platform message handlers run on the task-runner thread. Handlers that call
+/// COM APIs (shell dialogs, WIC, audio) need the thread in an apartment first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TaskRunnerComApartment {
+    /// The thread never calls `CoInitializeEx`.
+    #[default]
+    None,
+    /// `COINIT_APARTMENTTHREADED`.
+    SingleThreaded,
+    /// `COINIT_MULTITHREADED`.
+    MultiThreaded,
+}
+
+/// Typed engine command-line switches that are checked against the runtime mode.
+///
+/// Raw `engine_args` are passed through untouched, so a debug-only switch in a
+/// release bundle stops the engine from starting. Switches set here are only
+/// emitted when the overlay's mode supports them; the rest are dropped with a
+/// warning.
+///
+/// ```rust, no_run
+/// let flags = EngineFlags::new().trace_startup(true).enable_vm_service(8181);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EngineFlags {
+    disable_dart_asserts: bool,
+    trace_startup: bool,
+    vm_service_port: Option<u16>,
+    verbose_logging: bool,
+}
+
+impl EngineFlags {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `--disable-dart-asserts`. Debug only; AOT builds have no asserts.
+    pub fn disable_dart_asserts(mut self, enabled: bool) -> Self {
+        self.disable_dart_asserts = enabled;
+        self
+    }
+
+    /// `--trace-startup`. Debug and profile.
+    pub fn trace_startup(mut self, enabled: bool) -> Self {
+        self.trace_startup = enabled;
+        self
+    }
+
+    /// `--vm-service-port=<port>`, `0` picks a free port. Debug and profile.
+    pub fn enable_vm_service(mut self, port: u16) -> Self {
+        self.vm_service_port = Some(port);
+        self
+    }
+
+    /// `--verbose-logging`. All modes.
+    pub fn verbose_logging(mut self, enabled: bool) -> Self {
+        self.verbose_logging = enabled;
+        self
+    }
+
+    /// The switches valid for `mode`. Requested switches the mode doesn't
+    /// support are logged and left out.
+    pub fn switches_for(&self, mode: RuntimeMode) -> Vec<String> {
+        let mut switches = Vec::new();
+        let mut add = |requested: bool, supported: bool, switch: String| {
+            if !requested {
+                return;
+            }
+            if supported {
+                switches.push(switch);
+            } else {
+                warn!("[EngineFlags] `{switch}` is not supported in {mode:?} mode, skipping");
+            }
+        };
+
+        let not_release = mode != RuntimeMode::Release;
+        add(
+            self.disable_dart_asserts,
+            mode == RuntimeMode::Debug,
+            "--disable-dart-asserts".to_string(),
+        );
+        add(self.trace_startup, not_release, "--trace-startup".to_string());
+        if let Some(port) = self.vm_service_port {
+            add(true, not_release, format!("--vm-service-port={port}"));
+        }
+        add(self.verbose_logging, true, "--verbose-logging".to_string());
+        switches
+    }
+}
+
+/// How long each phase of overlay startup took, in milliseconds.
+///
+/// Returned by [`FlutterOverlay::get_startup_timings`]. The first three phases
+/// are fixed once `create` returns; `first_frame_ms` is measured from the start
+/// of `create` and stays `None` until the host has seen the first frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StartupTimings {
+    /// Loading (or reusing) `flutter_engine.dll`.
+    pub dll_load_ms: f64,
+    /// ANGLE / software renderer setup plus the D3D11 compositor renderers.
+    pub renderer_init_ms: f64,
+    /// `FlutterEngineRun` and the initial window metrics.
+    pub engine_run_ms: f64,
+    /// Time until the first frame was picked up by the host.
+    pub first_frame_ms: Option<f64>,
+}
+
+/// Per-overlay cost of getting new frames into the texture.
+///
+/// Returned by [`FlutterOverlay::get_fps_stats`]. Only ticks that produced a
+/// new frame are timed: the software upload or the copy out of the ANGLE
+/// texture. Hidden overlays report all zeroes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FpsStats {
+    /// Time spent on the most recent frame.
+    pub last_frame_ms: f32,
+    /// Average over the last [`FRAME_TIMER_WINDOW`] frames.
+    pub avg_frame_ms: f32,
+    /// Frames timed since the overlay was last shown.
+    pub present_count: u64,
+}
+
+/// Number of frames [`FpsStats::avg_frame_ms`] averages over.
+pub const FRAME_TIMER_WINDOW: usize = 60;
+
+/// Rolling window behind [`FpsStats`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FrameTimer {
+    samples: VecDeque<f32>,
+    sum: f32,
+    present_count: u64,
+}
+
+impl FrameTimer {
+    pub(crate) fn record(&mut self, frame_ms: f32) {
+        if self.samples.len() == FRAME_TIMER_WINDOW
+            && let Some(oldest) = self.samples.pop_front()
+        {
+            self.sum -= oldest;
+        }
+        self.samples.push_back(frame_ms);
+        self.sum += frame_ms;
+        self.present_count += 1;
+    }
+
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub(crate) fn stats(&self) -> FpsStats {
+        let Some(&last_frame_ms) = self.samples.back() else {
+            return FpsStats::default();
+        };
+        FpsStats {
+            last_frame_ms,
+            avg_frame_ms: self.sum / self.samples.len() as f32,
+            present_count: self.present_count,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -68,6 +358,22 @@ pub enum FlutterEmbedderError {
     OperationFailed(String),
     EngineNotRunning,
     InvalidHandle,
+    /// `flutter_engine.dll` could not be loaded from `path`.
+    DllLoadFailed {
+        path: PathBuf,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// A file the bundle needs is not there. `which` names it, e.g. `"icudtl.dat"`.
+    AssetMissing { which: &'static str, path: PathBuf },
+    /// `renderer` could not be set up. `egl_code` is the `eglGetError` value when
+    /// the failure came from EGL.
+    RendererInitFailed {
+        renderer: &'static str,
+        egl_code: Option<i32>,
+        message: String,
+    },
+    /// `FlutterEngineInitialize` or `FlutterEngineRunInitialized` returned `result_code`.
+    EngineRunFailed { result_code: e::FlutterEngineResult },
 }
 
 impl std::fmt::Display for FlutterEmbedderError {
@@ -85,10 +391,105 @@ impl std::fmt::Display for FlutterEmbedderError {
             FlutterEmbedderError::InvalidHandle => {
                 write!(f, "Invalid Flutter overlay handle provided.")
             }
+            FlutterEmbedderError::DllLoadFailed { path, source } => {
+                write!(f, "Failed to load flutter_engine.dll from `{}`: {source}", path.display())
+            }
+            FlutterEmbedderError::AssetMissing { which, path } => {
+                write!(f, "Missing {which}: `{}` does not exist", path.display())
+            }
+            FlutterEmbedderError::RendererInitFailed {
+                renderer,
+                egl_code,
+                message,
+            } => match egl_code {
+                Some(code) => write!(f, "{renderer} renderer failed: {message} (EGL 0x{code:04X})"),
+                None => write!(f, "{renderer} renderer failed: {message}"),
+            },
+            FlutterEmbedderError::EngineRunFailed { result_code } => {
+                write!(f, "Flutter engine failed to start: FlutterEngineResult {result_code}")
+            }
+        }
+    }
+}
+impl std::error::Error for FlutterEmbedderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FlutterEmbedderError::DllLoadFailed { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Alpha convention of 8-bit, 4-channel pixel data (BGRA or RGBA; alpha is
+/// always the last byte of each pixel).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlphaMode {
+    /// Color channels are already multiplied by alpha. This is what the Flutter
+    /// engine produces on both the OpenGL (ANGLE) and the software renderer.
+    #[default]
+    Premultiplied,
+    /// Color channels are independent of alpha, as most image formats (PNG)
+    /// and image tools expect.
+    Straight,
+}
+
+/// Converts straight-alpha pixels to premultiplied alpha in place.
+pub fn premultiply(buffer: &mut [u8]) {
+    for px in buffer.chunks_exact_mut(4) {
+        let a = px[3] as u32;
+        if a == 255 {
+            continue;
+        }
+        for c in &mut px[..3] {
+            *c = ((*c as u32 * a + 127) / 255) as u8;
         }
     }
 }
-impl std::error::Error for FlutterEmbedderError {}
+
+/// Converts premultiplied-alpha pixels to straight alpha in place.
+/// Fully transparent pixels become transparent black.
+pub fn unpremultiply(buffer: &mut [u8]) {
+    for px in buffer.chunks_exact_mut(4) {
+        let a = px[3] as u32;
+        match a {
+            255 => {}
+            0 => px[..3].fill(0),
+            _ => {
+                for c in &mut px[..3] {
+                    *c = ((*c as u32 * 255 + a / 2) / a).min(255) as u8;
+                }
+            }
+        }
+    }
+}
+
+/// Converts `buffer` from one alpha convention to another in place. No-op when
+/// `from == to`.
+pub fn convert_alpha(buffer: &mut [u8], from: AlphaMode, to: AlphaMode) {
+    match (from, to) {
+        (AlphaMode::Premultiplied, AlphaMode::Straight) => unpremultiply(buffer),
+        (AlphaMode::Straight, AlphaMode::Premultiplied) => premultiply(buffer),
+        _ => {}
+    }
+}
+
+/// Instant `nanos` after `start`, as stored in `last_present_nanos`; `None` for
+/// the `0` written before the first present.
+pub(crate) fn present_instant(start: Instant, nanos: u64) -> Option<Instant> {
+    (nanos != 0).then(|| start + Duration::from_nanos(nanos))
+}
+
+/// Whether an engine started at `started_at` counts as responsive at `now`:
+/// it presented within `timeout`, or it hasn't presented yet and is still
+/// within `timeout` of starting.
+pub(crate) fn presented_recently(
+    last_present: Option<Instant>,
+    started_at: Instant,
+    now: Instant,
+    timeout: Duration,
+) -> bool {
+    now.saturating_duration_since(last_present.unwrap_or(started_at)) <= timeout
+}
 
 /// True when a resize request is a no-op: not forced and the new geometry
 /// `(x, y, w, h)` equals the current geometry.
@@ -100,6 +501,14 @@ pub(crate) fn should_skip_resize(
     !force && current == new
 }
 
+/// New suspend state after a resize to `width` x `height`, or `None` if it
+/// stays `suspended`. A zero dimension suspends rendering, any other size
+/// resumes it.
+pub(crate) fn resize_suspend_change(suspended: bool, width: u32, height: u32) -> Option<bool> {
+    let suspend = width == 0 || height == 0;
+    (suspend != suspended).then_some(suspend)
+}
+
 impl FlutterOverlay {
     /// Creates and initializes a new `FlutterOverlay` instance.
     ///
@@ -118,6 +527,9 @@ impl FlutterOverlay {
     ///
     /// # Returns
     /// A `Result` containing a `Box<FlutterOverlay>` or a `FlutterEmbedderError`.
+    /// Startup failures use the structured variants (`DllLoadFailed`,
+    /// `AssetMissing`, `RendererInitFailed`, `EngineRunFailed`), so the cause can
+    /// be matched on or walked with `Error::source`.
     pub fn create(
         params: OverlayCreateParams,
         d3d11_device: &ID3D11Device,
@@ -128,19 +540,17 @@ impl FlutterOverlay {
             params.name, params.flutter_data_dir
         );
 
-        let overlay_box = internal_embedder_init::init_overlay(params, d3d11_device, swap_chain);
+        let overlay_box = internal_embedder_init::init_overlay(params, d3d11_device, swap_chain)?;
 
-        match overlay_box {
-            Some(ob) if !ob.engine.0.is_null() => Ok(ob),
-            _ => {
-                error!(
-                    "[FlutterOverlay::create] Initialization failed: Engine handle is null after init."
-                );
-                Err(FlutterEmbedderError::InitializationFailed(
-                    "Engine handle was null after internal init.".to_string(),
-                ))
-            }
+        if overlay_box.engine.0.is_null() {
+            error!(
+                "[FlutterOverlay::create] Initialization failed: Engine handle is null after init."
+            );
+            return Err(FlutterEmbedderError::InitializationFailed(
+                "Engine handle was null after internal init.".to_string(),
+            ));
         }
+        Ok(overlay_box)
     }
 
     /// Returns the raw `FlutterEngine` pointer. **USE WITH CAUTION.**
@@ -157,7 +567,8 @@ impl FlutterOverlay {
         new_height: u32,
         swap_chain: &IDXGISwapChain,
     ) {
-        self.handle_window_resize_inner(new_x, new_y, new_width, new_height, swap_chain, false);
+        let bounds = (new_x, new_y, new_width, new_height);
+        self.handle_window_resize_inner(bounds, swap_chain, false, None);
     }
 
     pub fn handle_window_resize_force(
@@ -168,18 +579,62 @@ impl FlutterOverlay {
         new_height: u32,
         swap_chain: &IDXGISwapChain,
     ) {
-        self.handle_window_resize_inner(new_x, new_y, new_width, new_height, swap_chain, true);
+        let bounds = (new_x, new_y, new_width, new_height);
+        self.handle_window_resize_inner(bounds, swap_chain, true, None);
     }
 
-    fn handle_window_resize_inner(
+    /// crate(INTERNAL) Like [`Self::handle_window_resize`], but a software pixel
+    /// buffer that outgrows its allocation is swapped for one from `pool`.
+    pub(crate) fn handle_window_resize_pooled(
         &mut self,
         new_x: i32,
         new_y: i32,
         new_width: u32,
         new_height: u32,
         swap_chain: &IDXGISwapChain,
+        pool: &mut PixelBufferPool,
+    ) {
+        let bounds = (new_x, new_y, new_width, new_height);
+        self.handle_window_resize_inner(bounds, swap_chain, false, Some(pool));
+    }
+
+    /// `bounds` is `(x, y, width, height)`.
+    fn handle_window_resize_inner(
+        &mut self,
+        bounds: (i32, i32, u32, u32),
+        swap_chain: &IDXGISwapChain,
         force: bool,
+        pool: Option<&mut PixelBufferPool>,
     ) {
+        let (new_x, new_y, new_width, new_height) = bounds;
+        if new_width > MAX_OVERLAY_DIMENSION || new_height > MAX_OVERLAY_DIMENSION {
+            warn!(
+                "[FlutterOverlay:'{}'] Resize to {new_width}x{new_height} exceeds the \
+                 {MAX_OVERLAY_DIMENSION} px texture limit, clamping.",
+                self.name
+            );
+        }
+        let new_width = new_width.min(MAX_OVERLAY_DIMENSION);
+        let new_height = new_height.min(MAX_OVERLAY_DIMENSION);
+        if let Some(suspended) = resize_suspend_change(self.render_suspended, new_width, new_height)
+        {
+            if suspended {
+                info!(
+                    "[FlutterOverlay:'{}'] Zero-size resize ({new_width}x{new_height}), suspending rendering.",
+                    self.name
+                );
+            } else {
+                info!(
+                    "[FlutterOverlay:'{}'] Resumed rendering at {new_width}x{new_height}.",
+                    self.name
+                );
+            }
+            self.render_suspended = suspended;
+        }
+        if self.render_suspended {
+            return;
+        }
+
         if should_skip_resize(
             (self.x, self.y, self.width, self.height),
             (new_x, new_y, new_width, new_height),
@@ -209,7 +664,15 @@ impl FlutterOverlay {
                     self.texture = create_texture(&game_device, self.width, self.height);
                     self.srv = create_srv(&game_device, &self.texture);
                     let new_buffer_size = (self.width as usize) * (self.height as usize) * 4;
-                    pixel_buffer.resize(new_buffer_size, 0);
+                    match pool {
+                        Some(pool) if pixel_buffer.capacity() < new_buffer_size => {
+                            let old = std::mem::replace(pixel_buffer, pool.acquire(new_buffer_size));
+                            pool.release(old);
+                        }
+                        _ => pixel_buffer.resize(new_buffer_size, 0),
+                    }
+                    // The new texture starts empty.
+                    self.full_repaint_needed.store(true, Ordering::Release);
                 }
             }
             RendererType::OpenGL => {
@@ -231,6 +694,14 @@ impl FlutterOverlay {
             }
         }
 
+        if let Some(options) = self.export_texture.as_ref().map(|export| export.options) {
+            self.export_texture = None;
+            match ExportTexture::new(&game_device, self.width, self.height, options) {
+                Ok(export) => self.export_texture = Some(export),
+                Err(e) => error!("[handle_window_resize] Dropping the shared texture copy: {e}"),
+            }
+        }
+
         if !self.engine.0.is_null() {
             update_flutter_window_metrics(
                 self.engine.0,
@@ -243,6 +714,12 @@ impl FlutterOverlay {
         }
     }
 
+    /// Returns `true` while rendering is suspended because the last resize had a
+    /// zero width or height (typically a minimized host window).
+    pub fn is_render_suspended(&self) -> bool {
+        self.render_suspended
+    }
+
     /// crate(INTERNAL) Starts the dedicated task runner thread for this overlay instance.
     /// Does nothing if the task runner is already running.
     pub(crate) fn start_task_runner(&mut self) {
@@ -271,7 +748,29 @@ impl FlutterOverlay {
     /// Performs per-frame updates, preparing the GPU texture with the latest Flutter content.
     /// - For `Software` mode, it uploads pixel data from the CPU.
     /// - For `OpenGL` mode, it waits for ANGLE to finish rendering, then copies from the shared texture.
-    pub fn tick(&self, context: &ID3D11DeviceContext) {
+    ///
+    /// Returns `true` if the overlay's texture received new content.
+    pub fn tick(&self, context: &ID3D11DeviceContext) -> bool {
+        let started = Instant::now();
+        let updated = self.update_texture(context);
+        if !self.visible {
+            self.reset_fps_stats();
+        } else if updated && let Ok(mut timer) = self.frame_timer.lock() {
+            timer.record(started.elapsed().as_secs_f32() * 1000.0);
+        }
+        if updated {
+            let nanos = self.startup_started_at.elapsed().as_nanos() as u64;
+            self.last_present_nanos.store(nanos.max(1), Ordering::Relaxed);
+            if let Some(export) = &self.export_texture {
+                export.copy_from(context, &self.texture);
+            }
+        }
+        updated
+    }
+
+    fn update_texture(&self, context: &ID3D11DeviceContext) -> bool {
+        self.note_first_frame();
+        end_trackpad_gesture(self, false);
         if !self.visible || self.width == 0 || self.height == 0 {
             if !self.secondary_view_ids().is_empty() {
                 // View 0 is hidden but satellite views still render. The engine's
@@ -299,17 +798,15 @@ impl FlutterOverlay {
                 }
                 let _ = self.request_frame();
             }
-            return;
+            return false;
         }
 
         match self.renderer_type {
-            RendererType::Software => {
-                tick(self, context);
-            }
+            RendererType::Software => tick(self, context),
             RendererType::OpenGL => {
                 if let Some(angle_state) = &self.angle_state
                     && angle_state.0.is_device_lost() {
-                        return;
+                        return false;
                     }
 
                 if let Some(angle_texture) = &self.angle_shared_texture {
@@ -330,7 +827,7 @@ impl FlutterOverlay {
                         // No damage → nothing changed, reuse previous texture as-is.
                         if damage.is_empty() {
                             self.angle_frame_copied.store(presented, Ordering::Relaxed);
-                            return;
+                            return false;
                         }
 
                         unsafe {
@@ -376,12 +873,43 @@ impl FlutterOverlay {
                             }
                         }
                         self.angle_frame_copied.store(presented, Ordering::Relaxed);
+                        return true;
                     }
                 }
+                false
             }
         }
     }
 
+    /// Keeps a shareable copy of the overlay texture for capture tools and
+    /// encoders, refreshed on every tick that produces a new frame. `None`
+    /// drops the copy; overlays start without one.
+    pub fn set_texture_sharing(
+        &mut self,
+        options: Option<TextureShareOptions>,
+    ) -> Result<(), FlutterEmbedderError> {
+        let Some(options) = options else {
+            self.export_texture = None;
+            return Ok(());
+        };
+        let device = unsafe { self.texture.GetDevice() }.map_err(|e| {
+            FlutterEmbedderError::OperationFailed(format!(
+                "cannot get the device of '{}': {e}",
+                self.name
+            ))
+        })?;
+        let export = ExportTexture::new(&device, self.width, self.height, options)
+            .map_err(FlutterEmbedderError::OperationFailed)?;
+        self.export_texture = Some(export);
+        Ok(())
+    }
+
+    /// Handle of the texture set up by `set_texture_sharing`. A resize creates a
+    /// new texture with a new handle, so consumers re-open it after one.
+    pub fn shared_texture_handle(&self) -> Option<HANDLE> {
+        self.export_texture.as_ref().map(|export| export.handle.0)
+    }
+
     /// Checks if the ANGLE device has been lost due to D3D11 device removal.
     /// When this returns true, rendering is disabled and recovery may be attempted.
     pub fn is_device_lost(&self) -> bool {
@@ -516,6 +1044,14 @@ impl FlutterOverlay {
                 self.primitive_renderer
                     .set_primitives(group_id, &[], vertices);
             }
+            PrimitiveType::Points | PrimitiveType::TriangleStrip => {
+                self.primitive_renderer.set_topology_primitives_ex(
+                    group_id,
+                    vertices,
+                    topology,
+                    PrimitiveOptions::default(),
+                );
+            }
         }
     }
 
@@ -535,9 +1071,49 @@ impl FlutterOverlay {
                 self.primitive_renderer
                     .set_primitives_ex(group_id, &[], vertices, options);
             }
+            PrimitiveType::Points | PrimitiveType::TriangleStrip => {
+                self.primitive_renderer
+                    .set_topology_primitives_ex(group_id, vertices, topology, options);
+            }
         }
     }
 
+    /// Sets a group of lines drawn `width_px` pixels wide. D3D11 lines are
+    /// always one pixel, so each segment is drawn as a screen-aligned quad.
+    pub fn set_thick_lines(
+        &mut self,
+        group_id: &str,
+        segments: &[(Vertex3D, Vertex3D)],
+        width_px: f32,
+        options: PrimitiveOptions,
+    ) {
+        self.primitive_renderer.set_thick_lines(group_id, segments, width_px, options);
+    }
+
+    /// Sets a group from shared `vertices` and `indices` into them, so a
+    /// vertex used by several triangles or lines is stored once.
+    pub fn set_indexed_primitives(
+        &mut self,
+        group_id: &str,
+        vertices: &[Vertex3D],
+        indices: &[u32],
+        topology: PrimitiveType,
+    ) {
+        self.primitive_renderer.set_indexed_primitives(group_id, vertices, indices, topology);
+    }
+
+    pub fn set_indexed_primitives_ex(
+        &mut self,
+        group_id: &str,
+        vertices: &[Vertex3D],
+        indices: &[u32],
+        topology: PrimitiveType,
+        options: PrimitiveOptions,
+    ) {
+        self.primitive_renderer
+            .set_indexed_primitives_ex(group_id, vertices, indices, topology, options);
+    }
+
     pub fn clear_primitives(&mut self, group_id: &str) {
         self.primitive_renderer.clear_primitives(group_id);
     }
@@ -546,6 +1122,23 @@ impl FlutterOverlay {
         self.primitive_renderer.latch_buffers();
     }
 
+    /// Enables GPU picking of primitive groups; see `pick_primitive_at`.
+    pub fn set_picking_enabled(&mut self, enabled: bool) {
+        self.primitive_renderer.set_picking_enabled(enabled);
+    }
+
+    /// Returns the id of the primitive group drawn at render-target pixel
+    /// `(x, y)` in the last frame, nearest to the camera. Needs picking enabled
+    /// and blocks until the GPU has finished that frame.
+    pub fn pick_primitive_at(
+        &self,
+        context: &ID3D11DeviceContext,
+        x: u32,
+        y: u32,
+    ) -> Option<String> {
+        self.primitive_renderer.pick_at(context, x, y)
+    }
+
     /// Registers a new custom shader effect from compiled byte code.
     /// This shader can then be used to render primitives by referencing its `effect_id`.
     ///
@@ -579,6 +1172,40 @@ impl FlutterOverlay {
         );
     }
 
+    /// Registers every effect in `entries`, returning one result per entry in
+    /// the same order. A failing entry is logged and the rest are still
+    /// registered.
+    pub fn register_custom_shaders_from_manifest(
+        &mut self,
+        device: &ID3D11Device,
+        entries: &[ShaderManifestEntry],
+    ) -> Vec<Result<(), FlutterEmbedderError>> {
+        entries
+            .iter()
+            .map(|entry| {
+                let ps = entry.pixel_shader.load();
+                let vs = entry.vertex_shader.as_ref().map(ShaderSource::load).transpose();
+                let result = match (ps, vs) {
+                    (Ok(ps), Ok(vs)) => self.primitive_renderer.try_register_custom_pixel_shader(
+                        device,
+                        &entry.effect_id,
+                        vs.as_deref(),
+                        &ps,
+                        entry.constant_buffer_size,
+                        entry.blend_mode,
+                    ),
+                    (Err(e), _) | (_, Err(e)) => {
+                        Err(format!("Effect '{}': {e}", entry.effect_id))
+                    }
+                };
+                result.map_err(|message| {
+                    warn!("[FlutterOverlay:'{}'] {message}", self.name);
+                    FlutterEmbedderError::OperationFailed(message)
+                })
+            })
+            .collect()
+    }
+
     /// Sets a texture at a specific shader resource slot for a custom effect.
     /// This allows binding textures to non-sequential slots, enabling optional textures
     /// like normal maps, specular maps, etc.
@@ -811,6 +1438,56 @@ impl FlutterOverlay {
         self.text_renderer.clear_all_text();
     }
 
+    /// Draws a short debug label with the built-in bitmap font.
+    ///
+    /// The label is centered on `world_pos`, laid out along +X / +Y, and stored
+    /// in `group_id` like any other text group, so it is picked up by the next
+    /// latch and can be cleared with `clear_text(DEBUG_FONT_ID, group_id)` or by
+    /// passing an empty string. Only printable ASCII is rendered. The font atlas
+    /// is uploaded on first use.
+    ///
+    /// # Arguments
+    /// * `group_id` - Unique identifier for this label (for updates/removal)
+    /// * `text` - The label text
+    /// * `world_pos` - The 3D position the label is centered on
+    /// * `color` - RGBA color for the text
+    /// * `size` - Height of one character cell in world units
+    ///
+    /// # Example
+    /// ```rust
+    /// overlay.draw_text_3d("enemy_42", "Enemy #42", [10.0, 2.5, 4.0], [1.0, 0.2, 0.2, 1.0], 0.5)?;
+    /// ```
+    pub fn draw_text_3d(
+        &mut self,
+        group_id: &str,
+        text: &str,
+        world_pos: [f32; 3],
+        color: [f32; 4],
+        size: f32,
+    ) -> Result<(), FlutterEmbedderError> {
+        let font_atlas = self
+            .text_renderer
+            .ensure_debug_font()
+            .map_err(FlutterEmbedderError::OperationFailed)?;
+
+        let vertices = generate_text_vertices_aligned(
+            text,
+            font_atlas,
+            TextStyle3D {
+                position: world_pos,
+                scale: size,
+                color,
+                right: [1.0, 0.0, 0.0],
+                up: [0.0, 1.0, 0.0],
+                align: 0.0,
+            },
+        );
+
+        self.text_renderer
+            .set_text(DEBUG_FONT_ID, group_id, &vertices, PrimitiveOptions::default());
+        Ok(())
+    }
+
     /// Latches the current text buffers for rendering.
     /// Must be called before rendering to prepare the text geometry.
     pub fn latch_queued_text(&mut self) {
@@ -824,6 +1501,55 @@ impl FlutterOverlay {
         handle_keyboard_event(self, msg, wparam, lparam)
     }
 
+    /// Processes `WM_IME_STARTCOMPOSITION`, `WM_IME_COMPOSITION` or
+    /// `WM_IME_ENDCOMPOSITION` for this overlay's focused text field, and moves
+    /// the IME candidate window to the caret.
+    /// # Returns
+    /// `true` if a text field took the message, `false` otherwise.
+    pub fn handle_ime_event(&self, hwnd: HWND, msg: u32, lparam: LPARAM) -> bool {
+        handle_ime_event(self, hwnd, msg, lparam)
+    }
+
+    /// Sends a synthetic key press or release to this overlay using Flutter key codes.
+    ///
+    /// # Arguments
+    /// * `logical_key` - Flutter logical key id (e.g. `0x1_0000_0302` for arrow left)
+    /// * `physical_key` - Flutter physical (USB HID) key id (e.g. `0x7_0050`)
+    /// * `down` - `true` for a press, `false` for a release
+    /// * `character` - Text produced by the press, also committed to a focused text field
+    ///
+    /// # Returns
+    /// `false` if the engine is not running, `true` otherwise.
+    pub fn inject_key(
+        &self,
+        logical_key: u64,
+        physical_key: u64,
+        down: bool,
+        character: Option<char>,
+    ) -> bool {
+        inject_key_event(self, logical_key, physical_key, down, character)
+    }
+
+    /// Releases every key, mouse button and touch contact Flutter still thinks
+    /// is held, for when the host window loses focus and the matching up
+    /// messages go elsewhere. `hwnd` is the host window, used to place the
+    /// mouse cancel.
+    pub fn release_held_input(&self, hwnd: HWND) {
+        let released = release_pressed_keys(self, &self.view0_keyboard);
+        if released > 0 {
+            debug!("[FlutterOverlay] Released {released} held key(s) for '{}'", self.name);
+        }
+        cancel_pressed_buttons(self, hwnd);
+        cancel_touch_contacts(self);
+    }
+
+    /// Returns the contents and selection of the focused text field, or `None`
+    /// if no text field has input focus.
+    pub fn get_editing_state(&self) -> Option<EditingState> {
+        let guard = self.active_text_input.lock().unwrap_or_else(|p| p.into_inner());
+        guard.as_ref().map(|active| active.model.editing_state())
+    }
+
     /// Processes a Windows mouse pointer message for this overlay.
     /// # Returns
     /// `true` if Flutter handled the event, `false` otherwise.
@@ -870,6 +1596,47 @@ impl FlutterOverlay {
         }
     }
 
+    /// Makes the engine re-read the installed system fonts, so text picks up
+    /// fonts added or changed since the engine started.
+    pub fn reload_system_fonts(&self) -> Result<(), FlutterEmbedderError> {
+        if self.engine.0.is_null() {
+            return Err(FlutterEmbedderError::EngineNotRunning);
+        }
+        let result_code =
+            unsafe { (self.engine_dll.FlutterEngineReloadSystemFonts)(self.engine.0) };
+        if result_code == e::FlutterEngineResult_kSuccess {
+            info!("[FlutterOverlay] Reloaded system fonts for '{}'", self.name);
+            Ok(())
+        } else {
+            let err_msg = format!(
+                "FlutterEngineReloadSystemFonts FAILED for '{}': {:?}",
+                self.name, result_code
+            );
+            error!("[FlutterOverlay] {err_msg}");
+            Err(FlutterEmbedderError::OperationFailed(err_msg))
+        }
+    }
+
+    /// Tells the engine the system is low on memory, so it drops its image and
+    /// raster caches and Dart runs a GC.
+    pub fn notify_low_memory(&self) -> Result<(), FlutterEmbedderError> {
+        if self.engine.0.is_null() {
+            return Err(FlutterEmbedderError::EngineNotRunning);
+        }
+        let result_code =
+            unsafe { (self.engine_dll.FlutterEngineNotifyLowMemoryWarning)(self.engine.0) };
+        if result_code == e::FlutterEngineResult_kSuccess {
+            Ok(())
+        } else {
+            let err_msg = format!(
+                "FlutterEngineNotifyLowMemoryWarning FAILED for '{}': {:?}",
+                self.name, result_code
+            );
+            error!("[FlutterOverlay] {err_msg}");
+            Err(FlutterEmbedderError::OperationFailed(err_msg))
+        }
+    }
+
     /// Retrieves the D3D11 Shader Resource View (SRV) for this overlay's texture.
     /// Used by the host application to render the Flutter UI.
     /// This clones the SRV (calls AddRef). The caller must Release it.
@@ -893,6 +1660,10 @@ impl FlutterOverlay {
     /// are properly deallocated. After calling this method, the overlay instance is consumed
     /// and can no longer be used.
     ///
+    /// The shared engine pointer is cleared before the engine is shut down, so
+    /// a task runner thread that could not be joined stops running tasks
+    /// instead of calling into a dead engine.
+    ///
     /// # Returns
     /// `Ok(())` on successful shutdown or if the overlay was already effectively shut down.
     /// Logs an error if `FlutterEngineShutdown` reports a failure but still attempts to complete resource cleanup.
@@ -903,6 +1674,7 @@ impl FlutterOverlay {
             );
             return Ok(());
         }
+        self.engine_atomic_ptr.store(std::ptr::null_mut(), Ordering::SeqCst);
 
         if let Some(handle_arc) = self.task_runner_thread {
             if let Ok(handle) = Arc::try_unwrap(handle_arc) {
@@ -961,6 +1733,15 @@ impl FlutterOverlay {
         (self.width, self.height)
     }
 
+    /// Returns the size the overlay is composited at, i.e. its dimensions
+    /// multiplied by the render scale.
+    pub fn get_scaled_dimensions(&self) -> (u32, u32) {
+        (
+            (self.width as f32 * self.render_scale).round() as u32,
+            (self.height as f32 * self.render_scale).round() as u32,
+        )
+    }
+
     /// Returns the current (x, y) position of the overlay.
     /// The counterpart to the `set_position` method you implemented.
     pub fn get_position(&self) -> (i32, i32) {
@@ -975,17 +1756,558 @@ impl FlutterOverlay {
         send_platform_message(self, channel, message)
     }
 
+    /// Sends a platform message and passes Dart's reply to `callback`, with
+    /// `None` for a `null` reply. The callback may never run, as a channel with
+    /// no Dart handler buffers the message rather than answering it.
+    pub fn send_platform_message_with_response<F>(
+        &self,
+        channel: &str,
+        message: &[u8],
+        callback: F,
+    ) -> Result<(), FlutterEmbedderError>
+    where
+        F: FnOnce(Option<&[u8]>) + Send + 'static,
+    {
+        send_platform_message_with_response(self, channel, message, Box::new(callback))
+    }
+
     /// Sets the visibility of the overlay.
     /// An invisible overlay will not be rendered and will not receive input.
+    /// Returns the latest state restoration data the framework stored, if any.
+    pub fn get_restoration_data(&self) -> Option<Vec<u8>> {
+        self.restoration_data
+            .lock()
+            .ok()
+            .and_then(|data| data.clone())
+    }
+
+    /// Replaces the state restoration data. It is served to the framework's next
+    /// `get` and pushed to a running app so restorable widgets pick it up.
+    pub fn set_restoration_data(&self, data: Vec<u8>) {
+        let push = encode_push_call(&data);
+        if let Ok(mut stored) = self.restoration_data.lock() {
+            *stored = Some(data);
+        }
+        if self.engine.0.is_null() {
+            return;
+        }
+        if let Ok(mut queue) = self.pending_platform_messages.lock() {
+            queue.push_back(PendingPlatformMessage {
+                channel: RESTORATION_CHANNEL.to_string(),
+                payload_bytes: push,
+            });
+        } else {
+            error!("[FlutterOverlay:'{}'] Failed to queue restoration push", self.name);
+        }
+        self.task_queue_state.waker.wake_up();
+    }
+
+    /// Cancels a running fade, leaving the overlay fully opaque and interactive.
     pub fn set_visibility(&mut self, is_visible: bool) {
+        if self.fade.take().is_some() {
+            self.post_processor.set_opacity(1.0);
+            self.input_enabled = true;
+        }
         self.visible = is_visible;
     }
 
+    /// Sets how opaque the overlay's UI is drawn, `0.0` to `1.0`. Cancels a running fade.
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.fade = None;
+        self.post_processor.set_opacity(opacity);
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.post_processor.opacity()
+    }
+
+    /// Crops the composited UI by the alpha of `mask`, or removes the mask with
+    /// `None`. The mask is stretched over the overlay's bounds.
+    pub fn set_stencil_mask(&mut self, mask: Option<ID3D11ShaderResourceView>) {
+        self.post_processor.set_mask(mask);
+    }
+
+    /// Sets how the composite pass samples past the overlay's edges.
+    /// `border_color` is only used with `AddressMode::Border`.
+    pub fn set_sampler_address_mode(
+        &mut self,
+        mode: AddressMode,
+        border_color: [f32; 4],
+    ) -> Result<(), FlutterEmbedderError> {
+        self.post_processor
+            .set_sampler_address_mode(mode, border_color)
+            .map_err(|e| {
+                FlutterEmbedderError::OperationFailed(format!(
+                    "cannot recreate the sampler of '{}': {e}",
+                    self.name
+                ))
+            })
+    }
+
+    /// Puts the overlay's placement and composite state back to how a new
+    /// overlay starts: at `(0, 0)`, unrotated and at native size, with no effect,
+    /// stencil mask or input offset, fully opaque, and clamped sampling. A
+    /// running fade is cancelled.
+    pub fn reset_display_state(&mut self) {
+        self.set_position(0, 0);
+        self.effect_config = EffectConfig::default();
+        self.effect_frames_remaining = 0;
+        self.effect_total_frames = 0;
+        if self.fade.take().is_some() {
+            self.input_enabled = true;
+        }
+        self.post_processor.set_opacity(1.0);
+        self.post_processor.set_mask(None);
+        self.input_transform = InputTransform::default();
+        self.render_scale = 1.0;
+        self.rotation_degrees = 0.0;
+        self.sync_quad_placement();
+        if let Err(e) = self.set_sampler_address_mode(AddressMode::Clamp, [0.0; 4]) {
+            warn!("[FlutterOverlay:'{}'] {e}", self.name);
+        }
+    }
+
+    /// Maps pointer input from window client coordinates into the overlay when
+    /// it is drawn at `(offset_x, offset_y)` scaled by `scale`. A non-positive
+    /// or non-finite `scale` is rejected.
+    pub fn set_input_offset(
+        &mut self,
+        offset_x: f64,
+        offset_y: f64,
+        scale: f64,
+    ) -> Result<(), FlutterEmbedderError> {
+        if !(scale.is_finite() && scale > 0.0) {
+            return Err(FlutterEmbedderError::OperationFailed(format!(
+                "input scale must be positive, got {scale}"
+            )));
+        }
+        self.input_transform = InputTransform {
+            offset_x,
+            offset_y,
+            scale,
+        };
+        Ok(())
+    }
+
+    /// Composites the overlay at `scale` times its size, anchored at its
+    /// position, without resizing the engine surface. Pointer input is mapped
+    /// back through the scale. A non-positive or non-finite `scale` is rejected.
+    pub fn set_render_scale(&mut self, scale: f32) -> Result<(), FlutterEmbedderError> {
+        if !(scale.is_finite() && scale > 0.0) {
+            return Err(FlutterEmbedderError::OperationFailed(format!(
+                "render scale must be positive, got {scale}"
+            )));
+        }
+        self.render_scale = scale;
+        self.sync_quad_placement();
+        Ok(())
+    }
+
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    /// Composites the overlay rotated clockwise by `degrees` around the center
+    /// of its (scaled) bounds. Pointer input is rotated back, so clicks land
+    /// where the rotated pixels appear. A non-finite angle is rejected.
+    pub fn set_rotation(&mut self, degrees: f32) -> Result<(), FlutterEmbedderError> {
+        if !degrees.is_finite() {
+            return Err(FlutterEmbedderError::OperationFailed(format!(
+                "rotation must be finite, got {degrees}"
+            )));
+        }
+        self.rotation_degrees = degrees % 360.0;
+        self.sync_quad_placement();
+        Ok(())
+    }
+
+    pub fn rotation(&self) -> f32 {
+        self.rotation_degrees
+    }
+
+    /// Untransformed overlays keep filling the viewport; scaled or rotated
+    /// ones are drawn as a quad at their own bounds.
+    fn sync_quad_placement(&mut self) {
+        let placed = self.render_scale != 1.0 || self.rotation_degrees != 0.0;
+        self.post_processor.set_placement(placed, self.rotation_degrees);
+    }
+
+    /// Maps a pointer position in window client coordinates into the
+    /// overlay: applies the input offset, then undoes the rotation and the
+    /// render scale.
+    pub(crate) fn client_to_overlay(&self, x: f64, y: f64) -> (f64, f64) {
+        let (x, y) = self.input_transform.client_to_overlay(x, y);
+        let (x, y) = self.unrotate(x, y);
+        unscale_about((self.x, self.y), self.render_scale, x, y)
+    }
+
+    /// Inverse of `client_to_overlay`: where overlay point `(x, y)` is drawn in
+    /// the host's client area.
+    pub(crate) fn overlay_to_client(&self, x: f64, y: f64) -> (f64, f64) {
+        let (ox, oy) = (self.x as f64, self.y as f64);
+        let scale = self.render_scale as f64;
+        let (x, y) = (ox + (x - ox) * scale, oy + (y - oy) * scale);
+        let (width, height) = self.get_scaled_dimensions();
+        let center = (ox + width as f64 / 2.0, oy + height as f64 / 2.0);
+        let (x, y) = unrotate_about(center, -self.rotation_degrees, x, y);
+        let transform = self.input_transform;
+        (
+            transform.offset_x + x * transform.scale,
+            transform.offset_y + y * transform.scale,
+        )
+    }
+
+    /// Rotates a point back around the center of the overlay's scaled bounds.
+    pub(crate) fn unrotate(&self, x: f64, y: f64) -> (f64, f64) {
+        let (width, height) = self.get_scaled_dimensions();
+        let center = (
+            self.x as f64 + width as f64 / 2.0,
+            self.y as f64 + height as f64 / 2.0,
+        );
+        unrotate_about(center, self.rotation_degrees, x, y)
+    }
+
+    /// Whether screen point `(x, y)` lies on the overlay's scaled and rotated
+    /// bounds.
+    pub(crate) fn contains_point(&self, x: f64, y: f64) -> bool {
+        let (width, height) = self.get_scaled_dimensions();
+        let (px, py) = self.unrotate(x, y);
+        let (ox, oy) = (self.x as f64, self.y as f64);
+        px >= ox && px < ox + width as f64 && py >= oy && py < oy + height as f64
+    }
+
+    /// Selects how satellite views wait for their GL work when presenting.
+    /// Takes effect from the next presented frame.
+    pub fn set_present_sync_mode(&self, mode: SyncMode) {
+        self.present_fence_sync
+            .store(mode == SyncMode::Fence, Ordering::Relaxed);
+    }
+
+    /// Requires a hover change to persist for more than `frames` frames before
+    /// the hover flag (and with it the cursor and auto-raise) follows. `0`
+    /// disables the debounce.
+    pub fn set_hover_debounce(&mut self, frames: u32) {
+        match self.hover_debounce.lock() {
+            Ok(mut debounce) => *debounce = HoverDebounce::new(frames),
+            Err(poisoned) => {
+                error!("[FlutterOverlay:'{}'] hover debounce lock poisoned: {poisoned}", self.name)
+            }
+        }
+    }
+
+    /// Rebuilds the semantics tree used for hit testing at most once per
+    /// `interval`, keeping the latest update. `Duration::ZERO` applies every
+    /// update.
+    pub fn set_semantics_update_interval(&mut self, interval: Duration) {
+        match self.semantics_throttle.lock() {
+            Ok(mut throttle) => *throttle = SemanticsThrottle::new(interval),
+            Err(poisoned) => error!(
+                "[FlutterOverlay:'{}'] semantics throttle lock poisoned: {poisoned}",
+                self.name
+            ),
+        }
+    }
+
+    /// Turns the engine's semantics tree on or off. It is on from startup,
+    /// and the hover detection over interactive widgets depends on it, so
+    /// disabling it also stops the hand cursor and hover-based auto-raise. The
+    /// last tree is dropped when disabling, as Flutter stops updating it.
+    pub fn set_semantics_enabled(&self, enabled: bool) -> Result<(), FlutterEmbedderError> {
+        if self.engine.0.is_null() {
+            return Err(FlutterEmbedderError::EngineNotRunning);
+        }
+        let result_code = unsafe {
+            (self.engine_dll.FlutterEngineUpdateSemanticsEnabled)(self.engine.0, enabled)
+        };
+        if result_code != e::FlutterEngineResult_kSuccess {
+            let err_msg = format!(
+                "FlutterEngineUpdateSemanticsEnabled FAILED for '{}': {:?}",
+                self.name, result_code
+            );
+            error!("[FlutterOverlay] {err_msg}");
+            return Err(FlutterEmbedderError::OperationFailed(err_msg));
+        }
+        if !enabled && let Ok(mut tree) = self.semantics_tree_data.lock() {
+            tree.clear();
+        }
+        Ok(())
+    }
+
+    /// Returns a copy of the current semantics tree, ordered by node id. Node
+    /// `0` is the root.
+    pub fn semantics_snapshot(&self) -> Vec<ProcessedSemanticsNode> {
+        let mut nodes: Vec<ProcessedSemanticsNode> = match self.semantics_tree_data.lock() {
+            Ok(tree) => tree.values().cloned().collect(),
+            Err(_) => return Vec::new(),
+        };
+        nodes.sort_by_key(|node| node.id);
+        nodes
+    }
+
+    /// Sizes the system cursors by the input scale, so a downscaled overlay
+    /// gets a matching smaller cursor. Off by default.
+    pub fn set_cursor_scaling(&mut self, enabled: bool) {
+        self.cursor_scaling = enabled;
+    }
+
+    /// Whether pointer and keyboard input should be routed to this overlay.
+    pub fn accepts_input(&self) -> bool {
+        self.visible && self.input_enabled
+    }
+
+    /// Fades the overlay out over `duration`. Input stops immediately; the
+    /// overlay is hidden once the fade completes, and shows at full opacity
+    /// again on the next `set_visibility(true)`.
+    pub fn fade_out(&mut self, duration: Duration) {
+        if !self.visible {
+            return;
+        }
+        let now = Instant::now();
+        self.input_enabled = false;
+        self.fade = Some(OverlayFade::new(self.opacity(), 0.0, duration, now));
+        self.advance_fade(now);
+    }
+
+    /// Shows the overlay and fades it in over `duration`. A hidden overlay
+    /// starts transparent, one that is fading out reverses from its current
+    /// opacity. Input is enabled once the fade completes.
+    pub fn fade_in(&mut self, duration: Duration) {
+        if self.visible && self.fade.is_none() && self.opacity() >= 1.0 {
+            return;
+        }
+        if !self.visible {
+            self.post_processor.set_opacity(0.0);
+            self.visible = true;
+        }
+        let now = Instant::now();
+        self.input_enabled = false;
+        self.fade = Some(OverlayFade::new(self.opacity(), 1.0, duration, now));
+        self.advance_fade(now);
+    }
+
+    /// Applies the running fade's opacity, and its end-of-fade state change
+    /// once it has finished.
+    pub(crate) fn advance_fade(&mut self, now: Instant) {
+        let Some(fade) = self.fade else {
+            return;
+        };
+        if !fade.is_finished(now) {
+            self.post_processor.set_opacity(fade.opacity_at(now));
+            return;
+        }
+        self.fade = None;
+        self.input_enabled = true;
+        self.post_processor.set_opacity(1.0);
+        if fade.is_fade_out() {
+            self.visible = false;
+        }
+    }
+
     /// Checks if the overlay is currently marked as visible.
     pub fn is_visible(&self) -> bool {
         self.visible
     }
 
+    /// Returns how long this overlay's startup phases took.
+    ///
+    /// # Example
+    /// ```rust
+    /// let t = overlay.get_startup_timings();
+    /// println!("engine run took {:.1} ms", t.engine_run_ms);
+    /// ```
+    pub fn get_startup_timings(&self) -> StartupTimings {
+        self.note_first_frame();
+        StartupTimings {
+            first_frame_ms: self.first_frame_ms.get().copied(),
+            ..self.startup_timings
+        }
+    }
+
+    /// Frame upload timings recorded by `tick`. All zeroes while the overlay is
+    /// hidden; showing it again starts a fresh window.
+    pub fn get_fps_stats(&self) -> FpsStats {
+        if !self.visible {
+            return FpsStats::default();
+        }
+        self.frame_timer
+            .lock()
+            .map(|timer| timer.stats())
+            .unwrap_or_default()
+    }
+
+    /// crate(INTERNAL) Drops the recorded timings, for hosts that stop calling
+    /// `tick` while the overlay is hidden.
+    pub(crate) fn reset_fps_stats(&self) {
+        if let Ok(mut timer) = self.frame_timer.lock() {
+            timer.reset();
+        }
+    }
+
+    /// When `tick` last copied a new frame into the overlay texture, i.e. the
+    /// earliest point the host could draw it. `None` before the first frame.
+    pub fn get_last_present_time(&self) -> Option<Instant> {
+        present_instant(
+            self.startup_started_at,
+            self.last_present_nanos.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Renders exactly one fresh frame into the overlay texture, blocking for
+    /// up to `timeout`. Waits for the task runner to run every task that is
+    /// already due and send the queued platform messages, so they are handled
+    /// before the frame, then schedules a frame, waits for the engine to present it and ticks
+    /// it in. A frame that was pending before the call is ticked away first.
+    ///
+    /// For tests and tooling that need deterministic frames; it sleeps while
+    /// waiting, so keep it out of a render loop.
+    pub fn render_one_frame(
+        &self,
+        context: &ID3D11DeviceContext,
+        timeout: Duration,
+    ) -> Result<(), FlutterEmbedderError> {
+        if self.engine.0.is_null() {
+            return Err(FlutterEmbedderError::EngineNotRunning);
+        }
+        let deadline = Instant::now() + timeout;
+        let wait = |what: &str| {
+            if Instant::now() >= deadline {
+                return Err(FlutterEmbedderError::OperationFailed(format!(
+                    "'{}' timed out waiting for {what}",
+                    self.name
+                )));
+            }
+            std::thread::sleep(Duration::from_millis(1));
+            Ok(())
+        };
+
+        let queue = &self.task_queue_state;
+        let runner_busy = || {
+            let now = unsafe { (self.engine_dll.FlutterEngineGetCurrentTime)() };
+            queue.has_due_task(now)
+                || self
+                    .pending_platform_messages
+                    .lock()
+                    .is_ok_and(|messages| !messages.is_empty())
+        };
+        queue.waker.wake_up();
+        while runner_busy() {
+            wait("the task runner")?;
+        }
+
+        self.tick(context);
+        self.request_frame()?;
+        while !self.has_untaken_frame() {
+            wait("a frame")?;
+        }
+        self.tick(context);
+        Ok(())
+    }
+
+    /// Whether the engine has presented a frame `tick` hasn't copied yet.
+    fn has_untaken_frame(&self) -> bool {
+        match self.renderer_type {
+            RendererType::Software => self.software_frame_dirty.load(Ordering::Acquire),
+            RendererType::OpenGL => {
+                self.angle_frame_presented.load(Ordering::Acquire)
+                    > self.angle_frame_copied.load(Ordering::Relaxed)
+            }
+        }
+    }
+
+    /// Whether the engine is running and put a frame into the texture within
+    /// `timeout`. A freshly started engine gets `timeout` for its first frame.
+    /// An idle UI presents nothing, so it reads as `false` as well.
+    pub fn is_presenting(&self, timeout: Duration) -> bool {
+        !self.engine.0.is_null()
+            && presented_recently(
+                self.get_last_present_time(),
+                self.startup_started_at,
+                Instant::now(),
+                timeout,
+            )
+    }
+
+    /// Saves the overlay as a PNG at `path`. With `include_effects` the image
+    /// is drawn the way the compositor would draw it (effect, opacity and
+    /// stencil mask), on a transparent background; otherwise it is the raw
+    /// Flutter frame. The pixels are written in `alpha_mode`; PNG expects
+    /// `AlphaMode::Straight`, `AlphaMode::Premultiplied` keeps the engine's
+    /// bytes. `time` drives animated effects. Blocks until the GPU has
+    /// finished the copy.
+    pub fn save_png(
+        &mut self,
+        context: &ID3D11DeviceContext,
+        path: &Path,
+        include_effects: bool,
+        alpha_mode: AlphaMode,
+        time: f32,
+    ) -> Result<(), FlutterEmbedderError> {
+        let source = if include_effects {
+            render_composited(self, context, time)?
+        } else {
+            self.texture.clone()
+        };
+        let rgba = read_texture_rgba(context, &source, self.width, self.height)?;
+        let size = (self.width, self.height);
+        save_rgba_png(path, size, rgba, self.native_alpha_mode(), alpha_mode)
+    }
+
+    /// Returns the overlay's current frame as tightly packed BGRA8, `width * 4`
+    /// bytes per row, in `alpha_mode` (the engine renders premultiplied).
+    /// Software overlays copy their CPU frame buffer; OpenGL overlays read the
+    /// texture back through a temporary staging texture, which waits for the
+    /// GPU.
+    pub fn capture_pixels(
+        &self,
+        context: &ID3D11DeviceContext,
+        alpha_mode: AlphaMode,
+    ) -> Result<Vec<u8>, FlutterEmbedderError> {
+        let len = self.width as usize * self.height as usize * 4;
+        let mut pixels = match &self.pixel_buffer {
+            Some(buffer) => buffer.get(..len).map(<[u8]>::to_vec).ok_or_else(|| {
+                FlutterEmbedderError::OperationFailed(format!(
+                    "frame buffer holds {} bytes, expected {len}",
+                    buffer.len()
+                ))
+            }),
+            None => read_texture_bgra(context, &self.texture, self.width, self.height),
+        }?;
+        convert_alpha(&mut pixels, self.native_alpha_mode(), alpha_mode);
+        Ok(pixels)
+    }
+
+    /// URI to attach DevTools to, once the engine reports the Dart VM service
+    /// as listening. Needs `EngineFlags::enable_vm_service` outside debug mode.
+    pub fn get_vm_service_uri(&self) -> Option<String> {
+        self.vm_service_uri.lock().ok()?.clone()
+    }
+
+    /// Records the first-frame time the first time a frame is seen.
+    fn note_first_frame(&self) {
+        if self.first_frame_ms.get().is_some() || !self.has_first_frame() {
+            return;
+        }
+        let elapsed_ms = self.startup_started_at.elapsed().as_secs_f64() * 1000.0;
+        if self.first_frame_ms.set(elapsed_ms).is_ok() {
+            info!(
+                "[FlutterOverlay:'{}'] First frame after {elapsed_ms:.1} ms.",
+                self.name
+            );
+        }
+    }
+
+    /// The alpha convention of this overlay's texture contents.
+    ///
+    /// Flutter composites in premultiplied alpha on both renderers, so this is
+    /// currently always [`AlphaMode::Premultiplied`]. Use [`convert_alpha`] to
+    /// hand pixels read back from the texture to tools that expect straight
+    /// alpha.
+    pub fn native_alpha_mode(&self) -> AlphaMode {
+        match self.renderer_type {
+            RendererType::OpenGL | RendererType::Software => AlphaMode::Premultiplied,
+        }
+    }
+
     /// Returns true once the renderer has produced at least one frame.
     pub fn has_first_frame(&self) -> bool {
         match self.renderer_type {
@@ -1035,6 +2357,74 @@ impl FlutterOverlay {
             }
         }
     }
+
+    /// Like [`register_channel_handler`](Self::register_channel_handler), but
+    /// the handler can fail. `Ok` bytes are sent back unchanged; `Err((code,
+    /// message))` is answered with a standard method codec error envelope, so a
+    /// Dart `MethodChannel.invokeMethod` throws a `PlatformException` with that
+    /// code and message.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// my_overlay.register_channel_handler_result("my_game/load_save", |payload| {
+    ///     let slot = payload.first().copied().unwrap_or(0);
+    ///     load_save(slot).map_err(|e| ("LOAD_FAILED".to_string(), e.to_string()))
+    /// });
+    /// ```
+    pub fn register_channel_handler_result<F>(&mut self, channel: &str, handler: F)
+    where
+        F: Fn(Vec<u8>) -> Result<Vec<u8>, (String, String)> + Send + Sync + 'static,
+    {
+        self.register_channel_handler(channel, move |payload| match handler(payload) {
+            Ok(response) => response,
+            Err((code, message)) => mc_encode_error_envelope(&code, &message),
+        });
+    }
+
+    /// Registers a handler for a channel that uses Dart's `JSONMethodCodec`.
+    /// The call is decoded into its method name and `args`, and the value the
+    /// handler returns is sent back as the result. A malformed call is answered
+    /// with an error envelope without calling the handler.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// my_overlay.register_json_method_handler("my_game/inventory", |method, args| {
+    ///     match method {
+    ///         "count" => json!(inventory_count(args["item"].as_str().unwrap_or(""))),
+    ///         _ => Value::Null,
+    ///     }
+    /// });
+    /// ```
+    pub fn register_json_method_handler<F>(&mut self, channel: &str, handler: F)
+    where
+        F: Fn(&str, Value) -> Value + Send + Sync + 'static,
+    {
+        self.register_channel_handler(channel, move |payload| {
+            json_method_response(&payload, &handler)
+        });
+    }
+
+    /// Removes the handler registered for `channel`. Returns whether one existed.
+    /// Messages on the channel then fall through to the built-in handling.
+    pub fn unregister_channel_handler(&mut self, channel: &str) -> bool {
+        match self.message_handlers.lock() {
+            Ok(mut handlers) => handlers.remove(channel).is_some(),
+            Err(poisoned) => {
+                log::error!(
+                    "Failed to acquire lock on message_handlers because it was poisoned: {poisoned}"
+                );
+                false
+            }
+        }
+    }
+
+    /// Whether a custom handler is registered for `channel`.
+    pub fn has_channel_handler(&self, channel: &str) -> bool {
+        self.message_handlers
+            .lock()
+            .map(|handlers| handlers.contains_key(channel))
+            .unwrap_or(false)
+    }
     /// Triggers a "Hot Restart" for the running Flutter application.
     ///
     /// This works by sending a specific message on the "app/lifecycle" platform
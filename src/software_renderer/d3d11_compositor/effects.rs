@@ -5,10 +5,21 @@
 //! [`post_processing_renderer`](super::post_processing_renderer) applies it. Every
 //! params struct implements [`Default`], so start from the defaults and tweak only
 //! the fields you care about.
+//!
+//! All config types are serde-serializable so tuned settings can be saved with
+//! [`EffectConfig::to_bytes`] and restored with [`EffectConfig::from_bytes`].
+//!
+//! For a generic settings UI, [`PostEffect::all`] lists the effects and
+//! [`effect_param_descriptors`] describes each tunable value as a named `f32`
+//! with a range. [`EffectParams::values`] and [`EffectParams::from_values`]
+//! convert between params and those flat values, in descriptor order.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Error as JsonError, from_slice, to_vec};
 
 /// Which post-processing effect a renderer should run. Used as a selector; the
 /// per-effect tuning lives in [`EffectParams`].
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PostEffect {
     /// No effect; draw the source unchanged.
     #[default]
@@ -19,10 +30,150 @@ pub enum PostEffect {
     WarpField,
     /// Glitch distortion (shares [`HologramParams`] tuning).
     Glitch,
+    /// Saturation, contrast, brightness and tint adjustment.
+    ColorGrade,
+}
+
+impl PostEffect {
+    /// Every effect, in declaration order.
+    pub fn all() -> &'static [PostEffect] {
+        &[
+            PostEffect::Passthrough,
+            PostEffect::Hologram,
+            PostEffect::WarpField,
+            PostEffect::Glitch,
+            PostEffect::ColorGrade,
+        ]
+    }
+}
+
+/// One tunable value of an effect, for building a slider.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParamDescriptor {
+    /// Field name; color channels are named like `color_inner.g`.
+    pub name: &'static str,
+    pub min: f32,
+    pub max: f32,
+    pub default: f32,
+}
+
+/// Name and slider range of each [`HologramParams`] field, in
+/// [`hologram_fields`] order.
+const HOLOGRAM_RANGES: [(&str, f32, f32); 3] = [
+    ("aberration_amount", 0.0, 0.05),
+    ("glitch_speed", 0.0, 50.0),
+    ("scanline_intensity", 0.0, 1.0),
+];
+
+fn hologram_fields(p: &mut HologramParams) -> [&mut f32; 3] {
+    [
+        &mut p.aberration_amount,
+        &mut p.glitch_speed,
+        &mut p.scanline_intensity,
+    ]
+}
+
+/// Name and slider range of each [`WarpFieldParams`] value, in
+/// [`warp_field_fields`] order.
+const WARP_FIELD_RANGES: [(&str, f32, f32); 19] = [
+    ("speed", 0.0, 10.0),
+    ("density", 0.1, 10.0),
+    ("star_base_size", 0.0, 0.02),
+    ("glow_falloff", 0.0, 20.0),
+    ("pulse_speed", 0.0, 10.0),
+    ("motion_blur_strength", 0.0, 0.5),
+    ("depth_blur_strength", 0.0, 0.01),
+    ("base_alpha", 0.0, 1.0),
+    ("color_inner.r", 0.0, 1.0),
+    ("color_inner.g", 0.0, 1.0),
+    ("color_inner.b", 0.0, 1.0),
+    ("color_outer.r", 0.0, 1.0),
+    ("color_outer.g", 0.0, 1.0),
+    ("color_outer.b", 0.0, 1.0),
+    ("color_pulse.r", 0.0, 1.0),
+    ("color_pulse.g", 0.0, 1.0),
+    ("color_pulse.b", 0.0, 1.0),
+    ("bloom_threshold", 0.0, 1.0),
+    ("bloom_intensity", 0.0, 4.0),
+];
+
+fn warp_field_fields(p: &mut WarpFieldParams) -> [&mut f32; 19] {
+    let [inner_r, inner_g, inner_b] = &mut p.color_inner;
+    let [outer_r, outer_g, outer_b] = &mut p.color_outer;
+    let [pulse_r, pulse_g, pulse_b] = &mut p.color_pulse;
+    [
+        &mut p.speed,
+        &mut p.density,
+        &mut p.star_base_size,
+        &mut p.glow_falloff,
+        &mut p.pulse_speed,
+        &mut p.motion_blur_strength,
+        &mut p.depth_blur_strength,
+        &mut p.base_alpha,
+        inner_r,
+        inner_g,
+        inner_b,
+        outer_r,
+        outer_g,
+        outer_b,
+        pulse_r,
+        pulse_g,
+        pulse_b,
+        &mut p.bloom_threshold,
+        &mut p.bloom_intensity,
+    ]
+}
+
+/// Name and slider range of each [`ColorGradeParams`] value, in
+/// [`color_grade_fields`] order.
+const COLOR_GRADE_RANGES: [(&str, f32, f32); 6] = [
+    ("saturation", 0.0, 2.0),
+    ("contrast", 0.0, 2.0),
+    ("brightness", -1.0, 1.0),
+    ("tint.r", 0.0, 1.0),
+    ("tint.g", 0.0, 1.0),
+    ("tint.b", 0.0, 1.0),
+];
+
+fn color_grade_fields(p: &mut ColorGradeParams) -> [&mut f32; 6] {
+    let [tint_r, tint_g, tint_b] = &mut p.tint;
+    [
+        &mut p.saturation,
+        &mut p.contrast,
+        &mut p.brightness,
+        tint_r,
+        tint_g,
+        tint_b,
+    ]
+}
+
+fn param_ranges(effect: PostEffect) -> &'static [(&'static str, f32, f32)] {
+    match effect {
+        PostEffect::Passthrough => &[],
+        PostEffect::Hologram | PostEffect::Glitch => &HOLOGRAM_RANGES,
+        PostEffect::WarpField => &WARP_FIELD_RANGES,
+        PostEffect::ColorGrade => &COLOR_GRADE_RANGES,
+    }
+}
+
+/// The tunable values of `effect` with their ranges and defaults. The order
+/// matches [`EffectParams::values`]. `Passthrough` has none.
+pub fn effect_param_descriptors(effect: PostEffect) -> Vec<ParamDescriptor> {
+    let defaults = EffectParams::default_for(effect).values();
+    param_ranges(effect)
+        .iter()
+        .zip(defaults)
+        .map(|(&(name, min, max), default)| ParamDescriptor {
+            name,
+            min,
+            max,
+            default,
+        })
+        .collect()
 }
 
 /// Where an effect is applied.
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub enum EffectTarget {
     /// Apply across the whole render target.
     #[default]
@@ -33,7 +184,8 @@ pub enum EffectTarget {
 }
 
 /// Tuning for the [`PostEffect::Hologram`] and [`PostEffect::Glitch`] effects.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct HologramParams {
     /// Strength of the RGB channel split. Default `0.005`.
     pub aberration_amount: f32,
@@ -53,8 +205,34 @@ impl Default for HologramParams {
     }
 }
 
+/// Tuning for the [`PostEffect::ColorGrade`] effect. The defaults leave the
+/// image unchanged.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColorGradeParams {
+    /// `0.0` is grayscale, `1.0` unchanged, above `1.0` more vivid. Default `1.0`.
+    pub saturation: f32,
+    /// Spread around mid-gray; `1.0` is unchanged. Default `1.0`.
+    pub contrast: f32,
+    /// Added to every channel after contrast. Default `0.0`.
+    pub brightness: f32,
+    /// Per-channel multiplier applied last, linear RGB. Default `[1.0, 1.0, 1.0]`.
+    pub tint: [f32; 3],
+}
+
+impl Default for ColorGradeParams {
+    fn default() -> Self {
+        Self {
+            saturation: 1.0,
+            contrast: 1.0,
+            brightness: 0.0,
+            tint: [1.0, 1.0, 1.0],
+        }
+    }
+}
+
 /// The selected effect together with its tuning parameters.
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub enum EffectParams {
     /// No effect (passthrough).
     #[default]
@@ -65,10 +243,67 @@ pub enum EffectParams {
     WarpField(WarpFieldParams),
     /// Glitch effect; reuses [`HologramParams`].
     Glitch(HologramParams),
+    /// Color grading with the given tuning.
+    ColorGrade(ColorGradeParams),
+}
+
+impl EffectParams {
+    /// `effect` with its default tuning.
+    pub fn default_for(effect: PostEffect) -> Self {
+        match effect {
+            PostEffect::Passthrough => EffectParams::None,
+            PostEffect::Hologram => EffectParams::Hologram(HologramParams::default()),
+            PostEffect::WarpField => EffectParams::WarpField(WarpFieldParams::default()),
+            PostEffect::Glitch => EffectParams::Glitch(HologramParams::default()),
+            PostEffect::ColorGrade => EffectParams::ColorGrade(ColorGradeParams::default()),
+        }
+    }
+
+    /// The effect these params select.
+    pub fn effect(&self) -> PostEffect {
+        match self {
+            EffectParams::None => PostEffect::Passthrough,
+            EffectParams::Hologram(_) => PostEffect::Hologram,
+            EffectParams::WarpField(_) => PostEffect::WarpField,
+            EffectParams::Glitch(_) => PostEffect::Glitch,
+            EffectParams::ColorGrade(_) => PostEffect::ColorGrade,
+        }
+    }
+
+    /// The tunable values, in [`effect_param_descriptors`] order.
+    pub fn values(&self) -> Vec<f32> {
+        match *self {
+            EffectParams::None => Vec::new(),
+            EffectParams::Hologram(mut p) | EffectParams::Glitch(mut p) => {
+                hologram_fields(&mut p).map(|v| *v).to_vec()
+            }
+            EffectParams::WarpField(mut p) => warp_field_fields(&mut p).map(|v| *v).to_vec(),
+            EffectParams::ColorGrade(mut p) => color_grade_fields(&mut p).map(|v| *v).to_vec(),
+        }
+    }
+
+    /// Builds params for `effect` from values in [`effect_param_descriptors`]
+    /// order. Each value is clamped to its range; missing values keep their
+    /// defaults and extra ones are ignored.
+    pub fn from_values(effect: PostEffect, values: &[f32]) -> Self {
+        let mut params = Self::default_for(effect);
+        let ranges = param_ranges(effect);
+        let fields: Vec<&mut f32> = match &mut params {
+            EffectParams::None => Vec::new(),
+            EffectParams::Hologram(p) | EffectParams::Glitch(p) => hologram_fields(p).into(),
+            EffectParams::WarpField(p) => warp_field_fields(p).into(),
+            EffectParams::ColorGrade(p) => color_grade_fields(p).into(),
+        };
+        for ((field, &value), &(_, min, max)) in fields.into_iter().zip(values).zip(ranges) {
+            *field = value.clamp(min, max);
+        }
+        params
+    }
 }
 
 /// A complete post-processing description: what to draw and where.
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct EffectConfig {
     /// The region the effect covers.
     pub target: EffectTarget,
@@ -76,9 +311,22 @@ pub struct EffectConfig {
     pub params: EffectParams,
 }
 
+impl EffectConfig {
+    /// Encodes the config as JSON. Fields missing on load fall back to their
+    /// defaults, so saved settings survive new tuning fields being added.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        to_vec(self).unwrap_or_default()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, JsonError> {
+        from_slice(bytes)
+    }
+}
+
 /// Tuning for the [`PostEffect::WarpField`] effect (an animated starfield).
 /// Defaults give a usable look; override individual fields as needed.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct WarpFieldParams {
     /// Animation speed of the field. Default `1.0`.
     pub speed: f32,
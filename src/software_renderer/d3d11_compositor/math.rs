@@ -0,0 +1,62 @@
+//! View-projection builders for the primitive and text renderers.
+//!
+//! The matrices follow DirectXMath conventions: left-handed, row vectors
+//! (`v * M`), and clip-space depth from 0 at the near plane to 1 at the far
+//! plane. The renderers transpose the matrix before uploading it, so pass these
+//! as is; don't transpose them yourself. Combine a view and a projection with
+//! [`view_projection`], which multiplies them in the order row vectors need.
+
+use directx_math::{
+    XMMatrix, XMMatrixLookAtLH, XMMatrixMultiply, XMMatrixPerspectiveFovLH, XMMatrixRotationZ,
+    XMMatrixScaling, XMMatrixTranslation, XMVectorSet,
+};
+
+/// Maps pixel coordinates to clip space: `(0, 0)` is the top-left corner of a
+/// `width` x `height` target, y grows downwards, and z stays in `0.0..=1.0`.
+pub fn ortho_screen(width: f32, height: f32) -> XMMatrix {
+    // directx_math has no off-center orthographic projection, so scale pixels
+    // to a 2-unit span with y flipped, then move the origin to the top left.
+    XMMatrix(XMMatrixMultiply(
+        XMMatrixScaling(2.0 / width, -2.0 / height, 1.0),
+        &XMMatrixTranslation(-1.0, 1.0, 0.0),
+    ))
+}
+
+/// Maps the unit square onto the `width` x `height` pixel rectangle at
+/// `(x, y)`, rotated clockwise by `degrees` around the rectangle's center.
+/// Follow it with [`ortho_screen`] to reach clip space.
+pub fn rotated_quad(x: f32, y: f32, width: f32, height: f32, degrees: f32) -> XMMatrix {
+    let steps = [
+        XMMatrixScaling(width, height, 1.0),
+        XMMatrixRotationZ(degrees.to_radians()),
+        XMMatrixTranslation(x + width / 2.0, y + height / 2.0, 0.0),
+    ];
+    XMMatrix(
+        steps
+            .iter()
+            .fold(XMMatrixTranslation(-0.5, -0.5, 0.0), |m, step| {
+                XMMatrixMultiply(m, step)
+            }),
+    )
+}
+
+/// Perspective projection with a vertical field of view of `fov_y` radians.
+/// `aspect` is width / height; `near` and `far` must be positive and distinct.
+pub fn perspective(fov_y: f32, aspect: f32, near: f32, far: f32) -> XMMatrix {
+    XMMatrix(XMMatrixPerspectiveFovLH(fov_y, aspect, near, far))
+}
+
+/// View matrix for a camera at `eye` looking at `target`. `up` must not be
+/// parallel to the viewing direction.
+pub fn look_at(eye: [f32; 3], target: [f32; 3], up: [f32; 3]) -> XMMatrix {
+    XMMatrix(XMMatrixLookAtLH(
+        XMVectorSet(eye[0], eye[1], eye[2], 1.0),
+        XMVectorSet(target[0], target[1], target[2], 1.0),
+        XMVectorSet(up[0], up[1], up[2], 0.0),
+    ))
+}
+
+/// `view` followed by `projection`, ready to pass as `view_projection_matrix`.
+pub fn view_projection(view: &XMMatrix, projection: &XMMatrix) -> XMMatrix {
+    XMMatrix(XMMatrixMultiply(view.0, &projection.0))
+}
@@ -25,27 +25,38 @@
 //!   pixel-shader effects.
 //! * [`primitive_presets`]: helpers that build common shapes (boxes, spheres,
 //!   lines) into `Vertex3D` buffers.
+//! * [`shader_manifest`]: [`shader_manifest::ShaderManifestEntry`] lists for
+//!   registering several custom effects at once.
 //! * [`text_3d_renderer`]: font-atlas-based 3D text
 //!   ([`text_3d_renderer::TexturedVertex3D`], [`text_3d_renderer::GlyphInfo`]).
 //! * [`text_presets`]: builds text vertices from a string and a font atlas.
+//! * [`debug_font`]: a built-in 8x8 bitmap font for debug labels, used by
+//!   [`FlutterOverlay::draw_text_3d`].
 //! * [`effects`]: post-processing effect configuration (hologram, warp field,
 //!   glitch) applied to the composited UI.
 //! * [`post_processing_renderer`]: the renderer that applies those effects.
+//! * [`math`]: view-projection builders ([`math::perspective`],
+//!   [`math::look_at`], [`math::ortho_screen`]) in the convention the renderers expect.
 //! * [`traits`]: the shared [`traits::Renderer`] interface and per-frame
 //!   [`traits::FrameParams`].
 //!
 //! [`FlutterOverlay`]: crate::software_renderer::api::FlutterOverlay
 //! [`FlutterOverlay::set_primitives`]: crate::software_renderer::api::FlutterOverlay::set_primitives
 //! [`FlutterOverlay::set_text`]: crate::software_renderer::api::FlutterOverlay::set_text
+//! [`FlutterOverlay::draw_text_3d`]: crate::software_renderer::api::FlutterOverlay::draw_text_3d
 //! [`FlutterOverlay::latch_queued_primitives`]: crate::software_renderer::api::FlutterOverlay::latch_queued_primitives
 //! [`FlutterOverlayManagerHandle`]: crate::software_renderer::overlays_manager_api::FlutterOverlayManagerHandle
 //! [`FlutterOverlayManagerHandle::render_primitives`]: crate::software_renderer::overlays_manager_api::FlutterOverlayManagerHandle::render_primitives
 //! [`FlutterOverlayManagerHandle::render_ui`]: crate::software_renderer::overlays_manager_api::FlutterOverlayManagerHandle::render_ui
 
+pub mod debug_font;
 pub mod effects;
+pub mod math;
 pub mod post_processing_renderer;
 pub mod primitive_3d_renderer;
 pub mod primitive_presets;
+pub(crate) mod primitive_picking;
+pub mod shader_manifest;
 pub mod text_3d_renderer;
 pub mod text_presets;
 pub mod traits;
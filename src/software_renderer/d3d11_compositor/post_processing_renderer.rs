@@ -1,15 +1,35 @@
 use crate::software_renderer::d3d11_compositor::effects::{
     EffectConfig, EffectParams, EffectTarget, PostEffect,
 };
+use crate::software_renderer::d3d11_compositor::math::{
+    ortho_screen, rotated_quad, view_projection,
+};
 use crate::software_renderer::d3d11_compositor::traits::{FrameParams, Renderer};
 use directx_math::{
     XMMatrix, XMMatrixIdentity, XMMatrixMultiply, XMMatrixOrthographicLH, XMMatrixScaling,
-    XMMatrixTranslation,
+    XMMatrixTranslation, XMMatrixTranspose,
 };
+use log::error;
 use std::{collections::HashMap, mem};
 use windows::Win32::Foundation::BOOL;
-use windows::Win32::Graphics::Direct3D::D3D11_PRIMITIVE_TOPOLOGY_TRIANGLESTRIP;
+use windows::Win32::Graphics::Direct3D::Fxc::D3DCompile;
+use windows::Win32::Graphics::Direct3D::{D3D11_PRIMITIVE_TOPOLOGY_TRIANGLESTRIP, ID3DBlob};
 use windows::Win32::Graphics::Direct3D11::*;
+use windows::core::{Error as WindowsError, PCSTR, Result as WindowsResult, s};
+
+/// Source of the color grade pixel shader, compiled at renderer creation.
+const COLOR_GRADE_HLSL: &str = include_str!("./shaders/color_grade_ps.hlsl");
+/// Source of the vertex shader for placed quads, compiled at renderer creation.
+const PLACED_QUAD_HLSL: &str = include_str!("./shaders/placed_quad_vs.hlsl");
+
+/// Below this the composited alpha rounds to zero in an 8-bit target, so the
+/// draw is skipped instead of paying for the two-pass translucent path.
+const MIN_VISIBLE_OPACITY: f32 = 0.5 / 255.0;
+
+/// Whether a draw at `opacity` would leave no visible trace.
+pub(crate) fn is_invisible(opacity: f32) -> bool {
+    opacity < MIN_VISIBLE_OPACITY
+}
 
 /// Placement + frame parameters for [`PostProcessRenderer::render_texture_internal`].
 struct RenderTextureParams {
@@ -20,6 +40,42 @@ struct RenderTextureParams {
     screen_width: f32,
     screen_height: f32,
     time: f32,
+    opacity: f32,
+}
+
+/// How the composite sampler treats texture coordinates outside the overlay.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AddressMode {
+    /// Repeat the edge texels. The default.
+    #[default]
+    Clamp,
+    /// Return the border color. A transparent border keeps edge texels from
+    /// smearing across a rotated or partly off-screen overlay.
+    Border,
+}
+
+/// Sampler description for the composite pass. `border_color` only applies to
+/// [`AddressMode::Border`].
+pub(crate) fn composite_sampler_desc(
+    mode: AddressMode,
+    border_color: [f32; 4],
+) -> D3D11_SAMPLER_DESC {
+    let address = match mode {
+        AddressMode::Clamp => D3D11_TEXTURE_ADDRESS_CLAMP,
+        AddressMode::Border => D3D11_TEXTURE_ADDRESS_BORDER,
+    };
+    D3D11_SAMPLER_DESC {
+        Filter: D3D11_FILTER_MIN_MAG_MIP_LINEAR,
+        AddressU: address,
+        AddressV: address,
+        AddressW: address,
+        ComparisonFunc: D3D11_COMPARISON_NEVER,
+        BorderColor: match mode {
+            AddressMode::Clamp => [0.0; 4],
+            AddressMode::Border => border_color,
+        },
+        ..Default::default()
+    }
 }
 
 #[repr(C)]
@@ -47,12 +103,41 @@ pub struct GpuParameters {
     pub color_pulse: [f32; 3],
     pub bloom_threshold: f32,
     pub bloom_intensity: f32,
+    pub _color_grade_padding: f32,
+    /// Starts register `c12` of the color grade shader.
+    pub saturation: f32,
+    pub contrast: f32,
+    pub brightness: f32,
+    pub _brightness_padding: f32,
+    /// Register `c13`.
+    pub tint: [f32; 3],
+    pub _tint_padding: f32,
 }
 
 #[derive(Clone)]
 pub struct PostProcessRenderer {
     blend_state: ID3D11BlendState,
+    /// Pass 1 of a translucent draw: writes `alpha * opacity` into the target's alpha.
+    coverage_blend_state: ID3D11BlendState,
+    /// Pass 2 of a translucent draw: blends colour by the alpha written in pass 1.
+    dest_alpha_blend_state: ID3D11BlendState,
+    /// Multiplies the target's alpha by the mask's alpha, between the two
+    /// passes of a translucent or masked draw.
+    mask_blend_state: ID3D11BlendState,
+    /// Opacity applied to every queued draw, `0.0` to `1.0`.
+    opacity: f32,
+    /// Texture whose alpha gates the queued draws, stretched over each draw's
+    /// bounds (UV `(0, 0)` is the top-left corner of the overlay).
+    mask: Option<ID3D11ShaderResourceView>,
     vs: ID3D11VertexShader,
+    /// Draws the quad where `world_projection` puts it. `None` if it failed to
+    /// compile, in which case placed draws fill the viewport like the others.
+    placed_vs: Option<ID3D11VertexShader>,
+    /// Whether the queued quads are drawn at their own bounds instead of
+    /// filling the viewport.
+    placed: bool,
+    /// Clockwise rotation of placed quads around their centers, in degrees.
+    rotation_degrees: f32,
     pixel_shaders: HashMap<PostEffect, ID3D11PixelShader>,
     sampler_state: ID3D11SamplerState,
     parameters_buffer: ID3D11Buffer,
@@ -64,9 +149,17 @@ impl PostProcessRenderer {
     pub fn new(device: &ID3D11Device) -> Self {
         Self {
             blend_state: Self::create_blend_state(device),
+            coverage_blend_state: Self::create_coverage_blend_state(device),
+            dest_alpha_blend_state: Self::create_dest_alpha_blend_state(device),
+            mask_blend_state: Self::create_mask_blend_state(device),
+            opacity: 1.0,
+            mask: None,
             vs: Self::load_vertex_shader(device),
+            placed_vs: Self::load_placed_vertex_shader(device),
+            placed: false,
+            rotation_degrees: 0.0,
             pixel_shaders: Self::load_pixel_shaders(device),
-            sampler_state: Self::create_sampler_state(device),
+            sampler_state: Self::create_sampler_state(device, AddressMode::Clamp, [0.0; 4]),
             parameters_buffer: Self::create_parameters_buffer(device),
             queued_draws: Vec::new(),
         }
@@ -85,6 +178,45 @@ impl PostProcessRenderer {
             .push((srv.clone(), *config, x, y, width, height));
     }
 
+    /// Sets the opacity the queued textures are drawn with. `1.0` draws them as
+    /// before; anything lower costs a second draw, and near zero skips drawing.
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// Sets (or with `None`, removes) the mask texture whose alpha crops the
+    /// queued draws. Masked draws take three passes.
+    pub fn set_mask(&mut self, mask: Option<ID3D11ShaderResourceView>) {
+        self.mask = mask;
+    }
+
+    pub fn has_mask(&self) -> bool {
+        self.mask.is_some()
+    }
+
+    /// Draws the queued quads at the bounds passed to `queue_texture_render`,
+    /// rotated clockwise by `rotation_degrees`, when `placed` is set. Otherwise
+    /// each quad fills the viewport, which is the default.
+    pub fn set_placement(&mut self, placed: bool, rotation_degrees: f32) {
+        self.placed = placed;
+        self.rotation_degrees = rotation_degrees;
+    }
+
+    /// Replaces the composite sampler with one using `mode`.
+    pub fn set_sampler_address_mode(
+        &mut self,
+        mode: AddressMode,
+        border_color: [f32; 4],
+    ) -> WindowsResult<()> {
+        let device = unsafe { self.sampler_state.GetDevice() }?;
+        self.sampler_state = Self::create_sampler_state(&device, mode, border_color);
+        Ok(())
+    }
+
     fn render_texture_internal(
         &self,
         context: &ID3D11DeviceContext,
@@ -100,6 +232,7 @@ impl PostProcessRenderer {
             screen_width,
             screen_height,
             time,
+            opacity,
         } = params;
         let mut gpu_params = GpuParameters {
             world_projection: XMMatrix(XMMatrixIdentity()),
@@ -124,6 +257,13 @@ impl PostProcessRenderer {
             color_pulse: [1.0, 0.7, 0.0],
             bloom_threshold: 0.5,
             bloom_intensity: 0.8,
+            _color_grade_padding: 0.0,
+            saturation: 1.0,
+            contrast: 1.0,
+            brightness: 0.0,
+            _brightness_padding: 0.0,
+            tint: [1.0; 3],
+            _tint_padding: 0.0,
         };
 
         let effect_type = match config.params {
@@ -156,6 +296,13 @@ impl PostProcessRenderer {
                 gpu_params.bloom_intensity = p.bloom_intensity;
                 PostEffect::WarpField
             }
+            EffectParams::ColorGrade(p) => {
+                gpu_params.saturation = p.saturation;
+                gpu_params.contrast = p.contrast;
+                gpu_params.brightness = p.brightness;
+                gpu_params.tint = p.tint;
+                PostEffect::ColorGrade
+            }
         };
 
         if let EffectTarget::Widget(bounds) = config.target {
@@ -178,17 +325,30 @@ impl PostProcessRenderer {
                 Some(&mut old_sample_mask),
             );
 
-            context.OMSetBlendState(&self.blend_state, None, 0xffffffff);
+            let placed_vs = self.placed_vs.as_ref().filter(|_| self.placed);
             context.IASetPrimitiveTopology(D3D11_PRIMITIVE_TOPOLOGY_TRIANGLESTRIP);
-            context.VSSetShader(&self.vs, None);
+            context.VSSetShader(placed_vs.unwrap_or(&self.vs), None);
             context.PSSetShader(pixel_shader, None);
 
-            let proj_matrix = XMMatrixOrthographicLH(screen_width, screen_height, 0.0, 1.0);
-            let scale_matrix = XMMatrixScaling(width as f32, height as f32, 1.0);
-            let translate_matrix = XMMatrixTranslation(x as f32, y as f32, 0.0);
-            let world_matrix = XMMatrixMultiply(scale_matrix, &translate_matrix);
-            gpu_params.world_projection =
-                XMMatrix(XMMatrixMultiply(world_matrix, &proj_matrix));
+            if placed_vs.is_some() {
+                let quad = rotated_quad(
+                    x as f32,
+                    y as f32,
+                    width as f32,
+                    height as f32,
+                    self.rotation_degrees,
+                );
+                let screen = ortho_screen(screen_width, screen_height);
+                let quad_to_clip = view_projection(&quad, &screen);
+                gpu_params.world_projection = XMMatrix(XMMatrixTranspose(quad_to_clip.0));
+            } else {
+                let proj_matrix = XMMatrixOrthographicLH(screen_width, screen_height, 0.0, 1.0);
+                let scale_matrix = XMMatrixScaling(width as f32, height as f32, 1.0);
+                let translate_matrix = XMMatrixTranslation(x as f32, y as f32, 0.0);
+                let world_matrix = XMMatrixMultiply(scale_matrix, &translate_matrix);
+                gpu_params.world_projection =
+                    XMMatrix(XMMatrixMultiply(world_matrix, &proj_matrix));
+            }
 
             let mut mapped_resource = D3D11_MAPPED_SUBRESOURCE::default();
             context
@@ -209,7 +369,30 @@ impl PostProcessRenderer {
             context.PSSetShaderResources(0, Some(&[Some(srv.clone())]));
             context.PSSetSamplers(0, Some(&[Some(self.sampler_state.clone())]));
 
-            context.Draw(4, 0);
+            if opacity >= 1.0 && self.mask.is_none() {
+                context.OMSetBlendState(&self.blend_state, None, 0xffffffff);
+                context.Draw(4, 0);
+            } else {
+                // The shaders have no opacity or mask input, so scale coverage
+                // with fixed-function passes: stash `alpha * opacity` in the
+                // target's alpha channel, multiply in the mask's alpha, then
+                // blend colour against the result.
+                let factor = [opacity; 4];
+                context.OMSetBlendState(&self.coverage_blend_state, Some(&factor), 0xffffffff);
+                context.Draw(4, 0);
+                if let Some(mask) = &self.mask
+                    && let Some(passthrough) = self.pixel_shaders.get(&PostEffect::Passthrough)
+                {
+                    context.PSSetShader(passthrough, None);
+                    context.PSSetShaderResources(0, Some(&[Some(mask.clone())]));
+                    context.OMSetBlendState(&self.mask_blend_state, None, 0xffffffff);
+                    context.Draw(4, 0);
+                    context.PSSetShader(pixel_shader, None);
+                    context.PSSetShaderResources(0, Some(&[Some(srv.clone())]));
+                }
+                context.OMSetBlendState(&self.dest_alpha_blend_state, None, 0xffffffff);
+                context.Draw(4, 0);
+            }
 
             context.OMSetBlendState(
                 old_blend_state.as_ref(),
@@ -242,6 +425,77 @@ impl PostProcessRenderer {
         blend_state.unwrap()
     }
 
+    fn create_coverage_blend_state(device: &ID3D11Device) -> ID3D11BlendState {
+        let desc = D3D11_BLEND_DESC {
+            RenderTarget: [D3D11_RENDER_TARGET_BLEND_DESC {
+                BlendEnable: BOOL(1),
+                SrcBlend: D3D11_BLEND_ZERO,
+                DestBlend: D3D11_BLEND_ONE,
+                BlendOp: D3D11_BLEND_OP_ADD,
+                SrcBlendAlpha: D3D11_BLEND_BLEND_FACTOR,
+                DestBlendAlpha: D3D11_BLEND_ZERO,
+                BlendOpAlpha: D3D11_BLEND_OP_ADD,
+                RenderTargetWriteMask: D3D11_COLOR_WRITE_ENABLE_ALPHA.0 as u8,
+            }; 8],
+            ..Default::default()
+        };
+        let mut blend_state: Option<ID3D11BlendState> = None;
+        unsafe {
+            device
+                .CreateBlendState(&desc, Some(&mut blend_state))
+                .expect("CreateBlendState for coverage pass failed");
+        }
+        blend_state.unwrap()
+    }
+
+    fn create_dest_alpha_blend_state(device: &ID3D11Device) -> ID3D11BlendState {
+        let desc = D3D11_BLEND_DESC {
+            RenderTarget: [D3D11_RENDER_TARGET_BLEND_DESC {
+                BlendEnable: BOOL(1),
+                SrcBlend: D3D11_BLEND_DEST_ALPHA,
+                DestBlend: D3D11_BLEND_INV_DEST_ALPHA,
+                BlendOp: D3D11_BLEND_OP_ADD,
+                SrcBlendAlpha: D3D11_BLEND_ZERO,
+                DestBlendAlpha: D3D11_BLEND_ONE,
+                BlendOpAlpha: D3D11_BLEND_OP_ADD,
+                RenderTargetWriteMask: (D3D11_COLOR_WRITE_ENABLE_RED.0
+                    | D3D11_COLOR_WRITE_ENABLE_GREEN.0
+                    | D3D11_COLOR_WRITE_ENABLE_BLUE.0) as u8,
+            }; 8],
+            ..Default::default()
+        };
+        let mut blend_state: Option<ID3D11BlendState> = None;
+        unsafe {
+            device
+                .CreateBlendState(&desc, Some(&mut blend_state))
+                .expect("CreateBlendState for dest-alpha pass failed");
+        }
+        blend_state.unwrap()
+    }
+
+    fn create_mask_blend_state(device: &ID3D11Device) -> ID3D11BlendState {
+        let desc = D3D11_BLEND_DESC {
+            RenderTarget: [D3D11_RENDER_TARGET_BLEND_DESC {
+                BlendEnable: BOOL(1),
+                SrcBlend: D3D11_BLEND_ZERO,
+                DestBlend: D3D11_BLEND_ONE,
+                BlendOp: D3D11_BLEND_OP_ADD,
+                SrcBlendAlpha: D3D11_BLEND_DEST_ALPHA,
+                DestBlendAlpha: D3D11_BLEND_ZERO,
+                BlendOpAlpha: D3D11_BLEND_OP_ADD,
+                RenderTargetWriteMask: D3D11_COLOR_WRITE_ENABLE_ALPHA.0 as u8,
+            }; 8],
+            ..Default::default()
+        };
+        let mut blend_state: Option<ID3D11BlendState> = None;
+        unsafe {
+            device
+                .CreateBlendState(&desc, Some(&mut blend_state))
+                .expect("CreateBlendState for mask pass failed");
+        }
+        blend_state.unwrap()
+    }
+
     fn load_vertex_shader(device: &ID3D11Device) -> ID3D11VertexShader {
         let bytes = include_bytes!("./shaders/fullscreen_quad_vs.cso");
         let mut vs: Option<ID3D11VertexShader> = None;
@@ -292,18 +546,90 @@ impl PostProcessRenderer {
         }
         shaders.insert(PostEffect::Glitch, glitch_ps.unwrap());
 
+        // Without a compiled shader the effect falls back to passthrough.
+        match Self::compile_pixel_shader(device, COLOR_GRADE_HLSL) {
+            Ok(color_grade_ps) => {
+                shaders.insert(PostEffect::ColorGrade, color_grade_ps);
+            }
+            Err(e) => error!("[PostProcessRenderer] Compiling the color grade shader failed: {e}"),
+        }
+
         shaders
     }
 
-    fn create_sampler_state(device: &ID3D11Device) -> ID3D11SamplerState {
-        let desc = D3D11_SAMPLER_DESC {
-            Filter: D3D11_FILTER_MIN_MAG_MIP_LINEAR,
-            AddressU: D3D11_TEXTURE_ADDRESS_CLAMP,
-            AddressV: D3D11_TEXTURE_ADDRESS_CLAMP,
-            AddressW: D3D11_TEXTURE_ADDRESS_CLAMP,
-            ComparisonFunc: D3D11_COMPARISON_NEVER,
-            ..Default::default()
-        };
+    fn load_placed_vertex_shader(device: &ID3D11Device) -> Option<ID3D11VertexShader> {
+        let compiled = Self::compile_hlsl(PLACED_QUAD_HLSL, s!("VSMain"), s!("vs_4_0"))
+            .and_then(|bytes| unsafe {
+                let mut shader: Option<ID3D11VertexShader> = None;
+                device.CreateVertexShader(&bytes, None, Some(&mut shader))?;
+                Ok(shader.unwrap())
+            });
+        match compiled {
+            Ok(shader) => Some(shader),
+            Err(e) => {
+                error!("[PostProcessRenderer] Compiling the placed quad shader failed: {e}");
+                None
+            }
+        }
+    }
+
+    /// Compiles `source` for `ps_4_0` with entry point `PSMain`. The error
+    /// carries the compiler output.
+    fn compile_pixel_shader(
+        device: &ID3D11Device,
+        source: &str,
+    ) -> WindowsResult<ID3D11PixelShader> {
+        let bytes = Self::compile_hlsl(source, s!("PSMain"), s!("ps_4_0"))?;
+        let mut shader: Option<ID3D11PixelShader> = None;
+        unsafe { device.CreatePixelShader(&bytes, None, Some(&mut shader))? };
+        Ok(shader.unwrap())
+    }
+
+    /// Compiles `source` with D3DCompile and returns the byte code.
+    fn compile_hlsl(source: &str, entry: PCSTR, target: PCSTR) -> WindowsResult<Vec<u8>> {
+        let mut code: Option<ID3DBlob> = None;
+        let mut errors: Option<ID3DBlob> = None;
+        unsafe {
+            let compiled = D3DCompile(
+                source.as_ptr() as *const _,
+                source.len(),
+                None,
+                None,
+                None,
+                entry,
+                target,
+                0,
+                0,
+                &mut code,
+                Some(&mut errors),
+            );
+            if let Err(e) = compiled {
+                let message = errors
+                    .map(|blob| {
+                        let bytes = std::slice::from_raw_parts(
+                            blob.GetBufferPointer() as *const u8,
+                            blob.GetBufferSize(),
+                        );
+                        String::from_utf8_lossy(bytes).into_owned()
+                    })
+                    .unwrap_or_else(|| e.message());
+                return Err(WindowsError::new(e.code(), message));
+            }
+            let code = code.unwrap();
+            let bytes = std::slice::from_raw_parts(
+                code.GetBufferPointer() as *const u8,
+                code.GetBufferSize(),
+            );
+            Ok(bytes.to_vec())
+        }
+    }
+
+    fn create_sampler_state(
+        device: &ID3D11Device,
+        mode: AddressMode,
+        border_color: [f32; 4],
+    ) -> ID3D11SamplerState {
+        let desc = composite_sampler_desc(mode, border_color);
         let mut sampler_state: Option<ID3D11SamplerState> = None;
         unsafe {
             device
@@ -336,6 +662,10 @@ impl Renderer for PostProcessRenderer {
         if self.queued_draws.is_empty() {
             return;
         }
+        if is_invisible(self.opacity) {
+            self.queued_draws.clear();
+            return;
+        }
 
         for (srv, config, x, y, width, height) in &self.queued_draws {
             self.render_texture_internal(
@@ -350,6 +680,7 @@ impl Renderer for PostProcessRenderer {
                     screen_width: params.screen_width,
                     screen_height: params.screen_height,
                     time: params.time,
+                    opacity: self.opacity,
                 },
             );
         }
@@ -1,25 +1,46 @@
-use directx_math::{XMMatrix, XMMatrixTranspose};
+use directx_math::{
+    XMMatrix, XMMatrixInverse, XMMatrixTranspose, XMVector4Transform, XMVectorGetW,
+    XMVectorGetX, XMVectorGetY, XMVectorGetZ, XMVectorSet, XMVectorZero,
+};
 use std::{collections::HashMap, mem};
 use windows::core::PCSTR;
 use windows::Win32::{
     Foundation::BOOL,
     Graphics::{
-        Direct3D::{D3D11_PRIMITIVE_TOPOLOGY_LINELIST, D3D11_PRIMITIVE_TOPOLOGY_TRIANGLELIST},
+        Direct3D::{
+            D3D_PRIMITIVE_TOPOLOGY, D3D11_PRIMITIVE_TOPOLOGY_LINELIST,
+            D3D11_PRIMITIVE_TOPOLOGY_POINTLIST, D3D11_PRIMITIVE_TOPOLOGY_TRIANGLELIST,
+            D3D11_PRIMITIVE_TOPOLOGY_TRIANGLESTRIP,
+        },
         Direct3D11::*,
-        Dxgi::Common::{DXGI_FORMAT_R32G32B32A32_FLOAT, DXGI_FORMAT_R32G32B32_FLOAT},
+        Dxgi::Common::{
+            DXGI_FORMAT_R32_UINT, DXGI_FORMAT_R32G32B32A32_FLOAT, DXGI_FORMAT_R32G32B32_FLOAT,
+        },
     },
 };
 
+use crate::software_renderer::d3d11_compositor::primitive_picking::{
+    MAX_PICK_ID, PickingTarget, encode_pick_id,
+};
 use crate::software_renderer::d3d11_compositor::traits::{FrameParams, Renderer};
+use log::warn;
 
 /// Maximum number of vertices that can be stored in each vertex buffer.
 /// This must match the buffer_capacity used when creating the vertex buffers.
 const MAX_VERTEX_BUFFER_CAPACITY: usize = 65536;
 
+/// Maximum number of `u32` indices in the index buffer used by indexed groups.
+pub(crate) const MAX_INDEX_BUFFER_CAPACITY: usize = MAX_VERTEX_BUFFER_CAPACITY * 3;
+
 #[derive(Clone, Copy, Debug)]
 pub enum PrimitiveType {
     Triangles,
     Lines,
+    /// One pixel-sized point per vertex.
+    Points,
+    /// Each vertex after the first two forms a triangle with the two before
+    /// it. Every group is drawn as its own strip.
+    TriangleStrip,
 }
 
 /// Defines the blending mode for rendering custom primitives.
@@ -113,6 +134,115 @@ struct CustomEffectResources {
     blend_mode: BlendMode,
 }
 
+/// Vertices plus `u32` indices into them, for groups submitted with
+/// `set_indexed_primitives`. Shared vertices are stored once.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct IndexedGeometry {
+    pub(crate) vertices: Vec<Vertex3D>,
+    pub(crate) indices: Vec<u32>,
+}
+
+impl IndexedGeometry {
+    /// Appends a group, rebasing its indices past the vertices already held.
+    /// A group that would overflow either buffer is left out whole, since a
+    /// partial index list would reference missing vertices. Returns whether
+    /// the group was added.
+    pub(crate) fn append(&mut self, vertices: &[Vertex3D], indices: &[u32]) -> bool {
+        if self.vertices.len() + vertices.len() > MAX_VERTEX_BUFFER_CAPACITY
+            || self.indices.len() + indices.len() > MAX_INDEX_BUFFER_CAPACITY
+        {
+            return false;
+        }
+        let base = self.vertices.len() as u32;
+        self.vertices.extend_from_slice(vertices);
+        self.indices.extend(indices.iter().map(|i| i + base));
+        true
+    }
+
+    /// The vertices in index order, as a non-indexed draw would take them.
+    pub(crate) fn expand(&self) -> Vec<Vertex3D> {
+        self.indices
+            .iter()
+            .filter_map(|&i| self.vertices.get(i as usize).copied())
+            .collect()
+    }
+}
+
+/// Start and end of one thick line segment.
+type LineSegment = (Vertex3D, Vertex3D);
+
+/// A submitted thick line group: segments, width in pixels and options.
+type ThickLineGroup = (Vec<LineSegment>, f32, PrimitiveOptions);
+
+/// Expands each segment into a screen-aligned quad `width_px` pixels wide,
+/// returned as triangle-list vertices. `viewport` is the render target size in
+/// pixels. Segments with an end behind the camera, or of zero length on
+/// screen, are left out, as is everything if `view_projection` has no inverse.
+pub(crate) fn expand_thick_lines(
+    segments: &[(Vertex3D, Vertex3D)],
+    width_px: f32,
+    view_projection: &XMMatrix,
+    viewport: [f32; 2],
+) -> Vec<Vertex3D> {
+    let mut determinant = XMVectorZero();
+    let inverse = XMMatrixInverse(Some(&mut determinant), view_projection.0);
+    let determinant = XMVectorGetX(determinant);
+    if determinant == 0.0 || !determinant.is_finite() {
+        return Vec::new();
+    }
+
+    let to_clip = |v: &Vertex3D| {
+        let [x, y, z] = v.position;
+        let c = XMVector4Transform(XMVectorSet(x, y, z, 1.0), view_projection.0);
+        [XMVectorGetX(c), XMVectorGetY(c), XMVectorGetZ(c), XMVectorGetW(c)]
+    };
+    let from_clip = |[x, y, z, w]: [f32; 4]| {
+        let p = XMVector4Transform(XMVectorSet(x, y, z, w), inverse);
+        let w = XMVectorGetW(p);
+        [XMVectorGetX(p) / w, XMVectorGetY(p) / w, XMVectorGetZ(p) / w]
+    };
+
+    let mut vertices = Vec::with_capacity(segments.len() * 6);
+    for (start, end) in segments {
+        let (a, b) = (to_clip(start), to_clip(end));
+        if a[3] <= 0.0 || b[3] <= 0.0 {
+            continue;
+        }
+        // The direction is measured in pixels so the width stays even when
+        // the viewport isn't square.
+        let dx = (b[0] / b[3] - a[0] / a[3]) * viewport[0];
+        let dy = (b[1] / b[3] - a[1] / a[3]) * viewport[1];
+        let length = (dx * dx + dy * dy).sqrt();
+        if length <= f32::EPSILON {
+            continue;
+        }
+        // Half the width, perpendicular to the segment, in NDC units. It is
+        // scaled by w below so it stays the same after the perspective divide.
+        let offset = [-dy / length * width_px / viewport[0], dx / length * width_px / viewport[1]];
+        let shift = |c: [f32; 4], v: &Vertex3D, sign: f32| Vertex3D {
+            position: from_clip([
+                c[0] + sign * offset[0] * c[3],
+                c[1] + sign * offset[1] * c[3],
+                c[2],
+                c[3],
+            ]),
+            color: v.color,
+        };
+        let (a_minus, a_plus) = (shift(a, start, -1.0), shift(a, start, 1.0));
+        let (b_minus, b_plus) = (shift(b, end, -1.0), shift(b, end, 1.0));
+        vertices.extend_from_slice(&[a_minus, a_plus, b_plus, a_minus, b_plus, b_minus]);
+    }
+    vertices
+}
+
+/// One group's geometry recolored with its pick id, latched for the pick pass.
+#[derive(Clone)]
+struct PickBatch {
+    topology: PrimitiveType,
+    options: PrimitiveOptions,
+    vertices: Vec<Vertex3D>,
+}
+
 #[derive(Clone)]
 pub struct Primitive3DRenderer {
     vertex_shader: ID3D11VertexShader,
@@ -123,12 +253,30 @@ pub struct Primitive3DRenderer {
 
     vertex_buffer_triangles: ID3D11Buffer,
     vertex_buffer_lines: ID3D11Buffer,
+    vertex_buffer_indexed: ID3D11Buffer,
+    index_buffer: ID3D11Buffer,
 
     submit_groups_triangles: HashMap<String, (Vec<Vertex3D>, PrimitiveOptions)>,
     submit_groups_lines: HashMap<String, (Vec<Vertex3D>, PrimitiveOptions)>,
     render_buffer_triangles: HashMap<PrimitiveOptions, Vec<Vertex3D>>,
     render_buffer_lines: HashMap<PrimitiveOptions, Vec<Vertex3D>>,
 
+    submit_groups_points: HashMap<String, (Vec<Vertex3D>, PrimitiveOptions)>,
+    submit_groups_strips: HashMap<String, (Vec<Vertex3D>, PrimitiveOptions)>,
+    render_buffer_points: HashMap<PrimitiveOptions, Vec<Vertex3D>>,
+    /// One entry per strip group; joining strips would connect them.
+    render_strips: Vec<(PrimitiveOptions, Vec<Vertex3D>)>,
+
+    /// Segments and width in pixels. They are expanded to quads at draw time,
+    /// when the view-projection matrix is known.
+    submit_groups_thick_lines: HashMap<String, ThickLineGroup>,
+    render_thick_lines: Vec<(PrimitiveOptions, f32, Vec<LineSegment>)>,
+
+    submit_groups_triangles_indexed: HashMap<String, (IndexedGeometry, PrimitiveOptions)>,
+    submit_groups_lines_indexed: HashMap<String, (IndexedGeometry, PrimitiveOptions)>,
+    render_buffer_triangles_indexed: HashMap<PrimitiveOptions, IndexedGeometry>,
+    render_buffer_lines_indexed: HashMap<PrimitiveOptions, IndexedGeometry>,
+
     blend_state_transparent: ID3D11BlendState,
     blend_state_opaque: ID3D11BlendState,
     depth_stencil_state: ID3D11DepthStencilState,
@@ -150,6 +298,12 @@ pub struct Primitive3DRenderer {
     render_buffer_lines_custom: HashMap<(String, PrimitiveOptions), Vec<Vertex3D>>,
 
     custom_effects: HashMap<String, CustomEffectResources>,
+
+    picking_enabled: bool,
+    picking: Option<PickingTarget>,
+    /// Group ids by pick id - 1, as latched for the last pick pass.
+    pick_groups: Vec<String>,
+    pick_batches: Vec<PickBatch>,
 }
 
 impl Primitive3DRenderer {
@@ -226,6 +380,27 @@ impl Primitive3DRenderer {
                 .expect("Failed to create line vertex buffer");
         }
 
+        let mut vertex_buffer_indexed: Option<ID3D11Buffer> = None;
+        unsafe {
+            device
+                .CreateBuffer(&vertex_buffer_desc, None, Some(&mut vertex_buffer_indexed))
+                .expect("Failed to create indexed vertex buffer");
+        }
+
+        let index_buffer_desc = D3D11_BUFFER_DESC {
+            ByteWidth: (mem::size_of::<u32>() * MAX_INDEX_BUFFER_CAPACITY) as u32,
+            Usage: D3D11_USAGE_DYNAMIC,
+            BindFlags: D3D11_BIND_INDEX_BUFFER.0 as u32,
+            CPUAccessFlags: D3D11_CPU_ACCESS_WRITE.0 as u32,
+            ..Default::default()
+        };
+        let mut index_buffer: Option<ID3D11Buffer> = None;
+        unsafe {
+            device
+                .CreateBuffer(&index_buffer_desc, None, Some(&mut index_buffer))
+                .expect("Failed to create index buffer");
+        }
+
         let constant_buffer_desc = D3D11_BUFFER_DESC {
             ByteWidth: mem::size_of::<SceneConstants>() as u32,
             Usage: D3D11_USAGE_DYNAMIC,
@@ -387,12 +562,24 @@ impl Primitive3DRenderer {
             input_layout: input_layout.unwrap(),
             vertex_buffer_triangles: vertex_buffer_triangles.unwrap(),
             vertex_buffer_lines: vertex_buffer_lines.unwrap(),
+            vertex_buffer_indexed: vertex_buffer_indexed.unwrap(),
+            index_buffer: index_buffer.unwrap(),
             constant_buffer: constant_buffer.unwrap(),
             time_constant_buffer: time_constant_buffer.unwrap(),
             submit_groups_triangles: HashMap::new(),
             submit_groups_lines: HashMap::new(),
             render_buffer_triangles: HashMap::new(),
             render_buffer_lines: HashMap::new(),
+            submit_groups_points: HashMap::new(),
+            submit_groups_strips: HashMap::new(),
+            render_buffer_points: HashMap::new(),
+            render_strips: Vec::new(),
+            submit_groups_thick_lines: HashMap::new(),
+            render_thick_lines: Vec::new(),
+            submit_groups_triangles_indexed: HashMap::new(),
+            submit_groups_lines_indexed: HashMap::new(),
+            render_buffer_triangles_indexed: HashMap::new(),
+            render_buffer_lines_indexed: HashMap::new(),
             blend_state_transparent: blend_state_transparent.unwrap(),
             blend_state_opaque: blend_state_opaque.unwrap(),
             depth_stencil_state: depth_stencil_state.unwrap(),
@@ -408,9 +595,39 @@ impl Primitive3DRenderer {
             render_buffer_triangles_custom: HashMap::new(),
             render_buffer_lines_custom: HashMap::new(),
             custom_effects: HashMap::new(),
+            picking_enabled: false,
+            picking: None,
+            pick_groups: Vec::new(),
+            pick_batches: Vec::new(),
+        }
+    }
+
+    /// Turns the per-frame pick pass on or off. It costs a second draw of every
+    /// group and a GPU readback per `pick_at`, so it is off by default.
+    pub fn set_picking_enabled(&mut self, enabled: bool) {
+        self.picking_enabled = enabled;
+        if !enabled {
+            self.picking = None;
+            self.pick_groups.clear();
+            self.pick_batches.clear();
         }
     }
 
+    pub fn is_picking_enabled(&self) -> bool {
+        self.picking_enabled
+    }
+
+    /// Returns the group drawn nearest to the camera at render-target pixel
+    /// `(x, y)` in the last drawn frame. Stalls until the GPU has finished that
+    /// frame's pick pass.
+    pub fn pick_at(&self, context: &ID3D11DeviceContext, x: u32, y: u32) -> Option<String> {
+        if self.pick_batches.is_empty() {
+            return None;
+        }
+        let id = self.picking.as_ref()?.read_id(context, x, y)?;
+        self.pick_groups.get(id as usize - 1).cloned()
+    }
+
     pub fn set_primitives(
         &mut self,
         group_id: &str,
@@ -442,6 +659,110 @@ impl Primitive3DRenderer {
         }
     }
 
+    /// Sets a group of point or triangle strip primitives. Empty `vertices`
+    /// removes it. For triangle and line lists use `set_primitives_ex`.
+    pub fn set_topology_primitives_ex(
+        &mut self,
+        group_id: &str,
+        vertices: &[Vertex3D],
+        topology: PrimitiveType,
+        options: PrimitiveOptions,
+    ) {
+        let groups = match topology {
+            PrimitiveType::Triangles => {
+                return self.set_primitives_ex(group_id, vertices, &[], options);
+            }
+            PrimitiveType::Lines => {
+                return self.set_primitives_ex(group_id, &[], vertices, options);
+            }
+            PrimitiveType::Points => &mut self.submit_groups_points,
+            PrimitiveType::TriangleStrip => &mut self.submit_groups_strips,
+        };
+        if vertices.is_empty() {
+            groups.remove(group_id);
+        } else {
+            groups.insert(group_id.to_string(), (vertices.to_vec(), options));
+        }
+    }
+
+    /// Sets a group of lines drawn `width_px` pixels wide regardless of their
+    /// distance from the camera. Empty `segments` removes the group. Thick
+    /// lines are not drawn in the pick pass.
+    pub fn set_thick_lines(
+        &mut self,
+        group_id: &str,
+        segments: &[(Vertex3D, Vertex3D)],
+        width_px: f32,
+        options: PrimitiveOptions,
+    ) {
+        if segments.is_empty() {
+            self.submit_groups_thick_lines.remove(group_id);
+            return;
+        }
+        if !(width_px.is_finite() && width_px > 0.0) {
+            warn!("[Primitive3DRenderer] Group '{group_id}': invalid line width {width_px}");
+            return;
+        }
+        self.submit_groups_thick_lines
+            .insert(group_id.to_string(), (segments.to_vec(), width_px, options));
+    }
+
+    pub fn set_indexed_primitives(
+        &mut self,
+        group_id: &str,
+        vertices: &[Vertex3D],
+        indices: &[u32],
+        topology: PrimitiveType,
+    ) {
+        self.set_indexed_primitives_ex(
+            group_id,
+            vertices,
+            indices,
+            topology,
+            PrimitiveOptions::default(),
+        );
+    }
+
+    /// Sets an indexed group: `indices` select from `vertices`, three per
+    /// triangle or two per line. An empty `indices` removes the group. A group
+    /// with an index past the end of `vertices` is rejected.
+    pub fn set_indexed_primitives_ex(
+        &mut self,
+        group_id: &str,
+        vertices: &[Vertex3D],
+        indices: &[u32],
+        topology: PrimitiveType,
+        options: PrimitiveOptions,
+    ) {
+        let groups = match topology {
+            PrimitiveType::Triangles => &mut self.submit_groups_triangles_indexed,
+            PrimitiveType::Lines => &mut self.submit_groups_lines_indexed,
+            PrimitiveType::Points | PrimitiveType::TriangleStrip => {
+                warn!(
+                    "[Primitive3DRenderer] Group '{group_id}': \
+                     indexed {topology:?} is not supported"
+                );
+                return;
+            }
+        };
+        if indices.is_empty() {
+            groups.remove(group_id);
+            return;
+        }
+        if let Some(bad) = indices.iter().find(|&&i| i as usize >= vertices.len()) {
+            warn!(
+                "[Primitive3DRenderer] Group '{group_id}' has index {bad} but only {} vertices",
+                vertices.len()
+            );
+            return;
+        }
+        let geometry = IndexedGeometry {
+            vertices: vertices.to_vec(),
+            indices: indices.to_vec(),
+        };
+        groups.insert(group_id.to_string(), (geometry, options));
+    }
+
     pub fn register_custom_pixel_shader(
         &mut self,
         device: &ID3D11Device,
@@ -451,8 +772,32 @@ impl Primitive3DRenderer {
         constant_buffer_size: Option<u32>,
         blend_mode: BlendMode,
     ) {
+        if let Err(e) = self.try_register_custom_pixel_shader(
+            device,
+            effect_id,
+            vs_bytes,
+            ps_bytes,
+            constant_buffer_size,
+            blend_mode,
+        ) {
+            warn!("[Primitive3DRenderer] {e}");
+        }
+    }
+
+    /// Like `register_custom_pixel_shader`, but returns which resource could
+    /// not be created instead of only logging it. Registering an id that is
+    /// already present is a no-op and succeeds.
+    pub fn try_register_custom_pixel_shader(
+        &mut self,
+        device: &ID3D11Device,
+        effect_id: &str,
+        vs_bytes: Option<&[u8]>,
+        ps_bytes: &[u8],
+        constant_buffer_size: Option<u32>,
+        blend_mode: BlendMode,
+    ) -> Result<(), String> {
         if self.custom_effects.contains_key(effect_id) {
-            return;
+            return Ok(());
         }
 
         let vertex_shader = if let Some(vs_data) = vs_bytes {
@@ -460,7 +805,7 @@ impl Primitive3DRenderer {
             unsafe {
                 device
                     .CreateVertexShader(vs_data, None, Some(&mut vs))
-                    .expect("Failed to create custom VS");
+                    .map_err(|e| format!("Failed to create VS for effect '{effect_id}': {e}"))?;
             }
             vs
         } else {
@@ -471,7 +816,7 @@ impl Primitive3DRenderer {
         unsafe {
             device
                 .CreatePixelShader(ps_bytes, None, Some(&mut pixel_shader))
-                .expect("Failed to create custom PS");
+                .map_err(|e| format!("Failed to create PS for effect '{effect_id}': {e}"))?;
         }
 
         let constant_buffer = if let Some(size) = constant_buffer_size {
@@ -484,9 +829,9 @@ impl Primitive3DRenderer {
             };
             let mut cb: Option<ID3D11Buffer> = None;
             unsafe {
-                device
-                    .CreateBuffer(&cb_desc, None, Some(&mut cb))
-                    .expect("Failed to create custom constant buffer");
+                device.CreateBuffer(&cb_desc, None, Some(&mut cb)).map_err(|e| {
+                    format!("Failed to create constant buffer for effect '{effect_id}': {e}")
+                })?;
             }
             cb
         } else {
@@ -505,6 +850,7 @@ impl Primitive3DRenderer {
                 blend_mode,
             },
         );
+        Ok(())
     }
 
     pub fn set_custom_effect_texture_at_slot(
@@ -592,6 +938,11 @@ impl Primitive3DRenderer {
     pub fn clear_primitives(&mut self, group_id: &str) {
         self.submit_groups_triangles.remove(group_id);
         self.submit_groups_lines.remove(group_id);
+        self.submit_groups_points.remove(group_id);
+        self.submit_groups_strips.remove(group_id);
+        self.submit_groups_thick_lines.remove(group_id);
+        self.submit_groups_triangles_indexed.remove(group_id);
+        self.submit_groups_lines_indexed.remove(group_id);
         self.submit_groups_triangles_custom.remove(group_id);
         self.submit_groups_lines_custom.remove(group_id);
     }
@@ -599,6 +950,11 @@ impl Primitive3DRenderer {
     pub fn clear_all_primitives(&mut self) {
         self.submit_groups_triangles.clear();
         self.submit_groups_lines.clear();
+        self.submit_groups_points.clear();
+        self.submit_groups_strips.clear();
+        self.submit_groups_thick_lines.clear();
+        self.submit_groups_triangles_indexed.clear();
+        self.submit_groups_lines_indexed.clear();
         self.submit_groups_triangles_custom.clear();
         self.submit_groups_lines_custom.clear();
     }
@@ -620,6 +976,42 @@ impl Primitive3DRenderer {
             buffer.extend_from_slice(&group_vertices[..vertices_to_add]);
         }
 
+        self.render_buffer_points.clear();
+        for (group_vertices, options) in self.submit_groups_points.values() {
+            let buffer = self.render_buffer_points.entry(*options).or_default();
+            let remaining_capacity = MAX_VERTEX_BUFFER_CAPACITY.saturating_sub(buffer.len());
+            let vertices_to_add = group_vertices.len().min(remaining_capacity);
+            buffer.extend_from_slice(&group_vertices[..vertices_to_add]);
+        }
+
+        self.render_strips.clear();
+        for (group_vertices, options) in self.submit_groups_strips.values() {
+            let count = group_vertices.len().min(MAX_VERTEX_BUFFER_CAPACITY);
+            self.render_strips.push((*options, group_vertices[..count].to_vec()));
+        }
+        self.render_strips.sort_by_key(|(options, _)| options.render_priority);
+
+        self.render_thick_lines.clear();
+        for (segments, width_px, options) in self.submit_groups_thick_lines.values() {
+            // Each segment becomes six vertices.
+            let count = segments.len().min(MAX_VERTEX_BUFFER_CAPACITY / 6);
+            self.render_thick_lines.push((*options, *width_px, segments[..count].to_vec()));
+        }
+        self.render_thick_lines.sort_by_key(|(options, ..)| options.render_priority);
+
+        for (submitted, latched) in [
+            (&self.submit_groups_triangles_indexed, &mut self.render_buffer_triangles_indexed),
+            (&self.submit_groups_lines_indexed, &mut self.render_buffer_lines_indexed),
+        ] {
+            latched.clear();
+            for (group_id, (geometry, options)) in submitted {
+                let batch = latched.entry(*options).or_default();
+                if !batch.append(&geometry.vertices, &geometry.indices) {
+                    warn!("[Primitive3DRenderer] Indexed group '{group_id}' skipped: buffer full");
+                }
+            }
+        }
+
         self.render_buffer_triangles_custom.clear();
         for (effect_id, group_vertices, options) in self.submit_groups_triangles_custom.values() {
             let buffer = self
@@ -640,6 +1032,87 @@ impl Primitive3DRenderer {
             let vertices_to_add = group_vertices.len().min(remaining_capacity);
             buffer.extend_from_slice(&group_vertices[..vertices_to_add]);
         }
+
+        if self.picking_enabled {
+            self.latch_pick_batches();
+        }
+    }
+
+    /// Snapshots every group, standard and custom, with its vertices recolored
+    /// to the group's pick id.
+    fn latch_pick_batches(&mut self) {
+        self.pick_groups.clear();
+        self.pick_batches.clear();
+
+        let standard = self
+            .submit_groups_triangles
+            .iter()
+            .map(|(id, (v, o))| (id, PrimitiveType::Triangles, v, o))
+            .chain(
+                self.submit_groups_lines
+                    .iter()
+                    .map(|(id, (v, o))| (id, PrimitiveType::Lines, v, o)),
+            )
+            .chain(
+                self.submit_groups_points
+                    .iter()
+                    .map(|(id, (v, o))| (id, PrimitiveType::Points, v, o)),
+            )
+            .chain(
+                self.submit_groups_strips
+                    .iter()
+                    .map(|(id, (v, o))| (id, PrimitiveType::TriangleStrip, v, o)),
+            );
+        // Pick batches are drawn non-indexed, so indexed groups are expanded.
+        let indexed_vertices: Vec<_> = self
+            .submit_groups_triangles_indexed
+            .iter()
+            .map(|(id, (g, o))| (id, PrimitiveType::Triangles, g.expand(), o))
+            .chain(
+                self.submit_groups_lines_indexed
+                    .iter()
+                    .map(|(id, (g, o))| (id, PrimitiveType::Lines, g.expand(), o)),
+            )
+            .collect();
+        let indexed = indexed_vertices
+            .iter()
+            .map(|(id, topology, v, o)| (*id, *topology, v, *o));
+        let custom = self
+            .submit_groups_triangles_custom
+            .iter()
+            .map(|(id, (_, v, o))| (id, PrimitiveType::Triangles, v, o))
+            .chain(
+                self.submit_groups_lines_custom
+                    .iter()
+                    .map(|(id, (_, v, o))| (id, PrimitiveType::Lines, v, o)),
+            );
+
+        for (group_id, topology, vertices, options) in standard.chain(indexed).chain(custom) {
+            // A group can have both triangles and lines; both get the same id.
+            let pick_id = match self.pick_groups.iter().position(|g| g == group_id) {
+                Some(index) => index as u32 + 1,
+                None => {
+                    if self.pick_groups.len() as u32 >= MAX_PICK_ID {
+                        continue;
+                    }
+                    self.pick_groups.push(group_id.clone());
+                    self.pick_groups.len() as u32
+                }
+            };
+            let color = encode_pick_id(pick_id);
+            let count = vertices.len().min(MAX_VERTEX_BUFFER_CAPACITY);
+            self.pick_batches.push(PickBatch {
+                topology,
+                options: *options,
+                vertices: vertices[..count]
+                    .iter()
+                    .map(|v| Vertex3D {
+                        position: v.position,
+                        color,
+                    })
+                    .collect(),
+            });
+        }
     }
 }
 
@@ -668,12 +1141,247 @@ impl Primitive3DRenderer {
 
         self.depth_stencil_state_transparent.clone()
     }
+
+    /// Draws the latched pick batches into the pick target, sized to cover the
+    /// current viewport. Uses the default shaders for every group, so custom
+    /// vertex shaders that move geometry are not reflected in picking.
+    /// Draws `batches` (already in render priority order) from the triangle
+    /// vertex buffer, one draw each. Used for points and triangle strips.
+    fn draw_vertex_batches<'a>(
+        &self,
+        context: &ID3D11DeviceContext,
+        batches: impl IntoIterator<Item = (&'a PrimitiveOptions, &'a [Vertex3D])>,
+        topology: D3D_PRIMITIVE_TOPOLOGY,
+        has_depth: bool,
+    ) {
+        unsafe {
+            context.IASetPrimitiveTopology(topology);
+            context.PSSetShader(&self.pixel_shader, None);
+
+            for (options, vertices) in batches {
+                if vertices.is_empty() {
+                    continue;
+                }
+
+                context.RSSetState(self.get_rasterizer_state(options));
+                let blend_state = if options.opaque {
+                    &self.blend_state_opaque
+                } else {
+                    &self.blend_state_transparent
+                };
+                context.OMSetBlendState(blend_state, None, 0xffffffff);
+                let depth_state = self.get_or_create_depth_stencil_state(options, has_depth);
+                context.OMSetDepthStencilState(&depth_state, options.stencil_ref as u32);
+
+                let mut mapped_vb = D3D11_MAPPED_SUBRESOURCE::default();
+                if context
+                    .Map(
+                        &self.vertex_buffer_triangles,
+                        0,
+                        D3D11_MAP_WRITE_DISCARD,
+                        0,
+                        Some(&mut mapped_vb),
+                    )
+                    .is_err()
+                {
+                    continue;
+                }
+                std::ptr::copy_nonoverlapping(
+                    vertices.as_ptr(),
+                    mapped_vb.pData as *mut Vertex3D,
+                    vertices.len(),
+                );
+                context.Unmap(&self.vertex_buffer_triangles, 0);
+
+                let stride = mem::size_of::<Vertex3D>() as u32;
+                let offset = 0;
+                context.IASetVertexBuffers(
+                    0,
+                    1,
+                    Some(&Some(self.vertex_buffer_triangles.clone())),
+                    Some(&stride),
+                    Some(&offset),
+                );
+                context.Draw(vertices.len() as u32, 0);
+            }
+        }
+    }
+
+    /// Draws latched indexed batches with the standard shaders, in render
+    /// priority order. Expects the shaders and constant buffers to be bound.
+    fn draw_indexed_batches(
+        &self,
+        context: &ID3D11DeviceContext,
+        batches: &HashMap<PrimitiveOptions, IndexedGeometry>,
+        topology: D3D_PRIMITIVE_TOPOLOGY,
+        has_depth: bool,
+    ) {
+        let mut sorted_batches: Vec<_> = batches.iter().collect();
+        sorted_batches.sort_by_key(|(options, _)| options.render_priority);
+
+        unsafe {
+            context.IASetPrimitiveTopology(topology);
+            context.PSSetShader(&self.pixel_shader, None);
+
+            for (options, geometry) in sorted_batches {
+                if geometry.indices.is_empty() {
+                    continue;
+                }
+
+                context.RSSetState(self.get_rasterizer_state(options));
+                let blend_state = if options.opaque {
+                    &self.blend_state_opaque
+                } else {
+                    &self.blend_state_transparent
+                };
+                context.OMSetBlendState(blend_state, None, 0xffffffff);
+                let depth_state = self.get_or_create_depth_stencil_state(options, has_depth);
+                context.OMSetDepthStencilState(&depth_state, options.stencil_ref as u32);
+
+                let mut mapped_vb = D3D11_MAPPED_SUBRESOURCE::default();
+                let mut mapped_ib = D3D11_MAPPED_SUBRESOURCE::default();
+                if context
+                    .Map(
+                        &self.vertex_buffer_indexed,
+                        0,
+                        D3D11_MAP_WRITE_DISCARD,
+                        0,
+                        Some(&mut mapped_vb),
+                    )
+                    .is_err()
+                {
+                    continue;
+                }
+                std::ptr::copy_nonoverlapping(
+                    geometry.vertices.as_ptr(),
+                    mapped_vb.pData as *mut Vertex3D,
+                    geometry.vertices.len(),
+                );
+                context.Unmap(&self.vertex_buffer_indexed, 0);
+                if context
+                    .Map(&self.index_buffer, 0, D3D11_MAP_WRITE_DISCARD, 0, Some(&mut mapped_ib))
+                    .is_err()
+                {
+                    continue;
+                }
+                std::ptr::copy_nonoverlapping(
+                    geometry.indices.as_ptr(),
+                    mapped_ib.pData as *mut u32,
+                    geometry.indices.len(),
+                );
+                context.Unmap(&self.index_buffer, 0);
+
+                let stride = mem::size_of::<Vertex3D>() as u32;
+                let offset = 0;
+                context.IASetVertexBuffers(
+                    0,
+                    1,
+                    Some(&Some(self.vertex_buffer_indexed.clone())),
+                    Some(&stride),
+                    Some(&offset),
+                );
+                context.IASetIndexBuffer(&self.index_buffer, DXGI_FORMAT_R32_UINT, 0);
+                context.DrawIndexed(geometry.indices.len() as u32, 0, 0);
+            }
+        }
+    }
+
+    fn draw_pick_pass(&mut self, context: &ID3D11DeviceContext) {
+        let mut viewport_count = 1u32;
+        let mut viewport = D3D11_VIEWPORT::default();
+        unsafe { context.RSGetViewports(&mut viewport_count, Some(&mut viewport as *mut D3D11_VIEWPORT)) };
+        let width = (viewport.TopLeftX + viewport.Width).ceil() as u32;
+        let height = (viewport.TopLeftY + viewport.Height).ceil() as u32;
+        if viewport_count == 0 || width == 0 || height == 0 {
+            return;
+        }
+
+        if self
+            .picking
+            .as_ref()
+            .is_none_or(|target| (target.width, target.height) != (width, height))
+        {
+            self.picking = match PickingTarget::new(&self.device, width, height) {
+                Ok(target) => Some(target),
+                Err(e) => {
+                    warn!("[Primitive3DRenderer] Failed to create pick target: {e}");
+                    None
+                }
+            };
+        }
+        let Some(target) = &self.picking else {
+            return;
+        };
+
+        unsafe {
+            context.ClearRenderTargetView(&target.rtv, &[0.0; 4]);
+            context.ClearDepthStencilView(&target.dsv, D3D11_CLEAR_DEPTH.0, 1.0, 0);
+            context.OMSetRenderTargets(Some(&[Some(target.rtv.clone())]), Some(&target.dsv));
+            context.OMSetBlendState(&self.blend_state_opaque, None, 0xffffffff);
+            context.OMSetDepthStencilState(&self.depth_stencil_state, 0);
+            context.VSSetShader(&self.vertex_shader, None);
+            context.PSSetShader(&self.pixel_shader, None);
+
+            for batch in &self.pick_batches {
+                if batch.vertices.is_empty() {
+                    continue;
+                }
+                let (topology, vertex_buffer) = match batch.topology {
+                    PrimitiveType::Triangles => {
+                        (D3D11_PRIMITIVE_TOPOLOGY_TRIANGLELIST, &self.vertex_buffer_triangles)
+                    }
+                    PrimitiveType::Lines => {
+                        (D3D11_PRIMITIVE_TOPOLOGY_LINELIST, &self.vertex_buffer_lines)
+                    }
+                    PrimitiveType::Points => {
+                        (D3D11_PRIMITIVE_TOPOLOGY_POINTLIST, &self.vertex_buffer_lines)
+                    }
+                    PrimitiveType::TriangleStrip => {
+                        (D3D11_PRIMITIVE_TOPOLOGY_TRIANGLESTRIP, &self.vertex_buffer_triangles)
+                    }
+                };
+                context.IASetPrimitiveTopology(topology);
+                context.RSSetState(self.get_rasterizer_state(&batch.options));
+
+                let vertex_count = batch.vertices.len() as u32;
+                let mut mapped_vb = D3D11_MAPPED_SUBRESOURCE::default();
+                if context
+                    .Map(vertex_buffer, 0, D3D11_MAP_WRITE_DISCARD, 0, Some(&mut mapped_vb))
+                    .is_err()
+                {
+                    continue;
+                }
+                std::ptr::copy_nonoverlapping(
+                    batch.vertices.as_ptr(),
+                    mapped_vb.pData as *mut Vertex3D,
+                    vertex_count as usize,
+                );
+                context.Unmap(vertex_buffer, 0);
+
+                let stride = mem::size_of::<Vertex3D>() as u32;
+                let offset = 0;
+                context.IASetVertexBuffers(
+                    0,
+                    1,
+                    Some(&Some(vertex_buffer.clone())),
+                    Some(&stride),
+                    Some(&offset),
+                );
+                context.Draw(vertex_count, 0);
+            }
+        }
+    }
 }
 
 impl Renderer for Primitive3DRenderer {
     fn draw(&mut self, params: &FrameParams) {
         if self.render_buffer_triangles.is_empty()
             && self.render_buffer_lines.is_empty()
+            && self.render_buffer_points.is_empty()
+            && self.render_strips.is_empty()
+            && self.render_thick_lines.is_empty()
+            && self.render_buffer_triangles_indexed.is_empty()
+            && self.render_buffer_lines_indexed.is_empty()
             && self.render_buffer_triangles_custom.is_empty()
             && self.render_buffer_lines_custom.is_empty()
         {
@@ -856,6 +1564,60 @@ impl Renderer for Primitive3DRenderer {
                 }
             }
 
+            let has_depth = params.depth_stencil_view.is_some();
+            let mut points: Vec<_> = self.render_buffer_points.iter().collect();
+            points.sort_by_key(|(options, _)| options.render_priority);
+            self.draw_vertex_batches(
+                context,
+                points.into_iter().map(|(o, v)| (o, v.as_slice())),
+                D3D11_PRIMITIVE_TOPOLOGY_POINTLIST,
+                has_depth,
+            );
+            self.draw_vertex_batches(
+                context,
+                self.render_strips.iter().map(|(o, v)| (o, v.as_slice())),
+                D3D11_PRIMITIVE_TOPOLOGY_TRIANGLESTRIP,
+                has_depth,
+            );
+            if !self.render_thick_lines.is_empty() {
+                let mut viewport_count = 1u32;
+                let mut viewport = D3D11_VIEWPORT::default();
+                context.RSGetViewports(&mut viewport_count, Some(&mut viewport as *mut _));
+                if viewport_count > 0 {
+                    let expanded: Vec<_> = self
+                        .render_thick_lines
+                        .iter()
+                        .map(|(options, width_px, segments)| {
+                            let vertices = expand_thick_lines(
+                                segments,
+                                *width_px,
+                                params.view_projection_matrix,
+                                [viewport.Width, viewport.Height],
+                            );
+                            (options, vertices)
+                        })
+                        .collect();
+                    self.draw_vertex_batches(
+                        context,
+                        expanded.iter().map(|(o, v)| (*o, v.as_slice())),
+                        D3D11_PRIMITIVE_TOPOLOGY_TRIANGLELIST,
+                        has_depth,
+                    );
+                }
+            }
+            self.draw_indexed_batches(
+                context,
+                &self.render_buffer_triangles_indexed,
+                D3D11_PRIMITIVE_TOPOLOGY_TRIANGLELIST,
+                has_depth,
+            );
+            self.draw_indexed_batches(
+                context,
+                &self.render_buffer_lines_indexed,
+                D3D11_PRIMITIVE_TOPOLOGY_LINELIST,
+                has_depth,
+            );
+
             if !self.render_buffer_triangles_custom.is_empty() {
                 context.IASetPrimitiveTopology(D3D11_PRIMITIVE_TOPOLOGY_TRIANGLELIST);
 
@@ -1034,6 +1796,10 @@ impl Renderer for Primitive3DRenderer {
                 }
             }
 
+            if self.picking_enabled {
+                self.draw_pick_pass(context);
+            }
+
             context.RSSetState(original_rs_state.as_ref());
             context.OMSetBlendState(
                 original_blend_state.as_ref(),
@@ -0,0 +1,140 @@
+//! GPU picking for 3D primitive groups.
+//!
+//! When picking is on, the primitive renderer draws every group a second time
+//! into an offscreen target, each group in a flat color that encodes its id,
+//! with its own depth buffer so the nearest group wins. Reading back the pixel
+//! under the cursor gives the id of the group drawn there.
+//!
+//! The ids are packed into the RGB bytes of an `R8G8B8A8_UNORM` target rather
+//! than an `R32_UINT` one. That way the pass runs on the existing primitive
+//! shaders, which only pass the vertex color through, and `n / 255` values
+//! survive UNORM storage exactly. 24 bits leave room for 16M groups.
+
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11_BIND_DEPTH_STENCIL, D3D11_BIND_RENDER_TARGET, D3D11_BOX, D3D11_CPU_ACCESS_READ,
+    D3D11_MAP_READ, D3D11_MAPPED_SUBRESOURCE, D3D11_TEXTURE2D_DESC, D3D11_USAGE_DEFAULT,
+    D3D11_USAGE_STAGING, ID3D11DepthStencilView, ID3D11Device, ID3D11DeviceContext,
+    ID3D11RenderTargetView, ID3D11Texture2D,
+};
+use windows::Win32::Graphics::Dxgi::Common::{
+    DXGI_FORMAT, DXGI_FORMAT_D24_UNORM_S8_UINT, DXGI_FORMAT_R8G8B8A8_UNORM, DXGI_SAMPLE_DESC,
+};
+use windows::core::Result as WindowsResult;
+
+/// Largest id that fits the 24 bits of the pick target.
+pub(crate) const MAX_PICK_ID: u32 = 0x00FF_FFFF;
+
+/// Vertex color that writes `id` into the pick target. `id` must be in
+/// `1..=MAX_PICK_ID`; `0` is what the cleared target reads as.
+pub(crate) fn encode_pick_id(id: u32) -> [f32; 4] {
+    let channel = |shift: u32| ((id >> shift) & 0xFF) as f32 / 255.0;
+    [channel(0), channel(8), channel(16), 1.0]
+}
+
+/// Id stored in an RGBA8 pixel of the pick target, `None` where nothing was drawn.
+pub(crate) fn decode_pick_id(pixel: [u8; 4]) -> Option<u32> {
+    let id = pixel[0] as u32 | (pixel[1] as u32) << 8 | (pixel[2] as u32) << 16;
+    (id != 0).then_some(id)
+}
+
+/// Offscreen color + depth target the pick pass renders into, plus a 1x1
+/// staging texture for reading single pixels back.
+#[derive(Clone)]
+pub(crate) struct PickingTarget {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    texture: ID3D11Texture2D,
+    pub(crate) rtv: ID3D11RenderTargetView,
+    pub(crate) dsv: ID3D11DepthStencilView,
+    staging: ID3D11Texture2D,
+}
+
+fn texture_desc(width: u32, height: u32, format: DXGI_FORMAT, bind: u32) -> D3D11_TEXTURE2D_DESC {
+    D3D11_TEXTURE2D_DESC {
+        Width: width,
+        Height: height,
+        MipLevels: 1,
+        ArraySize: 1,
+        Format: format,
+        SampleDesc: DXGI_SAMPLE_DESC {
+            Count: 1,
+            Quality: 0,
+        },
+        Usage: D3D11_USAGE_DEFAULT,
+        BindFlags: bind,
+        CPUAccessFlags: 0,
+        MiscFlags: 0,
+    }
+}
+
+impl PickingTarget {
+    pub(crate) fn new(device: &ID3D11Device, width: u32, height: u32) -> WindowsResult<Self> {
+        unsafe {
+            let color_desc = texture_desc(
+                width,
+                height,
+                DXGI_FORMAT_R8G8B8A8_UNORM,
+                D3D11_BIND_RENDER_TARGET.0 as u32,
+            );
+            let mut texture: Option<ID3D11Texture2D> = None;
+            device.CreateTexture2D(&color_desc, None, Some(&mut texture))?;
+            let texture = texture.unwrap();
+            let mut rtv: Option<ID3D11RenderTargetView> = None;
+            device.CreateRenderTargetView(&texture, None, Some(&mut rtv))?;
+
+            let depth_desc = texture_desc(
+                width,
+                height,
+                DXGI_FORMAT_D24_UNORM_S8_UINT,
+                D3D11_BIND_DEPTH_STENCIL.0 as u32,
+            );
+            let mut depth: Option<ID3D11Texture2D> = None;
+            device.CreateTexture2D(&depth_desc, None, Some(&mut depth))?;
+            let mut dsv: Option<ID3D11DepthStencilView> = None;
+            device.CreateDepthStencilView(&depth.unwrap(), None, Some(&mut dsv))?;
+
+            let staging_desc = D3D11_TEXTURE2D_DESC {
+                Usage: D3D11_USAGE_STAGING,
+                CPUAccessFlags: D3D11_CPU_ACCESS_READ.0 as u32,
+                ..texture_desc(1, 1, DXGI_FORMAT_R8G8B8A8_UNORM, 0)
+            };
+            let mut staging: Option<ID3D11Texture2D> = None;
+            device.CreateTexture2D(&staging_desc, None, Some(&mut staging))?;
+
+            Ok(Self {
+                width,
+                height,
+                texture,
+                rtv: rtv.unwrap(),
+                dsv: dsv.unwrap(),
+                staging: staging.unwrap(),
+            })
+        }
+    }
+
+    /// Reads the id drawn at `(x, y)`. This waits for the GPU to finish the
+    /// pick pass, so call it at most once per frame.
+    pub(crate) fn read_id(&self, context: &ID3D11DeviceContext, x: u32, y: u32) -> Option<u32> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let src_box = D3D11_BOX {
+            left: x,
+            top: y,
+            front: 0,
+            right: x + 1,
+            bottom: y + 1,
+            back: 1,
+        };
+        unsafe {
+            context.CopySubresourceRegion(&self.staging, 0, 0, 0, 0, &self.texture, 0, Some(&src_box));
+            let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+            context
+                .Map(&self.staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))
+                .ok()?;
+            let pixel = *(mapped.pData as *const [u8; 4]);
+            context.Unmap(&self.staging, 0);
+            decode_pick_id(pixel)
+        }
+    }
+}
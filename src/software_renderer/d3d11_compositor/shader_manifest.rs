@@ -0,0 +1,65 @@
+//! Batch registration of custom primitive shaders.
+//!
+//! A manifest is a list of [`ShaderManifestEntry`] values, one per effect. It is
+//! registered in one call with
+//! [`FlutterOverlayManagerHandle::register_custom_shaders_from_manifest`], which
+//! reports one result per entry so a bad `.cso` doesn't stop the rest.
+//!
+//! [`FlutterOverlayManagerHandle::register_custom_shaders_from_manifest`]: crate::software_renderer::overlays_manager_api::FlutterOverlayManagerHandle::register_custom_shaders_from_manifest
+
+use std::borrow::Cow;
+use std::io::{Error as IoError, Result as IoResult};
+use std::path::PathBuf;
+
+use crate::software_renderer::d3d11_compositor::primitive_3d_renderer::BlendMode;
+
+/// Compiled shader byte code, either in memory or in a `.cso` file on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShaderSource {
+    /// Path to a `.cso` file, read when the manifest is registered.
+    Path(PathBuf),
+    /// Byte code already in memory, e.g. from `include_bytes!`.
+    Bytes(Vec<u8>),
+}
+
+impl ShaderSource {
+    /// Returns the byte code, reading the file for `ShaderSource::Path`. The
+    /// error message names the file.
+    pub fn load(&self) -> IoResult<Cow<'_, [u8]>> {
+        match self {
+            ShaderSource::Bytes(bytes) => Ok(Cow::Borrowed(bytes)),
+            ShaderSource::Path(path) => std::fs::read(path).map(Cow::Owned).map_err(|e| {
+                IoError::new(e.kind(), format!("failed to read '{}': {e}", path.display()))
+            }),
+        }
+    }
+}
+
+/// One custom effect in a shader manifest. The fields mirror the arguments of
+/// `register_custom_pixel_shader`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderManifestEntry {
+    /// Id primitives use to select this effect.
+    pub effect_id: String,
+    /// Compiled pixel shader.
+    pub pixel_shader: ShaderSource,
+    /// Optional compiled vertex shader; `None` uses the default one.
+    pub vertex_shader: Option<ShaderSource>,
+    /// Size in bytes of the constant buffer bound at `b2`, if the shader has one.
+    pub constant_buffer_size: Option<u32>,
+    pub blend_mode: BlendMode,
+}
+
+impl ShaderManifestEntry {
+    /// Entry for a pixel shader with the default vertex shader, no constant
+    /// buffer and `BlendMode::Transparent`.
+    pub fn new(effect_id: impl Into<String>, pixel_shader: ShaderSource) -> Self {
+        Self {
+            effect_id: effect_id.into(),
+            pixel_shader,
+            vertex_shader: None,
+            constant_buffer_size: None,
+            blend_mode: BlendMode::default(),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+// Color grading: saturation, contrast, brightness and a tint multiplier.
+// Compiled with D3DCompile when the post-processing renderer is created, so
+// there is no .cso for it.
+
+// Same buffer as the other effects. The Rust GpuParameters struct is repr(C),
+// not HLSL-packed, so the grading fields are placed explicitly.
+cbuffer GpuParameters : register(b0)
+{
+    matrix worldProjection : packoffset(c0);
+    float iTime : packoffset(c4.x);
+    uint is_portal_active : packoffset(c4.y);
+    float2 iResolution : packoffset(c4.z);
+    float4 effect_bounds : packoffset(c5);
+
+    float saturation : packoffset(c12.x);
+    float contrast : packoffset(c12.y);
+    float brightness : packoffset(c12.z);
+    float3 tint : packoffset(c13);
+};
+
+Texture2D texture0 : register(t0);
+SamplerState sampler0 : register(s0);
+
+struct vOut
+{
+    float4 pos : SV_POSITION;
+    float2 uv  : TEXCOORD;
+};
+
+float4 PSMain(vOut input) : SV_TARGET
+{
+    float4 color = texture0.Sample(sampler0, input.uv);
+    if (is_portal_active > 0 &&
+       (input.uv.x < effect_bounds.x || input.uv.x > effect_bounds.z ||
+        input.uv.y < effect_bounds.y || input.uv.y > effect_bounds.w))
+    {
+        return color;
+    }
+    if (color.a <= 0.0)
+    {
+        return color;
+    }
+
+    // Flutter's output is premultiplied; grade the straight color.
+    float3 rgb = color.rgb / color.a;
+    float luma = dot(rgb, float3(0.2126, 0.7152, 0.0722));
+    rgb = lerp(float3(luma, luma, luma), rgb, saturation);
+    rgb = (rgb - 0.5) * contrast + 0.5 + brightness;
+    rgb = saturate(rgb * tint);
+
+    return float4(rgb * color.a, color.a);
+}
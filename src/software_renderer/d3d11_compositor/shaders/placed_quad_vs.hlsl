@@ -0,0 +1,25 @@
+// Textured quad placed by worldProjection instead of filling the viewport.
+// Used by the post-processing renderer for overlays with a render scale or
+// rotation. Compiled with D3DCompile when the renderer is created, so there is
+// no .cso for it.
+
+cbuffer GpuParameters : register(b0)
+{
+    matrix worldProjection : packoffset(c0);
+};
+
+struct vOut
+{
+    float4 pos : SV_POSITION;
+    float2 uv : TEXCOORD;
+};
+
+// Same strip as fullscreen_quad.hlsl, but in the unit square with (0, 0) at
+// the top-left, which worldProjection maps into clip space.
+vOut VSMain(uint vI : SV_VERTEXID)
+{
+    vOut Out;
+    Out.uv = float2(vI % 2, vI % 4 / 2);
+    Out.pos = mul(float4(Out.uv, 0.0f, 1.0f), worldProjection);
+    return Out;
+}
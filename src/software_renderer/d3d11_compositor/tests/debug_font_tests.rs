@@ -0,0 +1,51 @@
+use crate::software_renderer::d3d11_compositor::debug_font::{
+    DEBUG_FONT_CELL, debug_font_glyph_map, rasterize_debug_font_atlas,
+};
+
+fn alpha_at(pixels: &[u8], width: u32, x: u32, y: u32) -> u8 {
+    pixels[((y * width + x) * 4 + 3) as usize]
+}
+
+#[test]
+fn atlas_dimensions_fit_printable_ascii() {
+    let (pixels, width, height) = rasterize_debug_font_atlas();
+    assert_eq!(width, 16 * DEBUG_FONT_CELL);
+    assert_eq!(height, 6 * DEBUG_FONT_CELL);
+    assert_eq!(pixels.len(), (width * height * 4) as usize);
+}
+
+#[test]
+fn space_cell_is_transparent() {
+    let (pixels, width, _) = rasterize_debug_font_atlas();
+    for y in 0..DEBUG_FONT_CELL {
+        for x in 0..DEBUG_FONT_CELL {
+            assert_eq!(alpha_at(&pixels, width, x, y), 0);
+        }
+    }
+}
+
+#[test]
+fn underscore_fills_bottom_row_of_its_cell() {
+    let (pixels, width, _) = rasterize_debug_font_atlas();
+    let index = ('_' as u32) - 0x20;
+    let cell_x = (index % 16) * DEBUG_FONT_CELL;
+    let cell_y = (index / 16) * DEBUG_FONT_CELL;
+    for x in 0..DEBUG_FONT_CELL {
+        assert_eq!(alpha_at(&pixels, width, cell_x + x, cell_y + 7), 0xFF);
+        assert_eq!(alpha_at(&pixels, width, cell_x + x, cell_y), 0);
+    }
+}
+
+#[test]
+fn glyph_map_matches_atlas_layout() {
+    let glyphs = debug_font_glyph_map();
+    assert_eq!(glyphs.len(), 95);
+    let (_, width, height) = rasterize_debug_font_atlas();
+    let a = glyphs[&'A'];
+    let index = ('A' as u32) - 0x20;
+    let expected_u = (index % 16 * DEBUG_FONT_CELL) as f32 / width as f32;
+    let expected_v = (index / 16 * DEBUG_FONT_CELL) as f32 / height as f32;
+    assert!((a.uv_rect[0] - expected_u).abs() < 1e-5);
+    assert!((a.uv_rect[1] - expected_v).abs() < 1e-5);
+    assert_eq!(a.advance, DEBUG_FONT_CELL as f32);
+}
@@ -1,4 +1,7 @@
-use crate::software_renderer::d3d11_compositor::effects::{HologramParams, WarpFieldParams};
+use crate::software_renderer::d3d11_compositor::effects::{
+    ColorGradeParams, EffectConfig, EffectParams, EffectTarget, HologramParams, PostEffect,
+    WarpFieldParams, effect_param_descriptors,
+};
 
 #[test]
 fn hologram_defaults() {
@@ -22,3 +25,104 @@ fn warpfield_defaults_in_range() {
     }
     assert!(w.base_alpha > 0.0 && w.base_alpha <= 1.0);
 }
+
+#[test]
+fn effect_config_round_trips_through_bytes() {
+    let config = EffectConfig {
+        target: EffectTarget::Widget([10.0, 20.0, 300.0, 40.0]),
+        params: EffectParams::Hologram(HologramParams {
+            aberration_amount: 0.02,
+            ..HologramParams::default()
+        }),
+    };
+    let restored = EffectConfig::from_bytes(&config.to_bytes()).unwrap();
+    assert!(matches!(restored.target, EffectTarget::Widget([10.0, 20.0, 300.0, 40.0])));
+    match restored.params {
+        EffectParams::Hologram(h) => {
+            assert_eq!(h.aberration_amount, 0.02);
+            assert_eq!(h.glitch_speed, 10.0);
+        }
+        other => panic!("unexpected params {other:?}"),
+    }
+}
+
+#[test]
+fn missing_param_fields_use_defaults() {
+    let config =
+        EffectConfig::from_bytes(br#"{"params":{"WarpField":{"speed":3.0}}}"#).unwrap();
+    assert!(matches!(config.target, EffectTarget::Fullscreen));
+    match config.params {
+        EffectParams::WarpField(w) => {
+            assert_eq!(w.speed, 3.0);
+            assert_eq!(w.density, 2.0);
+        }
+        other => panic!("unexpected params {other:?}"),
+    }
+    assert!(EffectConfig::from_bytes(b"not json").is_err());
+}
+
+#[test]
+fn every_effect_has_descriptors_matching_its_values() {
+    for &effect in PostEffect::all() {
+        let descriptors = effect_param_descriptors(effect);
+        let defaults = EffectParams::default_for(effect);
+        assert_eq!(defaults.effect(), effect);
+        assert_eq!(descriptors.len(), defaults.values().len());
+        for (d, value) in descriptors.iter().zip(defaults.values()) {
+            assert_eq!(d.default, value, "{effect:?} {}", d.name);
+            assert!(d.min <= d.default && d.default <= d.max, "{effect:?} {}", d.name);
+        }
+    }
+    assert!(effect_param_descriptors(PostEffect::Passthrough).is_empty());
+    let warp = effect_param_descriptors(PostEffect::WarpField);
+    assert_eq!(warp[0].name, "speed");
+    assert_eq!(warp[9].name, "color_inner.g");
+}
+
+#[test]
+fn from_values_clamps_and_keeps_missing_defaults() {
+    match EffectParams::from_values(PostEffect::Glitch, &[0.02, 100.0]) {
+        EffectParams::Glitch(h) => {
+            assert_eq!(h.aberration_amount, 0.02);
+            assert_eq!(h.glitch_speed, 50.0);
+            assert_eq!(h.scanline_intensity, 0.1);
+        }
+        other => panic!("unexpected params {other:?}"),
+    }
+
+    let mut values = EffectParams::default_for(PostEffect::WarpField).values();
+    values[10] = 0.25;
+    match EffectParams::from_values(PostEffect::WarpField, &values) {
+        EffectParams::WarpField(w) => assert_eq!(w.color_inner, [0.1, 0.2, 0.25]),
+        other => panic!("unexpected params {other:?}"),
+    }
+}
+
+#[test]
+fn color_grade_defaults_leave_the_image_unchanged() {
+    let c = ColorGradeParams::default();
+    assert_eq!(c.saturation, 1.0);
+    assert_eq!(c.contrast, 1.0);
+    assert_eq!(c.brightness, 0.0);
+    assert_eq!(c.tint, [1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn color_grade_round_trips_through_values_and_bytes() {
+    let params = EffectParams::from_values(PostEffect::ColorGrade, &[0.0, 1.5, -0.25, 1.0, 0.5]);
+    assert_eq!(params.values(), vec![0.0, 1.5, -0.25, 1.0, 0.5, 1.0]);
+
+    let config = EffectConfig {
+        params,
+        target: EffectTarget::Widget([0.1, 0.1, 0.9, 0.9]),
+    };
+    let restored = EffectConfig::from_bytes(&config.to_bytes()).unwrap();
+    match restored.params {
+        EffectParams::ColorGrade(c) => {
+            assert_eq!(c.saturation, 0.0);
+            assert_eq!(c.tint, [1.0, 0.5, 1.0]);
+        }
+        other => panic!("expected ColorGrade, got {other:?}"),
+    }
+    assert!(matches!(restored.target, EffectTarget::Widget(_)));
+}
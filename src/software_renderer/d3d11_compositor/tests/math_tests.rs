@@ -0,0 +1,64 @@
+use directx_math::{
+    XMMatrix, XMVector3TransformCoord, XMVectorGetX, XMVectorGetY, XMVectorGetZ, XMVectorSet,
+};
+
+use crate::software_renderer::d3d11_compositor::math::{
+    look_at, ortho_screen, perspective, rotated_quad, view_projection,
+};
+
+fn transform(matrix: &XMMatrix, point: [f32; 3]) -> [f32; 3] {
+    let v = XMVector3TransformCoord(XMVectorSet(point[0], point[1], point[2], 1.0), matrix.0);
+    [XMVectorGetX(v), XMVectorGetY(v), XMVectorGetZ(v)]
+}
+
+fn assert_close(actual: [f32; 3], expected: [f32; 3]) {
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-4, "{actual:?} != {expected:?}");
+    }
+}
+
+#[test]
+fn ortho_screen_maps_the_top_left_corner_to_clip_top_left() {
+    let m = ortho_screen(1920.0, 1080.0);
+    assert_close(transform(&m, [0.0, 0.0, 0.0]), [-1.0, 1.0, 0.0]);
+    assert_close(transform(&m, [1920.0, 1080.0, 1.0]), [1.0, -1.0, 1.0]);
+    assert_close(transform(&m, [960.0, 540.0, 0.5]), [0.0, 0.0, 0.5]);
+}
+
+#[test]
+fn perspective_maps_near_and_far_to_zero_and_one() {
+    let m = perspective(std::f32::consts::FRAC_PI_2, 16.0 / 9.0, 0.1, 100.0);
+    assert_close(transform(&m, [0.0, 0.0, 0.1]), [0.0, 0.0, 0.0]);
+    assert_close(transform(&m, [0.0, 0.0, 100.0]), [0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn look_at_puts_the_target_straight_ahead() {
+    let view = look_at([0.0, 0.0, -5.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+    assert_close(transform(&view, [0.0, 0.0, 0.0]), [0.0, 0.0, 5.0]);
+    assert_close(transform(&view, [0.0, 0.0, -5.0]), [0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn view_projection_applies_the_view_first() {
+    let view = look_at([0.0, 0.0, -10.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+    let projection = perspective(std::f32::consts::FRAC_PI_2, 1.0, 1.0, 10.0);
+    let combined = view_projection(&view, &projection);
+    assert_close(transform(&combined, [0.0, 0.0, -9.0]), [0.0, 0.0, 0.0]);
+    assert_close(transform(&combined, [0.0, 0.0, 0.0]), [0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn unrotated_quad_covers_its_pixel_rectangle() {
+    let m = rotated_quad(100.0, 50.0, 400.0, 200.0, 0.0);
+    assert_close(transform(&m, [0.0, 0.0, 0.0]), [100.0, 50.0, 0.0]);
+    assert_close(transform(&m, [1.0, 1.0, 0.0]), [500.0, 250.0, 0.0]);
+}
+
+#[test]
+fn rotated_quad_turns_clockwise_around_its_center() {
+    let m = rotated_quad(0.0, 0.0, 200.0, 100.0, 90.0);
+    assert_close(transform(&m, [0.5, 0.5, 0.0]), [100.0, 50.0, 0.0]);
+    // The top-left corner ends up top-right of the center on a y-down screen.
+    assert_close(transform(&m, [0.0, 0.0, 0.0]), [150.0, -50.0, 0.0]);
+}
@@ -1,3 +1,10 @@
+mod debug_font_tests;
 mod effects_tests;
+mod math_tests;
+mod post_processing_tests;
+mod primitive_indexed_tests;
+mod primitive_picking_tests;
 mod primitive_presets_tests;
+mod primitive_thick_lines_tests;
+mod shader_manifest_tests;
 mod text_presets_tests;
@@ -0,0 +1,42 @@
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11_TEXTURE_ADDRESS_BORDER, D3D11_TEXTURE_ADDRESS_CLAMP,
+};
+
+use crate::software_renderer::d3d11_compositor::post_processing_renderer::{
+    AddressMode, GpuParameters, composite_sampler_desc, is_invisible,
+};
+
+#[test]
+fn clamp_is_the_default_address_mode() {
+    assert_eq!(AddressMode::default(), AddressMode::Clamp);
+    let desc = composite_sampler_desc(AddressMode::Clamp, [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(desc.AddressU, D3D11_TEXTURE_ADDRESS_CLAMP);
+    assert_eq!(desc.AddressV, D3D11_TEXTURE_ADDRESS_CLAMP);
+    assert_eq!(desc.BorderColor, [0.0; 4]);
+}
+
+#[test]
+fn border_mode_uses_the_border_color() {
+    let desc = composite_sampler_desc(AddressMode::Border, [0.0, 0.0, 0.0, 0.0]);
+    assert_eq!(desc.AddressU, D3D11_TEXTURE_ADDRESS_BORDER);
+    assert_eq!(desc.AddressV, D3D11_TEXTURE_ADDRESS_BORDER);
+    assert_eq!(desc.AddressW, D3D11_TEXTURE_ADDRESS_BORDER);
+
+    let tinted = composite_sampler_desc(AddressMode::Border, [0.1, 0.2, 0.3, 0.4]);
+    assert_eq!(tinted.BorderColor, [0.1, 0.2, 0.3, 0.4]);
+}
+
+#[test]
+fn color_grade_fields_match_the_shader_registers() {
+    assert_eq!(std::mem::offset_of!(GpuParameters, saturation), 12 * 16);
+    assert_eq!(std::mem::offset_of!(GpuParameters, tint), 13 * 16);
+    assert_eq!(std::mem::size_of::<GpuParameters>() % 16, 0);
+}
+
+#[test]
+fn near_zero_opacity_is_skipped() {
+    assert!(is_invisible(0.0));
+    assert!(is_invisible(0.001));
+    assert!(!is_invisible(1.0 / 255.0));
+    assert!(!is_invisible(1.0));
+}
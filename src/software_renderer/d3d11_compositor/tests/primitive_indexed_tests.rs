@@ -0,0 +1,39 @@
+use crate::software_renderer::d3d11_compositor::primitive_3d_renderer::{
+    IndexedGeometry, MAX_INDEX_BUFFER_CAPACITY, Vertex3D,
+};
+
+fn vertex(x: f32) -> Vertex3D {
+    Vertex3D {
+        position: [x, 0.0, 0.0],
+        color: [1.0; 4],
+    }
+}
+
+#[test]
+fn appended_indices_are_rebased_past_earlier_vertices() {
+    let mut batch = IndexedGeometry::default();
+    assert!(batch.append(&[vertex(0.0), vertex(1.0)], &[0, 1]));
+    assert!(batch.append(&[vertex(2.0), vertex(3.0), vertex(4.0)], &[0, 1, 1, 2]));
+    assert_eq!(batch.vertices.len(), 5);
+    assert_eq!(batch.indices, vec![0, 1, 2, 3, 3, 4]);
+}
+
+#[test]
+fn a_group_that_overflows_is_left_out_whole() {
+    let mut batch = IndexedGeometry::default();
+    let indices = vec![0; MAX_INDEX_BUFFER_CAPACITY];
+    assert!(batch.append(&[vertex(0.0)], &indices));
+    assert!(!batch.append(&[vertex(1.0)], &[0]));
+    assert_eq!(batch.vertices.len(), 1);
+    assert_eq!(batch.indices.len(), MAX_INDEX_BUFFER_CAPACITY);
+}
+
+#[test]
+fn expand_repeats_shared_vertices_in_index_order() {
+    let batch = IndexedGeometry {
+        vertices: vec![vertex(0.0), vertex(1.0), vertex(2.0)],
+        indices: vec![0, 1, 1, 2, 2, 0],
+    };
+    let xs: Vec<f32> = batch.expand().iter().map(|v| v.position[0]).collect();
+    assert_eq!(xs, vec![0.0, 1.0, 1.0, 2.0, 2.0, 0.0]);
+}
@@ -0,0 +1,20 @@
+use crate::software_renderer::d3d11_compositor::primitive_picking::{
+    MAX_PICK_ID, decode_pick_id, encode_pick_id,
+};
+
+/// What an R8G8B8A8_UNORM target stores for a color.
+fn store_unorm(color: [f32; 4]) -> [u8; 4] {
+    color.map(|c| (c * 255.0).round() as u8)
+}
+
+#[test]
+fn ids_survive_unorm_storage() {
+    for id in [1, 2, 255, 256, 0x1234, 0xABCDEF, MAX_PICK_ID] {
+        assert_eq!(decode_pick_id(store_unorm(encode_pick_id(id))), Some(id));
+    }
+}
+
+#[test]
+fn cleared_pixel_has_no_id() {
+    assert_eq!(decode_pick_id([0, 0, 0, 0]), None);
+}
@@ -0,0 +1,54 @@
+use crate::software_renderer::d3d11_compositor::math::{
+    look_at, ortho_screen, perspective, view_projection,
+};
+use crate::software_renderer::d3d11_compositor::primitive_3d_renderer::{
+    Vertex3D, expand_thick_lines,
+};
+
+fn vertex(position: [f32; 3]) -> Vertex3D {
+    Vertex3D {
+        position,
+        color: [1.0, 0.0, 0.0, 1.0],
+    }
+}
+
+#[test]
+fn horizontal_segment_becomes_a_quad_of_the_requested_width() {
+    let screen = ortho_screen(100.0, 100.0);
+    let segment = (vertex([10.0, 50.0, 0.5]), vertex([90.0, 50.0, 0.5]));
+    let vertices = expand_thick_lines(&[segment], 4.0, &screen, [100.0, 100.0]);
+
+    assert_eq!(vertices.len(), 6);
+    for v in &vertices {
+        let [x, y, z] = v.position;
+        assert!((x - 10.0).abs() < 1e-3 || (x - 90.0).abs() < 1e-3, "x = {x}");
+        assert!((y - 48.0).abs() < 1e-3 || (y - 52.0).abs() < 1e-3, "y = {y}");
+        assert!((z - 0.5).abs() < 1e-4);
+        assert_eq!(v.color, [1.0, 0.0, 0.0, 1.0]);
+    }
+}
+
+#[test]
+fn width_is_in_pixels_on_a_non_square_viewport() {
+    let screen = ortho_screen(200.0, 100.0);
+    let segment = (vertex([100.0, 10.0, 0.0]), vertex([100.0, 90.0, 0.0]));
+    let vertices = expand_thick_lines(&[segment], 6.0, &screen, [200.0, 100.0]);
+
+    let xs: Vec<f32> = vertices.iter().map(|v| v.position[0]).collect();
+    let min = xs.iter().cloned().fold(f32::MAX, f32::min);
+    let max = xs.iter().cloned().fold(f32::MIN, f32::max);
+    assert!((min - 97.0).abs() < 1e-3 && (max - 103.0).abs() < 1e-3, "{xs:?}");
+}
+
+#[test]
+fn segments_behind_the_camera_or_of_zero_length_are_skipped() {
+    let view = look_at([0.0, 0.0, -5.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+    let projection = perspective(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+    let vp = view_projection(&view, &projection);
+    let segments = [
+        (vertex([0.0, 0.0, -10.0]), vertex([1.0, 0.0, 0.0])),
+        (vertex([1.0, 1.0, 0.0]), vertex([1.0, 1.0, 0.0])),
+        (vertex([-1.0, 0.0, 0.0]), vertex([1.0, 0.0, 0.0])),
+    ];
+    assert_eq!(expand_thick_lines(&segments, 2.0, &vp, [100.0, 100.0]).len(), 6);
+}
@@ -0,0 +1,28 @@
+use crate::software_renderer::d3d11_compositor::primitive_3d_renderer::BlendMode;
+use crate::software_renderer::d3d11_compositor::shader_manifest::{
+    ShaderManifestEntry, ShaderSource,
+};
+
+#[test]
+fn bytes_source_loads_without_copying() {
+    let source = ShaderSource::Bytes(vec![0x44, 0x58, 0x42, 0x43]);
+    let loaded = source.load().unwrap();
+    assert_eq!(&*loaded, &[0x44, 0x58, 0x42, 0x43]);
+    assert!(matches!(loaded, std::borrow::Cow::Borrowed(_)));
+}
+
+#[test]
+fn missing_path_error_names_the_file() {
+    let source = ShaderSource::Path("does/not/exist/glow.cso".into());
+    let err = source.load().unwrap_err();
+    assert!(err.to_string().contains("glow.cso"), "{err}");
+}
+
+#[test]
+fn new_entry_uses_register_defaults() {
+    let entry = ShaderManifestEntry::new("glow", ShaderSource::Bytes(Vec::new()));
+    assert_eq!(entry.effect_id, "glow");
+    assert_eq!(entry.vertex_shader, None);
+    assert_eq!(entry.constant_buffer_size, None);
+    assert_eq!(entry.blend_mode, BlendMode::Transparent);
+}
@@ -12,6 +12,7 @@ use windows::Win32::{
     },
 };
 
+use super::debug_font::{DEBUG_FONT_ID, create_debug_font_atlas};
 use super::primitive_3d_renderer::PrimitiveOptions;
 use crate::software_renderer::d3d11_compositor::traits::{FrameParams, Renderer};
 
@@ -336,6 +337,16 @@ impl Text3DRenderer {
         self.font_atlases.get(font_id)
     }
 
+    /// Registers the built-in debug font under [`DEBUG_FONT_ID`] if it isn't
+    /// already, and returns it.
+    pub fn ensure_debug_font(&mut self) -> Result<&FontAtlas, String> {
+        if !self.font_atlases.contains_key(DEBUG_FONT_ID) {
+            let atlas = create_debug_font_atlas(&self.device)?;
+            self.font_atlases.insert(DEBUG_FONT_ID.to_string(), atlas);
+        }
+        Ok(&self.font_atlases[DEBUG_FONT_ID])
+    }
+
     pub fn latch_buffers(&mut self) {
         self.render_buffers.clear();
 
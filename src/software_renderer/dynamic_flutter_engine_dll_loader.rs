@@ -118,6 +118,22 @@ pub struct FlutterEngineDll {
             bytes_length: usize,
         ) -> e::FlutterEngineResult,
     >,
+    pub FlutterPlatformMessageCreateResponseHandle: Symbol<
+        'static,
+        unsafe extern "C" fn(
+            engine: e::FlutterEngine,
+            data_callback: e::FlutterDataCallback,
+            user_data: *mut c_void,
+            response_out: *mut *mut e::FlutterPlatformMessageResponseHandle,
+        ) -> e::FlutterEngineResult,
+    >,
+    pub FlutterPlatformMessageReleaseResponseHandle: Symbol<
+        'static,
+        unsafe extern "C" fn(
+            engine: e::FlutterEngine,
+            response: *mut e::FlutterPlatformMessageResponseHandle,
+        ) -> e::FlutterEngineResult,
+    >,
 
     pub FlutterEngineRunTask: Symbol<
         'static,
@@ -134,6 +150,25 @@ pub struct FlutterEngineDll {
         'static,
         unsafe extern "C" fn(engine: e::FlutterEngine, enabled: bool) -> e::FlutterEngineResult,
     >,
+    pub FlutterEngineUpdateAccessibilityFeatures: Symbol<
+        'static,
+        unsafe extern "C" fn(
+            engine: e::FlutterEngine,
+            features: e::FlutterAccessibilityFeature,
+        ) -> e::FlutterEngineResult,
+    >,
+    pub FlutterEngineReloadSystemFonts:
+        Symbol<'static, unsafe extern "C" fn(engine: e::FlutterEngine) -> e::FlutterEngineResult>,
+    pub FlutterEngineNotifyLowMemoryWarning:
+        Symbol<'static, unsafe extern "C" fn(engine: e::FlutterEngine) -> e::FlutterEngineResult>,
+    pub FlutterEngineUpdateLocales: Symbol<
+        'static,
+        unsafe extern "C" fn(
+            engine: e::FlutterEngine,
+            locales: *mut *const e::FlutterLocale,
+            locales_count: usize,
+        ) -> e::FlutterEngineResult,
+    >,
 
     pub FlutterEngineCreateAOTData: Symbol<
         'static,
@@ -250,6 +285,14 @@ impl FlutterEngineDll {
                 lib_static,
                 b"FlutterEngineSendPlatformMessageResponse\0"
             )?,
+            FlutterPlatformMessageCreateResponseHandle: load_symbol!(
+                lib_static,
+                b"FlutterPlatformMessageCreateResponseHandle\0"
+            )?,
+            FlutterPlatformMessageReleaseResponseHandle: load_symbol!(
+                lib_static,
+                b"FlutterPlatformMessageReleaseResponseHandle\0"
+            )?,
             FlutterEngineRunTask: load_symbol!(lib_static, b"FlutterEngineRunTask\0")?,
             FlutterEngineScheduleFrame: load_symbol!(lib_static, b"FlutterEngineScheduleFrame\0")?,
             FlutterEngineGetCurrentTime: load_symbol!(
@@ -260,6 +303,19 @@ impl FlutterEngineDll {
                 lib_static,
                 b"FlutterEngineUpdateSemanticsEnabled\0"
             )?,
+            FlutterEngineUpdateAccessibilityFeatures: load_symbol!(
+                lib_static,
+                b"FlutterEngineUpdateAccessibilityFeatures\0"
+            )?,
+            FlutterEngineReloadSystemFonts: load_symbol!(
+                lib_static,
+                b"FlutterEngineReloadSystemFonts\0"
+            )?,
+            FlutterEngineNotifyLowMemoryWarning: load_symbol!(
+                lib_static,
+                b"FlutterEngineNotifyLowMemoryWarning\0"
+            )?,
+            FlutterEngineUpdateLocales: load_symbol!(lib_static, b"FlutterEngineUpdateLocales\0")?,
             FlutterEngineCreateAOTData: load_symbol!(lib_static, b"FlutterEngineCreateAOTData\0")?,
             FlutterEngineOnVsync: load_symbol!(lib_static, b"FlutterEngineOnVsync\0")?,
             FlutterEnginePostDartObject: load_symbol!(
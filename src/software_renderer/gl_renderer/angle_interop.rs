@@ -1,5 +1,7 @@
 use crate::bindings::embedder;
 
+#[cfg(feature = "d3d-debug")]
+use crate::software_renderer::gl_renderer::d3d_debug;
 use crate::software_renderer::gl_renderer::nvidia_aftermath;
 use crate::software_renderer::multiview::view_surface::ViewGlResources;
 use crate::software_renderer::overlay::d3d::create_shared_texture_and_get_handle;
@@ -177,6 +179,14 @@ type GlFinish = unsafe extern "C" fn();
 /// Defines the signature for `glFlush`, an OpenGL command that ensures all previously
 /// submitted commands are dispatched to the GPU without waiting for completion.
 type GlFlush = unsafe extern "C" fn();
+/// Defines the signature for `glFenceSync` (GLES 3.0), which inserts a fence into the
+/// command stream that becomes signaled once all preceding commands have completed.
+type GlFenceSync = unsafe extern "C" fn(u32, u32) -> *mut c_void;
+/// Defines the signature for `glClientWaitSync`, which blocks for up to the given number
+/// of nanoseconds until a fence is signaled.
+type GlClientWaitSync = unsafe extern "C" fn(*mut c_void, u32, u64) -> u32;
+/// Defines the signature for `glDeleteSync`, which releases a fence.
+type GlDeleteSync = unsafe extern "C" fn(*mut c_void);
 /// Defines the signature for `eglCreatePbufferFromClientBuffer`, used to create an
 /// EGL pbuffer surface that wraps an existing native graphics resource, such as a Direct3D texture.
 /// This is a key function for GPU-level interoperability.
@@ -218,6 +228,20 @@ pub const GL_COLOR_ATTACHMENT0: u32 = 0x8CE0;
 pub const GL_TEXTURE_2D: u32 = 0x0DE1;
 pub const GL_FRAMEBUFFER_COMPLETE: u32 = 0x8CD5;
 pub const EGL_BACK_BUFFER: i32 = 0x3084;
+pub const GL_SYNC_GPU_COMMANDS_COMPLETE: u32 = 0x9117;
+pub const GL_SYNC_FLUSH_COMMANDS_BIT: u32 = 0x0000_0001;
+pub const GL_ALREADY_SIGNALED: u32 = 0x911A;
+pub const GL_CONDITION_SATISFIED: u32 = 0x911C;
+pub const GL_WAIT_FAILED: u32 = 0x911D;
+
+/// GLES 3.0 fence entry points. Resolved separately from [`ViewGlProcs`]'s
+/// required procs because a GLES 2 context doesn't expose them.
+#[derive(Clone, Copy)]
+pub struct GlFenceProcs {
+    pub fence_sync: GlFenceSync,
+    pub client_wait_sync: GlClientWaitSync,
+    pub delete_sync: GlDeleteSync,
+}
 
 /// GL/EGL function pointers resolved once and shared by all secondary views.
 /// These are resolved through the same `eglGetProcAddress` used by the engine.
@@ -234,6 +258,9 @@ pub struct ViewGlProcs {
     pub bind_tex_image: EglBindTexImage,
     pub flush: GlFlush,
     pub finish: GlFinish,
+    /// `None` when the context has no fence support; `SyncMode::Fence` then
+    /// falls back to `glFinish`.
+    pub fence: Option<GlFenceProcs>,
 }
 
 unsafe impl Send for ViewGlProcs {}
@@ -283,6 +310,16 @@ impl ViewGlProcs {
                 )?),
                 flush: mem::transmute::<*mut c_void, GlFlush>(get("glFlush")?),
                 finish: mem::transmute::<*mut c_void, GlFinish>(get("glFinish")?),
+                fence: match (get("glFenceSync"), get("glClientWaitSync"), get("glDeleteSync")) {
+                    (Ok(fence_sync), Ok(client_wait_sync), Ok(delete_sync)) => Some(GlFenceProcs {
+                        fence_sync: mem::transmute::<*mut c_void, GlFenceSync>(fence_sync),
+                        client_wait_sync: mem::transmute::<*mut c_void, GlClientWaitSync>(
+                            client_wait_sync,
+                        ),
+                        delete_sync: mem::transmute::<*mut c_void, GlDeleteSync>(delete_sync),
+                    }),
+                    _ => None,
+                },
             })
         }
     }
@@ -329,21 +366,48 @@ fn log_egl_error(func: &str, line: u32, egl_get_error_fn: EglGetError) {
     }
 }
 
+///
+/// Optional ANGLE display attributes. Both are on by default; some older Intel
+/// drivers show corruption with them, so they can be turned off per overlay.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AngleOptions {
+    /// Request `EGL_EXPERIMENTAL_PRESENT_PATH_FAST_ANGLE`.
+    pub experimental_present_path: bool,
+    /// Request `EGL_PLATFORM_ANGLE_ENABLE_AUTOMATIC_TRIM_ANGLE`.
+    pub automatic_trim: bool,
+}
+
+impl Default for AngleOptions {
+    fn default() -> Self {
+        Self {
+            experimental_present_path: true,
+            automatic_trim: true,
+        }
+    }
+}
+
 ///
 /// Builds the EGL display attributes array for ANGLE initialization.
 /// Debug layers are enabled only if the `ANGLE_DEBUG_LAYERS_ENABLED` environment variable is set.
 ///
-pub(crate) fn build_display_attributes() -> Vec<i32> {
+pub(crate) fn build_display_attributes(options: AngleOptions) -> Vec<i32> {
     let debug_layers_enabled = std::env::var("ANGLE_DEBUG_LAYERS_ENABLED").is_ok();
+    #[cfg(feature = "d3d-debug")]
+    let debug_layers_enabled = debug_layers_enabled || d3d_debug::debug_layers_requested();
 
     let mut attrs = vec![
         EGL_PLATFORM_ANGLE_TYPE_ANGLE,
         EGL_PLATFORM_ANGLE_TYPE_D3D11_ANGLE,
-        EGL_PLATFORM_ANGLE_ENABLE_AUTOMATIC_TRIM_ANGLE,
-        EGL_TRUE,
-        EGL_EXPERIMENTAL_PRESENT_PATH_ANGLE,
-        EGL_EXPERIMENTAL_PRESENT_PATH_FAST_ANGLE,
     ];
+    if options.automatic_trim {
+        attrs.push(EGL_PLATFORM_ANGLE_ENABLE_AUTOMATIC_TRIM_ANGLE);
+        attrs.push(EGL_TRUE);
+    }
+    if options.experimental_present_path {
+        attrs.push(EGL_EXPERIMENTAL_PRESENT_PATH_ANGLE);
+        attrs.push(EGL_EXPERIMENTAL_PRESENT_PATH_FAST_ANGLE);
+    }
 
     if debug_layers_enabled {
         attrs.push(EGL_PLATFORM_ANGLE_DEBUG_LAYERS_ENABLED_ANGLE);
@@ -469,6 +533,10 @@ pub struct AngleInteropState {
     /// (called from the resize thread) and destroyed in make_current_callback (on the
     /// render thread where the context is current).
     pub old_pbuffer_surface: Option<*mut c_void>,
+
+    /// Display attributes requested at creation, reused when the display is
+    /// recreated after a device loss.
+    pub options: AngleOptions,
 }
 
 impl AngleInteropState {
@@ -485,13 +553,14 @@ impl AngleInteropState {
     ///   `libGLESv2.dll`. This path is only used during the very first initialization
     ///   of the shared EGL state within the process. Subsequent calls will ignore this
     ///   parameter and reuse the existing shared state.
+    /// * `options`: Which optional ANGLE display attributes to request.
     ///
     /// # Returns
     ///
     /// A `Result` containing the fully initialized `AngleInteropState` on success,
     /// or an error string on failure.
     ///
-    pub fn new(engine_dir: Option<&Path>) -> Result<Box<Self>, String> {
+    pub fn new(engine_dir: Option<&Path>, options: AngleOptions) -> Result<Box<Self>, String> {
         unsafe {
             info!("[AngleInterop] Initializing ANGLE and letting it create a D3D11 device...");
 
@@ -516,7 +585,7 @@ impl AngleInteropState {
             let egl_initialize: EglInitialize = mem::transmute(get_proc("eglInitialize"));
             let egl_get_error: EglGetError = mem::transmute(get_proc("eglGetError"));
 
-            let display_attributes = build_display_attributes();
+            let display_attributes = build_display_attributes(options);
 
             let display = egl_get_platform_display_ext(
                 EGL_PLATFORM_ANGLE_ANGLE,
@@ -654,6 +723,7 @@ impl AngleInteropState {
                 device_lost: false,
                 pending_resize: None,
                 old_pbuffer_surface: None,
+                options,
             }))
         }
     }
@@ -836,7 +906,7 @@ impl AngleInteropState {
 
             let _ = egl_get_error();
 
-            let display_attributes = build_display_attributes();
+            let display_attributes = build_display_attributes(self.options);
 
             let new_display = egl_get_platform_display_ext(
                 EGL_PLATFORM_ANGLE_ANGLE,
@@ -1374,10 +1444,14 @@ extern "C" fn clear_current_callback(user_data: *mut c_void) -> bool {
 /// so `populate_existing_damage_callback` can feed them back next frame, telling Flutter
 /// which parts of the FBO are already dirty.
 ///
-extern "C" fn present_with_info_callback(
+pub(crate) extern "C" fn present_with_info_callback(
     user_data: *mut c_void,
     present_info: *const embedder::FlutterPresentInfo,
 ) -> bool {
+    if user_data.is_null() {
+        error!("present_with_info: user_data is null");
+        return false;
+    }
     unsafe {
         let overlay = &*(user_data as *mut FlutterOverlay);
 
@@ -0,0 +1,52 @@
+//! Opt-in D3D11 validation for tracking down resource lifetime bugs.
+//!
+//! Only compiled with the `d3d-debug` feature. Two pieces:
+//!
+//! * [`request_debug_layers`] makes ANGLE create its D3D11 device with the
+//!   debug layer (same effect as the `ANGLE_DEBUG_LAYERS_ENABLED` env var). It
+//!   must be called before the first OpenGL overlay is created; the EGL display
+//!   is shared by the whole process.
+//! * [`report_live_objects`] prints every live object of a device through
+//!   `ID3D11Debug::ReportLiveDeviceObjects`. The report goes to the debugger
+//!   output (`OutputDebugString`), so attach a debugger or run DebugView.
+//!
+//! The host's own device only supports reports if the host created it with
+//! `D3D11_CREATE_DEVICE_DEBUG`; otherwise only ANGLE's device is reported.
+
+use log::{info, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11_RLDO_DETAIL, D3D11_RLDO_IGNORE_INTERNAL, ID3D11Debug, ID3D11Device,
+};
+use windows::core::Interface;
+
+static DEBUG_LAYERS_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Asks ANGLE to enable the D3D11 debug layer for displays created from now on.
+pub fn request_debug_layers() {
+    DEBUG_LAYERS_REQUESTED.store(true, Ordering::Relaxed);
+}
+
+pub(crate) fn debug_layers_requested() -> bool {
+    DEBUG_LAYERS_REQUESTED.load(Ordering::Relaxed)
+}
+
+/// Reports the live objects of `device`, labelled with `label` in the log.
+/// Returns `false` if the device was not created with the debug layer.
+pub fn report_live_objects(device: &ID3D11Device, label: &str) -> bool {
+    let debug: ID3D11Debug = match device.cast() {
+        Ok(debug) => debug,
+        Err(_) => {
+            warn!("[D3DDebug] {label}: device has no debug layer, skipping live object report");
+            return false;
+        }
+    };
+    info!("[D3DDebug] {label}: reporting live device objects to the debugger output");
+    if let Err(e) =
+        unsafe { debug.ReportLiveDeviceObjects(D3D11_RLDO_DETAIL | D3D11_RLDO_IGNORE_INTERNAL) }
+    {
+        warn!("[D3DDebug] {label}: ReportLiveDeviceObjects failed: {e}");
+        return false;
+    }
+    true
+}
@@ -18,10 +18,14 @@
 //!   config, and [`angle_interop::SendableAngleState`] (which also handles
 //!   D3D device-loss detection and recovery).
 //! * [`nvidia_aftermath`]: optional NVIDIA Aftermath GPU crash diagnostics.
+//! * `d3d_debug`: D3D11 debug layer and live object reports (`d3d-debug`
+//!   feature only).
 //! * `d3d_backup`: fallback resources used when ANGLE init fails.
 
 pub mod angle_interop;
 pub mod d3d_backup;
+#[cfg(feature = "d3d-debug")]
+pub mod d3d_debug;
 pub mod nvidia_aftermath;
 #[cfg(test)]
 mod tests;
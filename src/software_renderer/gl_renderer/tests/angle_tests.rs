@@ -1,5 +1,7 @@
 use crate::software_renderer::gl_renderer::angle_interop::{
-    EGL_NONE, build_display_attributes, egl_error_to_string,
+    AngleOptions, EGL_EXPERIMENTAL_PRESENT_PATH_ANGLE, EGL_NONE,
+    EGL_PLATFORM_ANGLE_ENABLE_AUTOMATIC_TRIM_ANGLE, build_display_attributes, egl_error_to_string,
+    present_with_info_callback,
 };
 
 #[test]
@@ -18,8 +20,30 @@ fn egl_error_unknown_code() {
 
 #[test]
 fn display_attributes_terminated_with_none() {
-    let attrs = build_display_attributes();
+    let attrs = build_display_attributes(AngleOptions::default());
     assert!(!attrs.is_empty());
     assert_eq!(*attrs.last().unwrap(), EGL_NONE);
     assert_eq!(attrs.iter().filter(|&&a| a == EGL_NONE).count(), 1);
 }
+
+#[test]
+fn display_attributes_follow_options() {
+    let defaults = build_display_attributes(AngleOptions::default());
+    assert!(defaults.contains(&EGL_EXPERIMENTAL_PRESENT_PATH_ANGLE));
+    assert!(defaults.contains(&EGL_PLATFORM_ANGLE_ENABLE_AUTOMATIC_TRIM_ANGLE));
+
+    let stripped = build_display_attributes(AngleOptions {
+        experimental_present_path: false,
+        automatic_trim: false,
+    });
+    assert!(!stripped.contains(&EGL_EXPERIMENTAL_PRESENT_PATH_ANGLE));
+    assert!(!stripped.contains(&EGL_PLATFORM_ANGLE_ENABLE_AUTOMATIC_TRIM_ANGLE));
+    assert_eq!(*stripped.last().unwrap(), EGL_NONE);
+}
+
+#[test]
+fn present_after_shutdown_is_rejected() {
+    // A present still in flight once the overlay is gone reaches the callback
+    // without an overlay behind it.
+    assert!(!present_with_info_callback(std::ptr::null_mut(), std::ptr::null()));
+}
@@ -35,7 +35,7 @@ use crate::software_renderer::multiview::resize_decision::should_realloc_texture
 use crate::software_renderer::multiview::backing_store::{
     GlBackingStore, fill_opengl_backing_store,
 };
-use crate::software_renderer::multiview::view_surface::ViewSurface;
+use crate::software_renderer::multiview::view_surface::{SyncMode, ViewSurface};
 use crate::software_renderer::overlay::d3d::create_shared_texture_no_mutex;
 use crate::software_renderer::overlay::overlay_impl::{FlutterOverlay, SendableHandle};
 
@@ -290,9 +290,14 @@ extern "C" fn present_view_callback(info: *const FlutterPresentViewInfo) -> bool
         return present_implicit_view(host, info);
     }
 
+    let sync_mode = if host.present_fence_sync.load(Ordering::Relaxed) {
+        SyncMode::Fence
+    } else {
+        SyncMode::Finish
+    };
     let presented = host.view_registry.with_view(view_id, |surface| {
-        // Ensure GL work is submitted before the host reads the shared texture.
-        surface.gl_flush();
+        // Ensure GL work has completed before the host reads the shared texture.
+        surface.gl_sync(sync_mode);
 
         // Record damage for this frame (used by the host partial-copy step).
         // FlutterPresentViewInfo carries layers, not damage rects directly; the
@@ -35,7 +35,8 @@ use windows::Win32::UI::WindowsAndMessaging::{
 };
 use windows::core::{Interface, PCWSTR};
 
-use crate::software_renderer::api::{OverlayCreateParams, RendererType};
+use crate::software_renderer::api::{OverlayCreateParams, RendererType, TaskRunnerComApartment};
+use crate::software_renderer::gl_renderer::angle_interop::AngleOptions;
 use crate::software_renderer::multiview::window::{SatelliteWindow, WindowSpec, WindowStyle};
 use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
 
@@ -259,6 +260,14 @@ impl EngineHarness {
             flutter_data_dir: bundle,
             dart_entrypoint_args: dart_args,
             engine_args: None,
+            icu_override: None,
+            runtime_mode: None,
+            engine_flags: None,
+            task_runner_com: TaskRunnerComApartment::None,
+            custom_render_task_runner: true,
+            angle_options: AngleOptions::default(),
+            preferred_renderer: None,
+            flutter_paths: None,
         };
 
         let mut overlay = match FlutterOverlay::create(params, &device, &swap_chain) {
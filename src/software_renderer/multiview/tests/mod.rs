@@ -1,5 +1,6 @@
 mod resize_decision_tests;
 mod view_registry_tests;
+mod view_surface_tests;
 #[cfg(feature = "engine-tests")]
 mod harness;
 #[cfg(feature = "engine-tests")]
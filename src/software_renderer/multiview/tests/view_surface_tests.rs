@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use crate::software_renderer::multiview::view_surface::spin_until_signaled;
+
+#[test]
+fn returns_once_signaled() {
+    let mut polls = Vec::new();
+    let signaled = spin_until_signaled(Duration::from_secs(5), |first| {
+        polls.push(first);
+        polls.len() == 3
+    });
+    assert!(signaled);
+    assert_eq!(polls, [true, false, false]);
+}
+
+#[test]
+fn gives_up_after_timeout() {
+    let mut polls = 0;
+    let signaled = spin_until_signaled(Duration::from_millis(5), |_| {
+        polls += 1;
+        false
+    });
+    assert!(!signaled);
+    assert!(polls >= 2);
+}
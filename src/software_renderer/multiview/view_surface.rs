@@ -27,6 +27,10 @@
 
 use std::sync::Mutex;
 use std::sync::atomic::AtomicU64;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::warn;
 
 use windows::Win32::Foundation::CloseHandle;
 use windows::Win32::Graphics::Direct3D11::{
@@ -38,10 +42,81 @@ use std::ffi::c_void;
 
 use crate::bindings::embedder::{FlutterRect, FlutterViewId};
 use crate::software_renderer::gl_renderer::angle_interop::{
-    GL_COLOR_ATTACHMENT0, GL_FRAMEBUFFER, GL_FRAMEBUFFER_COMPLETE, GL_TEXTURE_2D, ViewGlProcs,
+    GL_ALREADY_SIGNALED, GL_COLOR_ATTACHMENT0, GL_CONDITION_SATISFIED, GL_FRAMEBUFFER,
+    GL_FRAMEBUFFER_COMPLETE, GL_SYNC_FLUSH_COMMANDS_BIT, GL_SYNC_GPU_COMMANDS_COMPLETE,
+    GL_TEXTURE_2D, GL_WAIT_FAILED, GlFenceProcs, ViewGlProcs,
 };
 use crate::software_renderer::overlay::overlay_impl::{SendHwnd, SendableHandle};
 
+/// How the present callback waits for a satellite view's GL work before the
+/// host may sample the shared texture.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SyncMode {
+    /// `glFinish`: stalls the raster thread until the whole GL pipeline drains.
+    #[default]
+    Finish,
+    /// A fence inserted after the frame, polled with `glClientWaitSync` and
+    /// yielding between polls for at most [`PRESENT_FENCE_TIMEOUT`]. Falls back
+    /// to `glFinish` if the context has no fences.
+    Fence,
+}
+
+/// Upper bound on a `SyncMode::Fence` wait. A frame still pending after this
+/// is presented anyway rather than stalling the raster thread indefinitely.
+pub const PRESENT_FENCE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Calls `signaled` until it returns `true` or `timeout` passes, yielding the
+/// thread between calls. `signaled` receives `true` on the first call only.
+/// Returns whether it was signaled in time.
+pub(crate) fn spin_until_signaled(
+    timeout: Duration,
+    mut signaled: impl FnMut(bool) -> bool,
+) -> bool {
+    let deadline = Instant::now() + timeout;
+    let mut first = true;
+    loop {
+        if signaled(first) {
+            return true;
+        }
+        first = false;
+        if Instant::now() >= deadline {
+            return false;
+        }
+        thread::yield_now();
+    }
+}
+
+/// Waits on a fence placed after the current GL work. Returns `false` if the
+/// fence could not be created or waited on, so the caller can fall back to
+/// `glFinish`.
+fn wait_for_gl_fence(fence: &GlFenceProcs) -> bool {
+    unsafe {
+        let sync = (fence.fence_sync)(GL_SYNC_GPU_COMMANDS_COMPLETE, 0);
+        if sync.is_null() {
+            return false;
+        }
+        let mut failed = false;
+        let signaled = spin_until_signaled(PRESENT_FENCE_TIMEOUT, |first| {
+            let flags = if first { GL_SYNC_FLUSH_COMMANDS_BIT } else { 0 };
+            match (fence.client_wait_sync)(sync, flags, 0) {
+                GL_ALREADY_SIGNALED | GL_CONDITION_SATISFIED => true,
+                GL_WAIT_FAILED => {
+                    failed = true;
+                    true
+                }
+                _ => false,
+            }
+        });
+        (fence.delete_sync)(sync);
+        if !signaled {
+            warn!(
+                "[ViewSurface] GL fence not signaled after {PRESENT_FENCE_TIMEOUT:?}, presenting anyway"
+            );
+        }
+        !failed
+    }
+}
+
 /// GL/EGL render resources for one secondary view: a pbuffer surface wrapping
 /// the view's shared D3D11 texture, a GL color texture bound from it, and an FBO
 /// the engine renders into. All created on the render thread.
@@ -169,16 +244,23 @@ impl ViewSurface {
         self.gl.as_ref().map(|g| g.color_texture).unwrap_or(0)
     }
 
-    /// Non-blocking GL flush, ensuring submitted commands reach the GPU before
-    /// the host reads the shared texture. There is no keyed mutex on satellite
-    /// views (it does not work across the three device round-trips), so GPU
-    /// ordering is provided by this flush plus the window thread's present-hold,
-    /// which never samples the texture until a fresh frame at the current size
-    /// has been confirmed.
-    pub fn gl_flush(&self) {
-        if let Some(gl) = &self.gl {
-            unsafe { (gl.procs.finish)() };
+    /// Waits for the submitted GL commands to complete before the host reads
+    /// the shared texture. There is no keyed mutex on satellite views (it does
+    /// not work across the three device round-trips), so GPU ordering is
+    /// provided by this wait plus the window thread's present-hold, which never
+    /// samples the texture until a fresh frame at the current size has been
+    /// confirmed.
+    pub fn gl_sync(&self, mode: SyncMode) {
+        let Some(gl) = &self.gl else {
+            return;
+        };
+        if mode == SyncMode::Fence
+            && let Some(fence) = &gl.procs.fence
+            && wait_for_gl_fence(fence)
+        {
+            return;
         }
+        unsafe { (gl.procs.finish)() };
     }
 
     /// Tears down this view's GL resources (FBO + color texture + pbuffer).
@@ -0,0 +1,77 @@
+//! Anchoring an overlay to a point of the screen.
+//!
+//! An overlay's `x`/`y` is its top-left corner. With an [`Anchor`] other than
+//! `TopLeft`, a screen resize keeps the overlay at the same distance from the
+//! anchored edge or corner instead: the distance is measured against the old
+//! screen and applied to the new one.
+
+/// Screen point an overlay keeps its distance to across resizes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Anchor {
+    /// Absolute positioning; resizes move the overlay back to the screen origin.
+    #[default]
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    Center,
+    CenterRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Horizontal and vertical position of the anchor in half-extents:
+    /// 0 = left/top, 1 = center, 2 = right/bottom.
+    fn halves(self) -> (i64, i64) {
+        match self {
+            Anchor::TopLeft => (0, 0),
+            Anchor::TopCenter => (1, 0),
+            Anchor::TopRight => (2, 0),
+            Anchor::CenterLeft => (0, 1),
+            Anchor::Center => (1, 1),
+            Anchor::CenterRight => (2, 1),
+            Anchor::BottomLeft => (0, 2),
+            Anchor::BottomCenter => (1, 2),
+            Anchor::BottomRight => (2, 2),
+        }
+    }
+
+    /// Top-left corner of a `size` overlay whose anchor point sits `offset`
+    /// pixels from the same point of the screen rect `origin`/`screen`.
+    pub fn position(
+        self,
+        offset: (i32, i32),
+        size: (u32, u32),
+        origin: (i32, i32),
+        screen: (u32, u32),
+    ) -> (i32, i32) {
+        let (hx, hy) = self.halves();
+        let axis = |offset: i32, size: u32, origin: i32, screen: u32, halves: i64| {
+            let slack = screen as i64 - size as i64;
+            let value = origin as i64 + slack * halves / 2 + offset as i64;
+            value.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+        };
+        (
+            axis(offset.0, size.0, origin.0, screen.0, hx),
+            axis(offset.1, size.1, origin.1, screen.1, hy),
+        )
+    }
+
+    /// Inverse of [`Anchor::position`]: how far an overlay at `position` is
+    /// from the anchor point of the screen rect.
+    pub fn offset(
+        self,
+        position: (i32, i32),
+        size: (u32, u32),
+        origin: (i32, i32),
+        screen: (u32, u32),
+    ) -> (i32, i32) {
+        let at_zero = self.position((0, 0), size, origin, screen);
+        (
+            position.0.saturating_sub(at_zero.0),
+            position.1.saturating_sub(at_zero.1),
+        )
+    }
+}
@@ -1,10 +1,15 @@
-use windows::Win32::Foundation::{HANDLE, HMODULE};
+use windows::Win32::Foundation::{CloseHandle, HANDLE, HMODULE};
 use windows::Win32::Graphics::Direct3D::{
     D3D_DRIVER_TYPE_UNKNOWN, D3D_FEATURE_LEVEL, D3D11_SRV_DIMENSION_TEXTURE2D,
 };
 use windows::Win32::Graphics::Direct3D11::*;
-use windows::Win32::Graphics::Dxgi::{Common::*, IDXGIDevice, IDXGIResource};
-use windows::core::{Interface, Result as WindowsResult};
+use windows::Win32::Graphics::Dxgi::{
+    Common::*, DXGI_SHARED_RESOURCE_READ, DXGI_SHARED_RESOURCE_WRITE, IDXGIDevice,
+    IDXGIKeyedMutex, IDXGIResource, IDXGIResource1,
+};
+use windows::core::{Interface, PCWSTR, Result as WindowsResult};
+
+use crate::software_renderer::overlay::overlay_impl::SendableHandle;
 
 pub fn create_d3d_device_on_same_adapter(
     existing_device: &ID3D11Device,
@@ -38,7 +43,8 @@ pub fn create_d3d_device_on_same_adapter(
     Ok(device.unwrap())
 }
 
-/// Create a dynamic RGBA8 texture of the given size.
+/// Create the RGBA8 texture the software renderer uploads into with
+/// `UpdateSubresource`, which needs `D3D11_USAGE_DEFAULT`.
 pub fn create_texture(device: &ID3D11Device, width: u32, height: u32) -> ID3D11Texture2D {
     let desc = D3D11_TEXTURE2D_DESC {
         Width: width,
@@ -50,9 +56,8 @@ pub fn create_texture(device: &ID3D11Device, width: u32, height: u32) -> ID3D11T
             Count: 1,
             Quality: 0,
         },
-        Usage: D3D11_USAGE_DYNAMIC,
+        Usage: D3D11_USAGE_DEFAULT,
         BindFlags: D3D11_BIND_SHADER_RESOURCE.0 as u32,
-        CPUAccessFlags: D3D11_CPU_ACCESS_WRITE.0 as u32,
         ..Default::default()
     };
     unsafe {
@@ -177,6 +182,134 @@ pub fn create_shared_texture_no_mutex(
     }
 }
 
+/// How an overlay's exported texture is shared with other devices or processes.
+///
+/// The default (both `false`) is a legacy `D3D11_RESOURCE_MISC_SHARED` texture
+/// opened with `OpenSharedResource`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextureShareOptions {
+    /// Share through an NT handle from `IDXGIResource1::CreateSharedHandle`, as
+    /// needed by `OpenSharedResource1`, CUDA and OpenCL interop. The overlay
+    /// owns the handle and closes it when the texture is dropped or recreated.
+    pub nt_handle: bool,
+    /// Guard the texture with a keyed mutex. The overlay takes key 0 around each
+    /// copy and releases it back to key 0, so readers do the same.
+    pub keyed_mutex: bool,
+}
+
+/// `MiscFlags` for an export texture created with `options`.
+pub(crate) fn export_misc_flags(options: TextureShareOptions) -> u32 {
+    let mut flags = if options.keyed_mutex {
+        D3D11_RESOURCE_MISC_SHARED_KEYEDMUTEX.0 as u32
+    } else {
+        D3D11_RESOURCE_MISC_SHARED.0 as u32
+    };
+    if options.nt_handle {
+        flags |= D3D11_RESOURCE_MISC_SHARED_NTHANDLE.0 as u32;
+    }
+    flags
+}
+
+/// Shareable copy of an overlay's texture, refreshed whenever the overlay's
+/// own texture receives new content.
+pub(crate) struct ExportTexture {
+    pub(crate) options: TextureShareOptions,
+    texture: ID3D11Texture2D,
+    pub(crate) handle: SendableHandle,
+    keyed_mutex: Option<IDXGIKeyedMutex>,
+}
+
+impl ExportTexture {
+    pub(crate) fn new(
+        device: &ID3D11Device,
+        width: u32,
+        height: u32,
+        options: TextureShareOptions,
+    ) -> Result<Self, String> {
+        let desc = D3D11_TEXTURE2D_DESC {
+            Width: width,
+            Height: height,
+            MipLevels: 1,
+            ArraySize: 1,
+            Format: DXGI_FORMAT_B8G8R8A8_UNORM,
+            SampleDesc: DXGI_SAMPLE_DESC {
+                Count: 1,
+                Quality: 0,
+            },
+            Usage: D3D11_USAGE_DEFAULT,
+            BindFlags: D3D11_BIND_RENDER_TARGET.0 as u32 | D3D11_BIND_SHADER_RESOURCE.0 as u32,
+            CPUAccessFlags: 0,
+            MiscFlags: export_misc_flags(options),
+        };
+        unsafe {
+            let mut texture_opt: Option<ID3D11Texture2D> = None;
+            device
+                .CreateTexture2D(&desc, None, Some(&mut texture_opt))
+                .map_err(|e| format!("Failed to create export texture: {e}"))?;
+            let texture = texture_opt.unwrap();
+
+            let handle = if options.nt_handle {
+                let resource: IDXGIResource1 = texture
+                    .cast()
+                    .map_err(|e| format!("Failed to cast texture to IDXGIResource1: {e}"))?;
+                resource
+                    .CreateSharedHandle(
+                        None,
+                        (DXGI_SHARED_RESOURCE_READ | DXGI_SHARED_RESOURCE_WRITE).0,
+                        PCWSTR::null(),
+                    )
+                    .map_err(|e| format!("Failed to create NT shared handle: {e}"))?
+            } else {
+                let resource: IDXGIResource = texture
+                    .cast()
+                    .map_err(|e| format!("Failed to cast texture to IDXGIResource: {e}"))?;
+                resource
+                    .GetSharedHandle()
+                    .map_err(|e| format!("Failed to get shared handle: {e}"))?
+            };
+
+            let keyed_mutex = if options.keyed_mutex {
+                texture.cast().ok()
+            } else {
+                None
+            };
+
+            Ok(Self {
+                options,
+                texture,
+                handle: SendableHandle(handle),
+                keyed_mutex,
+            })
+        }
+    }
+
+    /// Copies `source` into the export texture. With a keyed mutex, a frame is
+    /// skipped rather than stalling the host while a reader holds the key.
+    pub(crate) fn copy_from(&self, context: &ID3D11DeviceContext, source: &ID3D11Texture2D) {
+        unsafe {
+            if let Some(mutex) = &self.keyed_mutex {
+                // `AcquireSync` maps `WAIT_TIMEOUT` to `Ok`, so read the raw HRESULT.
+                let hr = (Interface::vtable(mutex).AcquireSync)(Interface::as_raw(mutex), 0, 0);
+                if hr.0 != 0 {
+                    return;
+                }
+            }
+            context.CopyResource(&self.texture, source);
+            if let Some(mutex) = &self.keyed_mutex {
+                let _ = mutex.ReleaseSync(0);
+            }
+        }
+    }
+}
+
+impl Drop for ExportTexture {
+    fn drop(&mut self) {
+        if self.options.nt_handle {
+            let _ = unsafe { CloseHandle(self.handle.0) };
+        }
+    }
+}
+
 pub fn create_compositing_texture(
     device: &ID3D11Device,
     width: u32,
@@ -2,12 +2,14 @@ use crate::bindings::embedder::{
     self, FlutterEngine, FlutterProjectArgs, FlutterRendererConfig, FlutterWindowMetricsEvent,
 };
 
+use crate::software_renderer::api::FlutterEmbedderError;
 use crate::software_renderer::dynamic_flutter_engine_dll_loader::FlutterEngineDll;
+use crate::software_renderer::overlay::lifecycle::{LIFECYCLE_CHANNEL, LifecycleState};
 use crate::software_renderer::overlay::overlay_impl::{
     PendingPlatformMessage, SendableFlutterEngine,
 };
 
-use log::error;
+use log::{error, warn};
 use std::ffi::c_void;
 use std::ptr;
 use std::sync::Arc;
@@ -22,7 +24,7 @@ pub(crate) fn run_engine(
     user_data: *mut c_void,
     overlay_raw_ptr: *mut FlutterOverlay,
     engine_dll_arc: Arc<FlutterEngineDll>,
-) -> Result<FlutterEngine, String> {
+) -> Result<FlutterEngine, FlutterEmbedderError> {
     unsafe {
         let mut engine_handle: FlutterEngine = ptr::null_mut();
 
@@ -31,7 +33,7 @@ pub(crate) fn run_engine(
                 "[Engine] overlay_raw_ptr is null. Cannot proceed with engine initialization."
                     .to_string();
             error!("{err_msg}");
-            return Err(err_msg);
+            return Err(FlutterEmbedderError::InitializationFailed(err_msg));
         }
 
         if user_data as *mut FlutterOverlay != overlay_raw_ptr {
@@ -39,7 +41,7 @@ pub(crate) fn run_engine(
                 "[Engine] user_data and overlay_raw_ptr mismatch — cannot safely proceed."
                     .to_string();
             error!("{err_msg}");
-            return Err(err_msg);
+            return Err(FlutterEmbedderError::InitializationFailed(err_msg));
         }
 
         let init_result = (engine_dll_arc.FlutterEngineInitialize)(
@@ -50,12 +52,15 @@ pub(crate) fn run_engine(
             &mut engine_handle,
         );
 
-        if init_result != embedder::FlutterEngineResult_kSuccess || engine_handle.is_null() {
-            let err_msg = format!(
-                "[Engine] FlutterEngineInitialize failed with result: {init_result:?} or engine handle is null."
-            );
-            error!("{err_msg}");
-            return Err(err_msg);
+        if init_result != embedder::FlutterEngineResult_kSuccess {
+            error!("[Engine] FlutterEngineInitialize failed with result: {init_result:?}");
+            return Err(FlutterEmbedderError::EngineRunFailed {
+                result_code: init_result,
+            });
+        }
+        if engine_handle.is_null() {
+            error!("[Engine] FlutterEngineInitialize succeeded but returned a null engine handle.");
+            return Err(FlutterEmbedderError::EngineNotRunning);
         }
 
         (*overlay_raw_ptr).engine = SendableFlutterEngine(engine_handle);
@@ -66,10 +71,9 @@ pub(crate) fn run_engine(
         let run_result = (engine_dll_arc.FlutterEngineRunInitialized)(engine_handle);
 
         if run_result != embedder::FlutterEngineResult_kSuccess {
-            let err_msg = format!(
+            error!(
                 "[Engine] FlutterEngineRunInitialized failed with result: {run_result:?}"
             );
-            error!("{err_msg}");
 
             (engine_dll_arc.FlutterEngineDeinitialize)(engine_handle);
             (*overlay_raw_ptr).engine = SendableFlutterEngine(ptr::null_mut());
@@ -77,12 +81,42 @@ pub(crate) fn run_engine(
                 .engine_atomic_ptr
                 .store(ptr::null_mut(), Ordering::SeqCst);
 
-            return Err(err_msg);
+            return Err(FlutterEmbedderError::EngineRunFailed {
+                result_code: run_result,
+            });
         }
         Ok(engine_handle)
     }
 }
 
+/// Largest texture edge every D3D11 feature level 11 device supports. Bigger
+/// overlays can't get a texture, so sizes are clamped to it.
+pub(crate) const MAX_OVERLAY_DIMENSION: u32 = 16384;
+
+/// Metrics as sent to the engine: `(left, top, width, height)`.
+pub(crate) type WindowMetrics = (usize, usize, usize, usize);
+
+/// Brings host-supplied metrics into the range the engine and the compositor
+/// can handle: a negative origin becomes `0` (the engine's origin is
+/// unsigned) and each edge is kept within `1..=MAX_OVERLAY_DIMENSION`. The
+/// second value is `true` if anything had to change.
+pub(crate) fn sanitize_window_metrics(
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+) -> (WindowMetrics, bool) {
+    let edge = |v: u32| v.clamp(1, MAX_OVERLAY_DIMENSION);
+    let metrics = (
+        x.max(0) as usize,
+        y.max(0) as usize,
+        edge(width) as usize,
+        edge(height) as usize,
+    );
+    let corrected = x < 0 || y < 0 || edge(width) != width || edge(height) != height;
+    (metrics, corrected)
+}
+
 pub(crate) fn update_flutter_window_metrics(
     engine: FlutterEngine,
     x: i32,
@@ -99,11 +133,19 @@ pub(crate) fn update_flutter_window_metrics(
     let mut wm: FlutterWindowMetricsEvent = unsafe { std::mem::zeroed() };
     wm.struct_size = std::mem::size_of::<FlutterWindowMetricsEvent>();
 
-    wm.width = width as usize;
-    wm.height = height as usize;
+    let ((left, top, sent_width, sent_height), corrected) =
+        sanitize_window_metrics(x, y, width, height);
+    if corrected {
+        warn!(
+            "[Metrics] Corrected window metrics ({x}, {y}) {width}x{height} to ({left}, {top}) \
+             {sent_width}x{sent_height}"
+        );
+    }
+    wm.width = sent_width;
+    wm.height = sent_height;
     wm.pixel_ratio = 1.0;
-    wm.left = x as usize;
-    wm.top = y as usize;
+    wm.left = left;
+    wm.top = top;
     let r = unsafe { (engine_dll.FlutterEngineSendWindowMetricsEvent)(engine, &wm) };
     if r != embedder::FlutterEngineResult_kSuccess {
         error!(
@@ -121,8 +163,8 @@ pub(crate) unsafe extern "C" fn on_root_isolate_created(user_data: *mut ::std::o
 
     let overlay: &mut FlutterOverlay = unsafe { &mut *(user_data as *mut FlutterOverlay) };
 
-    let channel = "flutter/lifecycle".to_string();
-    let payload_bytes = "AppLifecycleState.resumed".to_string().into_bytes();
+    let channel = LIFECYCLE_CHANNEL.to_string();
+    let payload_bytes = LifecycleState::Resumed.as_message().as_bytes().to_vec();
 
     let msg_lifecycle = PendingPlatformMessage {
         channel,
@@ -0,0 +1,50 @@
+//! Animated show/hide for overlays.
+//!
+//! A fade interpolates the overlay's opacity over a fixed duration. The state
+//! changes happen at the ends: a fade-out stops input when it starts and hides
+//! the overlay when it finishes, a fade-in shows the overlay when it starts and
+//! accepts input again only once it is fully opaque.
+
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct OverlayFade {
+    from: f32,
+    to: f32,
+    started_at: Instant,
+    duration: Duration,
+}
+
+impl OverlayFade {
+    /// Starts a fade from `from` to `to`, both clamped to `0.0..=1.0`.
+    pub(crate) fn new(from: f32, to: f32, duration: Duration, now: Instant) -> Self {
+        Self {
+            from: from.clamp(0.0, 1.0),
+            to: to.clamp(0.0, 1.0),
+            started_at: now,
+            duration,
+        }
+    }
+
+    /// Whether the fade ends hidden.
+    pub(crate) fn is_fade_out(&self) -> bool {
+        self.to < self.from || self.to == 0.0
+    }
+
+    /// Progress in `0.0..=1.0`; a zero duration is finished immediately.
+    fn progress(&self, now: Instant) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        let elapsed = now.saturating_duration_since(self.started_at);
+        (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    pub(crate) fn opacity_at(&self, now: Instant) -> f32 {
+        self.from + (self.to - self.from) * self.progress(now)
+    }
+
+    pub(crate) fn is_finished(&self, now: Instant) -> bool {
+        self.progress(now) >= 1.0
+    }
+}
@@ -0,0 +1,95 @@
+//! Engine liveness probe.
+//!
+//! A frozen UI isolate keeps a valid engine handle but stops answering platform
+//! messages. The probe needs a channel that is sure to reply: a message on a
+//! channel without a Dart handler is buffered until one is set, not answered.
+//! So it calls [`HEARTBEAT_METHOD`] on `flutter/navigation`, which
+//! `WidgetsBinding` handles in every app started with `runApp`. The binding
+//! ignores methods it doesn't know and answers with a success envelope holding
+//! `null`, which is all the probe looks for, so no Dart code is needed. A reply within the timeout means
+//! the isolate's event loop is still running.
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::{json, to_vec};
+
+use crate::software_renderer::api::FlutterEmbedderError;
+use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
+use crate::software_renderer::overlay::platform_message_callback::{
+    PlatformMessageReplyCallback, send_platform_message_with_response,
+};
+
+/// Channel the heartbeat is sent on. The framework registers its handler
+/// when `WidgetsBinding` starts.
+pub(crate) const HEARTBEAT_CHANNEL: &str = "flutter/navigation";
+
+/// Method the heartbeat calls. The navigation handler doesn't implement it,
+/// so the call has no effect beyond the reply.
+pub(crate) const HEARTBEAT_METHOD: &str = "FlutterEmbedder.heartbeat";
+
+/// JSON method call sent as the heartbeat.
+pub(crate) fn heartbeat_message() -> Vec<u8> {
+    to_vec(&json!({ "method": HEARTBEAT_METHOD, "args": null })).unwrap_or_default()
+}
+
+/// Set by the engine's reply callback, waited on by the caller.
+#[derive(Default)]
+pub(crate) struct Heartbeat {
+    answered: Mutex<bool>,
+    signal: Condvar,
+}
+
+impl Heartbeat {
+    pub(crate) fn mark_answered(&self) {
+        *self.answered.lock().unwrap_or_else(|p| p.into_inner()) = true;
+        self.signal.notify_all();
+    }
+
+    /// Whether the reply has arrived, without waiting.
+    pub(crate) fn is_answered(&self) -> bool {
+        *self.answered.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    /// Blocks until the reply arrives or `timeout` passes.
+    pub(crate) fn wait(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut answered = self.answered.lock().unwrap_or_else(|p| p.into_inner());
+        while !*answered {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            answered = match self.signal.wait_timeout(answered, deadline - now) {
+                Ok((guard, _)) => guard,
+                Err(poisoned) => poisoned.into_inner().0,
+            };
+        }
+        true
+    }
+}
+
+/// Reply callback that marks `heartbeat` answered. The reply itself is
+/// ignored; any answer shows the isolate is still processing messages.
+pub(crate) fn heartbeat_reply(heartbeat: &Arc<Heartbeat>) -> PlatformMessageReplyCallback {
+    let heartbeat = heartbeat.clone();
+    Box::new(move |_reply| heartbeat.mark_answered())
+}
+
+/// Sends one heartbeat ping and returns the handle to wait on.
+///
+/// The reply callback owns one reference to the `Heartbeat`; a ping that is
+/// never answered, say because the app doesn't use `WidgetsBinding`, leaks that
+/// small allocation.
+pub(crate) fn send_heartbeat(
+    overlay: &FlutterOverlay,
+) -> Result<Arc<Heartbeat>, FlutterEmbedderError> {
+    let heartbeat = Arc::new(Heartbeat::default());
+    send_platform_message_with_response(
+        overlay,
+        HEARTBEAT_CHANNEL,
+        &heartbeat_message(),
+        heartbeat_reply(&heartbeat),
+    )?;
+    Ok(heartbeat)
+}
@@ -0,0 +1,242 @@
+//! IME composition for text fields.
+//!
+//! With a CJK IME active, keystrokes go to the IME rather than becoming
+//! `WM_CHAR`. The text being composed arrives as `WM_IME_COMPOSITION` with
+//! `GCS_COMPSTR` and the confirmed text with `GCS_RESULTSTR`. Both are written
+//! into the focused field's [`TextInputModel`], the composing part marked as
+//! the composing range so Flutter draws it underlined in place, and sent back
+//! with `TextInputClient.updateEditingState`.
+//!
+//! Flutter reports where the field is with `TextInput.setEditableSizeAndTransform`
+//! and where the composing text or caret is with `TextInput.setMarkedTextRect`
+//! / `TextInput.setCaretRect`. [`CaretGeometry`] keeps the latest of these so
+//! the candidate window can open under the caret instead of at the window
+//! corner.
+//!
+//! [`TextInputModel`]: crate::software_renderer::overlay::textinput::TextInputModel
+
+use serde_json::Value;
+use windows::Win32::Foundation::{HWND, LPARAM, POINT, RECT};
+use windows::Win32::UI::Input::Ime::{
+    CANDIDATEFORM, CFS_EXCLUDE, CFS_POINT, COMPOSITIONFORM, GCS_COMPSTR, GCS_CURSORPOS,
+    GCS_RESULTSTR, HIMC, IME_COMPOSITION_STRING, ImmGetCompositionStringW, ImmGetContext,
+    ImmReleaseContext, ImmSetCandidateWindow, ImmSetCompositionWindow,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION, WM_IME_STARTCOMPOSITION,
+};
+
+use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
+use crate::software_renderer::overlay::textinput::{
+    TextInputModel, send_update_editing_state_to_flutter,
+};
+
+/// Column-major 4x4 identity, the transform until Flutter sends one.
+const IDENTITY: [f64; 16] = [
+    1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+];
+
+/// Where the focused text field and its caret are, as last reported on
+/// `flutter/textinput`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CaretGeometry {
+    /// Field-to-view transform from `setEditableSizeAndTransform`.
+    transform: [f64; 16],
+    /// `(x, y, width, height)` of the composing text, in field coordinates.
+    marked_rect: Option<[f64; 4]>,
+    /// `(x, y, width, height)` of the caret, in field coordinates.
+    caret_rect: Option<[f64; 4]>,
+}
+
+impl Default for CaretGeometry {
+    fn default() -> Self {
+        Self {
+            transform: IDENTITY,
+            marked_rect: None,
+            caret_rect: None,
+        }
+    }
+}
+
+impl CaretGeometry {
+    /// Records the geometry carried by a `flutter/textinput` method call.
+    /// Other methods are ignored, except `setClient`, which starts over for
+    /// the new field.
+    pub(crate) fn apply(&mut self, method_name: &str, args: Option<&Value>) {
+        match method_name {
+            "TextInput.setClient" => *self = Self::default(),
+            "TextInput.setEditableSizeAndTransform" => {
+                let transform = args
+                    .and_then(|a| a.get("transform"))
+                    .and_then(|t| t.as_array())
+                    .filter(|t| t.len() == 16);
+                if let Some(transform) = transform {
+                    for (slot, value) in self.transform.iter_mut().zip(transform) {
+                        *slot = value.as_f64().unwrap_or(0.0);
+                    }
+                }
+            }
+            "TextInput.setMarkedTextRect" => self.marked_rect = args.and_then(read_rect),
+            "TextInput.setCaretRect" => self.caret_rect = args.and_then(read_rect),
+            _ => {}
+        }
+    }
+
+    /// `(left, top, right, bottom)` of the composing text, or of the caret
+    /// before anything is composed, in view pixels.
+    pub(crate) fn view_rect(&self) -> Option<(f64, f64, f64, f64)> {
+        let [x, y, width, height] = self.marked_rect.or(self.caret_rect)?;
+        let (left, top) = self.transform_point(x, y);
+        let (right, bottom) = self.transform_point(x + width, y + height);
+        Some((left.min(right), top.min(bottom), left.max(right), top.max(bottom)))
+    }
+
+    fn transform_point(&self, x: f64, y: f64) -> (f64, f64) {
+        let m = &self.transform;
+        let w = m[3] * x + m[7] * y + m[15];
+        let w = if w == 0.0 { 1.0 } else { w };
+        ((m[0] * x + m[4] * y + m[12]) / w, (m[1] * x + m[5] * y + m[13]) / w)
+    }
+}
+
+/// Reads `{x, y, width, height}`. Flutter sends negative sizes when it has no
+/// rect to report.
+fn read_rect(args: &Value) -> Option<[f64; 4]> {
+    let field = |name| args.get(name).and_then(Value::as_f64);
+    let rect = [field("x")?, field("y")?, field("width")?, field("height")?];
+    (rect[2] >= 0.0 && rect[3] >= 0.0).then_some(rect)
+}
+
+/// Handles `WM_IME_STARTCOMPOSITION`, `WM_IME_COMPOSITION` and
+/// `WM_IME_ENDCOMPOSITION` for the overlay's focused text field. Returns
+/// `false` when no field has input focus, leaving the IME to its defaults.
+pub(crate) fn handle_ime_event(
+    overlay: &FlutterOverlay,
+    hwnd: HWND,
+    msg: u32,
+    lparam: LPARAM,
+) -> bool {
+    let has_client = overlay.active_text_input.lock().is_ok_and(|active| active.is_some());
+    let is_ime_message = matches!(
+        msg,
+        WM_IME_STARTCOMPOSITION | WM_IME_COMPOSITION | WM_IME_ENDCOMPOSITION
+    );
+    if !has_client || !is_ime_message {
+        return false;
+    }
+    let himc = unsafe { ImmGetContext(hwnd) };
+    if himc.0.is_null() {
+        return false;
+    }
+    match msg {
+        WM_IME_COMPOSITION => {
+            let flags = lparam.0 as u32;
+            let result = (flags & GCS_RESULTSTR.0 != 0)
+                .then(|| composition_string(himc, GCS_RESULTSTR))
+                .flatten();
+            let composing = (flags & GCS_COMPSTR.0 != 0)
+                .then(|| composition_string(himc, GCS_COMPSTR))
+                .flatten();
+            let cursor = (flags & GCS_CURSORPOS.0 != 0)
+                .then(|| unsafe { ImmGetCompositionStringW(himc, GCS_CURSORPOS, None, 0) })
+                .filter(|cursor| *cursor >= 0)
+                .map(|cursor| cursor as usize);
+            update_model(overlay, |model| {
+                if let Some(result) = &result {
+                    model.commit_composing(result);
+                }
+                if let Some(composing) = &composing {
+                    model.set_composing_text(composing, cursor);
+                }
+            });
+        }
+        WM_IME_ENDCOMPOSITION => {
+            // A cancelled composition has already been cleared by an empty
+            // GCS_COMPSTR; whatever is left stays in the field as typed.
+            update_model(overlay, |model| model.composing_utf8 = None);
+        }
+        _ => {}
+    }
+    place_ime_windows(overlay, himc);
+    unsafe {
+        let _ = ImmReleaseContext(hwnd, himc);
+    }
+    true
+}
+
+/// Reads one of the IME's composition strings.
+fn composition_string(himc: HIMC, kind: IME_COMPOSITION_STRING) -> Option<String> {
+    let byte_len = unsafe { ImmGetCompositionStringW(himc, kind, None, 0) };
+    if byte_len < 0 {
+        return None;
+    }
+    let mut buffer = vec![0u16; byte_len as usize / 2];
+    if !buffer.is_empty() {
+        let written = unsafe {
+            ImmGetCompositionStringW(himc, kind, Some(buffer.as_mut_ptr().cast()), byte_len as u32)
+        };
+        buffer.truncate(written.max(0) as usize / 2);
+    }
+    Some(String::from_utf16_lossy(&buffer))
+}
+
+/// Applies `edit` to the focused field's model and sends Flutter the new
+/// editing state if anything changed.
+fn update_model(overlay: &FlutterOverlay, edit: impl FnOnce(&mut TextInputModel)) {
+    let update = {
+        let Ok(mut active) = overlay.active_text_input.lock() else {
+            return;
+        };
+        let Some(active) = active.as_mut() else {
+            return;
+        };
+        let before = active.model.editing_state();
+        edit(&mut active.model);
+        (active.model.editing_state() != before).then(|| (active.client_id, active.model.clone()))
+    };
+    if let Some((client_id, model)) = update {
+        send_update_editing_state_to_flutter(&overlay.pending_platform_messages, client_id, &model);
+    }
+}
+
+/// Moves the composition window to the caret and opens the candidate list
+/// below it without covering the composing text.
+fn place_ime_windows(overlay: &FlutterOverlay, himc: HIMC) {
+    let Some((left, top, right, bottom)) = overlay
+        .caret_geometry
+        .lock()
+        .ok()
+        .and_then(|geometry| geometry.view_rect())
+    else {
+        return;
+    };
+    let (x0, y0) = overlay.overlay_to_client(left, top);
+    let (x1, y1) = overlay.overlay_to_client(right, bottom);
+    let area = RECT {
+        left: x0.min(x1).floor() as i32,
+        top: y0.min(y1).floor() as i32,
+        right: x0.max(x1).ceil() as i32,
+        bottom: y0.max(y1).ceil() as i32,
+    };
+    let composition = COMPOSITIONFORM {
+        dwStyle: CFS_POINT,
+        ptCurrentPos: POINT {
+            x: area.left,
+            y: area.top,
+        },
+        rcArea: RECT::default(),
+    };
+    let candidate = CANDIDATEFORM {
+        dwIndex: 0,
+        dwStyle: CFS_EXCLUDE,
+        ptCurrentPos: POINT {
+            x: area.left,
+            y: area.bottom,
+        },
+        rcArea: area,
+    };
+    unsafe {
+        let _ = ImmSetCompositionWindow(himc, &composition);
+        let _ = ImmSetCandidateWindow(himc, &candidate);
+    }
+}
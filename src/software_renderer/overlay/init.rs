@@ -1,5 +1,8 @@
 use crate::path_utils::load_flutter_build_paths;
-use crate::software_renderer::api::{OverlayCreateParams, RendererType};
+use crate::software_renderer::api::{
+    FlutterEmbedderError, FrameTimer, OverlayCreateParams, RendererType, RuntimeMode,
+    StartupTimings,
+};
 use crate::software_renderer::d3d11_compositor::effects::EffectConfig;
 use crate::software_renderer::d3d11_compositor::post_processing_renderer::PostProcessRenderer;
 use crate::software_renderer::d3d11_compositor::primitive_3d_renderer::Primitive3DRenderer;
@@ -10,6 +13,7 @@ use windows::core::Interface;
 use crate::software_renderer::gl_renderer::angle_interop::{
     AngleInteropState, SendableAngleState, build_opengl_renderer_config,
 };
+use crate::software_renderer::overlay::anchor::Anchor;
 use crate::software_renderer::overlay::d3d::{
     create_compositing_texture, create_srv, create_texture,
 };
@@ -24,20 +28,28 @@ use crate::software_renderer::overlay::textinput::{
     ViewKeyboardState, register_view_keyboard_state,
 };
 use crate::software_renderer::overlay::project_args::{
-    build_project_args_and_strings, flutter_log_callback, maybe_load_aot_path_to_cstring,
+    append_engine_switches, build_project_args_and_strings, engine_args_with_runtime_mode,
+    flutter_log_callback, maybe_load_aot_path_to_cstring,
 };
 use crate::software_renderer::multiview::ViewRegistry;
 use crate::software_renderer::multiview::compositor::{
     build_compositor, view_focus_change_request_callback,
 };
 use crate::software_renderer::overlay::renderer::build_software_renderer_config;
+use crate::software_renderer::overlay::ime::CaretGeometry;
+use crate::software_renderer::overlay::input::{InputTransform, ScaledCursorCache, ScrollConfig};
+use crate::software_renderer::overlay::touch::TouchContacts;
+use crate::software_renderer::overlay::trackpad::TrackpadScrollState;
 
 use crate::bindings::embedder::{
     self, FlutterCustomTaskRunners, FlutterEngineAOTDataSource,
     FlutterEngineAOTDataSourceType_kFlutterEngineAOTDataSourceTypeElfPath,
     FlutterEngineResult_kSuccess, FlutterProjectArgs, FlutterTaskRunnerDescription,
 };
-use crate::software_renderer::overlay::semantics_handler::semantics_update_callback;
+use crate::software_renderer::overlay::lifecycle::LifecycleState;
+use crate::software_renderer::overlay::semantics_handler::{
+    HitTestMode, HoverDebounce, SemanticsThrottle, semantics_update_callback,
+};
 use crate::software_renderer::ticker::spawn::start_task_runner;
 use crate::software_renderer::ticker::task_runner_window::Waker;
 use crate::software_renderer::ticker::task_scheduler::{
@@ -46,11 +58,12 @@ use crate::software_renderer::ticker::task_scheduler::{
     runs_task_on_current_thread_callback,
 };
 
-use log::error;
+use log::{error, info, warn};
 use std::collections::{HashMap, VecDeque};
 use std::ffi::{CString, c_char};
 use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicPtr, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
 use std::{ffi::c_void, path::PathBuf, ptr};
 use windows::Win32::Graphics::Direct3D11::{
     D3D11_QUERY_DESC, D3D11_QUERY_EVENT, ID3D11Device, ID3D11Query, ID3D11ShaderResourceView,
@@ -87,7 +100,7 @@ pub(crate) fn init_overlay(
     params: OverlayCreateParams,
     device: &ID3D11Device,
     swap_chain: &IDXGISwapChain,
-) -> Option<Box<FlutterOverlay>> {
+) -> Result<Box<FlutterOverlay>, FlutterEmbedderError> {
     let OverlayCreateParams {
         name,
         x,
@@ -97,12 +110,22 @@ pub(crate) fn init_overlay(
         flutter_data_dir,
         dart_entrypoint_args,
         engine_args,
+        icu_override,
+        runtime_mode,
+        engine_flags,
+        task_runner_com,
+        custom_render_task_runner,
+        angle_options,
+        preferred_renderer,
+        flutter_paths,
     } = params;
+    let require_opengl = preferred_renderer == Some(RendererType::OpenGL);
     let data_dir: Option<PathBuf> = Some(flutter_data_dir);
     let dart_args_opt: Option<&[String]> = dart_entrypoint_args.as_deref();
-    let engine_args_opt: Option<&[String]> = engine_args.as_deref();
+    let mut engine_args = engine_args_with_runtime_mode(engine_args.as_deref(), runtime_mode);
 
     unsafe {
+        let startup_started_at = Instant::now();
         let engine_dll_load_dir = data_dir.as_deref();
         let engine_dll_arc = match FlutterEngineDll::get_for(engine_dll_load_dir) {
             Ok(dll) => dll,
@@ -110,20 +133,76 @@ pub(crate) fn init_overlay(
                 error!(
                     "Failed to load flutter_engine.dll from `{engine_dll_load_dir:?}`: {e:?}"
                 );
-                return None;
+                return Err(FlutterEmbedderError::DllLoadFailed {
+                    path: engine_dll_load_dir.map(PathBuf::from).unwrap_or_default(),
+                    source: e.into(),
+                });
             }
         };
 
+        let dll_load_ms = elapsed_ms(startup_started_at);
+
         if width == 0 || height == 0 {
             error!(
                 "Width and height must be non-zero, got {width}x{height}"
             );
-            return None;
+            return Err(FlutterEmbedderError::InitializationFailed(format!(
+                "width and height must be non-zero, got {width}x{height}"
+            )));
+        }
+
+        if flutter_paths.is_none()
+            && let Some(icu) = icu_override.as_deref()
+            && !icu.is_file()
+        {
+            error!("ICU data override `{}` does not exist", icu.display());
+            return Err(FlutterEmbedderError::AssetMissing {
+                which: "icudtl.dat",
+                path: icu.to_path_buf(),
+            });
         }
 
-        let (assets, icu, aot_opt) = load_flutter_build_paths(data_dir.clone());
+        let (assets, icu, mut aot_opt) = match flutter_paths {
+            Some(paths) => {
+                if let Err(e) = paths.validate() {
+                    error!("[InitOverlay] {e}");
+                    return Err(e);
+                }
+                (
+                    paths.assets.into_os_string(),
+                    paths.icu.into_os_string(),
+                    paths.aot.map(PathBuf::into_os_string),
+                )
+            }
+            None => load_flutter_build_paths(data_dir.clone(), icu_override.as_deref()),
+        };
+        match runtime_mode {
+            Some(mode) if mode.uses_aot() && aot_opt.is_none() => {
+                error!("Runtime mode {mode:?} requires app.so, but the bundle has none");
+                return Err(FlutterEmbedderError::AssetMissing {
+                    which: "app.so",
+                    path: data_dir.clone().unwrap_or_default(),
+                });
+            }
+            Some(RuntimeMode::Debug) => aot_opt = None,
+            _ => {}
+        }
+        if let Some(mode) = runtime_mode {
+            info!("[InitOverlay] Starting '{name}' in {mode:?} mode");
+        }
         let initial_is_debug = aot_opt.is_none();
 
+        if let Some(flags) = engine_flags.as_ref() {
+            let effective_mode = runtime_mode.unwrap_or(if initial_is_debug {
+                RuntimeMode::Debug
+            } else {
+                RuntimeMode::Release
+            });
+            let switches = flags.switches_for(effective_mode);
+            engine_args = append_engine_switches(engine_args.as_deref(), &switches);
+        }
+        let engine_args_opt: Option<&[String]> = engine_args.as_deref();
+
         let (assets_c_temp, icu_c_temp, engine_argv_cs_temp, mut dart_argv_cs_temp) =
             build_project_args_and_strings(
                 &assets.to_string_lossy(),
@@ -138,12 +217,18 @@ pub(crate) fn init_overlay(
             Ok(desc) => desc,
             Err(e) => {
                 error!("Failed to get swap chain description: {e}");
-                return None;
+                return Err(FlutterEmbedderError::RendererInitFailed {
+                    renderer: "D3D11",
+                    egl_code: None,
+                    message: format!("IDXGISwapChain::GetDesc failed: {e}"),
+                });
             }
         };
         let hwnd = swap_chain_desc.OutputWindow;
         let game_device: &ID3D11Device = device;
 
+        let renderer_init_started_at = Instant::now();
+
         let RendererInitResources {
             rdr_cfg,
             texture: texture_for_struct,
@@ -158,12 +243,20 @@ pub(crate) fn init_overlay(
             game_keyed_mutex: game_keyed_mutex_for_struct,
             renderer_type: final_renderer_type,
         } = 'opengl_attempt: {
+            if preferred_renderer == Some(RendererType::Software) {
+                info!("[InitOverlay] '{name}' requested the software renderer, skipping ANGLE");
+                break 'opengl_attempt build_software_renderer_config_tuple(
+                    game_device,
+                    width,
+                    height,
+                );
+            }
             if OPENGL_CONTEXT_CREATED
                 .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
                 .is_ok()
             {
                 let opengl_init_result =
-                    AngleInteropState::new(data_dir.as_deref()).and_then(|mut state| {
+                    AngleInteropState::new(data_dir.as_deref(), angle_options).and_then(|mut state| {
                         state
                             .recreate_resources(width, height)
                             .map(|(texture, handle)| (state, texture, handle))
@@ -179,11 +272,20 @@ pub(crate) fn init_overlay(
                         {
                             Some(tex) => tex,
                             None => {
+                                drop(angle_state);
+                                OPENGL_CONTEXT_CREATED.store(false, Ordering::SeqCst);
+                                if require_opengl {
+                                    error!("[InitOverlay] OpenSharedResource failed for '{name}'");
+                                    return Err(FlutterEmbedderError::RendererInitFailed {
+                                        renderer: "OpenGL",
+                                        egl_code: None,
+                                        message: "OpenSharedResource failed for the ANGLE texture"
+                                            .to_string(),
+                                    });
+                                }
                                 error!(
                                     "[InitOverlay] OpenSharedResource failed for '{name}'. Falling back to software renderer.",
                                 );
-                                drop(angle_state);
-                                OPENGL_CONTEXT_CREATED.store(false, Ordering::SeqCst);
                                 break 'opengl_attempt build_software_renderer_config_tuple(
                                     game_device,
                                     width,
@@ -248,13 +350,30 @@ pub(crate) fn init_overlay(
                     }
                     Err(e) => {
                         // If even the first attempt fails, reset the flag and fall back.
+                        OPENGL_CONTEXT_CREATED.store(false, Ordering::SeqCst);
+                        if require_opengl {
+                            error!("OpenGL initialization failed for overlay '{name}': {e}");
+                            return Err(FlutterEmbedderError::RendererInitFailed {
+                                renderer: "OpenGL",
+                                egl_code: None,
+                                message: e,
+                            });
+                        }
                         error!(
                             "OpenGL initialization failed for overlay: {e}. Falling back to software."
                         );
-                        OPENGL_CONTEXT_CREATED.store(false, Ordering::SeqCst);
                         build_software_renderer_config_tuple(game_device, width, height)
                     }
                 }
+            } else if require_opengl {
+                error!(
+                    "[InitOverlay] '{name}' requires OpenGL, but another overlay already uses it"
+                );
+                return Err(FlutterEmbedderError::RendererInitFailed {
+                    renderer: "OpenGL",
+                    egl_code: None,
+                    message: "another overlay already owns the OpenGL context".to_string(),
+                });
             } else {
                 build_software_renderer_config_tuple(game_device, width, height)
             }
@@ -262,7 +381,9 @@ pub(crate) fn init_overlay(
         let post_processor = PostProcessRenderer::new(device);
         let primitive_renderer = Primitive3DRenderer::new(device);
         let text_renderer = Text3DRenderer::new(device);
+        let renderer_init_ms = elapsed_ms(renderer_init_started_at);
 
+        info!("[InitOverlay] '{name}' uses the {final_renderer_type:?} renderer");
         let renderer_arg = match final_renderer_type {
             RendererType::OpenGL => "--renderer=opengl",
             RendererType::Software => "--renderer=software",
@@ -288,11 +409,28 @@ pub(crate) fn init_overlay(
             pixel_buffer: pixel_buffer_for_struct,
             software_frame_dirty: AtomicBool::new(false),
             software_first_frame_rendered: AtomicBool::new(false),
+            software_damage: Mutex::new(None),
             width,
             height,
             visible: true,
             keep_alive: false,
             ui_hidden: false,
+            auto_raise_on_hover: true,
+            modal: false,
+            input_priority: 0,
+            anchor: Anchor::TopLeft,
+            consumes_input: true,
+            hit_test_mode: HitTestMode::SemanticsOnly,
+            fixed_size: None,
+            user_data: None,
+            input_enabled: true,
+            input_transform: InputTransform::default(),
+            render_scale: 1.0,
+            rotation_degrees: 0.0,
+            scroll_config: ScrollConfig::default(),
+            cursor_scaling: false,
+            scaled_cursors: Arc::new(Mutex::new(ScaledCursorCache::default())),
+            fade: None,
             effect_config: EffectConfig::default(),
             effect_frames_remaining: 0,
             effect_total_frames: 0,
@@ -305,6 +443,8 @@ pub(crate) fn init_overlay(
             primitive_renderer,
             text_renderer,
             desired_cursor: Arc::new(Mutex::new(None)),
+            restoration_data: Arc::new(Mutex::new(None)),
+            vm_service_uri: Arc::new(Mutex::new(None)),
             task_queue_state: task_queue_arc,
             task_runner_thread: None,
             message_handlers: Arc::new(Mutex::new(HashMap::new())),
@@ -321,13 +461,18 @@ pub(crate) fn init_overlay(
             engine_dll: engine_dll_arc.clone(),
             view0_keyboard: Arc::new(ViewKeyboardState::new()),
             active_text_input: Arc::new(Mutex::new(None)),
+            caret_geometry: Arc::new(Mutex::new(CaretGeometry::default())),
             pending_platform_messages: Arc::new(Mutex::new(VecDeque::new())),
             pending_key_events: Arc::new(Mutex::new(VecDeque::new())),
             pending_view_focus: Arc::new(Mutex::new(VecDeque::new())),
             mouse_buttons_state: AtomicI32::new(0),
             is_mouse_added: AtomicBool::new(false),
+            trackpad_scroll: Mutex::new(TrackpadScrollState::default()),
+            touch_contacts: Mutex::new(TouchContacts::default()),
             semantics_tree_data: Arc::new(Mutex::new(HashMap::new())),
             is_interactive_widget_hovered: AtomicBool::new(false),
+            hover_debounce: Mutex::new(HoverDebounce::default()),
+            semantics_throttle: Mutex::new(SemanticsThrottle::default()),
             windows_handler: SendHwnd(hwnd),
             is_debug_build: initial_is_debug,
             angle_shared_texture: angle_shared_texture_for_struct,
@@ -338,15 +483,29 @@ pub(crate) fn init_overlay(
             renderer_type: final_renderer_type,
             angle_state: angle_state_for_struct,
             d3d11_shared_handle: d3d11_shared_handle_for_struct,
+            export_texture: None,
             angle_frame_complete_query: angle_query_for_struct,
             angle_frame_presented: std::sync::atomic::AtomicU64::new(0),
             angle_frame_copied: std::sync::atomic::AtomicU64::new(0),
             damage_rects: std::sync::Mutex::new(Vec::new()),
             frame_damage_rects: std::sync::Mutex::new(Vec::new()),
             full_repaint_needed: std::sync::atomic::AtomicBool::new(true),
+            present_fence_sync: std::sync::atomic::AtomicBool::new(false),
             view_registry: Arc::new(ViewRegistry::new()),
             view0_gl: None,
             compositor_active,
+            render_suspended: false,
+            lifecycle_state: LifecycleState::Resumed,
+            startup_timings: StartupTimings {
+                dll_load_ms,
+                renderer_init_ms,
+                ..Default::default()
+            },
+            startup_started_at,
+            first_frame_ms: OnceLock::new(),
+            last_present_nanos: std::sync::atomic::AtomicU64::new(0),
+            frame_timer: Mutex::new(FrameTimer::default()),
+            task_runner_com,
         });
 
         register_view_keyboard_state(0, overlay_box.view0_keyboard.clone());
@@ -361,7 +520,9 @@ pub(crate) fn init_overlay(
                 Some(ctx) => ctx.as_ref() as *const _ as *mut c_void,
                 None => {
                     error!("[InitOverlay] Platform runner context missing");
-                    return None;
+                    return Err(FlutterEmbedderError::InitializationFailed(
+                        "platform runner context missing".to_string(),
+                    ));
                 }
             },
             runs_task_on_current_thread_callback: Some(runs_task_on_current_thread_callback),
@@ -373,10 +534,17 @@ pub(crate) fn init_overlay(
         let platform_description_box =
             Box::new(SendableFlutterTaskRunnerDescription(platform_description));
 
+        // A null render runner makes the engine spawn its own raster thread.
+        let render_task_runner: *const FlutterTaskRunnerDescription =
+            if custom_render_task_runner {
+                &platform_description_box.0
+            } else {
+                ptr::null()
+            };
         let custom_task_runners = FlutterCustomTaskRunners {
             struct_size: std::mem::size_of::<FlutterCustomTaskRunners>(),
             platform_task_runner: &platform_description_box.0,
-            render_task_runner: &platform_description_box.0,
+            render_task_runner,
             thread_priority_setter: None,
             ui_task_runner: &platform_description_box.0, // Merged thread mode: UI runs on platform thread
         };
@@ -494,6 +662,7 @@ pub(crate) fn init_overlay(
         }
 
         let raw_ptr_to_overlay_for_run_engine: *mut FlutterOverlay = &mut *overlay_box;
+        let engine_run_started_at = Instant::now();
         let engine_run_result = run_engine(
             FLUTTER_ENGINE_VERSION,
             &rdr_cfg,
@@ -510,21 +679,46 @@ pub(crate) fn init_overlay(
                     "[InitOverlay] Engine initialization failed during run_engine: {e}"
                 );
                 engine_atomic_ptr_instance.store(ptr::null_mut(), Ordering::SeqCst);
-                return None;
+                return Err(e);
             }
         };
 
-        (engine_dll_arc.FlutterEngineUpdateSemanticsEnabled)(engine_handle, true);
+        // Flutter only sends semantics updates once they are enabled, and the
+        // hover detection over interactive widgets runs on that tree.
+        let semantics_result =
+            (engine_dll_arc.FlutterEngineUpdateSemanticsEnabled)(engine_handle, true);
+        if semantics_result != FlutterEngineResult_kSuccess {
+            warn!(
+                "[InitOverlay] Enabling semantics for '{}' failed ({semantics_result:?}); hover detection over widgets is off",
+                overlay_box.name
+            );
+        }
 
         overlay_box.engine = SendableFlutterEngine(engine_handle);
         engine_atomic_ptr_instance.store(engine_handle, Ordering::SeqCst);
 
         update_flutter_window_metrics(engine_handle, x, y, width, height, engine_dll_arc.clone());
 
-        Some(overlay_box)
+        overlay_box.startup_timings.engine_run_ms = elapsed_ms(engine_run_started_at);
+        let timings = overlay_box.startup_timings;
+        info!(
+            "[InitOverlay] '{}' started in {:.1} ms (dll load {:.1} ms, renderer init {:.1} ms, engine run {:.1} ms, {:?})",
+            overlay_box.name,
+            elapsed_ms(startup_started_at),
+            timings.dll_load_ms,
+            timings.renderer_init_ms,
+            timings.engine_run_ms,
+            overlay_box.renderer_type,
+        );
+
+        Ok(overlay_box)
     }
 }
 
+fn elapsed_ms(since: Instant) -> f64 {
+    since.elapsed().as_secs_f64() * 1000.0
+}
+
 fn build_software_renderer_config_tuple(
     game_device: &ID3D11Device,
     width: u32,
@@ -1,28 +1,199 @@
+use std::collections::HashMap;
+use std::ffi::c_void;
 use std::sync::atomic::Ordering;
+use std::time::Instant;
 
 use windows::Win32::Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, POINT, WPARAM};
 use windows::Win32::Graphics::Gdi::ScreenToClient;
+use windows::Win32::UI::Input::Touch::{
+    GESTURECONFIG_ID, GESTUREINFO, GID_ROTATE, GID_ZOOM, GetGestureInfo, HGESTUREINFO,
+};
 use windows::Win32::UI::WindowsAndMessaging::{
-    HCURSOR, HTCLIENT, IDC_ARROW, IDC_HAND, IDC_IBEAM, IDC_NO, LoadCursorW, SetCursor,
-    WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MOUSEMOVE, WM_MOUSEWHEEL,
-    WM_NCMOUSELEAVE, WM_RBUTTONDOWN, WM_RBUTTONUP,
+    DestroyCursor, GF_BEGIN, GF_END, GetCursorPos, GetSystemMetrics, HCURSOR, HTCLIENT,
+    IDC_ARROW, IDC_HAND, IDC_IBEAM, IDC_NO, IMAGE_CURSOR, LR_DEFAULTCOLOR, LoadCursorW,
+    LoadImageW, SM_CXCURSOR, SM_CYCURSOR, SetCursor, WM_GESTURE, WM_LBUTTONDOWN, WM_LBUTTONUP,
+    WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCMOUSELEAVE,
+    WM_POINTERDOWN, WM_POINTERHWHEEL, WM_POINTERLEAVE, WM_POINTERUP, WM_POINTERUPDATE,
+    WM_POINTERWHEEL, WM_RBUTTONDOWN, WM_RBUTTONUP,
 };
+use windows::core::PCWSTR;
 
 use winapi::um::winuser::{
-    MK_LBUTTON as WINAPI_MK_LBUTTON, MK_MBUTTON as WINAPI_MK_MBUTTON,
-    MK_RBUTTON as WINAPI_MK_RBUTTON, WHEEL_DELTA,
+    GetKeyState, GetPointerInfo, MK_LBUTTON as WINAPI_MK_LBUTTON,
+    MK_MBUTTON as WINAPI_MK_MBUTTON, MK_RBUTTON as WINAPI_MK_RBUTTON, POINTER_FLAG_CANCELED,
+    POINTER_FLAG_INCONTACT, POINTER_FLAG_INRANGE, POINTER_INFO, PT_PEN, PT_TOUCH, VK_CONTROL,
+    WHEEL_DELTA,
 };
 
 use crate::bindings::embedder::{
-    FlutterEngine, FlutterEngineResult, FlutterPointerDeviceKind_kFlutterPointerDeviceKindMouse,
-    FlutterPointerEvent, FlutterPointerPhase, FlutterPointerPhase_kAdd, FlutterPointerPhase_kDown,
-    FlutterPointerPhase_kHover, FlutterPointerPhase_kMove, FlutterPointerPhase_kRemove,
-    FlutterPointerPhase_kUp, FlutterPointerSignalKind_kFlutterPointerSignalKindNone,
+    FlutterEngine, FlutterEngineResult, FlutterPointerDeviceKind,
+    FlutterPointerDeviceKind_kFlutterPointerDeviceKindMouse,
+    FlutterPointerDeviceKind_kFlutterPointerDeviceKindStylus,
+    FlutterPointerDeviceKind_kFlutterPointerDeviceKindTouch,
+    FlutterPointerDeviceKind_kFlutterPointerDeviceKindTrackpad, FlutterPointerEvent,
+    FlutterPointerPhase, FlutterPointerPhase_kAdd, FlutterPointerPhase_kCancel,
+    FlutterPointerPhase_kDown, FlutterPointerPhase_kHover, FlutterPointerPhase_kMove,
+    FlutterPointerPhase_kPanZoomEnd, FlutterPointerPhase_kPanZoomStart,
+    FlutterPointerPhase_kPanZoomUpdate, FlutterPointerPhase_kRemove, FlutterPointerPhase_kUp,
+    FlutterPointerSignalKind_kFlutterPointerSignalKindNone,
     FlutterPointerSignalKind_kFlutterPointerSignalKindScroll,
 };
 
 use crate::software_renderer::dynamic_flutter_engine_dll_loader::FlutterEngineDll;
 use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
+use crate::software_renderer::overlay::touch::ContactEvent;
+use crate::software_renderer::overlay::trackpad::{
+    PanUpdate, TouchGesture, TrackpadScrollState, rotate_angle_from_argument,
+};
+
+/// Client-space position carried by a pointer message, or `None` for messages
+/// without one (`WM_NCMOUSELEAVE`, `WM_POINTERLEAVE`). Wheel messages, touch
+/// and pen messages and `WM_GESTURE` report screen coordinates, which are
+/// converted against `hwnd`.
+pub fn pointer_event_client_position(hwnd: HWND, msg: u32, lparam: LPARAM) -> Option<(f64, f64)> {
+    let x = (lparam.0 & 0xFFFF) as i16 as i32;
+    let y = ((lparam.0 >> 16) & 0xFFFF) as i16 as i32;
+    match msg {
+        WM_MOUSEMOVE | WM_LBUTTONDOWN | WM_RBUTTONDOWN | WM_MBUTTONDOWN | WM_LBUTTONUP
+        | WM_RBUTTONUP | WM_MBUTTONUP => Some((x as f64, y as f64)),
+        WM_MOUSEWHEEL | WM_MOUSEHWHEEL | WM_POINTERWHEEL | WM_POINTERHWHEEL | WM_POINTERDOWN
+        | WM_POINTERUPDATE | WM_POINTERUP => screen_to_client(hwnd, x, y),
+        WM_GESTURE => {
+            let (x, y) = read_gesture(lparam)?.screen;
+            screen_to_client(hwnd, x, y)
+        }
+        _ => None,
+    }
+}
+
+fn screen_to_client(hwnd: HWND, x: i32, y: i32) -> Option<(f64, f64)> {
+    let mut point = POINT { x, y };
+    if !unsafe { ScreenToClient(hwnd, &mut point) }.as_bool() {
+        return None;
+    }
+    Some((point.x as f64, point.y as f64))
+}
+
+/// Zoom or rotate step carried by a `WM_GESTURE` message.
+struct GestureSample {
+    gesture: TouchGesture,
+    begins: bool,
+    ends: bool,
+    /// Gesture center in screen coordinates.
+    screen: (i32, i32),
+}
+
+/// Decodes a `WM_GESTURE` message, `None` for gestures other than zoom and
+/// rotate. The handle is left open: every overlay in the z-order may read it,
+/// and the host's `DefWindowProc` closes it.
+fn read_gesture(lparam: LPARAM) -> Option<GestureSample> {
+    let mut info = GESTUREINFO {
+        cbSize: std::mem::size_of::<GESTUREINFO>() as u32,
+        ..GESTUREINFO::default()
+    };
+    let handle = HGESTUREINFO(lparam.0 as *mut c_void);
+    if unsafe { GetGestureInfo(handle, &mut info) }.is_err() {
+        return None;
+    }
+    let gesture = match GESTURECONFIG_ID(info.dwID) {
+        GID_ZOOM => TouchGesture::Zoom {
+            distance: info.ullArguments as f64,
+        },
+        GID_ROTATE => TouchGesture::Rotate {
+            angle: rotate_angle_from_argument(info.ullArguments),
+        },
+        _ => return None,
+    };
+    Some(GestureSample {
+        gesture,
+        begins: info.dwFlags & GF_BEGIN != 0,
+        ends: info.dwFlags & GF_END != 0,
+        screen: (info.ptsLocation.x as i32, info.ptsLocation.y as i32),
+    })
+}
+
+/// Maps window client coordinates to the overlay's own pixel space.
+///
+/// Needed when the host draws the overlay letterboxed or scaled inside its
+/// window: a point at `offset + p * scale` in the client area is `p` in the
+/// overlay. The default is the identity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputTransform {
+    pub offset_x: f64,
+    pub offset_y: f64,
+    pub scale: f64,
+}
+
+impl Default for InputTransform {
+    fn default() -> Self {
+        Self {
+            offset_x: 0.0,
+            offset_y: 0.0,
+            scale: 1.0,
+        }
+    }
+}
+
+impl InputTransform {
+    pub fn client_to_overlay(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            (x - self.offset_x) / self.scale,
+            (y - self.offset_y) / self.scale,
+        )
+    }
+}
+
+/// Maps a point on an overlay drawn from `origin` at `scale` times its size
+/// back to where it lies on the unscaled overlay.
+pub(crate) fn unscale_about(origin: (i32, i32), scale: f32, x: f64, y: f64) -> (f64, f64) {
+    let (ox, oy) = (origin.0 as f64, origin.1 as f64);
+    let scale = scale as f64;
+    (ox + (x - ox) / scale, oy + (y - oy) / scale)
+}
+
+/// Maps a point on an overlay drawn rotated clockwise by `degrees` around
+/// `center` back to where it lies on the unrotated overlay.
+pub(crate) fn unrotate_about(center: (f64, f64), degrees: f32, x: f64, y: f64) -> (f64, f64) {
+    let (sin, cos) = (degrees as f64).to_radians().sin_cos();
+    let (dx, dy) = (x - center.0, y - center.1);
+    (center.0 + dx * cos + dy * sin, center.1 - dx * sin + dy * cos)
+}
+
+/// How `WM_MOUSEWHEEL` and `WM_MOUSEHWHEEL` notches become Flutter scroll deltas.
+///
+/// The default scrolls 20 logical pixels per notch, content moving up when
+/// the wheel turns toward the user.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollConfig {
+    /// Scroll distance for one `WHEEL_DELTA` (120) of wheel movement.
+    pub pixels_per_notch: f32,
+    /// Flip the direction, e.g. to match natural scrolling.
+    pub invert: bool,
+}
+
+impl Default for ScrollConfig {
+    fn default() -> Self {
+        Self {
+            pixels_per_notch: 20.0,
+            invert: false,
+        }
+    }
+}
+
+impl ScrollConfig {
+    /// Flutter `scroll_delta_y` for a raw wheel delta from `WM_MOUSEWHEEL`.
+    pub fn scroll_delta(&self, wheel_delta: i16) -> f64 {
+        let delta = -(wheel_delta as f64 / WHEEL_DELTA as f64) * self.pixels_per_notch as f64;
+        if self.invert { -delta } else { delta }
+    }
+
+    /// Flutter `scroll_delta_x` for a raw delta from `WM_MOUSEHWHEEL`. Unlike
+    /// the vertical wheel, a positive delta (tilt right) already points the
+    /// same way as Flutter's axis, so only `invert` flips it.
+    pub fn horizontal_scroll_delta(&self, wheel_delta: i16) -> f64 {
+        -self.scroll_delta(wheel_delta)
+    }
+}
 
 pub fn handle_pointer_event(
     overlay: &FlutterOverlay,
@@ -38,10 +209,17 @@ pub fn handle_pointer_event(
         return false;
     }
 
+    // Any other pointer input closes an open trackpad gesture first.
+    if !matches!(msg, WM_POINTERWHEEL | WM_POINTERHWHEEL | WM_GESTURE) {
+        end_trackpad_gesture(overlay, true);
+    }
+
     match msg {
         WM_MOUSEMOVE => {
-            let x = (lparam.0 & 0xFFFF) as i16 as f64;
-            let y = ((lparam.0 >> 16) & 0xFFFF) as i16 as f64;
+            let (x, y) = overlay.client_to_overlay(
+                (lparam.0 & 0xFFFF) as i16 as f64,
+                ((lparam.0 >> 16) & 0xFFFF) as i16 as f64,
+            );
             let key_states_from_wparam = wparam.0;
             let mut calculated_mk_buttons_i32: i32 = 0;
 
@@ -84,8 +262,10 @@ pub fn handle_pointer_event(
             true
         }
         WM_LBUTTONDOWN | WM_RBUTTONDOWN | WM_MBUTTONDOWN => {
-            let x = (lparam.0 & 0xFFFF) as i16 as f64;
-            let y = ((lparam.0 >> 16) & 0xFFFF) as i16 as f64;
+            let (x, y) = overlay.client_to_overlay(
+                (lparam.0 & 0xFFFF) as i16 as f64,
+                ((lparam.0 >> 16) & 0xFFFF) as i16 as f64,
+            );
             let button_flag_to_set: i32 = match msg {
                 WM_LBUTTONDOWN => WINAPI_MK_LBUTTON as i32,
                 WM_RBUTTONDOWN => WINAPI_MK_RBUTTON as i32,
@@ -130,8 +310,10 @@ pub fn handle_pointer_event(
             true
         }
         WM_LBUTTONUP | WM_RBUTTONUP | WM_MBUTTONUP => {
-            let x = (lparam.0 & 0xFFFF) as i16 as f64;
-            let y = ((lparam.0 >> 16) & 0xFFFF) as i16 as f64;
+            let (x, y) = overlay.client_to_overlay(
+                (lparam.0 & 0xFFFF) as i16 as f64,
+                ((lparam.0 >> 16) & 0xFFFF) as i16 as f64,
+            );
             let button_flag_to_clear: i32 = match msg {
                 WM_LBUTTONUP => WINAPI_MK_LBUTTON as i32,
                 WM_RBUTTONUP => WINAPI_MK_RBUTTON as i32,
@@ -179,7 +361,7 @@ pub fn handle_pointer_event(
             overlay.mouse_buttons_state.store(0, Ordering::Relaxed);
             false
         }
-        WM_MOUSEWHEEL => {
+        WM_MOUSEWHEEL | WM_MOUSEHWHEEL => {
             let wheel_delta = (wparam.0 >> 16) as i16;
             let x_screen = (lparam.0 & 0xFFFF) as i16;
             let y_screen = ((lparam.0 >> 16) & 0xFFFF) as i16;
@@ -194,9 +376,14 @@ pub fn handle_pointer_event(
                 }
             }
 
-            let x_client = point.x as f64;
-            let y_client = point.y as f64;
-            let scroll_delta_y_flutter = -(wheel_delta as f64 / WHEEL_DELTA as f64) * 20.0;
+            let (x_client, y_client) = overlay
+                .input_transform
+                .client_to_overlay(point.x as f64, point.y as f64);
+            let (scroll_delta_x, scroll_delta_y) = if msg == WM_MOUSEHWHEEL {
+                (overlay.scroll_config.horizontal_scroll_delta(wheel_delta), 0.0)
+            } else {
+                (0.0, overlay.scroll_config.scroll_delta(wheel_delta))
+            };
 
             send_pointer_event_to_flutter(
                 engine.0,
@@ -205,65 +392,308 @@ pub fn handle_pointer_event(
                     phase: FlutterPointerPhase_kHover,
                     x: x_client,
                     y: y_client,
-                    scroll_delta_x: 0.0,
-                    scroll_delta_y: scroll_delta_y_flutter,
+                    scroll_delta_x,
+                    scroll_delta_y,
                     buttons: overlay.mouse_buttons_state.load(Ordering::Relaxed) as i64,
                 },
             );
             true
         }
+        WM_POINTERWHEEL | WM_POINTERHWHEEL => {
+            let Some((client_x, client_y)) = pointer_event_client_position(hwnd, msg, lparam)
+            else {
+                return true;
+            };
+            let position = overlay.client_to_overlay(client_x, client_y);
+            let wheel_delta = (wparam.0 >> 16) as i16;
+            // Precision touchpads report a pinch as a vertical wheel with Ctrl down.
+            let pinch = msg == WM_POINTERWHEEL && unsafe { GetKeyState(VK_CONTROL) } < 0;
+            let vertical = msg == WM_POINTERWHEEL;
+            dispatch_trackpad_step(overlay, position, |state| {
+                if pinch {
+                    state.on_pinch(wheel_delta, position, Instant::now())
+                } else {
+                    state.on_wheel(wheel_delta, vertical, position, Instant::now())
+                }
+            })
+        }
+        WM_GESTURE => {
+            let Some(sample) = read_gesture(lparam) else {
+                return false;
+            };
+            let (screen_x, screen_y) = sample.screen;
+            let Some((client_x, client_y)) = screen_to_client(hwnd, screen_x, screen_y) else {
+                return true;
+            };
+            let position = overlay.client_to_overlay(client_x, client_y);
+            let handled = dispatch_trackpad_step(overlay, position, |state| {
+                state.on_gesture(sample.gesture, sample.begins, position, Instant::now())
+            });
+            if sample.ends {
+                end_trackpad_gesture(overlay, true);
+            }
+            handled
+        }
+        WM_POINTERDOWN | WM_POINTERUPDATE | WM_POINTERUP | WM_POINTERLEAVE => {
+            handle_contact_event(overlay, hwnd, msg, wparam)
+        }
         _ => false,
     }
 }
 
+/// One touch or pen contact, read with `GetPointerInfo`.
+struct ContactSample {
+    pointer_id: u32,
+    device_kind: FlutterPointerDeviceKind,
+    flags: u32,
+    screen: (i32, i32),
+}
+
+/// Returns `None` for mouse and touchpad pointers, which keep arriving as the
+/// promoted `WM_MOUSE*` messages.
+fn read_contact(wparam: WPARAM) -> Option<ContactSample> {
+    let pointer_id = (wparam.0 & 0xFFFF) as u32;
+    let mut info: POINTER_INFO = unsafe { std::mem::zeroed() };
+    if unsafe { GetPointerInfo(pointer_id, &mut info) } == 0 {
+        return None;
+    }
+    let device_kind = match info.pointerType {
+        PT_TOUCH => FlutterPointerDeviceKind_kFlutterPointerDeviceKindTouch,
+        PT_PEN => FlutterPointerDeviceKind_kFlutterPointerDeviceKindStylus,
+        _ => return None,
+    };
+    Some(ContactSample {
+        pointer_id,
+        device_kind,
+        flags: info.pointerFlags,
+        screen: (info.ptPixelLocation.x, info.ptPixelLocation.y),
+    })
+}
+
+/// Sends a touch or pen message as events of the contact's own device, so
+/// several fingers can be down at once.
+fn handle_contact_event(overlay: &FlutterOverlay, hwnd: HWND, msg: u32, wparam: WPARAM) -> bool {
+    let Some(sample) = read_contact(wparam) else {
+        return false;
+    };
+    let (screen_x, screen_y) = sample.screen;
+    let Some((client_x, client_y)) = screen_to_client(hwnd, screen_x, screen_y) else {
+        return true;
+    };
+    let position = overlay.client_to_overlay(client_x, client_y);
+    let event = match msg {
+        WM_POINTERDOWN => ContactEvent::Down,
+        WM_POINTERUPDATE => ContactEvent::Update {
+            in_contact: sample.flags & POINTER_FLAG_INCONTACT != 0,
+        },
+        WM_POINTERUP => ContactEvent::Up {
+            canceled: sample.flags & POINTER_FLAG_CANCELED != 0,
+            in_range: sample.device_kind
+                == FlutterPointerDeviceKind_kFlutterPointerDeviceKindStylus
+                && sample.flags & POINTER_FLAG_INRANGE != 0,
+        },
+        _ => ContactEvent::Leave,
+    };
+    let (device, phases) = match overlay.touch_contacts.lock() {
+        Ok(mut contacts) => {
+            contacts.on_event(sample.pointer_id, sample.device_kind, event, position)
+        }
+        Err(_) => return false,
+    };
+    for phase in phases {
+        send_contact_event(overlay, phase, device, sample.device_kind, position);
+    }
+    true
+}
+
+/// Cancels and removes every touch and pen contact, e.g. after focus loss.
+pub(crate) fn cancel_touch_contacts(overlay: &FlutterOverlay) {
+    let closing = match overlay.touch_contacts.lock() {
+        Ok(mut contacts) => contacts.cancel_all(),
+        Err(_) => return,
+    };
+    for (contact, phase) in closing {
+        send_contact_event(overlay, phase, contact.device, contact.device_kind, contact.position);
+    }
+}
+
+fn send_contact_event(
+    overlay: &FlutterOverlay,
+    phase: FlutterPointerPhase,
+    device: i32,
+    device_kind: FlutterPointerDeviceKind,
+    (x, y): (f64, f64),
+) {
+    let engine = overlay.engine.0;
+    if engine.is_null() {
+        return;
+    }
+    let engine_dll = &overlay.engine_dll;
+    let event = FlutterPointerEvent {
+        struct_size: std::mem::size_of::<FlutterPointerEvent>(),
+        phase,
+        timestamp: unsafe { (engine_dll.FlutterEngineGetCurrentTime)() } as usize / 1000,
+        x,
+        y,
+        device,
+        signal_kind: FlutterPointerSignalKind_kFlutterPointerSignalKindNone,
+        scroll_delta_x: 0.0,
+        scroll_delta_y: 0.0,
+        device_kind,
+        buttons: 0,
+        pan_x: 0.0,
+        pan_y: 0.0,
+        scale: 1.0,
+        rotation: 0.0,
+        view_id: 0,
+    };
+    let _res: FlutterEngineResult =
+        unsafe { (engine_dll.FlutterEngineSendPointerEvent)(engine, &event as *const _, 1) };
+}
+
+/// Records one trackpad step with `record` and sends the resulting events,
+/// announcing the device and opening the gesture first when needed.
+fn dispatch_trackpad_step(
+    overlay: &FlutterOverlay,
+    (x, y): (f64, f64),
+    record: impl FnOnce(&mut TrackpadScrollState) -> PanUpdate,
+) -> bool {
+    let engine = overlay.engine.0;
+    let engine_dll = &overlay.engine_dll;
+    let (update, announce_device) = {
+        let Ok(mut state) = overlay.trackpad_scroll.lock() else {
+            return false;
+        };
+        let announce = !state.device_added;
+        state.device_added = true;
+        (record(&mut state), announce)
+    };
+
+    if announce_device {
+        send_trackpad_event(engine, engine_dll, FlutterPointerPhase_kAdd, x, y, None);
+    }
+    if update.starts_gesture {
+        send_trackpad_event(engine, engine_dll, FlutterPointerPhase_kPanZoomStart, x, y, None);
+    }
+    send_trackpad_event(
+        engine,
+        engine_dll,
+        FlutterPointerPhase_kPanZoomUpdate,
+        x,
+        y,
+        Some(update),
+    );
+    true
+}
+
+/// Sends `kPanZoomEnd` for an open trackpad gesture. Without `force`, only ends
+/// it once the gesture has been idle long enough; called from `tick`.
+pub(crate) fn end_trackpad_gesture(overlay: &FlutterOverlay, force: bool) {
+    if overlay.engine.0.is_null() {
+        return;
+    }
+    let origin = match overlay.trackpad_scroll.lock() {
+        Ok(mut state) => state.take_end(Instant::now(), force),
+        Err(_) => None,
+    };
+    if let Some((x, y)) = origin {
+        send_trackpad_event(
+            overlay.engine.0,
+            &overlay.engine_dll,
+            FlutterPointerPhase_kPanZoomEnd,
+            x,
+            y,
+            None,
+        );
+    }
+}
+
+/// System cursors loaded at a non-default size, keyed by cursor id and pixel
+/// size. `LoadImageW` without `LR_SHARED` hands out a new handle on every call,
+/// so each size is loaded once and destroyed with the cache.
+#[derive(Default)]
+pub(crate) struct ScaledCursorCache {
+    cursors: HashMap<(usize, i32), usize>,
+}
+
+impl ScaledCursorCache {
+    fn get_or_load(&mut self, id: PCWSTR, scale: f64) -> Option<HCURSOR> {
+        let (base_w, base_h) = unsafe {
+            (GetSystemMetrics(SM_CXCURSOR), GetSystemMetrics(SM_CYCURSOR))
+        };
+        let width = scaled_cursor_size(base_w, scale);
+        let height = scaled_cursor_size(base_h, scale);
+        let key = (id.0 as usize, width);
+        if let Some(&handle) = self.cursors.get(&key) {
+            return Some(HCURSOR(handle as *mut _));
+        }
+        let handle =
+            unsafe { LoadImageW(None, id, IMAGE_CURSOR, width, height, LR_DEFAULTCOLOR) }.ok()?;
+        self.cursors.insert(key, handle.0 as usize);
+        Some(HCURSOR(handle.0))
+    }
+}
+
+impl Drop for ScaledCursorCache {
+    fn drop(&mut self) {
+        for &handle in self.cursors.values() {
+            let _ = unsafe { DestroyCursor(HCURSOR(handle as *mut _)) };
+        }
+    }
+}
+
+/// Cursor edge length for a system size `base` drawn at `scale`, never below
+/// one pixel.
+pub(crate) fn scaled_cursor_size(base: i32, scale: f64) -> i32 {
+    ((base as f64 * scale).round() as i32).max(1)
+}
+
+fn cursor_id_for_kind(kind: &str) -> Option<PCWSTR> {
+    match kind {
+        "basic" | "basic.default" => Some(IDC_ARROW),
+        "click" | "pointer" => Some(IDC_HAND),
+        "text" | "text.TextEditable" => Some(IDC_IBEAM),
+        "forbidden" | "basic.forbidden" => Some(IDC_NO),
+        _ => None,
+    }
+}
+
 pub fn handle_set_cursor(
     overlay: &FlutterOverlay,
     hwnd_from_wparam: HWND,
     lparam_from_message: LPARAM,
     main_app_hwnd: HWND,
 ) -> Option<LRESULT> {
-    unsafe {
-        let hit_test_code = (lparam_from_message.0 & 0xFFFF) as i16;
-
-        if hwnd_from_wparam == main_app_hwnd
-            && hit_test_code == HTCLIENT as i16
-            && let Ok(desired_kind_guard) = overlay.desired_cursor.try_lock()
-            && let Some(kind) = desired_kind_guard.as_ref()
-        {
-            let mut h_cursor_to_set: HCURSOR = HCURSOR(std::ptr::null_mut());
-            let mut flutter_did_request_cursor_change = true;
-
-            let h_instance_null: HINSTANCE = HINSTANCE(std::ptr::null_mut());
-
-            match kind.as_str() {
-                "basic" | "basic.default" => {
-                    h_cursor_to_set = LoadCursorW(Some(h_instance_null), IDC_ARROW)
-                        .unwrap_or(HCURSOR(std::ptr::null_mut()));
-                }
-                "click" | "pointer" => {
-                    h_cursor_to_set = LoadCursorW(Some(h_instance_null), IDC_HAND)
-                        .unwrap_or(HCURSOR(std::ptr::null_mut()));
-                }
-                "text" | "text.TextEditable" => {
-                    h_cursor_to_set = LoadCursorW(Some(h_instance_null), IDC_IBEAM)
-                        .unwrap_or(HCURSOR(std::ptr::null_mut()));
-                }
-                "forbidden" | "basic.forbidden" => {
-                    h_cursor_to_set = LoadCursorW(Some(h_instance_null), IDC_NO)
-                        .unwrap_or(HCURSOR(std::ptr::null_mut()));
-                }
-                _ => {
-                    flutter_did_request_cursor_change = false;
-                }
-            }
+    let hit_test_code = (lparam_from_message.0 & 0xFFFF) as i16;
 
-            if flutter_did_request_cursor_change && !h_cursor_to_set.0.is_null() {
-                SetCursor(Some(h_cursor_to_set));
-                return Some(LRESULT(1));
-            }
+    if hwnd_from_wparam == main_app_hwnd
+        && hit_test_code == HTCLIENT as i16
+        && let Ok(desired_kind_guard) = overlay.desired_cursor.try_lock()
+        && let Some(kind) = desired_kind_guard.as_ref()
+        && let Some(cursor_id) = cursor_id_for_kind(kind)
+    {
+        let scale = overlay.input_transform.scale;
+        let scaled = if overlay.cursor_scaling && scale != 1.0 {
+            overlay
+                .scaled_cursors
+                .lock()
+                .ok()
+                .and_then(|mut cache| cache.get_or_load(cursor_id, scale))
+        } else {
+            None
+        };
+        let h_cursor_to_set = match scaled {
+            Some(cursor) => cursor,
+            None => unsafe { LoadCursorW(Some(HINSTANCE(std::ptr::null_mut())), cursor_id) }
+                .unwrap_or(HCURSOR(std::ptr::null_mut())),
+        };
+
+        if !h_cursor_to_set.0.is_null() {
+            unsafe { SetCursor(Some(h_cursor_to_set)) };
+            return Some(LRESULT(1));
         }
-        None
     }
+    None
 }
 /// One mouse pointer sample to forward to the Flutter engine.
 struct PointerSample {
@@ -275,6 +705,74 @@ struct PointerSample {
     buttons: i64,
 }
 
+/// Device id for trackpad gestures, kept apart from the mouse (device 0) so the
+/// engine tracks their add/down state independently.
+const TRACKPAD_DEVICE_ID: i32 = 1;
+
+fn send_trackpad_event(
+    engine: FlutterEngine,
+    engine_dll: &FlutterEngineDll,
+    phase: FlutterPointerPhase,
+    x: f64,
+    y: f64,
+    update: Option<PanUpdate>,
+) {
+    if engine.is_null() {
+        return;
+    }
+    let (pan_x, pan_y, scale, rotation) = update
+        .map(|u| (u.pan_x, u.pan_y, u.scale, u.rotation))
+        .unwrap_or((0.0, 0.0, 1.0, 0.0));
+    let event = FlutterPointerEvent {
+        struct_size: std::mem::size_of::<FlutterPointerEvent>(),
+        phase,
+        timestamp: unsafe { (engine_dll.FlutterEngineGetCurrentTime)() } as usize / 1000,
+        x,
+        y,
+        device: TRACKPAD_DEVICE_ID,
+        signal_kind: FlutterPointerSignalKind_kFlutterPointerSignalKindNone,
+        scroll_delta_x: 0.0,
+        scroll_delta_y: 0.0,
+        device_kind: FlutterPointerDeviceKind_kFlutterPointerDeviceKindTrackpad,
+        buttons: 0,
+        pan_x,
+        pan_y,
+        scale,
+        rotation,
+        view_id: 0,
+    };
+    let _res: FlutterEngineResult =
+        unsafe { (engine_dll.FlutterEngineSendPointerEvent)(engine, &event as *const _, 1) };
+}
+
+/// Clears the tracked mouse buttons after the host window lost focus. If any
+/// were down, Flutter gets a cancel at the current cursor position, since the
+/// matching `WM_*BUTTONUP` went to another window.
+pub(crate) fn cancel_pressed_buttons(overlay: &FlutterOverlay, hwnd: HWND) {
+    let buttons = overlay.mouse_buttons_state.swap(0, Ordering::Relaxed);
+    if buttons == 0 || overlay.engine.0.is_null() {
+        return;
+    }
+    let mut point = POINT::default();
+    let (x, y) = match unsafe { GetCursorPos(&mut point) } {
+        Ok(()) => screen_to_client(hwnd, point.x, point.y).unwrap_or_default(),
+        Err(_) => (0.0, 0.0),
+    };
+    let (x, y) = overlay.client_to_overlay(x, y);
+    send_pointer_event_to_flutter(
+        overlay.engine.0,
+        &overlay.engine_dll,
+        PointerSample {
+            phase: FlutterPointerPhase_kCancel,
+            x,
+            y,
+            scroll_delta_x: 0.0,
+            scroll_delta_y: 0.0,
+            buttons: 0,
+        },
+    );
+}
+
 fn send_pointer_event_to_flutter(
     engine: FlutterEngine,
     engine_dll: &FlutterEngineDll,
@@ -227,7 +227,7 @@ fn handle_keyboard_event_for_view_impl(
                 // Track this key as pressed (only for non-repeat events)
                 if !is_repeat
                     && let Ok(mut pressed) = state.pressed_keys.lock() {
-                        pressed.insert(physical_key);
+                        pressed.insert(physical_key, logical_key);
                     }
 
                 let characters_bytes_for_flutter_key_event =
@@ -265,7 +265,7 @@ fn handle_keyboard_event_for_view_impl(
                 );
 
                 let was_pressed = if let Ok(mut pressed) = state.pressed_keys.lock() {
-                    pressed.remove(&physical_key)
+                    pressed.remove(&physical_key).is_some()
                 } else {
                     false
                 };
@@ -296,33 +296,10 @@ fn handle_keyboard_event_for_view_impl(
             }
             WM_CHAR => {
                 let char_code = wparam.0 as u32;
-                let mut event_handled_by_text_input = false;
-                let mut send_update_args_char: Option<(i32, TextInputModel)> = None;
-
-                if let Some(char_val) = std::char::from_u32(char_code) {
-                    {
-                        let mut active_state_guard = overlay.active_text_input.lock().unwrap();
-
-                        if let Some(active_state) = active_state_guard.as_mut()
-                            && char_val != '\x08' && char_val != '\r' && !char_val.is_control() {
-                                active_state.model.insert_char(char_val);
-                                send_update_args_char =
-                                    Some((active_state.client_id, active_state.model.clone()));
-                                event_handled_by_text_input = true;
-                            }
-                    }
-                    if let Some((client_id, cloned_model)) = send_update_args_char {
-                        send_update_editing_state_to_flutter(
-                            &overlay.pending_platform_messages,
-                            client_id,
-                            &cloned_model,
-                        );
-                    }
-                    if event_handled_by_text_input {
-                        return true;
-                    }
+                match std::char::from_u32(char_code) {
+                    Some(char_val) => commit_char_to_text_input(overlay, char_val),
+                    None => false,
                 }
-                false
             }
             _ => {
                 false
@@ -331,6 +308,129 @@ fn handle_keyboard_event_for_view_impl(
     }
 }
 
+/// Inserts `char_val` into the active text input client, if any, and queues the
+/// editing-state update. Returns `true` if a text input consumed the character.
+fn commit_char_to_text_input(overlay: &FlutterOverlay, char_val: char) -> bool {
+    let mut send_update_args_char: Option<(i32, TextInputModel)> = None;
+    {
+        let mut active_state_guard = overlay.active_text_input.lock().unwrap();
+
+        if let Some(active_state) = active_state_guard.as_mut()
+            && char_val != '\x08' && char_val != '\r' && !char_val.is_control() {
+                active_state.model.insert_char(char_val);
+                send_update_args_char =
+                    Some((active_state.client_id, active_state.model.clone()));
+            }
+    }
+    if let Some((client_id, cloned_model)) = send_update_args_char {
+        send_update_editing_state_to_flutter(
+            &overlay.pending_platform_messages,
+            client_id,
+            &cloned_model,
+        );
+        return true;
+    }
+    false
+}
+
+/// Sends a synthetic key press or release built from Flutter key codes rather
+/// than a Win32 message, so it does not depend on the OS keyboard state.
+///
+/// Pressed-key tracking is shared with real input: a release for a key this
+/// overlay never saw pressed is dropped, a second press becomes a repeat. On a
+/// press, `character` is attached to the key event and committed to the active
+/// text input client (the `WM_CHAR` equivalent). No legacy `flutter/keyevent`
+/// message is sent since there is no Win32 virtual key to report.
+pub(crate) fn inject_key_event(
+    overlay: &FlutterOverlay,
+    logical_key: u64,
+    physical_key: u64,
+    down: bool,
+    character: Option<char>,
+) -> bool {
+    if overlay.engine.0.is_null() {
+        return false;
+    }
+
+    let state = &overlay.view0_keyboard;
+
+    if down {
+        let already_pressed = match state.pressed_keys.lock() {
+            Ok(mut pressed) => pressed.insert(physical_key, logical_key).is_some(),
+            Err(_) => false,
+        };
+        let event_type = if already_pressed {
+            FlutterKeyEventType_kFlutterKeyEventTypeRepeat
+        } else {
+            FlutterKeyEventType_kFlutterKeyEventTypeDown
+        };
+
+        let mut characters = [0u8; 8];
+        if let Some(ch) = character {
+            ch.encode_utf8(&mut characters[..7]);
+        }
+
+        send_key_event_to_flutter(
+            &overlay.pending_key_events,
+            event_type,
+            physical_key,
+            logical_key,
+            &characters,
+            false,
+        );
+
+        if let Some(ch) = character {
+            commit_char_to_text_input(overlay, ch);
+        }
+    } else {
+        let was_pressed = match state.pressed_keys.lock() {
+            Ok(mut pressed) => pressed.remove(&physical_key).is_some(),
+            Err(_) => false,
+        };
+
+        if was_pressed {
+            send_key_event_to_flutter(
+                &overlay.pending_key_events,
+                FlutterKeyEventType_kFlutterKeyEventTypeUp,
+                physical_key,
+                logical_key,
+                &[0u8; 8],
+                false,
+            );
+        }
+    }
+
+    overlay.task_queue_state.waker.wake_up();
+    true
+}
+
+/// Sends a synthesized release for every key `state` still holds down.
+///
+/// Windows sends no `WM_KEYUP` for keys released while the window is inactive,
+/// so after Alt-Tab Flutter would otherwise keep Alt pressed. Returns the
+/// number of keys released.
+pub(crate) fn release_pressed_keys(
+    overlay: &FlutterOverlay,
+    state: &SharedViewKeyboardState,
+) -> usize {
+    let pressed = state.take_pressed_keys();
+    if overlay.engine.0.is_null() || pressed.is_empty() {
+        return 0;
+    }
+    for &(physical_key, logical_key) in &pressed {
+        send_key_event_to_flutter(
+            &overlay.pending_key_events,
+            FlutterKeyEventType_kFlutterKeyEventTypeUp,
+            physical_key,
+            logical_key,
+            &[0u8; 8],
+            true,
+        );
+    }
+    overlay.task_queue_state.waker.wake_up();
+    pressed.len()
+}
+
 fn send_key_event_to_flutter(
     message_queue: &PendingKeyEventQueue,
     type_: FlutterKeyEventType,
@@ -0,0 +1,58 @@
+//! App lifecycle state sent on `flutter/lifecycle`.
+//!
+//! The engine only says `resumed` once the root isolate starts; after that the
+//! host decides. The channel uses the string codec, so the payload is just the
+//! Dart enum value as text. Dart fills in the intermediate states itself for
+//! `AppLifecycleListener`, so jumping straight from `resumed` to `paused` is
+//! fine.
+
+use log::info;
+
+use crate::software_renderer::api::FlutterEmbedderError;
+use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
+use crate::software_renderer::overlay::platform_message_callback::send_platform_message;
+
+pub(crate) const LIFECYCLE_CHANNEL: &str = "flutter/lifecycle";
+
+/// Mirrors Dart's `AppLifecycleState`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LifecycleState {
+    /// Visible and receiving input.
+    #[default]
+    Resumed,
+    /// Visible but not focused, e.g. the host window was deactivated.
+    Inactive,
+    /// Not visible, but still considered running.
+    Hidden,
+    /// Not visible and not expected to be; e.g. the host window is minimized.
+    /// `tick_overlays` stops ticking overlays in this state.
+    Paused,
+    /// The engine is about to go away.
+    Detached,
+}
+
+impl LifecycleState {
+    /// Payload for `flutter/lifecycle`.
+    pub fn as_message(self) -> &'static str {
+        match self {
+            LifecycleState::Resumed => "AppLifecycleState.resumed",
+            LifecycleState::Inactive => "AppLifecycleState.inactive",
+            LifecycleState::Hidden => "AppLifecycleState.hidden",
+            LifecycleState::Paused => "AppLifecycleState.paused",
+            LifecycleState::Detached => "AppLifecycleState.detached",
+        }
+    }
+}
+
+/// Sends `state` to the overlay's engine and records it on the overlay.
+pub(crate) fn set_lifecycle_state(
+    overlay: &mut FlutterOverlay,
+    state: LifecycleState,
+) -> Result<(), FlutterEmbedderError> {
+    send_platform_message(overlay, LIFECYCLE_CHANNEL, state.as_message().as_bytes())?;
+    if overlay.lifecycle_state != state {
+        info!("[FlutterOverlay] '{}' is now {state:?}", overlay.name);
+    }
+    overlay.lifecycle_state = state;
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! Pushing the user's preferred locales to the engine.
+//!
+//! `FlutterEngineUpdateLocales` replaces the engine's whole locale list on
+//! every call, so [`update_locales`] always sends every preferred locale, most
+//! preferred first. Dart sees the list as `PlatformDispatcher.locales`.
+
+use std::ffi::CString;
+
+use crate::bindings::embedder::{FlutterEngineResult_kSuccess, FlutterLocale};
+use crate::software_renderer::api::FlutterEmbedderError;
+use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
+
+/// One preferred locale, as BCP 47 subtags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locale {
+    /// Language subtag, e.g. `"en"`. Required.
+    pub language: String,
+    /// Region subtag, e.g. `"US"`.
+    pub country: Option<String>,
+    /// Script subtag, e.g. `"Hant"`.
+    pub script: Option<String>,
+}
+
+impl Locale {
+    pub fn new(language: impl Into<String>) -> Self {
+        Self {
+            language: language.into(),
+            country: None,
+            script: None,
+        }
+    }
+
+    pub fn with_country(mut self, country: impl Into<String>) -> Self {
+        self.country = Some(country.into());
+        self
+    }
+
+    pub fn with_script(mut self, script: impl Into<String>) -> Self {
+        self.script = Some(script.into());
+        self
+    }
+}
+
+/// C strings backing one `FlutterLocale`. They must outlive the call the
+/// `FlutterLocale` is passed to.
+pub(crate) struct LocaleStrings {
+    language: CString,
+    country: Option<CString>,
+    script: Option<CString>,
+}
+
+impl LocaleStrings {
+    /// Rejects an empty language and subtags containing a NUL byte.
+    pub(crate) fn new(locale: &Locale) -> Result<Self, FlutterEmbedderError> {
+        if locale.language.is_empty() {
+            return Err(FlutterEmbedderError::OperationFailed(
+                "locale language must not be empty".to_string(),
+            ));
+        }
+        let c_string = |subtag: &str| {
+            CString::new(subtag).map_err(|_| {
+                FlutterEmbedderError::OperationFailed(format!(
+                    "locale subtag {subtag:?} contains a NUL byte"
+                ))
+            })
+        };
+        Ok(Self {
+            language: c_string(&locale.language)?,
+            country: locale.country.as_deref().map(c_string).transpose()?,
+            script: locale.script.as_deref().map(c_string).transpose()?,
+        })
+    }
+
+    pub(crate) fn as_flutter_locale(&self) -> FlutterLocale {
+        let ptr =
+            |subtag: &Option<CString>| subtag.as_ref().map_or(std::ptr::null(), |s| s.as_ptr());
+        FlutterLocale {
+            struct_size: std::mem::size_of::<FlutterLocale>(),
+            language_code: self.language.as_ptr(),
+            country_code: ptr(&self.country),
+            script_code: ptr(&self.script),
+            variant_code: std::ptr::null(),
+        }
+    }
+}
+
+/// Sends `locales` to the overlay's engine, replacing its current list. The
+/// first entry is the most preferred; the list must not be empty.
+pub(crate) fn update_locales(
+    overlay: &FlutterOverlay,
+    locales: &[Locale],
+) -> Result<(), FlutterEmbedderError> {
+    if overlay.engine.0.is_null() {
+        return Err(FlutterEmbedderError::EngineNotRunning);
+    }
+    if locales.is_empty() {
+        return Err(FlutterEmbedderError::OperationFailed(
+            "at least one locale is required".to_string(),
+        ));
+    }
+    let strings = locales
+        .iter()
+        .map(LocaleStrings::new)
+        .collect::<Result<Vec<_>, _>>()?;
+    let flutter_locales: Vec<FlutterLocale> =
+        strings.iter().map(LocaleStrings::as_flutter_locale).collect();
+    let mut pointers: Vec<*const FlutterLocale> =
+        flutter_locales.iter().map(|l| l as *const _).collect();
+    let result = unsafe {
+        (overlay.engine_dll.FlutterEngineUpdateLocales)(
+            overlay.engine.0,
+            pointers.as_mut_ptr(),
+            pointers.len(),
+        )
+    };
+    if result != FlutterEngineResult_kSuccess {
+        return Err(FlutterEmbedderError::OperationFailed(format!(
+            "FlutterEngineUpdateLocales failed: {result:?}"
+        )));
+    }
+    Ok(())
+}
@@ -1,8 +1,14 @@
+pub mod anchor;
 pub mod d3d;
 pub mod engine;
+pub(crate) mod fade;
+pub(crate) mod heartbeat;
+pub(crate) mod ime;
 pub mod init;
 pub mod input;
 pub mod keyevents;
+pub mod lifecycle;
+pub mod locale;
 #[cfg(test)]
 mod keyevents_tests;
 #[cfg(test)]
@@ -11,5 +17,10 @@ pub mod overlay_impl;
 pub mod platform_message_callback;
 pub mod project_args;
 pub mod renderer;
+pub(crate) mod restoration;
 pub mod semantics_handler;
+pub(crate) mod snapshot;
+pub(crate) mod system_settings;
 pub mod textinput;
+pub(crate) mod touch;
+pub(crate) mod trackpad;
@@ -2,10 +2,11 @@ use std::{
     collections::{HashMap, VecDeque},
     ffi::{CStr, CString},
     sync::{
-        Arc, Mutex,
+        Arc, Mutex, OnceLock,
         atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicPtr, AtomicU64},
     },
     thread,
+    time::Instant,
 };
 
 use windows::Win32::{
@@ -19,7 +20,7 @@ use crate::{
         self, FlutterCompositor, FlutterEngine, FlutterKeyEventType, FlutterRect, FlutterViewId,
     },
     software_renderer::{
-        api::RendererType,
+        api::{FrameTimer, RendererType, StartupTimings, TaskRunnerComApartment},
         d3d11_compositor::{
             effects::EffectConfig, post_processing_renderer::PostProcessRenderer,
             primitive_3d_renderer::Primitive3DRenderer,
@@ -29,11 +30,22 @@ use crate::{
         gl_renderer::angle_interop::SendableAngleState,
         multiview::{ViewRegistry, view_surface::ViewGlResources},
         overlay::{
-            semantics_handler::ProcessedSemanticsNode,
+            anchor::Anchor,
+            d3d::ExportTexture,
+            fade::OverlayFade,
+            ime::CaretGeometry,
+            input::{InputTransform, ScaledCursorCache, ScrollConfig},
+            lifecycle::LifecycleState,
+            semantics_handler::{
+                HitTestMode, HoverDebounce, ProcessedSemanticsNode, SemanticsThrottle,
+            },
             textinput::{ActiveTextInputState, SharedViewKeyboardState},
+            touch::TouchContacts,
+            trackpad::TrackpadScrollState,
         },
         ticker::{
             on_present as ticker_on_present,
+            present::DirtyRect,
             task_scheduler::{
                 SendableFlutterCustomTaskRunners, SendableFlutterTaskRunnerDescription,
                 TaskQueueState, TaskRunnerContext,
@@ -136,6 +148,9 @@ pub struct FlutterOverlay {
     pub x: i32,
     /// Overlay position X
     pub y: i32,
+    /// Screen point `x`/`y` stay relative to when the manager resizes
+    /// overlays. `Anchor::TopLeft` (the default) keeps absolute coordinates.
+    pub anchor: Anchor,
 
     /// By default true and does nothing directly even when set to false
     /// Caller requires to call it when appropiated. Example like this:
@@ -151,6 +166,48 @@ pub struct FlutterOverlay {
     pub visible: bool,
     pub keep_alive: bool,
     pub ui_hidden: bool,
+    /// Whether hovering an interactive widget raises this overlay to the top of
+    /// the Z-order. Defaults to `true`; turn off for pinned overlays.
+    pub auto_raise_on_hover: bool,
+    /// While visible, this overlay takes all pointer and keyboard input and
+    /// everything beneath it, including the host, gets none.
+    pub modal: bool,
+    /// Rank in pointer routing, independent of the Z-order. Overlays with a
+    /// higher priority are hit-tested first; equal priorities fall back to the
+    /// Z-order. Defaults to `0`.
+    pub input_priority: i32,
+    /// Whether a hit on this overlay stops the pointer event there. When
+    /// `false` the overlay still receives events and hover state, but they
+    /// carry on to the overlays below. Defaults to `true`.
+    pub consumes_input: bool,
+    /// Which pointer events count as a hit on this overlay. Defaults to
+    /// `HitTestMode::SemanticsOnly`.
+    pub hit_test_mode: HitTestMode,
+    /// Size requested through `OverlayOptions::bounds`. Kept across swap-chain
+    /// resizes; `None` follows the swap chain.
+    pub(crate) fixed_size: Option<(u32, u32)>,
+    /// Opaque host-defined tag (entity id, panel kind, ...). Never read by the
+    /// embedder; dropped together with the overlay.
+    pub user_data: Option<u64>,
+    /// Whether pointer and keyboard input reach this overlay while it is visible.
+    /// Cleared for the duration of a fade-out.
+    pub input_enabled: bool,
+    /// Client-to-overlay mapping for pointer input, set by `set_input_offset`.
+    pub(crate) input_transform: InputTransform,
+    /// Size the UI is composited at relative to the engine surface, set by
+    /// `set_render_scale`. Flutter's layout is unaffected.
+    pub(crate) render_scale: f32,
+    /// Clockwise rotation of the composited UI around its center, in degrees,
+    /// set by `set_rotation`.
+    pub(crate) rotation_degrees: f32,
+    /// Wheel scaling and direction, set by `set_scroll_config`.
+    pub(crate) scroll_config: ScrollConfig,
+    /// Whether cursors are loaded at the size matching `input_transform.scale`.
+    /// Off by default, which keeps the fixed-size system cursors.
+    pub(crate) cursor_scaling: bool,
+    pub(crate) scaled_cursors: Arc<Mutex<ScaledCursorCache>>,
+    /// Opacity animation started by `fade_in` / `fade_out`, advanced every tick.
+    pub(crate) fade: Option<OverlayFade>,
 
     pub effect_config: EffectConfig,
     pub effect_frames_remaining: u32,
@@ -165,6 +222,10 @@ pub struct FlutterOverlay {
     /// interactive widget (e.g., button, text field) within this overlay's semantics tree.
     /// Can be read by other parts of the crate (e.g., game input logic) to alter behavior.
     pub is_interactive_widget_hovered: AtomicBool,
+    /// Debounce applied to the per-frame hover update, set by `set_hover_debounce`.
+    pub(crate) hover_debounce: Mutex<HoverDebounce>,
+    /// Rate limit for semantics tree rebuilds, set by `set_semantics_update_interval`.
+    pub(crate) semantics_throttle: Mutex<SemanticsThrottle>,
 
     /// A boolean flag indicating if this specific overlay instance is running with
     /// debug assets (e.g., in JIT mode due to the absence of an AOT snapshot).
@@ -200,11 +261,20 @@ pub struct FlutterOverlay {
     pub(crate) software_frame_dirty: AtomicBool,
     /// Set once by `on_present` after the first frame is rendered. Never cleared.
     pub(crate) software_first_frame_rendered: AtomicBool,
+    /// Union of the areas `on_present` changed since the last `tick`, which
+    /// uploads only this rectangle.
+    pub(crate) software_damage: Mutex<Option<DirtyRect>>,
 
     /// The current cursor style requested by Flutter. Managed internally by `handle_set_cursor`
     /// and platform message callbacks.
     pub(crate) desired_cursor: Arc<Mutex<Option<String>>>,
 
+    /// Latest `flutter/restoration` blob, written by the framework's `put` and
+    /// by `set_restoration_data`, returned on `get`.
+    pub(crate) restoration_data: Arc<Mutex<Option<Vec<u8>>>>,
+    /// Dart VM service URI, taken from the engine log once the service is up.
+    pub(crate) vm_service_uri: Arc<Mutex<Option<String>>>,
+
     /// The Windows HWND this overlay is associated with. Set by `init_overlay`, used internally.
     pub(crate) windows_handler: SendHwnd,
 
@@ -217,6 +287,9 @@ pub struct FlutterOverlay {
 
     pub(crate) active_text_input: Arc<Mutex<Option<ActiveTextInputState>>>,
 
+    /// Caret position of the focused text field, for placing the IME windows.
+    pub(crate) caret_geometry: Arc<Mutex<CaretGeometry>>,
+
     /// Queue for pending platform messages that need to be sent from the platform thread.
     /// Messages sent from non-platform threads (like Windows UI thread) are queued here
     /// and processed by the platform task runner thread.
@@ -236,18 +309,50 @@ pub struct FlutterOverlay {
     /// Join handle for the task runner thread. Managed by `start_task_runner` and potentially `drop`.
     pub(crate) task_runner_thread: Option<Arc<thread::JoinHandle<()>>>,
 
+    /// COM apartment the task runner thread enters at start and leaves at exit.
+    pub(crate) task_runner_com: TaskRunnerComApartment,
+
     /// Tracks pressed mouse buttons for this overlay. Managed by `handle_pointer_event`.
     pub(crate) mouse_buttons_state: AtomicI32,
 
     /// Tracks if the `kAdd` pointer event was sent. Managed by `handle_pointer_event`.
     pub(crate) is_mouse_added: AtomicBool,
 
+    /// Open trackpad pan/zoom gesture built from `WM_POINTERWHEEL` and
+    /// `WM_GESTURE` messages.
+    pub(crate) trackpad_scroll: Mutex<TrackpadScrollState>,
+
+    /// Touch and pen contacts from `WM_POINTER*`, each its own Flutter device.
+    pub(crate) touch_contacts: Mutex<TouchContacts>,
+
     /// Semantics tree data for this overlay. Managed by semantics callbacks and hover state updates.
     pub(crate) semantics_tree_data: Arc<Mutex<HashMap<i32, ProcessedSemanticsNode>>>,
 
     /// The Dart port used for sending messages directly to the Dart isolate.
     pub(crate) dart_send_port: Arc<AtomicI64>,
 
+    /// Set while the host window has a zero-sized client area (e.g. minimized).
+    /// The last good texture and metrics are kept; ticking and compositing are
+    /// skipped until a non-zero resize arrives.
+    pub(crate) render_suspended: bool,
+
+    /// Last state sent with `set_lifecycle_state`. `tick_overlays` skips the
+    /// overlay while it is `Paused`.
+    pub(crate) lifecycle_state: LifecycleState,
+
+    /// Phase timings recorded by `init_overlay`. `first_frame_ms` is tracked
+    /// separately in `first_frame_ms` since it is only known later.
+    pub(crate) startup_timings: StartupTimings,
+    /// When `init_overlay` started; the reference point for `first_frame_ms`.
+    pub(crate) startup_started_at: Instant,
+    /// Set once, the first time the host sees a frame from this overlay.
+    pub(crate) first_frame_ms: OnceLock<f64>,
+    /// Nanoseconds after `startup_started_at` at which `tick` last copied a new
+    /// frame into `texture`; `0` until the first one.
+    pub(crate) last_present_nanos: AtomicU64,
+    /// Timings of the frames `tick` uploaded, read by `get_fps_stats`.
+    pub(crate) frame_timer: Mutex<FrameTimer>,
+
     // --- ANGLE (OpenGL) specific fields ---
     /// Manages the state for ANGLE's EGL context and surfaces for OpenGL rendering.
     pub(crate) angle_state: Option<SendableAngleState>,
@@ -260,6 +365,8 @@ pub struct FlutterOverlay {
     pub(crate) angle_shared_texture: Option<ID3D11Texture2D>,
     /// Second shared texture for double buffering (back buffer, index 1).
     pub(crate) angle_shared_texture_back: Option<ID3D11Texture2D>,
+    /// Shareable copy of `texture` requested with `set_texture_sharing`.
+    pub(crate) export_texture: Option<ExportTexture>,
     /// Keyed mutex on the ANGLE-side shared texture for cross-device GPU sync.
     /// Key 0 = ANGLE owns (can write), Key 1 = game owns (can read).
     pub(crate) angle_keyed_mutex: Option<IDXGIKeyedMutex>,
@@ -284,6 +391,9 @@ pub struct FlutterOverlay {
     pub(crate) frame_damage_rects: Mutex<Vec<FlutterRect>>,
     /// When true, forces a full repaint on the next frame. Set on resize and device recovery.
     pub(crate) full_repaint_needed: AtomicBool,
+    /// Set by `set_present_sync_mode(SyncMode::Fence)`; read by the satellite
+    /// view present callback on the raster thread.
+    pub(crate) present_fence_sync: AtomicBool,
 
     /// Registry of secondary (satellite) views driven by this engine. The
     /// implicit view (`view_id == 0`) is this overlay itself and is NOT stored
@@ -364,12 +474,15 @@ impl Clone for FlutterOverlay {
             primitive_renderer: self.primitive_renderer.clone(),
             text_renderer: self.text_renderer.clone(),
             desired_cursor: self.desired_cursor.clone(),
+            restoration_data: self.restoration_data.clone(),
+            vm_service_uri: self.vm_service_uri.clone(),
             name: self.name.clone(),
             dart_send_port: self.dart_send_port.clone(),
             engine_dll: self.engine_dll.clone(),
             task_queue_state: self.task_queue_state.clone(),
             view0_keyboard: self.view0_keyboard.clone(),
             active_text_input: self.active_text_input.clone(),
+            caret_geometry: self.caret_geometry.clone(),
             pending_platform_messages: self.pending_platform_messages.clone(),
             pending_key_events: self.pending_key_events.clone(),
             pending_view_focus: self.pending_view_focus.clone(),
@@ -383,16 +496,33 @@ impl Clone for FlutterOverlay {
             visible: self.visible,
             keep_alive: self.keep_alive,
             ui_hidden: self.ui_hidden,
+            auto_raise_on_hover: self.auto_raise_on_hover,
+            modal: self.modal,
+            input_priority: self.input_priority,
+            consumes_input: self.consumes_input,
+            hit_test_mode: self.hit_test_mode,
+            fixed_size: self.fixed_size,
+            user_data: self.user_data,
+            input_enabled: self.input_enabled,
+            input_transform: self.input_transform,
+            render_scale: self.render_scale,
+            rotation_degrees: self.rotation_degrees,
+            scroll_config: self.scroll_config,
+            cursor_scaling: self.cursor_scaling,
+            scaled_cursors: self.scaled_cursors.clone(),
+            fade: self.fade,
             effect_config: self.effect_config,
             effect_frames_remaining: self.effect_frames_remaining,
             effect_total_frames: self.effect_total_frames,
             x: self.x,
             y: self.y,
+            anchor: self.anchor,
             windows_handler: self.windows_handler,
             is_debug_build: self.is_debug_build,
             pixel_buffer: self.pixel_buffer.clone(),
             software_frame_dirty: AtomicBool::new(false),
             software_first_frame_rendered: AtomicBool::new(false),
+            software_damage: Mutex::new(None),
 
             mouse_buttons_state: AtomicI32::new(
                 self.mouse_buttons_state
@@ -402,10 +532,24 @@ impl Clone for FlutterOverlay {
                 self.is_mouse_added
                     .load(std::sync::atomic::Ordering::Relaxed),
             ),
+            trackpad_scroll: Mutex::new(TrackpadScrollState::default()),
+            touch_contacts: Mutex::new(TouchContacts::default()),
             is_interactive_widget_hovered: AtomicBool::new(
                 self.is_interactive_widget_hovered
                     .load(std::sync::atomic::Ordering::Relaxed),
             ),
+            hover_debounce: Mutex::new(
+                self.hover_debounce
+                    .lock()
+                    .map(|debounce| *debounce)
+                    .unwrap_or_default(),
+            ),
+            semantics_throttle: Mutex::new(SemanticsThrottle::new(
+                self.semantics_throttle
+                    .lock()
+                    .map(|throttle| throttle.interval)
+                    .unwrap_or_default(),
+            )),
 
             task_runner_thread: None,
             _platform_runner_context: None,
@@ -414,12 +558,17 @@ impl Clone for FlutterOverlay {
             _compositor: None,
             angle_state: None,
             d3d11_shared_handle: None,
+            export_texture: None,
             angle_frame_complete_query: None,
             angle_frame_presented: AtomicU64::new(0),
             angle_frame_copied: AtomicU64::new(0),
             damage_rects: Mutex::new(Vec::new()),
             frame_damage_rects: Mutex::new(Vec::new()),
             full_repaint_needed: AtomicBool::new(true),
+            present_fence_sync: AtomicBool::new(
+                self.present_fence_sync
+                    .load(std::sync::atomic::Ordering::Relaxed),
+            ),
             angle_shared_texture_back: None,
             angle_keyed_mutex: None,
             game_keyed_mutex: None,
@@ -436,6 +585,17 @@ impl Clone for FlutterOverlay {
             view_registry: self.view_registry.clone(),
             view0_gl: None,
             compositor_active: self.compositor_active,
+            render_suspended: self.render_suspended,
+            lifecycle_state: self.lifecycle_state,
+            startup_timings: self.startup_timings,
+            startup_started_at: self.startup_started_at,
+            first_frame_ms: self.first_frame_ms.clone(),
+            last_present_nanos: AtomicU64::new(
+                self.last_present_nanos
+                    .load(std::sync::atomic::Ordering::Relaxed),
+            ),
+            frame_timer: Mutex::new(FrameTimer::default()),
+            task_runner_com: self.task_runner_com,
         }
     }
 }
@@ -2,6 +2,9 @@ use crate::bindings::embedder::{self};
 use crate::software_renderer::api::FlutterEmbedderError;
 use crate::software_renderer::dynamic_flutter_engine_dll_loader::FlutterEngineDll;
 use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
+use crate::software_renderer::overlay::restoration::{
+    RESTORATION_CHANNEL, handle_restoration_call,
+};
 use crate::software_renderer::overlay::textinput::custom_text_input_platform_message_handler;
 
 use byteorder::{LittleEndian, ReadBytesExt};
@@ -38,6 +41,8 @@ pub(crate) enum FlutterChannel<'a> {
     Isolate,
     /// Navigation channel - handles route/navigation events
     Navigation,
+    /// Restoration channel - stores and serves state restoration data
+    Restoration,
     /// Custom application-defined channel
     Custom(&'a str),
     /// Unknown or unrecognized channel
@@ -56,6 +61,7 @@ impl<'a> FlutterChannel<'a> {
             "flutter/keyevent" => FlutterChannel::KeyEvent,
             "flutter/isolate" => FlutterChannel::Isolate,
             "flutter/navigation" => FlutterChannel::Navigation,
+            RESTORATION_CHANNEL => FlutterChannel::Restoration,
             _ => {
                 if channel.starts_with("flutter/") {
                     FlutterChannel::Unknown(channel)
@@ -77,6 +83,7 @@ impl<'a> FlutterChannel<'a> {
             FlutterChannel::KeyEvent => "flutter/keyevent",
             FlutterChannel::Isolate => "flutter/isolate",
             FlutterChannel::Navigation => "flutter/navigation",
+            FlutterChannel::Restoration => RESTORATION_CHANNEL,
             FlutterChannel::Custom(name) | FlutterChannel::Unknown(name) => name,
         }
     }
@@ -90,6 +97,8 @@ const K_SMC_INT32: u8 = 3;
 const K_SMC_STRING: u8 = 7;
 const K_SMC_LIST: u8 = 12;
 const K_SMC_MAP: u8 = 13;
+/// First byte of a method call result that failed.
+const K_SMC_ERROR_ENVELOPE: u8 = 1;
 
 //  helper functions to decode simple messages without a full codec dependency.
 
@@ -116,6 +125,34 @@ pub(crate) fn mc_read_size(cursor: &mut Cursor<&[u8]>) -> Result<usize, IoError>
     }
 }
 
+pub(crate) fn mc_write_size(out: &mut Vec<u8>, size: usize) {
+    if size < 254 {
+        out.push(size as u8);
+    } else if size <= u16::MAX as usize {
+        out.push(254);
+        out.extend_from_slice(&(size as u16).to_le_bytes());
+    } else {
+        out.push(255);
+        out.extend_from_slice(&(size as u32).to_le_bytes());
+    }
+}
+
+pub(crate) fn mc_write_string(out: &mut Vec<u8>, value: &str) {
+    out.push(K_SMC_STRING);
+    mc_write_size(out, value.len());
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// Standard method codec error envelope: code, message and null details. Dart
+/// throws it from `invokeMethod` as a `PlatformException`.
+pub(crate) fn mc_encode_error_envelope(code: &str, message: &str) -> Vec<u8> {
+    let mut out = vec![K_SMC_ERROR_ENVELOPE];
+    mc_write_string(&mut out, code);
+    mc_write_string(&mut out, message);
+    out.push(K_SMC_NULL);
+    out
+}
+
 fn mc_read_string(cursor: &mut Cursor<&[u8]>) -> Result<String, IoError> {
     let len = mc_read_size(cursor)?;
     let mut buffer = vec![0; len];
@@ -300,6 +337,42 @@ fn handle_navigation_message(_message: &embedder::FlutterPlatformMessage) -> Cha
     ChannelHandlerResult::RespondNull
 }
 
+/// Handle messages on the flutter/restoration channel
+fn handle_restoration_message(
+    message: &embedder::FlutterPlatformMessage,
+    overlay: &FlutterOverlay,
+) -> ChannelHandlerResult {
+    if message.message.is_null() || message.message_size == 0 {
+        return ChannelHandlerResult::RespondNull;
+    }
+    let slice = unsafe { std::slice::from_raw_parts(message.message, message.message_size) };
+    match handle_restoration_call(slice, &overlay.restoration_data) {
+        Some(response) => ChannelHandlerResult::RespondWith(response),
+        None => ChannelHandlerResult::RespondNull,
+    }
+}
+
+/// Answers a JSON method codec call (`{"method": .., "args": ..}`) with
+/// `handler`'s return value in a `[result]` success envelope. A payload that
+/// isn't such a call gets a `[code, message, null]` error envelope instead.
+pub(crate) fn json_method_response(
+    payload: &[u8],
+    handler: impl Fn(&str, Value) -> Value,
+) -> Vec<u8> {
+    let response = match from_slice::<Value>(payload) {
+        Ok(Value::Object(mut call)) => match call.remove("method") {
+            Some(Value::String(method)) => {
+                let args = call.remove("args").unwrap_or(Value::Null);
+                json!([handler(&method, args)])
+            }
+            _ => json!(["malformed_call", "method call has no method name", null]),
+        },
+        Ok(_) => json!(["malformed_call", "method call is not a JSON object", null]),
+        Err(e) => json!(["malformed_call", format!("invalid JSON: {e}"), null]),
+    };
+    response.to_string().into_bytes()
+}
+
 /// Handle messages on the flutter/platform channel (clipboard, system chrome, etc.)
 fn handle_platform_message(message: &embedder::FlutterPlatformMessage) -> ChannelHandlerResult {
     unsafe {
@@ -539,6 +612,8 @@ pub(crate) extern "C" fn simple_platform_message_callback(
 
             FlutterChannel::Navigation => handle_navigation_message(message),
 
+            FlutterChannel::Restoration => handle_restoration_message(message, overlay),
+
             FlutterChannel::Custom(name) => handle_custom_channel(name, message, overlay),
 
             FlutterChannel::Unknown(_name) => ChannelHandlerResult::RespondNull,
@@ -621,3 +696,87 @@ pub fn send_platform_message(
         Err(FlutterEmbedderError::OperationFailed(err_msg))
     }
 }
+
+/// Called with Dart's reply to a message sent by
+/// [`send_platform_message_with_response`]. `None` when Dart replied `null`.
+/// A channel without a Dart handler buffers the message instead of answering
+/// it, so the callback may never run.
+pub(crate) type PlatformMessageReplyCallback = Box<dyn FnOnce(Option<&[u8]>) + Send>;
+
+pub(crate) unsafe extern "C" fn on_platform_message_reply(
+    data: *const u8,
+    size: usize,
+    user_data: *mut c_void,
+) {
+    let callback = unsafe { Box::from_raw(user_data as *mut PlatformMessageReplyCallback) };
+    let reply = if data.is_null() || size == 0 {
+        None
+    } else {
+        Some(unsafe { std::slice::from_raw_parts(data, size) })
+    };
+    callback(reply);
+}
+
+/// Like [`send_platform_message`], but attaches a response handle so Dart's
+/// reply reaches `callback`.
+///
+/// The callback runs at most once, on the thread that runs the engine's
+/// platform tasks. It never runs if nothing on the Dart side handles the
+/// channel, or if the engine shuts down before replying; its box is leaked
+/// then.
+pub(crate) fn send_platform_message_with_response(
+    overlay: &FlutterOverlay,
+    channel: &str,
+    message: &[u8],
+    callback: PlatformMessageReplyCallback,
+) -> Result<(), FlutterEmbedderError> {
+    let engine = overlay.engine.0;
+    if engine.is_null() {
+        return Err(FlutterEmbedderError::EngineNotRunning);
+    }
+    let channel_cstring = CString::new(channel).map_err(|e| {
+        FlutterEmbedderError::OperationFailed(format!("Invalid channel name: {e}"))
+    })?;
+
+    let dll = &overlay.engine_dll;
+    let baton = Box::into_raw(Box::new(callback)) as *mut c_void;
+    let mut response_handle: *mut embedder::FlutterPlatformMessageResponseHandle =
+        ptr::null_mut();
+    let created = unsafe {
+        (dll.FlutterPlatformMessageCreateResponseHandle)(
+            engine,
+            Some(on_platform_message_reply),
+            baton,
+            &mut response_handle,
+        )
+    };
+    if created != embedder::FlutterEngineResult_kSuccess {
+        drop(unsafe { Box::from_raw(baton as *mut PlatformMessageReplyCallback) });
+        return Err(FlutterEmbedderError::OperationFailed(format!(
+            "Failed to create response handle for channel '{channel}': {created:?}"
+        )));
+    }
+
+    let platform_message = embedder::FlutterPlatformMessage {
+        struct_size: std::mem::size_of::<embedder::FlutterPlatformMessage>(),
+        channel: channel_cstring.as_ptr(),
+        message: message.as_ptr(),
+        message_size: message.len(),
+        response_handle,
+    };
+    let sent = unsafe { (dll.FlutterEngineSendPlatformMessage)(engine, &platform_message) };
+    // The engine keeps what it needs from the handle once the message is sent.
+    unsafe {
+        (dll.FlutterPlatformMessageReleaseResponseHandle)(engine, response_handle);
+    }
+    if sent != embedder::FlutterEngineResult_kSuccess {
+        // A rejected message is never answered, so the callback is ours again.
+        drop(unsafe { Box::from_raw(baton as *mut PlatformMessageReplyCallback) });
+        let err_msg = format!(
+            "Failed to send platform message on channel '{channel}': {sent:?}"
+        );
+        error!("[FlutterOverlay:'{}'] {}", overlay.name, err_msg);
+        return Err(FlutterEmbedderError::OperationFailed(err_msg));
+    }
+    Ok(())
+}
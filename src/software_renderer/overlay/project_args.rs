@@ -1,19 +1,49 @@
 use crate::bindings::embedder::FlutterTaskRunnerDescription;
-use crate::software_renderer::overlay::overlay_impl::FLUTTER_LOG_TAG;
+use crate::software_renderer::api::RuntimeMode;
+use crate::software_renderer::overlay::overlay_impl::{FLUTTER_LOG_TAG, FlutterOverlay};
 use crate::software_renderer::ticker::task_scheduler::{
     TaskQueueState, TaskRunnerContext, destroy_task_runner_context_callback, post_task_callback,
     runs_task_on_current_thread_callback,
 };
 
-use log::info;
+use log::{info, warn};
 use std::ffi::{CStr, CString, OsStr, c_void};
-use std::sync::Arc;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::{Arc, RwLock};
+
+/// Receives every engine / Dart log line as `(tag, message)`, including Dart
+/// `print` and `debugPrint` output. May be called from any engine thread.
+pub type EngineLogHandler = Arc<dyn Fn(&str, &str) + Send + Sync + 'static>;
+
+/// Process-wide log sink. `None` routes engine logs through the `log` crate.
+static ENGINE_LOG_HANDLER: RwLock<Option<EngineLogHandler>> = RwLock::new(None);
+
+pub(crate) fn set_engine_log_handler(handler: Option<EngineLogHandler>) {
+    match ENGINE_LOG_HANDLER.write() {
+        Ok(mut slot) => *slot = handler,
+        Err(poisoned) => *poisoned.into_inner() = handler,
+    }
+}
+
+fn engine_log_handler() -> Option<EngineLogHandler> {
+    ENGINE_LOG_HANDLER.read().ok().and_then(|slot| slot.clone())
+}
+
+/// The VM service URI in an engine log line such as
+/// `The Dart VM service is listening on http://127.0.0.1:50300/abc=/`. Older
+/// engines print `Observatory listening on ...`.
+pub(crate) fn parse_vm_service_uri(message: &str) -> Option<&str> {
+    let (_, rest) = message.split_once("listening on ")?;
+    rest.split_whitespace()
+        .next()
+        .filter(|uri| uri.starts_with("http://") || uri.starts_with("https://"))
+}
 
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn flutter_log_callback(
     tag: *const std::os::raw::c_char,
     message: *const std::os::raw::c_char,
-    _user_data: *mut c_void,
+    user_data: *mut c_void,
 ) {
     let tag_str = if tag.is_null() {
         FLUTTER_LOG_TAG.to_string_lossy().into_owned()
@@ -25,6 +55,20 @@ pub unsafe extern "C" fn flutter_log_callback(
     } else {
         unsafe { &CStr::from_ptr(message).to_string_lossy().into_owned() }
     };
+    if let Some(uri) = parse_vm_service_uri(msg_str)
+        && let Some(overlay) = unsafe { (user_data as *const FlutterOverlay).as_ref() }
+        && let Ok(mut slot) = overlay.vm_service_uri.lock()
+    {
+        info!("[Flutter] '{}' Dart VM service at {uri}", overlay.name);
+        *slot = Some(uri.to_string());
+    }
+    if let Some(handler) = engine_log_handler() {
+        // Unwinding out of an `extern "C"` callback aborts the process.
+        if catch_unwind(AssertUnwindSafe(|| handler(&tag_str, msg_str))).is_ok() {
+            return;
+        }
+        warn!("[Flutter] engine log handler panicked, falling back to `log`");
+    }
     info!("[Flutter][{tag_str}] {msg_str}");
 }
 
@@ -77,6 +121,41 @@ pub(crate) fn build_project_args_and_strings(
 
     (assets_c, icu_c, engine_argv_cs, dart_argv_cs)
 }
+/// Placeholder `argv[0]`; the engine skips the first command-line argument.
+const ENGINE_ARGV0: &str = "flutter_rust_windows_embedder";
+
+/// Appends the switches for `mode` to the user's engine args, skipping any the
+/// user already passed.
+pub(crate) fn engine_args_with_runtime_mode(
+    engine_args_opt: Option<&[String]>,
+    mode: Option<RuntimeMode>,
+) -> Option<Vec<String>> {
+    let switches = mode.map(RuntimeMode::engine_switches).unwrap_or(&[]);
+    append_engine_switches(engine_args_opt, switches)
+}
+
+/// Appends `switches` to the engine args, inserting the `argv[0]` placeholder
+/// when there are no user args and skipping switches already present.
+pub(crate) fn append_engine_switches<S: AsRef<str>>(
+    engine_args_opt: Option<&[String]>,
+    switches: &[S],
+) -> Option<Vec<String>> {
+    if switches.is_empty() {
+        return engine_args_opt.map(<[String]>::to_vec);
+    }
+
+    let mut args = match engine_args_opt {
+        Some(user_args) if !user_args.is_empty() => user_args.to_vec(),
+        _ => vec![ENGINE_ARGV0.to_string()],
+    };
+    for switch in switches.iter().map(AsRef::as_ref) {
+        if !args.iter().any(|a| a == switch) {
+            args.push(switch.to_string());
+        }
+    }
+    Some(args)
+}
+
 pub(crate) fn maybe_load_aot_path_to_cstring(aot_opt: Option<&OsStr>) -> Option<CString> {
     if let Some(os) = aot_opt {
         let path = os.to_string_lossy();
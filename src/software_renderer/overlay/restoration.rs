@@ -0,0 +1,125 @@
+//! `flutter/restoration` channel support.
+//!
+//! The framework's `RestorationManager` asks for the stored restoration data
+//! with `get` on startup and sends every change back with `put`. The embedder
+//! keeps the latest blob per overlay so the host can persist it and hand it to
+//! the next run; the bytes are opaque and never interpreted here.
+
+use crate::software_renderer::overlay::platform_message_callback::{
+    mc_read_size, mc_write_size, mc_write_string,
+};
+
+use byteorder::ReadBytesExt;
+use std::io::{Cursor, Error as IoError, ErrorKind as IoErrorKind, Read};
+use std::sync::Mutex;
+
+pub(crate) const RESTORATION_CHANNEL: &str = "flutter/restoration";
+
+// Standard message codec tags used by the restoration protocol.
+const K_NULL: u8 = 0;
+const K_TRUE: u8 = 1;
+const K_STRING: u8 = 7;
+const K_UINT8_LIST: u8 = 8;
+const K_MAP: u8 = 13;
+
+/// Envelope prefix of a successful method result.
+const K_SUCCESS_ENVELOPE: u8 = 0;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum RestorationCall {
+    /// The framework requests the stored data.
+    Get,
+    /// The framework stores new data.
+    Put(Vec<u8>),
+    Other(String),
+}
+
+/// Decodes a standard method codec call on `flutter/restoration`.
+pub(crate) fn parse_restoration_call(payload: &[u8]) -> Result<RestorationCall, IoError> {
+    let mut cursor = Cursor::new(payload);
+    if cursor.read_u8()? != K_STRING {
+        return Err(IoError::new(
+            IoErrorKind::InvalidData,
+            "Expected K_STRING for restoration method name",
+        ));
+    }
+    let len = mc_read_size(&mut cursor)?;
+    let mut name = vec![0; len];
+    cursor.read_exact(&mut name)?;
+    let method =
+        String::from_utf8(name).map_err(|e| IoError::new(IoErrorKind::InvalidData, e))?;
+
+    match method.as_str() {
+        "get" => Ok(RestorationCall::Get),
+        "put" => {
+            let data = match cursor.read_u8()? {
+                K_UINT8_LIST => {
+                    let len = mc_read_size(&mut cursor)?;
+                    let mut data = vec![0; len];
+                    cursor.read_exact(&mut data)?;
+                    data
+                }
+                K_NULL => Vec::new(),
+                _ => {
+                    return Err(IoError::new(
+                        IoErrorKind::InvalidData,
+                        "Expected K_UINT8_LIST for restoration data",
+                    ));
+                }
+            };
+            Ok(RestorationCall::Put(data))
+        }
+        _ => Ok(RestorationCall::Other(method)),
+    }
+}
+
+/// Writes the `{enabled: true, data: <bytes or null>}` map the framework expects.
+fn write_restoration_state(out: &mut Vec<u8>, data: Option<&[u8]>) {
+    out.push(K_MAP);
+    mc_write_size(out, 2);
+    mc_write_string(out, "enabled");
+    out.push(K_TRUE);
+    mc_write_string(out, "data");
+    match data {
+        Some(bytes) => {
+            out.push(K_UINT8_LIST);
+            mc_write_size(out, bytes.len());
+            out.extend_from_slice(bytes);
+        }
+        None => out.push(K_NULL),
+    }
+}
+
+/// Success envelope answering `get`.
+pub(crate) fn encode_get_response(data: Option<&[u8]>) -> Vec<u8> {
+    let mut out = vec![K_SUCCESS_ENVELOPE];
+    write_restoration_state(&mut out, data);
+    out
+}
+
+/// `push` call that replaces the framework's restoration data at runtime.
+pub(crate) fn encode_push_call(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    mc_write_string(&mut out, "push");
+    write_restoration_state(&mut out, Some(data));
+    out
+}
+
+/// Answers one restoration call against `store`. Returns the response
+/// envelope, or `None` for methods this embedder doesn't implement.
+pub(crate) fn handle_restoration_call(
+    payload: &[u8],
+    store: &Mutex<Option<Vec<u8>>>,
+) -> Option<Vec<u8>> {
+    match parse_restoration_call(payload) {
+        Ok(RestorationCall::Get) => {
+            let guard = store.lock().unwrap_or_else(|p| p.into_inner());
+            Some(encode_get_response(guard.as_deref()))
+        }
+        Ok(RestorationCall::Put(data)) => {
+            *store.lock().unwrap_or_else(|p| p.into_inner()) = Some(data);
+            Some(vec![K_SUCCESS_ENVELOPE, K_NULL])
+        }
+        Ok(RestorationCall::Other(_)) | Err(_) => None,
+    }
+}
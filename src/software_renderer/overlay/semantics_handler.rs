@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::ffi::CStr;
 use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 use windows::Win32::Foundation::POINT;
 use windows::Win32::Graphics::Gdi::ScreenToClient;
 use windows::Win32::UI::WindowsAndMessaging::{GetCursorPos, GetForegroundWindow};
@@ -265,14 +266,6 @@ pub extern "C" fn semantics_update_callback(
 
         let overlay: &mut FlutterOverlay = &mut *(user_data as *mut FlutterOverlay);
 
-        if update_ref.node_count == 0 {
-            if let Ok(mut tree_guard) = overlay.semantics_tree_data.lock()
-                && !tree_guard.is_empty() {
-                    tree_guard.clear();
-                }
-            return;
-        }
-
         let mut new_tree_snapshot = HashMap::new();
 
         for i in 0..update_ref.node_count {
@@ -320,10 +313,83 @@ pub extern "C" fn semantics_update_callback(
             );
         }
 
-        if let Ok(mut tree_guard) = overlay.semantics_tree_data.lock() {
-            *tree_guard = new_tree_snapshot;
+        let due = match overlay.semantics_throttle.lock() {
+            Ok(mut throttle) => throttle.offer(new_tree_snapshot, Instant::now()),
+            Err(_) => Some(new_tree_snapshot),
+        };
+        if let Some(tree) = due {
+            publish_semantics_tree(overlay, tree);
+        }
+    }
+}
+
+fn publish_semantics_tree(overlay: &FlutterOverlay, tree: HashMap<i32, ProcessedSemanticsNode>) {
+    if let Ok(mut tree_guard) = overlay.semantics_tree_data.lock() {
+        *tree_guard = tree;
+    }
+}
+
+/// Coalesces semantics updates so the tree used for hit testing is replaced at
+/// most once per `interval`. An update that arrives too early is held back, and
+/// only the latest held-back tree is kept. `Duration::ZERO` applies every
+/// update as it arrives.
+#[derive(Debug, Clone, Default)]
+pub struct SemanticsThrottle {
+    pub interval: Duration,
+    last_applied: Option<Instant>,
+    pending: Option<HashMap<i32, ProcessedSemanticsNode>>,
+}
+
+impl SemanticsThrottle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            ..Self::default()
+        }
+    }
+
+    fn is_due(&self, now: Instant) -> bool {
+        self.last_applied
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.interval)
+    }
+
+    /// Takes a freshly built tree and returns it if it should be applied now;
+    /// otherwise it replaces any tree already held back.
+    pub fn offer(
+        &mut self,
+        tree: HashMap<i32, ProcessedSemanticsNode>,
+        now: Instant,
+    ) -> Option<HashMap<i32, ProcessedSemanticsNode>> {
+        if self.is_due(now) {
+            self.last_applied = Some(now);
+            self.pending = None;
+            Some(tree)
+        } else {
+            self.pending = Some(tree);
+            None
         }
     }
+
+    /// Returns the held-back tree once its interval has passed.
+    pub fn take_due(&mut self, now: Instant) -> Option<HashMap<i32, ProcessedSemanticsNode>> {
+        if self.pending.is_none() || !self.is_due(now) {
+            return None;
+        }
+        self.last_applied = Some(now);
+        self.pending.take()
+    }
+}
+
+/// Applies a semantics tree the throttle held back, if its interval has passed.
+fn flush_pending_semantics(overlay: &FlutterOverlay) {
+    let due = overlay
+        .semantics_throttle
+        .lock()
+        .ok()
+        .and_then(|mut throttle| throttle.take_due(Instant::now()));
+    if let Some(tree) = due {
+        publish_semantics_tree(overlay, tree);
+    }
 }
 fn is_point_in_flutter_rect(point_x: f64, point_y: f64, rect: &FlutterRect) -> bool {
     point_x >= rect.left && point_x <= rect.right && point_y >= rect.top && point_y <= rect.bottom
@@ -392,57 +458,145 @@ fn hit_test_node_recursive(
     None
 }
 
+/// True if `(x, y)` (client coordinates) lands on an interactive node of `tree`.
+///
+/// An empty tree never hits, so fully transparent overlays let pointer input
+/// fall through to the overlay beneath.
+pub fn hit_test_interactive(tree: &HashMap<i32, ProcessedSemanticsNode>, x: f64, y: f64) -> bool {
+    !tree.is_empty() && hit_test_node_recursive(0, x, y, tree).is_some()
+}
+
+/// Which pointer events an overlay claims, stopping them from reaching the
+/// overlays below and the host.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HitTestMode {
+    /// Only events over an interactive semantics node (button, text field,
+    /// link, ...). Transparent areas and plain content let clicks through.
+    #[default]
+    SemanticsOnly,
+    /// Every event inside the overlay's bounds, like an opaque window.
+    Opaque,
+}
+
+impl HitTestMode {
+    /// Whether an event is claimed, given whether it landed on an interactive
+    /// node. `in_bounds` is only asked in `Opaque` mode.
+    pub fn claims(self, interactive: bool, in_bounds: impl FnOnce() -> bool) -> bool {
+        match self {
+            HitTestMode::SemanticsOnly => interactive,
+            HitTestMode::Opaque => interactive || in_bounds(),
+        }
+    }
+}
+
+/// Holds back hover flips until the new state has been seen on more than
+/// `frames` consecutive samples, which stops the flag from flickering while the
+/// cursor sits on a widget boundary. `frames == 0` applies every sample as is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HoverDebounce {
+    pub frames: u32,
+    streak: u32,
+}
+
+impl HoverDebounce {
+    pub fn new(frames: u32) -> Self {
+        Self { frames, streak: 0 }
+    }
+
+    /// Feeds one raw hit-test result and returns the hover state to publish.
+    pub fn sample(&mut self, current: bool, raw: bool) -> bool {
+        if raw == current {
+            self.streak = 0;
+            return current;
+        }
+        self.streak += 1;
+        if self.streak > self.frames {
+            self.streak = 0;
+            raw
+        } else {
+            current
+        }
+    }
+}
+
+fn hit_test_overlay_at(overlay: &FlutterOverlay, x: f64, y: f64) -> bool {
+    let (x, y) = overlay.client_to_overlay(x, y);
+    overlay
+        .semantics_tree_data
+        .lock()
+        .map(|tree_guard| hit_test_interactive(&tree_guard, x, y))
+        .unwrap_or(false)
+}
+
+/// Publishes one per-frame hover sample through the overlay's debounce.
+fn store_hover_sample(overlay: &FlutterOverlay, raw: bool) {
+    let current = overlay.is_interactive_widget_hovered.load(Ordering::Relaxed);
+    let hovered = match overlay.hover_debounce.lock() {
+        Ok(mut debounce) => debounce.sample(current, raw),
+        Err(_) => raw,
+    };
+    overlay
+        .is_interactive_widget_hovered
+        .store(hovered, Ordering::Relaxed);
+}
+
+/// Hit-tests the overlay's semantics tree at the position of a pointer event and
+/// stores the result in `is_interactive_widget_hovered`. `x`/`y` are client
+/// coordinates; the overlay's input transform is applied here.
+///
+/// Unlike `update_interactive_widget_hover_state`, which samples the cursor once
+/// per frame, this uses the event's own coordinates, so routing decisions don't
+/// depend on a hover flag that is up to a frame stale. The raw result is always
+/// returned; with a hover debounce set, the stored flag is left to the per-frame
+/// update so the debounce counts frames, not pointer events.
+pub fn update_interactive_widget_hover_state_at(
+    overlay: &FlutterOverlay,
+    x: f64,
+    y: f64,
+) -> bool {
+    let hovered = hit_test_overlay_at(overlay, x, y);
+    let debounced = overlay
+        .hover_debounce
+        .lock()
+        .map(|debounce| debounce.frames > 0)
+        .unwrap_or(false);
+    if !debounced {
+        overlay
+            .is_interactive_widget_hovered
+            .store(hovered, Ordering::Relaxed);
+    }
+    hovered
+}
+
 pub fn update_interactive_widget_hover_state(overlay: &FlutterOverlay) {
+    flush_pending_semantics(overlay);
+
     let mut cursor_pos_screen: POINT = POINT { x: 0, y: 0 };
 
     let overlay_hwnd = overlay.windows_handler;
 
     unsafe {
         if GetCursorPos(&mut cursor_pos_screen).is_err() {
-            overlay
-                .is_interactive_widget_hovered
-                .store(false, Ordering::Relaxed);
+            store_hover_sample(overlay, false);
             return;
         }
 
         if GetForegroundWindow() != overlay_hwnd.0 {
-            overlay
-                .is_interactive_widget_hovered
-                .store(false, Ordering::Relaxed);
+            store_hover_sample(overlay, false);
             return;
         }
 
         let mut client_cursor_pos = cursor_pos_screen;
 
         if !ScreenToClient(overlay_hwnd.0, &mut client_cursor_pos).as_bool() {
-            overlay
-                .is_interactive_widget_hovered
-                .store(false, Ordering::Relaxed);
+            store_hover_sample(overlay, false);
             return;
         }
 
         let mouse_x_for_flutter = client_cursor_pos.x as f64;
         let mouse_y_for_flutter = client_cursor_pos.y as f64;
 
-        let new_hover_state = {
-            if let Ok(tree_guard) = overlay.semantics_tree_data.lock() {
-                if !tree_guard.is_empty() {
-                    hit_test_node_recursive(
-                        0,
-                        mouse_x_for_flutter,
-                        mouse_y_for_flutter,
-                        &tree_guard,
-                    )
-                    .is_some()
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        };
-        overlay
-            .is_interactive_widget_hovered
-            .store(new_hover_state, Ordering::Relaxed);
+        let hovered = hit_test_overlay_at(overlay, mouse_x_for_flutter, mouse_y_for_flutter);
+        store_hover_sample(overlay, hovered);
     }
 }
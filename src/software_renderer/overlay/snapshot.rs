@@ -0,0 +1,238 @@
+//! Reading an overlay's pixels back and saving them as PNG.
+//!
+//! The overlay texture is `B8G8R8A8`, and a mapped staging copy can pad each
+//! row beyond `width * 4` bytes. [`bgra_rows_to_rgba`] strips the padding and
+//! swaps the channels into the tightly packed RGBA the `image` crate expects;
+//! [`pack_bgra_rows`] only strips the padding, for raw captures.
+
+use std::path::Path;
+
+use directx_math::{XMMatrix, XMMatrixIdentity};
+use image::{ExtendedColorType, ImageFormat, save_buffer_with_format};
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11_BIND_RENDER_TARGET, D3D11_BIND_SHADER_RESOURCE, D3D11_CPU_ACCESS_READ, D3D11_MAP_READ,
+    D3D11_MAPPED_SUBRESOURCE, D3D11_TEXTURE2D_DESC, D3D11_USAGE_DEFAULT, D3D11_USAGE_STAGING,
+    D3D11_VIEWPORT, ID3D11DeviceContext, ID3D11RenderTargetView, ID3D11Texture2D,
+};
+use windows::Win32::Graphics::Dxgi::Common::{DXGI_FORMAT_B8G8R8A8_UNORM, DXGI_SAMPLE_DESC};
+
+use crate::software_renderer::api::{AlphaMode, FlutterEmbedderError, convert_alpha};
+use crate::software_renderer::d3d11_compositor::traits::{FrameParams, Renderer};
+use crate::software_renderer::gl_renderer::d3d_backup::D3D11StateBackup;
+use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
+
+/// Packs mapped texture rows as `(data, width, height, row_pitch)`, like
+/// [`bgra_rows_to_rgba`] and [`pack_bgra_rows`].
+type RowPacker = fn(&[u8], u32, u32, usize) -> Option<Vec<u8>>;
+
+/// Packs `height` rows of `width` BGRA pixels, each starting `row_pitch` bytes
+/// after the previous one, into RGBA. Returns `None` if `data` is too short.
+pub(crate) fn bgra_rows_to_rgba(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    row_pitch: usize,
+) -> Option<Vec<u8>> {
+    let row_bytes = width as usize * 4;
+    if height == 0 {
+        return Some(Vec::new());
+    }
+    if row_pitch < row_bytes || data.len() < row_pitch * (height as usize - 1) + row_bytes {
+        return None;
+    }
+    let mut rgba = Vec::with_capacity(row_bytes * height as usize);
+    for row in data.chunks(row_pitch).take(height as usize) {
+        for pixel in row[..row_bytes].chunks_exact(4) {
+            rgba.extend_from_slice(&[pixel[2], pixel[1], pixel[0], pixel[3]]);
+        }
+    }
+    Some(rgba)
+}
+
+/// Like [`bgra_rows_to_rgba`], but keeps the BGRA byte order.
+pub(crate) fn pack_bgra_rows(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    row_pitch: usize,
+) -> Option<Vec<u8>> {
+    let row_bytes = width as usize * 4;
+    if height == 0 {
+        return Some(Vec::new());
+    }
+    if row_pitch < row_bytes || data.len() < row_pitch * (height as usize - 1) + row_bytes {
+        return None;
+    }
+    let mut packed = Vec::with_capacity(row_bytes * height as usize);
+    for row in data.chunks(row_pitch).take(height as usize) {
+        packed.extend_from_slice(&row[..row_bytes]);
+    }
+    Some(packed)
+}
+
+fn failed(what: &str, e: impl std::fmt::Display) -> FlutterEmbedderError {
+    FlutterEmbedderError::OperationFailed(format!("{what}: {e}"))
+}
+
+fn texture_desc(width: u32, height: u32) -> D3D11_TEXTURE2D_DESC {
+    D3D11_TEXTURE2D_DESC {
+        Width: width,
+        Height: height,
+        MipLevels: 1,
+        ArraySize: 1,
+        Format: DXGI_FORMAT_B8G8R8A8_UNORM,
+        SampleDesc: DXGI_SAMPLE_DESC {
+            Count: 1,
+            Quality: 0,
+        },
+        ..Default::default()
+    }
+}
+
+/// Copies a `width` x `height` BGRA texture to the CPU as RGBA. Waits for the
+/// GPU, so this is for one-off captures, not per-frame use.
+pub(crate) fn read_texture_rgba(
+    context: &ID3D11DeviceContext,
+    texture: &ID3D11Texture2D,
+    width: u32,
+    height: u32,
+) -> Result<Vec<u8>, FlutterEmbedderError> {
+    read_texture_with(context, texture, width, height, bgra_rows_to_rgba)
+}
+
+/// Like [`read_texture_rgba`], but returns tightly packed BGRA.
+pub(crate) fn read_texture_bgra(
+    context: &ID3D11DeviceContext,
+    texture: &ID3D11Texture2D,
+    width: u32,
+    height: u32,
+) -> Result<Vec<u8>, FlutterEmbedderError> {
+    read_texture_with(context, texture, width, height, pack_bgra_rows)
+}
+
+/// Copies `texture` into a staging texture created for this call, maps it and
+/// hands the rows to `pack` with the mapped row pitch.
+fn read_texture_with(
+    context: &ID3D11DeviceContext,
+    texture: &ID3D11Texture2D,
+    width: u32,
+    height: u32,
+    pack: RowPacker,
+) -> Result<Vec<u8>, FlutterEmbedderError> {
+    unsafe {
+        let device = texture.GetDevice().map_err(|e| failed("GetDevice", e))?;
+        let desc = D3D11_TEXTURE2D_DESC {
+            Usage: D3D11_USAGE_STAGING,
+            CPUAccessFlags: D3D11_CPU_ACCESS_READ.0 as u32,
+            ..texture_desc(width, height)
+        };
+        let mut staging: Option<ID3D11Texture2D> = None;
+        device
+            .CreateTexture2D(&desc, None, Some(&mut staging))
+            .map_err(|e| failed("creating the staging texture", e))?;
+        let staging = staging.unwrap();
+        context.CopyResource(&staging, texture);
+
+        let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+        context
+            .Map(&staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))
+            .map_err(|e| failed("mapping the staging texture", e))?;
+        let pitch = mapped.RowPitch as usize;
+        let len = pitch * height.saturating_sub(1) as usize + width as usize * 4;
+        let data = std::slice::from_raw_parts(mapped.pData as *const u8, len);
+        let pixels = pack(data, width, height, pitch);
+        context.Unmap(&staging, 0);
+        pixels.ok_or_else(|| failed("reading the staging texture", "row pitch too small"))
+    }
+}
+
+/// Draws the overlay with its current effect, opacity and mask into a new
+/// transparent texture of the overlay's size. The context state is restored
+/// afterwards.
+pub(crate) fn render_composited(
+    overlay: &mut FlutterOverlay,
+    context: &ID3D11DeviceContext,
+    time: f32,
+) -> Result<ID3D11Texture2D, FlutterEmbedderError> {
+    let (width, height) = (overlay.width, overlay.height);
+    unsafe {
+        let device = overlay.texture.GetDevice().map_err(|e| failed("GetDevice", e))?;
+        let desc = D3D11_TEXTURE2D_DESC {
+            Usage: D3D11_USAGE_DEFAULT,
+            BindFlags: (D3D11_BIND_RENDER_TARGET.0 | D3D11_BIND_SHADER_RESOURCE.0) as u32,
+            ..texture_desc(width, height)
+        };
+        let mut target: Option<ID3D11Texture2D> = None;
+        device
+            .CreateTexture2D(&desc, None, Some(&mut target))
+            .map_err(|e| failed("creating the capture target", e))?;
+        let target = target.unwrap();
+        let mut rtv: Option<ID3D11RenderTargetView> = None;
+        device
+            .CreateRenderTargetView(&target, None, Some(&mut rtv))
+            .map_err(|e| failed("creating the capture target view", e))?;
+
+        let _backup = D3D11StateBackup::new(context);
+        context.OMSetRenderTargets(Some(&[rtv.clone()]), None);
+        context.RSSetViewports(Some(&[D3D11_VIEWPORT {
+            TopLeftX: 0.0,
+            TopLeftY: 0.0,
+            Width: width as f32,
+            Height: height as f32,
+            MinDepth: 0.0,
+            MaxDepth: 1.0,
+        }]));
+        context.ClearRenderTargetView(rtv.as_ref().unwrap(), &[0.0; 4]);
+
+        let identity = XMMatrix(XMMatrixIdentity());
+        let frame_params = FrameParams {
+            context,
+            view_projection_matrix: &identity,
+            depth_stencil_view: &None,
+            screen_width: width as f32,
+            screen_height: height as f32,
+            time,
+        };
+        overlay.post_processor.queue_texture_render(
+            &overlay.srv,
+            &overlay.effect_config,
+            0,
+            0,
+            width,
+            height,
+        );
+        overlay.post_processor.draw(&frame_params);
+        Ok(target)
+    }
+}
+
+/// Writes tightly packed RGBA pixels to `path` as PNG.
+pub(crate) fn write_png(
+    path: &Path,
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+) -> Result<(), FlutterEmbedderError> {
+    save_buffer_with_format(
+        path,
+        rgba,
+        width,
+        height,
+        ExtendedColorType::Rgba8,
+        ImageFormat::Png,
+    )
+    .map_err(|e| failed(&format!("writing '{}'", path.display()), e))
+}
+
+/// Converts RGBA pixels from the `from` to the `to` alpha convention and
+/// writes them to `path`. PNG stores straight alpha.
+pub(crate) fn save_rgba_png(
+    path: &Path,
+    (width, height): (u32, u32),
+    mut rgba: Vec<u8>,
+    from: AlphaMode,
+    to: AlphaMode,
+) -> Result<(), FlutterEmbedderError> {
+    convert_alpha(&mut rgba, from, to);
+    write_png(path, width, height, &rgba)
+}
@@ -0,0 +1,160 @@
+//! OS theme and accessibility settings.
+//!
+//! Flutter learns the platform brightness and text scale from a JSON message on
+//! `flutter/settings`, and high contrast / reduced animations from
+//! `FlutterEngineUpdateAccessibilityFeatures`. Overlays have no window of their
+//! own to receive `WM_SETTINGCHANGE`, so the host calls
+//! `notify_system_theme_changed` and the current values are read back here.
+
+use serde_json::json;
+use windows::Win32::Foundation::BOOL;
+use windows::Win32::System::Registry::{HKEY_CURRENT_USER, RRF_RT_REG_DWORD, RegGetValueW};
+use windows::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW};
+use windows::Win32::UI::WindowsAndMessaging::{
+    SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+    SystemParametersInfoW,
+};
+use windows::core::{PCWSTR, w};
+
+use crate::bindings::embedder::{
+    FlutterAccessibilityFeature,
+    FlutterAccessibilityFeature_kFlutterAccessibilityFeatureDisableAnimations,
+    FlutterAccessibilityFeature_kFlutterAccessibilityFeatureHighContrast,
+    FlutterEngineResult_kSuccess,
+};
+use crate::software_renderer::api::FlutterEmbedderError;
+use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
+use crate::software_renderer::overlay::platform_message_callback::send_platform_message;
+
+pub(crate) const SETTINGS_CHANNEL: &str = "flutter/settings";
+
+/// The subset of OS settings Flutter reacts to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SystemSettings {
+    pub(crate) dark_mode: bool,
+    pub(crate) high_contrast: bool,
+    pub(crate) disable_animations: bool,
+    /// Windows "Text size" accessibility setting, 1.0 to 2.25.
+    pub(crate) text_scale_factor: f64,
+}
+
+impl Default for SystemSettings {
+    fn default() -> Self {
+        Self {
+            dark_mode: false,
+            high_contrast: false,
+            disable_animations: false,
+            text_scale_factor: 1.0,
+        }
+    }
+}
+
+impl SystemSettings {
+    /// Reads the current values. Settings that can't be read keep their defaults.
+    pub(crate) fn read() -> Self {
+        let mut settings = Self::default();
+        if let Some(light) = read_user_dword(
+            w!("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize"),
+            w!("AppsUseLightTheme"),
+        ) {
+            settings.dark_mode = light == 0;
+        }
+        if let Some(percent) =
+            read_user_dword(w!("Software\\Microsoft\\Accessibility"), w!("TextScaleFactor"))
+        {
+            settings.text_scale_factor = percent as f64 / 100.0;
+        }
+
+        let mut contrast = HIGHCONTRASTW {
+            cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+            ..Default::default()
+        };
+        if unsafe {
+            SystemParametersInfoW(
+                SPI_GETHIGHCONTRAST,
+                contrast.cbSize,
+                Some(&mut contrast as *mut _ as *mut _),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+            )
+        }
+        .is_ok()
+        {
+            settings.high_contrast = contrast.dwFlags.contains(HCF_HIGHCONTRASTON);
+        }
+
+        let mut animations = BOOL(1);
+        if unsafe {
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                Some(&mut animations as *mut _ as *mut _),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+            )
+        }
+        .is_ok()
+        {
+            settings.disable_animations = !animations.as_bool();
+        }
+        settings
+    }
+
+    /// Payload for `flutter/settings`.
+    pub(crate) fn settings_message(&self) -> Vec<u8> {
+        json!({
+            "textScaleFactor": self.text_scale_factor,
+            "alwaysUse24HourFormat": false,
+            "platformBrightness": if self.dark_mode { "dark" } else { "light" },
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    pub(crate) fn accessibility_features(&self) -> FlutterAccessibilityFeature {
+        let mut features = 0;
+        if self.high_contrast {
+            features |= FlutterAccessibilityFeature_kFlutterAccessibilityFeatureHighContrast;
+        }
+        if self.disable_animations {
+            features |= FlutterAccessibilityFeature_kFlutterAccessibilityFeatureDisableAnimations;
+        }
+        features
+    }
+}
+
+fn read_user_dword(subkey: PCWSTR, value: PCWSTR) -> Option<u32> {
+    let mut data = 0u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            subkey,
+            value,
+            RRF_RT_REG_DWORD,
+            None,
+            Some(&mut data as *mut u32 as *mut _),
+            Some(&mut size),
+        )
+    }
+    .is_ok()
+    .then_some(data)
+}
+
+/// Sends `settings` to the overlay's engine.
+pub(crate) fn push_system_settings(
+    overlay: &FlutterOverlay,
+    settings: &SystemSettings,
+) -> Result<(), FlutterEmbedderError> {
+    send_platform_message(overlay, SETTINGS_CHANNEL, &settings.settings_message())?;
+    let result = unsafe {
+        (overlay.engine_dll.FlutterEngineUpdateAccessibilityFeatures)(
+            overlay.engine.0,
+            settings.accessibility_features(),
+        )
+    };
+    if result != FlutterEngineResult_kSuccess {
+        return Err(FlutterEmbedderError::OperationFailed(format!(
+            "FlutterEngineUpdateAccessibilityFeatures failed: {result:?}"
+        )));
+    }
+    Ok(())
+}
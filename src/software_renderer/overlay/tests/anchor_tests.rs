@@ -0,0 +1,40 @@
+use crate::software_renderer::overlay::anchor::Anchor;
+
+#[test]
+fn top_left_is_origin_plus_offset() {
+    let pos = Anchor::TopLeft.position((10, 20), (100, 50), (5, 5), (800, 600));
+    assert_eq!(pos, (15, 25));
+}
+
+#[test]
+fn bottom_right_keeps_distance_to_corner_across_resize() {
+    let old = ((800, 600), (680, 530));
+    let offset = Anchor::BottomRight.offset(old.1, (100, 50), (0, 0), old.0);
+    assert_eq!(offset, (-20, -20));
+    let pos = Anchor::BottomRight.position(offset, (100, 50), (0, 0), (1920, 1080));
+    assert_eq!(pos, (1800, 1010));
+}
+
+#[test]
+fn center_stays_centered() {
+    let pos = Anchor::Center.position((0, 0), (200, 100), (0, 0), (1000, 500));
+    assert_eq!(pos, (400, 200));
+    let offset = Anchor::Center.offset((410, 190), (200, 100), (0, 0), (1000, 500));
+    assert_eq!(offset, (10, -10));
+}
+
+#[test]
+fn edge_anchors_mix_axes() {
+    let pos = Anchor::TopRight.position((0, 8), (100, 40), (0, 0), (800, 600));
+    assert_eq!(pos, (700, 8));
+    let pos = Anchor::BottomCenter.position((0, 0), (100, 40), (0, 0), (800, 600));
+    assert_eq!(pos, (350, 560));
+}
+
+#[test]
+fn full_screen_overlay_offset_is_unchanged_by_anchor() {
+    for anchor in [Anchor::TopLeft, Anchor::Center, Anchor::BottomRight] {
+        let offset = anchor.offset((30, 40), (800, 600), (0, 0), (800, 600));
+        assert_eq!(offset, (30, 40));
+    }
+}
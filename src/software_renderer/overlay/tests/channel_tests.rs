@@ -1,7 +1,11 @@
 use crate::software_renderer::overlay::platform_message_callback::{
-    FlutterChannel, mc_parse_method_call, mc_read_size,
+    FlutterChannel, PlatformMessageReplyCallback, json_method_response, mc_encode_error_envelope,
+    mc_parse_method_call, mc_read_size, on_platform_message_reply,
 };
+use serde_json::{Value, from_slice, json};
+use std::ffi::c_void;
 use std::io::Cursor;
+use std::sync::{Arc, Mutex};
 
 #[test]
 fn from_str_known_channels() {
@@ -11,6 +15,7 @@ fn from_str_known_channels() {
     assert_eq!(FlutterChannel::from_str("flutter/keyboard"), FlutterChannel::Keyboard);
     assert_eq!(FlutterChannel::from_str("flutter/keyevent"), FlutterChannel::KeyEvent);
     assert_eq!(FlutterChannel::from_str("flutter/navigation"), FlutterChannel::Navigation);
+    assert_eq!(FlutterChannel::from_str("flutter/restoration"), FlutterChannel::Restoration);
 }
 
 #[test]
@@ -78,3 +83,75 @@ fn mc_parse_method_call_with_kind_arg() {
     assert_eq!(method, "activateSystemCursor");
     assert_eq!(kind, Some("text".to_string()));
 }
+
+#[test]
+fn error_envelope_carries_code_message_and_null_details() {
+    let envelope = mc_encode_error_envelope("LOAD_FAILED", "no save");
+    let mut expected = vec![1, 7, 11];
+    expected.extend_from_slice(b"LOAD_FAILED");
+    expected.extend_from_slice(&[7, 7]);
+    expected.extend_from_slice(b"no save");
+    expected.push(0);
+    assert_eq!(envelope, expected);
+}
+
+#[test]
+fn error_envelope_uses_wide_sizes_for_long_messages() {
+    let message = "x".repeat(300);
+    let envelope = mc_encode_error_envelope("E", &message);
+    assert_eq!(&envelope[..3], &[1, 7, 1]);
+    assert_eq!(&envelope[4..8], &[7, 254, 44, 1]);
+    assert_eq!(envelope.len(), 8 + 300 + 1);
+}
+
+#[test]
+fn json_method_call_is_decoded_and_wrapped() {
+    let payload = br#"{"method":"heal","args":{"amount":5}}"#;
+    let response = json_method_response(payload, |method, args| {
+        assert_eq!(method, "heal");
+        json!(args["amount"].as_i64().unwrap() * 2)
+    });
+    assert_eq!(from_slice::<Value>(&response).unwrap(), json!([10]));
+}
+
+#[test]
+fn json_method_call_without_args_gets_null() {
+    let response = json_method_response(br#"{"method":"ping"}"#, |_, args| {
+        assert_eq!(args, Value::Null);
+        json!("pong")
+    });
+    assert_eq!(from_slice::<Value>(&response).unwrap(), json!(["pong"]));
+}
+
+#[test]
+fn malformed_json_method_call_gets_an_error_envelope() {
+    for payload in [&b"not json"[..], br#"[1, 2]"#, br#"{"args":1}"#] {
+        let response = json_method_response(payload, |_, _| panic!("handler must not run"));
+        let envelope = from_slice::<Value>(&response).unwrap();
+        assert_eq!(envelope[0], "malformed_call");
+        assert!(envelope[1].is_string());
+        assert_eq!(envelope[2], Value::Null);
+    }
+}
+
+fn deliver_reply(data: *const u8, size: usize) -> Option<Option<Vec<u8>>> {
+    let received = Arc::new(Mutex::new(None));
+    let sink = received.clone();
+    let callback: PlatformMessageReplyCallback = Box::new(move |reply| {
+        *sink.lock().unwrap() = Some(reply.map(<[u8]>::to_vec));
+    });
+    let baton = Box::into_raw(Box::new(callback)) as *mut c_void;
+    unsafe { on_platform_message_reply(data, size, baton) };
+    received.lock().unwrap().take()
+}
+
+#[test]
+fn reply_trampoline_passes_the_bytes_to_the_callback() {
+    let reply = b"pong";
+    assert_eq!(deliver_reply(reply.as_ptr(), reply.len()), Some(Some(reply.to_vec())));
+}
+
+#[test]
+fn empty_reply_reaches_the_callback_as_none() {
+    assert_eq!(deliver_reply(std::ptr::null(), 0), Some(None));
+}
@@ -0,0 +1,35 @@
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11_RESOURCE_MISC_SHARED, D3D11_RESOURCE_MISC_SHARED_KEYEDMUTEX,
+    D3D11_RESOURCE_MISC_SHARED_NTHANDLE,
+};
+
+use crate::software_renderer::overlay::d3d::{TextureShareOptions, export_misc_flags};
+
+#[test]
+fn default_sharing_uses_legacy_shared_flag() {
+    assert_eq!(
+        export_misc_flags(TextureShareOptions::default()),
+        D3D11_RESOURCE_MISC_SHARED.0 as u32
+    );
+}
+
+#[test]
+fn keyed_mutex_replaces_plain_shared_flag() {
+    let flags = export_misc_flags(TextureShareOptions {
+        nt_handle: false,
+        keyed_mutex: true,
+    });
+    assert_eq!(flags, D3D11_RESOURCE_MISC_SHARED_KEYEDMUTEX.0 as u32);
+}
+
+#[test]
+fn nt_handle_is_added_to_either_sharing_mode() {
+    for keyed_mutex in [false, true] {
+        let flags = export_misc_flags(TextureShareOptions {
+            nt_handle: true,
+            keyed_mutex,
+        });
+        assert_ne!(flags & D3D11_RESOURCE_MISC_SHARED_NTHANDLE.0 as u32, 0);
+        assert_ne!(flags & !(D3D11_RESOURCE_MISC_SHARED_NTHANDLE.0 as u32), 0);
+    }
+}
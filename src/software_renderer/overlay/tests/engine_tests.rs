@@ -0,0 +1,27 @@
+use crate::software_renderer::overlay::engine::{MAX_OVERLAY_DIMENSION, sanitize_window_metrics};
+
+#[test]
+fn valid_metrics_pass_through() {
+    assert_eq!(
+        sanitize_window_metrics(10, 20, 800, 600),
+        ((10, 20, 800, 600), false)
+    );
+}
+
+#[test]
+fn negative_origin_is_moved_to_zero() {
+    assert_eq!(
+        sanitize_window_metrics(-5, -1, 800, 600),
+        ((0, 0, 800, 600), true)
+    );
+}
+
+#[test]
+fn sizes_are_kept_within_the_texture_limit() {
+    let max = MAX_OVERLAY_DIMENSION as usize;
+    assert_eq!(sanitize_window_metrics(0, 0, 0, 600), ((0, 0, 1, 600), true));
+    assert_eq!(
+        sanitize_window_metrics(0, 0, u32::MAX, MAX_OVERLAY_DIMENSION + 1),
+        ((0, 0, max, max), true)
+    );
+}
@@ -0,0 +1,38 @@
+use std::time::{Duration, Instant};
+
+use crate::software_renderer::overlay::fade::OverlayFade;
+
+#[test]
+fn opacity_interpolates_over_duration() {
+    let start = Instant::now();
+    let fade = OverlayFade::new(1.0, 0.0, Duration::from_millis(200), start);
+    assert_eq!(fade.opacity_at(start), 1.0);
+    let halfway = fade.opacity_at(start + Duration::from_millis(100));
+    assert!((halfway - 0.5).abs() < 1e-6);
+    assert!(!fade.is_finished(start + Duration::from_millis(100)));
+    assert_eq!(fade.opacity_at(start + Duration::from_millis(500)), 0.0);
+    assert!(fade.is_finished(start + Duration::from_millis(200)));
+}
+
+#[test]
+fn zero_duration_finishes_immediately() {
+    let start = Instant::now();
+    let fade = OverlayFade::new(0.0, 1.0, Duration::ZERO, start);
+    assert!(fade.is_finished(start));
+    assert_eq!(fade.opacity_at(start), 1.0);
+}
+
+#[test]
+fn direction_follows_target() {
+    let now = Instant::now();
+    let duration = Duration::from_millis(100);
+    assert!(OverlayFade::new(0.4, 0.0, duration, now).is_fade_out());
+    assert!(!OverlayFade::new(0.4, 1.0, duration, now).is_fade_out());
+}
+
+#[test]
+fn endpoints_are_clamped() {
+    let now = Instant::now();
+    let fade = OverlayFade::new(3.0, -1.0, Duration::ZERO, now);
+    assert_eq!(fade.opacity_at(now), 0.0);
+}
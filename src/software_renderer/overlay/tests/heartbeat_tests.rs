@@ -0,0 +1,57 @@
+use std::ffi::c_void;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{Value, from_slice};
+
+use crate::software_renderer::overlay::heartbeat::{
+    HEARTBEAT_METHOD, Heartbeat, heartbeat_message, heartbeat_reply,
+};
+use crate::software_renderer::overlay::platform_message_callback::on_platform_message_reply;
+
+/// Hands `heartbeat`'s reply callback to the engine-facing trampoline the way
+/// a response handle would.
+fn reply(heartbeat: &Arc<Heartbeat>, data: &[u8]) {
+    let baton = Box::into_raw(Box::new(heartbeat_reply(heartbeat))) as *mut c_void;
+    unsafe { on_platform_message_reply(data.as_ptr(), data.len(), baton) };
+}
+
+#[test]
+fn wait_times_out_without_reply() {
+    let heartbeat = Heartbeat::default();
+    assert!(!heartbeat.wait(Duration::from_millis(10)));
+}
+
+#[test]
+fn wait_returns_once_answered_from_another_thread() {
+    let heartbeat = Arc::new(Heartbeat::default());
+    let replier = heartbeat.clone();
+    let handle = thread::spawn(move || replier.mark_answered());
+    assert!(heartbeat.wait(Duration::from_secs(5)));
+    handle.join().unwrap();
+}
+
+#[test]
+fn reply_callback_marks_answered_and_drops_its_reference() {
+    let heartbeat = Arc::new(Heartbeat::default());
+    // The navigation handler answers an unknown method with a `null` result.
+    reply(&heartbeat, b"[null]");
+    assert!(heartbeat.is_answered());
+    assert!(heartbeat.wait(Duration::ZERO));
+    assert_eq!(Arc::strong_count(&heartbeat), 1);
+}
+
+#[test]
+fn reply_callback_accepts_an_empty_reply() {
+    let heartbeat = Arc::new(Heartbeat::default());
+    reply(&heartbeat, &[]);
+    assert!(heartbeat.is_answered());
+}
+
+#[test]
+fn heartbeat_is_a_json_method_call() {
+    let call: Value = from_slice(&heartbeat_message()).unwrap();
+    assert_eq!(call["method"], HEARTBEAT_METHOD);
+    assert!(call["args"].is_null());
+}
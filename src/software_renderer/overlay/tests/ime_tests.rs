@@ -0,0 +1,46 @@
+use serde_json::{Value, json};
+
+use crate::software_renderer::overlay::ime::CaretGeometry;
+
+fn rect(x: f64, y: f64, width: f64, height: f64) -> Value {
+    json!({ "x": x, "y": y, "width": width, "height": height })
+}
+
+#[test]
+fn no_rect_until_flutter_reports_one() {
+    assert_eq!(CaretGeometry::default().view_rect(), None);
+}
+
+#[test]
+fn caret_rect_is_moved_by_the_field_transform() {
+    let mut geometry = CaretGeometry::default();
+    let mut transform = [0.0; 16];
+    for i in [0, 5, 10, 15] {
+        transform[i] = 1.0;
+    }
+    transform[12] = 100.0;
+    transform[13] = 50.0;
+    let args = json!({ "width": 300.0, "height": 40.0, "transform": transform });
+    geometry.apply("TextInput.setEditableSizeAndTransform", Some(&args));
+    geometry.apply("TextInput.setCaretRect", Some(&rect(10.0, 4.0, 2.0, 20.0)));
+    assert_eq!(geometry.view_rect(), Some((110.0, 54.0, 112.0, 74.0)));
+}
+
+#[test]
+fn marked_text_rect_wins_over_the_caret() {
+    let mut geometry = CaretGeometry::default();
+    geometry.apply("TextInput.setCaretRect", Some(&rect(10.0, 0.0, 2.0, 20.0)));
+    geometry.apply("TextInput.setMarkedTextRect", Some(&rect(4.0, 0.0, 30.0, 20.0)));
+    assert_eq!(geometry.view_rect(), Some((4.0, 0.0, 34.0, 20.0)));
+
+    geometry.apply("TextInput.setMarkedTextRect", Some(&rect(-1.0, -1.0, -1.0, -1.0)));
+    assert_eq!(geometry.view_rect(), Some((10.0, 0.0, 12.0, 20.0)));
+}
+
+#[test]
+fn set_client_forgets_the_previous_field() {
+    let mut geometry = CaretGeometry::default();
+    geometry.apply("TextInput.setCaretRect", Some(&rect(10.0, 0.0, 2.0, 20.0)));
+    geometry.apply("TextInput.setClient", Some(&json!([2, {}])));
+    assert_eq!(geometry.view_rect(), None);
+}
@@ -0,0 +1,83 @@
+use crate::software_renderer::overlay::input::{
+    InputTransform, ScrollConfig, scaled_cursor_size, unrotate_about, unscale_about,
+};
+
+#[test]
+fn default_transform_is_identity() {
+    assert_eq!(InputTransform::default().client_to_overlay(12.0, 34.0), (12.0, 34.0));
+}
+
+#[test]
+fn offset_and_scale_map_back_into_overlay() {
+    let transform = InputTransform {
+        offset_x: 320.0,
+        offset_y: 180.0,
+        scale: 2.0,
+    };
+    assert_eq!(transform.client_to_overlay(320.0, 180.0), (0.0, 0.0));
+    assert_eq!(transform.client_to_overlay(420.0, 280.0), (50.0, 50.0));
+    assert_eq!(transform.client_to_overlay(300.0, 170.0), (-10.0, -5.0));
+}
+
+#[test]
+fn cursor_size_follows_scale_and_stays_positive() {
+    assert_eq!(scaled_cursor_size(32, 1.0), 32);
+    assert_eq!(scaled_cursor_size(32, 0.5), 16);
+    assert_eq!(scaled_cursor_size(32, 1.5), 48);
+    assert_eq!(scaled_cursor_size(32, 0.001), 1);
+}
+
+#[test]
+fn default_scroll_config_keeps_twenty_pixels_per_notch() {
+    let config = ScrollConfig::default();
+    assert_eq!(config.scroll_delta(120), -20.0);
+    assert_eq!(config.scroll_delta(-240), 40.0);
+}
+
+#[test]
+fn scroll_config_scales_and_inverts() {
+    let config = ScrollConfig {
+        pixels_per_notch: 8.0,
+        invert: true,
+    };
+    assert_eq!(config.scroll_delta(120), 8.0);
+    assert_eq!(config.scroll_delta(60), 4.0);
+}
+
+#[test]
+fn horizontal_wheel_right_scrolls_right() {
+    let config = ScrollConfig::default();
+    assert_eq!(config.horizontal_scroll_delta(120), 20.0);
+    assert_eq!(config.horizontal_scroll_delta(-60), -10.0);
+
+    let inverted = ScrollConfig {
+        invert: true,
+        ..ScrollConfig::default()
+    };
+    assert_eq!(inverted.horizontal_scroll_delta(120), -20.0);
+}
+
+#[test]
+fn render_scale_maps_about_the_overlay_origin() {
+    assert_eq!(unscale_about((100, 50), 1.0, 130.0, 70.0), (130.0, 70.0));
+    assert_eq!(unscale_about((100, 50), 0.5, 100.0, 50.0), (100.0, 50.0));
+    assert_eq!(unscale_about((100, 50), 0.5, 130.0, 70.0), (160.0, 90.0));
+    assert_eq!(unscale_about((0, 0), 2.0, 40.0, 10.0), (20.0, 5.0));
+}
+
+fn assert_near(actual: (f64, f64), expected: (f64, f64)) {
+    assert!(
+        (actual.0 - expected.0).abs() < 1e-9 && (actual.1 - expected.1).abs() < 1e-9,
+        "{actual:?} != {expected:?}"
+    );
+}
+
+#[test]
+fn rotation_is_undone_around_the_center() {
+    let center = (100.0, 50.0);
+    assert_near(unrotate_about(center, 0.0, 130.0, 70.0), (130.0, 70.0));
+    assert_near(unrotate_about(center, 45.0, 100.0, 50.0), (100.0, 50.0));
+    // A quarter turn clockwise moves the point left of the center above it.
+    assert_near(unrotate_about(center, 90.0, 100.0, 20.0), (70.0, 50.0));
+    assert_near(unrotate_about(center, 180.0, 130.0, 50.0), (70.0, 50.0));
+}
@@ -0,0 +1,20 @@
+use crate::software_renderer::overlay::lifecycle::LifecycleState;
+
+#[test]
+fn lifecycle_messages_match_the_dart_enum() {
+    let cases = [
+        (LifecycleState::Resumed, "AppLifecycleState.resumed"),
+        (LifecycleState::Inactive, "AppLifecycleState.inactive"),
+        (LifecycleState::Hidden, "AppLifecycleState.hidden"),
+        (LifecycleState::Paused, "AppLifecycleState.paused"),
+        (LifecycleState::Detached, "AppLifecycleState.detached"),
+    ];
+    for (state, message) in cases {
+        assert_eq!(state.as_message(), message);
+    }
+}
+
+#[test]
+fn overlays_start_resumed() {
+    assert_eq!(LifecycleState::default(), LifecycleState::Resumed);
+}
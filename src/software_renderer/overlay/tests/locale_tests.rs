@@ -0,0 +1,33 @@
+use std::ffi::CStr;
+
+use crate::software_renderer::overlay::locale::{Locale, LocaleStrings};
+
+fn read(ptr: *const std::os::raw::c_char) -> Option<String> {
+    (!ptr.is_null()).then(|| unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned())
+}
+
+#[test]
+fn locale_subtags_become_c_strings() {
+    let locale = Locale::new("zh").with_country("TW").with_script("Hant");
+    let strings = LocaleStrings::new(&locale).unwrap();
+    let flutter = strings.as_flutter_locale();
+    assert_eq!(flutter.struct_size, std::mem::size_of_val(&flutter));
+    assert_eq!(read(flutter.language_code).as_deref(), Some("zh"));
+    assert_eq!(read(flutter.country_code).as_deref(), Some("TW"));
+    assert_eq!(read(flutter.script_code).as_deref(), Some("Hant"));
+    assert!(flutter.variant_code.is_null());
+}
+
+#[test]
+fn missing_subtags_are_null() {
+    let strings = LocaleStrings::new(&Locale::new("en")).unwrap();
+    let flutter = strings.as_flutter_locale();
+    assert!(flutter.country_code.is_null());
+    assert!(flutter.script_code.is_null());
+}
+
+#[test]
+fn empty_language_and_nul_bytes_are_rejected() {
+    assert!(LocaleStrings::new(&Locale::new("")).is_err());
+    assert!(LocaleStrings::new(&Locale::new("en").with_country("U\0S")).is_err());
+}
@@ -1,4 +1,18 @@
+mod anchor_tests;
 mod channel_tests;
+mod d3d_tests;
+mod engine_tests;
+mod fade_tests;
+mod heartbeat_tests;
+mod ime_tests;
+mod input_tests;
+mod lifecycle_tests;
+mod locale_tests;
 mod project_args_tests;
+mod restoration_tests;
 mod semantics_tests;
+mod snapshot_tests;
+mod system_settings_tests;
 mod textinput_tests;
+mod touch_tests;
+mod trackpad_tests;
@@ -1,5 +1,7 @@
+use crate::software_renderer::api::RuntimeMode;
 use crate::software_renderer::overlay::project_args::{
-    build_project_args_and_strings, maybe_load_aot_path_to_cstring,
+    build_project_args_and_strings, engine_args_with_runtime_mode, maybe_load_aot_path_to_cstring,
+    parse_vm_service_uri,
 };
 use std::ffi::{CString, OsStr};
 
@@ -41,3 +43,39 @@ fn aot_path_some() {
     let result = maybe_load_aot_path_to_cstring(Some(p)).unwrap();
     assert_eq!(result, CString::new("/path/app.so").unwrap());
 }
+
+#[test]
+fn runtime_mode_none_keeps_user_args() {
+    let user = vec!["--trace".to_string()];
+    assert_eq!(engine_args_with_runtime_mode(Some(&user), None), Some(user.clone()));
+    assert_eq!(engine_args_with_runtime_mode(None, None), None);
+    assert_eq!(engine_args_with_runtime_mode(None, Some(RuntimeMode::Release)), None);
+}
+
+#[test]
+fn profile_mode_adds_switches_after_argv0() {
+    let args = engine_args_with_runtime_mode(None, Some(RuntimeMode::Profile)).unwrap();
+    assert_eq!(args.len(), 3);
+    assert_eq!(&args[1..], RuntimeMode::Profile.engine_switches());
+}
+
+#[test]
+fn profile_mode_does_not_duplicate_user_switches() {
+    let user = vec!["app".to_string(), "--endless-trace-buffer".to_string()];
+    let args = engine_args_with_runtime_mode(Some(&user), Some(RuntimeMode::Profile)).unwrap();
+    assert_eq!(args, ["app", "--endless-trace-buffer", "--enable-dart-profiling"]);
+}
+
+#[test]
+fn parses_vm_service_uri_from_log_lines() {
+    assert_eq!(
+        parse_vm_service_uri("The Dart VM service is listening on http://127.0.0.1:50300/aB3d=/"),
+        Some("http://127.0.0.1:50300/aB3d=/")
+    );
+    assert_eq!(
+        parse_vm_service_uri("Observatory listening on http://127.0.0.1:8181/"),
+        Some("http://127.0.0.1:8181/")
+    );
+    assert_eq!(parse_vm_service_uri("flutter: listening on port 8181"), None);
+    assert_eq!(parse_vm_service_uri("frame rendered"), None);
+}
@@ -0,0 +1,63 @@
+use std::sync::Mutex;
+
+use crate::software_renderer::overlay::restoration::{
+    RestorationCall, encode_get_response, encode_push_call, handle_restoration_call,
+    parse_restoration_call,
+};
+
+fn method_call(name: &str, args: &[u8]) -> Vec<u8> {
+    let mut out = vec![7, name.len() as u8];
+    out.extend_from_slice(name.as_bytes());
+    out.extend_from_slice(args);
+    out
+}
+
+#[test]
+fn parses_get_and_put() {
+    assert_eq!(parse_restoration_call(&method_call("get", &[0])).unwrap(), RestorationCall::Get);
+    assert_eq!(
+        parse_restoration_call(&method_call("put", &[8, 3, 1, 2, 3])).unwrap(),
+        RestorationCall::Put(vec![1, 2, 3])
+    );
+    assert_eq!(
+        parse_restoration_call(&method_call("other", &[0])).unwrap(),
+        RestorationCall::Other("other".to_string())
+    );
+}
+
+#[test]
+fn get_response_without_data_is_enabled_with_null_data() {
+    let mut expected = vec![0, 13, 2, 7, 7];
+    expected.extend_from_slice(b"enabled");
+    expected.extend_from_slice(&[1, 7, 4]);
+    expected.extend_from_slice(b"data");
+    expected.push(0);
+    assert_eq!(encode_get_response(None), expected);
+}
+
+#[test]
+fn put_is_served_by_next_get() {
+    let store = Mutex::new(None);
+    let ack = handle_restoration_call(&method_call("put", &[8, 2, 9, 8]), &store).unwrap();
+    assert_eq!(ack, [0, 0]);
+    assert_eq!(store.lock().unwrap().as_deref(), Some(&[9u8, 8][..]));
+
+    let response = handle_restoration_call(&method_call("get", &[0]), &store).unwrap();
+    assert_eq!(response, encode_get_response(Some(&[9, 8])));
+    assert!(response.ends_with(&[8, 2, 9, 8]));
+}
+
+#[test]
+fn large_blobs_use_extended_size() {
+    let data = vec![0xAB; 300];
+    let push = encode_push_call(&data);
+    let tail = &push[push.len() - 304..];
+    assert_eq!(&tail[..4], &[8, 254, 0x2C, 0x01]);
+    assert_eq!(&tail[4..], &data[..]);
+}
+
+#[test]
+fn unknown_methods_are_not_answered() {
+    let store = Mutex::new(None);
+    assert!(handle_restoration_call(&method_call("push", &[0]), &store).is_none());
+}
@@ -1,11 +1,15 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
 use crate::bindings::embedder::{
     FlutterSemanticsFlag_kFlutterSemanticsFlagHasCheckedState,
     FlutterSemanticsFlag_kFlutterSemanticsFlagIsButton,
     FlutterSemanticsFlag_kFlutterSemanticsFlagIsChecked,
-    FlutterSemanticsFlag_kFlutterSemanticsFlagIsTextField,
+    FlutterSemanticsFlag_kFlutterSemanticsFlagIsTextField, FlutterRect, FlutterTransformation,
 };
 use crate::software_renderer::overlay::semantics_handler::{
-    RustSemanticsFlag, ffi_flags_to_rust_set,
+    HitTestMode, HoverDebounce, ProcessedSemanticsNode, RustSemanticsFlag, SemanticsThrottle,
+    ffi_flags_to_rust_set,
 };
 
 #[test]
@@ -37,3 +41,101 @@ fn textfield_flag() {
     assert!(set.contains(&RustSemanticsFlag::IsTextField));
     assert!(!set.contains(&RustSemanticsFlag::IsButton));
 }
+
+#[test]
+fn hover_debounce_off_follows_every_sample() {
+    let mut debounce = HoverDebounce::default();
+    assert!(debounce.sample(false, true));
+    assert!(!debounce.sample(true, false));
+}
+
+#[test]
+fn hover_debounce_waits_for_a_stable_change() {
+    let mut debounce = HoverDebounce::new(2);
+    let mut hovered = false;
+    for _ in 0..2 {
+        hovered = debounce.sample(hovered, true);
+        assert!(!hovered);
+    }
+    hovered = debounce.sample(hovered, true);
+    assert!(hovered);
+
+    // A single frame of jitter resets the streak.
+    hovered = debounce.sample(hovered, false);
+    hovered = debounce.sample(hovered, true);
+    hovered = debounce.sample(hovered, false);
+    hovered = debounce.sample(hovered, false);
+    assert!(hovered);
+    assert!(!debounce.sample(hovered, false));
+}
+
+/// A tree of `nodes` empty nodes; only the node count matters to the throttle tests.
+fn tree(nodes: i32) -> HashMap<i32, ProcessedSemanticsNode> {
+    (0..nodes)
+        .map(|id| {
+            let node = ProcessedSemanticsNode {
+                id,
+                flags: HashSet::new(),
+                rect: FlutterRect {
+                    left: 0.0,
+                    top: 0.0,
+                    right: 0.0,
+                    bottom: 0.0,
+                },
+                transform_to_parent: FlutterTransformation {
+                    scaleX: 1.0,
+                    skewX: 0.0,
+                    transX: 0.0,
+                    skewY: 0.0,
+                    scaleY: 1.0,
+                    transY: 0.0,
+                    pers0: 0.0,
+                    pers1: 0.0,
+                    pers2: 1.0,
+                },
+                children_in_hit_test_order: Vec::new(),
+                label: String::new(),
+            };
+            (id, node)
+        })
+        .collect()
+}
+
+#[test]
+fn semantics_throttle_off_applies_every_update() {
+    let mut throttle = SemanticsThrottle::default();
+    let now = Instant::now();
+    assert_eq!(throttle.offer(tree(1), now).map(|t| t.len()), Some(1));
+    assert_eq!(throttle.offer(tree(2), now).map(|t| t.len()), Some(2));
+    assert!(throttle.take_due(now).is_none());
+}
+
+#[test]
+fn semantics_throttle_keeps_the_latest_update() {
+    let mut throttle = SemanticsThrottle::new(Duration::from_millis(100));
+    let start = Instant::now();
+    assert!(throttle.offer(tree(1), start).is_some());
+
+    let early = start + Duration::from_millis(30);
+    assert!(throttle.offer(tree(2), early).is_none());
+    assert!(throttle.offer(tree(3), early).is_none());
+    assert!(throttle.take_due(early).is_none());
+
+    let due = start + Duration::from_millis(100);
+    assert_eq!(throttle.take_due(due).map(|t| t.len()), Some(3));
+    assert!(throttle.take_due(due + Duration::from_secs(1)).is_none());
+}
+
+#[test]
+fn semantics_only_mode_ignores_the_bounds() {
+    let mode = HitTestMode::default();
+    assert!(mode.claims(true, || panic!("bounds must not be checked")));
+    assert!(!mode.claims(false, || panic!("bounds must not be checked")));
+}
+
+#[test]
+fn opaque_mode_claims_anything_in_bounds() {
+    assert!(HitTestMode::Opaque.claims(false, || true));
+    assert!(HitTestMode::Opaque.claims(true, || false));
+    assert!(!HitTestMode::Opaque.claims(false, || false));
+}
@@ -0,0 +1,60 @@
+use image::open;
+
+use crate::software_renderer::api::AlphaMode;
+use crate::software_renderer::overlay::snapshot::{
+    bgra_rows_to_rgba, pack_bgra_rows, save_rgba_png,
+};
+
+#[test]
+fn swaps_channels_and_drops_row_padding() {
+    // 2x2 BGRA with 4 bytes of padding after each row.
+    let data = [
+        1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, //
+        9, 10, 11, 12, 13, 14, 15, 16, 0, 0, 0, 0,
+    ];
+    let rgba = bgra_rows_to_rgba(&data, 2, 2, 12).unwrap();
+    assert_eq!(
+        rgba,
+        [3, 2, 1, 4, 7, 6, 5, 8, 11, 10, 9, 12, 15, 14, 13, 16]
+    );
+}
+
+#[test]
+fn last_row_needs_no_padding() {
+    let data = [1, 2, 3, 4, 0, 0, 0, 0, 5, 6, 7, 8];
+    assert_eq!(
+        bgra_rows_to_rgba(&data, 1, 2, 8).unwrap(),
+        [3, 2, 1, 4, 7, 6, 5, 8]
+    );
+}
+
+#[test]
+fn rejects_short_buffers_and_pitches() {
+    assert!(bgra_rows_to_rgba(&[0; 7], 2, 1, 8).is_none());
+    assert!(bgra_rows_to_rgba(&[0; 16], 2, 2, 4).is_none());
+    assert_eq!(bgra_rows_to_rgba(&[], 4, 0, 16), Some(Vec::new()));
+}
+
+#[test]
+fn packing_keeps_bgra_order_and_drops_padding() {
+    let data = [
+        1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, //
+        9, 10, 11, 12, 13, 14, 15, 16, 0, 0, 0, 0,
+    ];
+    assert_eq!(
+        pack_bgra_rows(&data, 2, 2, 12).unwrap(),
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]
+    );
+    assert!(pack_bgra_rows(&data, 2, 2, 4).is_none());
+}
+
+#[test]
+fn png_stores_straight_alpha() {
+    let path = std::env::temp_dir().join("snapshot_test_straight_alpha.png");
+    // An opaque pixel and a half-transparent one, premultiplied.
+    let rgba = vec![10, 20, 30, 255, 64, 32, 0, 128];
+    save_rgba_png(&path, (2, 1), rgba, AlphaMode::Premultiplied, AlphaMode::Straight).unwrap();
+    let saved = open(&path).unwrap().into_rgba8().into_raw();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(saved, [10, 20, 30, 255, 128, 64, 0, 128]);
+}
@@ -0,0 +1,46 @@
+use crate::bindings::embedder::{
+    FlutterAccessibilityFeature_kFlutterAccessibilityFeatureDisableAnimations,
+    FlutterAccessibilityFeature_kFlutterAccessibilityFeatureHighContrast,
+};
+use crate::software_renderer::overlay::system_settings::SystemSettings;
+use serde_json::{Value, from_slice, json};
+
+#[test]
+fn settings_message_carries_brightness_and_text_scale() {
+    let settings = SystemSettings {
+        dark_mode: true,
+        text_scale_factor: 1.25,
+        ..Default::default()
+    };
+    let message: Value = from_slice(&settings.settings_message()).unwrap();
+    assert_eq!(
+        message,
+        json!({
+            "textScaleFactor": 1.25,
+            "alwaysUse24HourFormat": false,
+            "platformBrightness": "dark",
+        })
+    );
+}
+
+#[test]
+fn default_settings_are_light_without_features() {
+    let settings = SystemSettings::default();
+    let message: Value = from_slice(&settings.settings_message()).unwrap();
+    assert_eq!(message["platformBrightness"], "light");
+    assert_eq!(settings.accessibility_features(), 0);
+}
+
+#[test]
+fn accessibility_features_combine() {
+    let settings = SystemSettings {
+        high_contrast: true,
+        disable_animations: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        settings.accessibility_features(),
+        FlutterAccessibilityFeature_kFlutterAccessibilityFeatureHighContrast
+            | FlutterAccessibilityFeature_kFlutterAccessibilityFeatureDisableAnimations
+    );
+}
@@ -1,5 +1,6 @@
 use crate::software_renderer::overlay::textinput::{
-    ActiveTextInputState, TextInputModel, apply_text_input_method,
+    ActiveTextInputState, EditingState, TextInputModel, ViewKeyboardState,
+    apply_text_input_method,
 };
 use serde_json::json;
 
@@ -275,3 +276,81 @@ fn apply_set_editing_state_utf16_to_utf8() {
     let st = slot.unwrap();
     assert_eq!(st.model.selection_base_utf8, 5);
 }
+
+#[test]
+fn editing_state_reports_utf16_selection() {
+    let m = model_with("a😀b", 1, 5);
+    assert_eq!(
+        m.editing_state(),
+        EditingState {
+            text: "a😀b".to_string(),
+            selection_base: 1,
+            selection_extent: 3,
+            composing: None,
+        }
+    );
+}
+
+#[test]
+fn take_pressed_keys_returns_logical_keys_and_clears() {
+    let state = ViewKeyboardState::new();
+    {
+        let mut pressed = state.pressed_keys.lock().unwrap();
+        pressed.insert(0x7_00e2, 0x2_0000_0104);
+        pressed.insert(0x7_0004, 0x61);
+    }
+    let mut released = state.take_pressed_keys();
+    released.sort();
+    assert_eq!(released, vec![(0x7_0004, 0x61), (0x7_00e2, 0x2_0000_0104)]);
+    assert!(state.take_pressed_keys().is_empty());
+}
+
+#[test]
+fn composing_text_replaces_the_selection_and_is_marked() {
+    let mut m = model_with("ab", 1, 1);
+    m.set_composing_text("に", None);
+    m.set_composing_text("日本", Some(1));
+    assert_eq!(m.text, "a日本b");
+    assert_eq!(m.composing_utf8, Some((1, 7)));
+    assert_eq!(m.selection_base_utf8, 4);
+    assert_eq!(m.editing_state().composing, Some((1, 3)));
+}
+
+#[test]
+fn committing_replaces_the_composing_text_and_ends_it() {
+    let mut m = model_with("ab", 1, 1);
+    m.set_composing_text("にほん", None);
+    m.commit_composing("日本");
+    assert_eq!(m.text, "a日本b");
+    assert_eq!(m.composing_utf8, None);
+    assert_eq!(m.selection_base_utf8, 7);
+    assert_eq!(m.selection_extent_utf8, 7);
+}
+
+#[test]
+fn empty_composition_removes_the_composing_text() {
+    let mut m = model_with("ab", 2, 2);
+    m.set_composing_text("ㅎ", None);
+    m.set_composing_text("", None);
+    assert_eq!(m.text, "ab");
+    assert_eq!(m.composing_utf8, None);
+    assert_eq!(m.selection_base_utf8, 2);
+}
+
+#[test]
+fn apply_set_editing_state_reads_composing_range() {
+    let mut slot = Some(ActiveTextInputState {
+        client_id: 1,
+        input_action: "x".to_string(),
+        model: TextInputModel::new(),
+    });
+    let args = json!({
+        "text": "a日本",
+        "selectionBase": 3,
+        "selectionExtent": 3,
+        "composingBase": 1,
+        "composingExtent": 3,
+    });
+    apply_text_input_method("TextInput.setEditingState", Some(&args), &mut slot);
+    assert_eq!(slot.unwrap().model.composing_utf8, Some((1, 7)));
+}
@@ -0,0 +1,111 @@
+use crate::bindings::embedder::{
+    FlutterPointerDeviceKind, FlutterPointerDeviceKind_kFlutterPointerDeviceKindStylus,
+    FlutterPointerDeviceKind_kFlutterPointerDeviceKindTouch, FlutterPointerPhase,
+    FlutterPointerPhase_kAdd, FlutterPointerPhase_kCancel, FlutterPointerPhase_kDown,
+    FlutterPointerPhase_kHover, FlutterPointerPhase_kMove, FlutterPointerPhase_kRemove,
+    FlutterPointerPhase_kUp,
+};
+use crate::software_renderer::overlay::touch::{
+    ContactEvent, FIRST_CONTACT_DEVICE_ID, TouchContacts,
+};
+
+const TOUCH: FlutterPointerDeviceKind = FlutterPointerDeviceKind_kFlutterPointerDeviceKindTouch;
+const PEN: FlutterPointerDeviceKind = FlutterPointerDeviceKind_kFlutterPointerDeviceKindStylus;
+
+const LIFT: ContactEvent = ContactEvent::Up {
+    canceled: false,
+    in_range: false,
+};
+
+fn send(
+    contacts: &mut TouchContacts,
+    pointer_id: u32,
+    kind: FlutterPointerDeviceKind,
+    event: ContactEvent,
+) -> (i32, Vec<FlutterPointerPhase>) {
+    contacts.on_event(pointer_id, kind, event, (10.0, 20.0))
+}
+
+#[test]
+fn a_tap_adds_presses_releases_and_removes_the_contact() {
+    let mut contacts = TouchContacts::default();
+    let (device, down) = send(&mut contacts, 7, TOUCH, ContactEvent::Down);
+    assert_eq!(device, FIRST_CONTACT_DEVICE_ID);
+    assert_eq!(down, [FlutterPointerPhase_kAdd, FlutterPointerPhase_kDown]);
+
+    let moved = send(&mut contacts, 7, TOUCH, ContactEvent::Update { in_contact: true }).1;
+    assert_eq!(moved, [FlutterPointerPhase_kMove]);
+
+    let up = send(&mut contacts, 7, TOUCH, LIFT).1;
+    assert_eq!(up, [FlutterPointerPhase_kUp, FlutterPointerPhase_kRemove]);
+    assert!(contacts.cancel_all().is_empty());
+}
+
+#[test]
+fn simultaneous_contacts_get_distinct_devices_and_reuse_freed_ones() {
+    let mut contacts = TouchContacts::default();
+    let first = send(&mut contacts, 100, TOUCH, ContactEvent::Down).0;
+    let second = send(&mut contacts, 101, TOUCH, ContactEvent::Down).0;
+    assert_eq!((first, second), (FIRST_CONTACT_DEVICE_ID, FIRST_CONTACT_DEVICE_ID + 1));
+
+    send(&mut contacts, 100, TOUCH, LIFT);
+    let third = send(&mut contacts, 102, TOUCH, ContactEvent::Down).0;
+    assert_eq!(third, FIRST_CONTACT_DEVICE_ID);
+    let moved = send(&mut contacts, 101, TOUCH, ContactEvent::Update { in_contact: true });
+    assert_eq!(moved.0, second);
+}
+
+#[test]
+fn a_canceled_up_sends_cancel() {
+    let mut contacts = TouchContacts::default();
+    send(&mut contacts, 1, TOUCH, ContactEvent::Down);
+    let up = ContactEvent::Up {
+        canceled: true,
+        in_range: false,
+    };
+    let phases = send(&mut contacts, 1, TOUCH, up).1;
+    assert_eq!(phases, [FlutterPointerPhase_kCancel, FlutterPointerPhase_kRemove]);
+}
+
+#[test]
+fn a_pen_hovers_until_it_leaves() {
+    let mut contacts = TouchContacts::default();
+    let hover = ContactEvent::Update { in_contact: false };
+    let phases = send(&mut contacts, 3, PEN, hover).1;
+    assert_eq!(phases, [FlutterPointerPhase_kAdd, FlutterPointerPhase_kHover]);
+
+    send(&mut contacts, 3, PEN, ContactEvent::Down);
+    let lift = ContactEvent::Up {
+        canceled: false,
+        in_range: true,
+    };
+    assert_eq!(send(&mut contacts, 3, PEN, lift).1, [FlutterPointerPhase_kUp]);
+    let left = send(&mut contacts, 3, PEN, ContactEvent::Leave).1;
+    assert_eq!(left, [FlutterPointerPhase_kRemove]);
+}
+
+#[test]
+fn leaving_an_unknown_pointer_sends_nothing() {
+    let mut contacts = TouchContacts::default();
+    assert!(send(&mut contacts, 9, TOUCH, ContactEvent::Leave).1.is_empty());
+}
+
+#[test]
+fn cancel_all_closes_contacts_at_their_last_position() {
+    let mut contacts = TouchContacts::default();
+    send(&mut contacts, 1, PEN, ContactEvent::Down);
+    let summary: Vec<_> = contacts
+        .cancel_all()
+        .into_iter()
+        .map(|(contact, phase)| (contact.device, contact.device_kind, phase, contact.position))
+        .collect();
+    let device = FIRST_CONTACT_DEVICE_ID;
+    assert_eq!(
+        summary,
+        [
+            (device, PEN, FlutterPointerPhase_kCancel, (10.0, 20.0)),
+            (device, PEN, FlutterPointerPhase_kRemove, (10.0, 20.0)),
+        ]
+    );
+    assert!(contacts.cancel_all().is_empty());
+}
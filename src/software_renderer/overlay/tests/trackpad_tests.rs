@@ -0,0 +1,107 @@
+use std::time::{Duration, Instant};
+
+use crate::software_renderer::overlay::trackpad::{
+    GESTURE_IDLE_TIMEOUT, PINCH_SCALE_PER_NOTCH, PIXELS_PER_NOTCH, TouchGesture,
+    TrackpadScrollState, rotate_angle_from_argument, wheel_delta_to_pixels,
+};
+
+#[test]
+fn full_notch_matches_legacy_wheel_step() {
+    assert_eq!(wheel_delta_to_pixels(120), PIXELS_PER_NOTCH);
+    assert_eq!(wheel_delta_to_pixels(-120), -PIXELS_PER_NOTCH);
+}
+
+#[test]
+fn sub_notch_deltas_stay_fractional() {
+    assert_eq!(wheel_delta_to_pixels(30), PIXELS_PER_NOTCH / 4.0);
+    assert!((wheel_delta_to_pixels(1) - PIXELS_PER_NOTCH / 120.0).abs() < 1e-12);
+}
+
+#[test]
+fn first_delta_starts_gesture_and_later_ones_accumulate() {
+    let mut state = TrackpadScrollState::default();
+    let now = Instant::now();
+    let first = state.on_wheel(30, true, (10.0, 20.0), now);
+    assert!(first.starts_gesture);
+    assert_eq!(first.pan_y, 5.0);
+
+    let second = state.on_wheel(30, true, (50.0, 60.0), now);
+    assert!(!second.starts_gesture);
+    assert_eq!(second.pan_y, 10.0);
+    assert_eq!(second.pan_x, 0.0);
+}
+
+#[test]
+fn horizontal_wheel_pans_opposite_to_delta() {
+    let mut state = TrackpadScrollState::default();
+    let update = state.on_wheel(60, false, (0.0, 0.0), Instant::now());
+    assert_eq!(update.pan_x, -10.0);
+    assert_eq!(update.pan_y, 0.0);
+}
+
+#[test]
+fn gesture_ends_at_start_position_after_idle_timeout() {
+    let mut state = TrackpadScrollState::default();
+    let start = Instant::now();
+    state.on_wheel(30, true, (10.0, 20.0), start);
+
+    assert_eq!(state.take_end(start + Duration::from_millis(1), false), None);
+    assert_eq!(state.take_end(start + GESTURE_IDLE_TIMEOUT, false), Some((10.0, 20.0)));
+    assert_eq!(state.take_end(start + GESTURE_IDLE_TIMEOUT * 2, false), None);
+}
+
+#[test]
+fn forced_end_closes_immediately_and_next_delta_restarts() {
+    let mut state = TrackpadScrollState::default();
+    let now = Instant::now();
+    assert_eq!(state.take_end(now, true), None);
+
+    state.on_wheel(30, true, (1.0, 2.0), now);
+    assert_eq!(state.take_end(now, true), Some((1.0, 2.0)));
+
+    let restarted = state.on_wheel(30, true, (3.0, 4.0), now);
+    assert!(restarted.starts_gesture);
+    assert_eq!(restarted.pan_y, 5.0);
+}
+
+#[test]
+fn pinch_multiplies_scale_and_keeps_pan() {
+    let mut state = TrackpadScrollState::default();
+    let now = Instant::now();
+    assert_eq!(state.on_wheel(30, true, (0.0, 0.0), now).scale, 1.0);
+
+    let zoomed = state.on_pinch(120, (0.0, 0.0), now);
+    assert!(!zoomed.starts_gesture);
+    assert_eq!(zoomed.pan_y, 5.0);
+    assert!((zoomed.scale - PINCH_SCALE_PER_NOTCH).abs() < 1e-12);
+
+    let back = state.on_pinch(-120, (0.0, 0.0), now);
+    assert!((back.scale - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn zoom_gesture_scales_relative_to_begin_distance() {
+    let mut state = TrackpadScrollState::default();
+    let now = Instant::now();
+    let begin = state.on_gesture(TouchGesture::Zoom { distance: 100.0 }, true, (5.0, 5.0), now);
+    assert!(begin.starts_gesture);
+    assert_eq!(begin.scale, 1.0);
+
+    let update = state.on_gesture(TouchGesture::Zoom { distance: 150.0 }, false, (5.0, 5.0), now);
+    assert_eq!(update.scale, 1.5);
+}
+
+#[test]
+fn rotate_gesture_is_reported_clockwise() {
+    let mut state = TrackpadScrollState::default();
+    let now = Instant::now();
+    state.on_gesture(TouchGesture::Rotate { angle: 0.0 }, true, (0.0, 0.0), now);
+    let update = state.on_gesture(TouchGesture::Rotate { angle: 0.5 }, false, (0.0, 0.0), now);
+    assert_eq!(update.rotation, -0.5);
+}
+
+#[test]
+fn rotate_argument_midpoint_is_zero() {
+    assert!(rotate_angle_from_argument(0x7FFF).abs() < 1e-3);
+    assert!((rotate_angle_from_argument(0) + 2.0 * std::f64::consts::PI).abs() < 1e-12);
+}
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::ffi::{CStr, c_void};
 use std::ptr;
 use std::sync::{Arc, Mutex, OnceLock};
@@ -14,13 +14,23 @@ use crate::software_renderer::overlay::overlay_impl::{
 /// Per-view raw-key bookkeeping (down/up dedup). Text editing lives in the
 /// single global [`ACTIVE`] model, like Flutter's one `TextInputPlugin`.
 pub struct ViewKeyboardState {
-    pub(crate) pressed_keys: Mutex<HashSet<u64>>,
+    /// Keys Flutter has seen go down, physical key to the logical key sent
+    /// with the press, so a release can be synthesized after focus loss.
+    pub(crate) pressed_keys: Mutex<HashMap<u64, u64>>,
 }
 
 impl ViewKeyboardState {
     pub(crate) fn new() -> Self {
         Self {
-            pressed_keys: Mutex::new(HashSet::new()),
+            pressed_keys: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Forgets every pressed key and returns them as `(physical, logical)`.
+    pub(crate) fn take_pressed_keys(&self) -> Vec<(u64, u64)> {
+        match self.pressed_keys.lock() {
+            Ok(mut pressed) => pressed.drain().collect(),
+            Err(_) => Vec::new(),
         }
     }
 }
@@ -63,11 +73,24 @@ pub(crate) struct FlutterTextEditingState {
     composing_extent: i32,
 }
 
+/// Snapshot of a text field's contents, as returned by `get_editing_state`.
+/// Offsets are UTF-16 code units, as in Flutter's `TextSelection`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditingState {
+    pub text: String,
+    pub selection_base: i32,
+    pub selection_extent: i32,
+    /// IME composing range, `None` when nothing is being composed.
+    pub composing: Option<(i32, i32)>,
+}
+
 #[derive(Debug, Clone)]
 pub struct TextInputModel {
     pub text: String,
     pub selection_base_utf8: usize,
     pub selection_extent_utf8: usize,
+    /// Byte range of the text the IME is still composing, if any.
+    pub composing_utf8: Option<(usize, usize)>,
 }
 
 impl TextInputModel {
@@ -76,6 +99,7 @@ impl TextInputModel {
             text: String::new(),
             selection_base_utf8: 0,
             selection_extent_utf8: 0,
+            composing_utf8: None,
         }
     }
 
@@ -84,13 +108,32 @@ impl TextInputModel {
             utf8_byte_offset_to_utf16_code_unit_offset(&self.text, self.selection_base_utf8);
         let selection_extent_utf16 =
             utf8_byte_offset_to_utf16_code_unit_offset(&self.text, self.selection_extent_utf8);
+        let (composing_base, composing_extent) = match self.composing_utf8 {
+            Some((start, end)) => (
+                utf8_byte_offset_to_utf16_code_unit_offset(&self.text, start),
+                utf8_byte_offset_to_utf16_code_unit_offset(&self.text, end),
+            ),
+            None => (-1, -1),
+        };
 
         FlutterTextEditingState {
             text: self.text.clone(),
             selection_base: selection_base_utf16,
             selection_extent: selection_extent_utf16,
-            composing_base: -1,
-            composing_extent: -1,
+            composing_base,
+            composing_extent,
+        }
+    }
+
+    pub(crate) fn editing_state(&self) -> EditingState {
+        let state = self.to_flutter_editing_state();
+        let composing = (state.composing_base >= 0 && state.composing_extent >= 0)
+            .then_some((state.composing_base, state.composing_extent));
+        EditingState {
+            text: state.text,
+            selection_base: state.selection_base,
+            selection_extent: state.selection_extent,
+            composing,
         }
     }
 
@@ -100,6 +143,11 @@ impl TextInputModel {
             snap_to_char_boundary(&self.text, self.selection_base_utf8.min(byte_len));
         self.selection_extent_utf8 =
             snap_to_char_boundary(&self.text, self.selection_extent_utf8.min(byte_len));
+        self.composing_utf8 = self.composing_utf8.and_then(|(start, end)| {
+            let start = snap_to_char_boundary(&self.text, start.min(byte_len));
+            let end = snap_to_char_boundary(&self.text, end.min(byte_len));
+            (start < end).then_some((start, end))
+        });
     }
 
     fn get_ordered_selection_utf8(&self) -> (usize, usize) {
@@ -116,6 +164,40 @@ impl TextInputModel {
         let new_cursor_pos = sel_start + ch.len_utf8();
         self.selection_base_utf8 = new_cursor_pos;
         self.selection_extent_utf8 = new_cursor_pos;
+        self.composing_utf8 = None;
+        self.sanitize_offsets();
+    }
+
+    /// Range the IME's next string replaces: the text being composed, or the
+    /// selection when a composition is just starting.
+    fn composing_or_selection_utf8(&self) -> (usize, usize) {
+        self.composing_utf8.unwrap_or_else(|| self.get_ordered_selection_utf8())
+    }
+
+    /// Replaces the composing text with the IME's current composition and
+    /// marks it as composing. `cursor_utf16` is the caret within `text`,
+    /// defaulting to its end. An empty `text` cancels the composition.
+    pub(crate) fn set_composing_text(&mut self, text: &str, cursor_utf16: Option<usize>) {
+        let (start, end) = self.composing_or_selection_utf8();
+        self.text.replace_range(start..end, text);
+        let cursor = cursor_utf16.map_or(text.len(), |cursor| {
+            utf16_code_unit_offset_to_utf8_byte_offset(text, cursor)
+        });
+        self.selection_base_utf8 = start + cursor;
+        self.selection_extent_utf8 = start + cursor;
+        self.composing_utf8 = (!text.is_empty()).then_some((start, start + text.len()));
+        self.sanitize_offsets();
+    }
+
+    /// Replaces the composing text with the string the user confirmed and
+    /// ends the composition, leaving the caret after it.
+    pub(crate) fn commit_composing(&mut self, text: &str) {
+        let (start, end) = self.composing_or_selection_utf8();
+        self.text.replace_range(start..end, text);
+        let new_cursor_pos = start + text.len();
+        self.selection_base_utf8 = new_cursor_pos;
+        self.selection_extent_utf8 = new_cursor_pos;
+        self.composing_utf8 = None;
         self.sanitize_offsets();
     }
 
@@ -424,6 +506,17 @@ pub(crate) fn apply_text_input_method(
                         &current_state.model.text,
                         flutter_state.selection_extent.max(0) as usize,
                     );
+                current_state.model.composing_utf8 = (flutter_state.composing_base >= 0
+                    && flutter_state.composing_extent >= 0)
+                    .then(|| {
+                        let text = &current_state.model.text;
+                        let base = flutter_state.composing_base as usize;
+                        let extent = flutter_state.composing_extent as usize;
+                        (
+                            utf16_code_unit_offset_to_utf8_byte_offset(text, base.min(extent)),
+                            utf16_code_unit_offset_to_utf8_byte_offset(text, base.max(extent)),
+                        )
+                    });
                 current_state.model.sanitize_offsets();
             }
         }
@@ -462,6 +555,9 @@ pub(crate) unsafe extern "C" fn custom_text_input_platform_message_handler(
                     if let Ok(mut guard) = overlay.active_text_input.lock() {
                         apply_text_input_method(method_name, args, &mut guard);
                     }
+                    if let Ok(mut geometry) = overlay.caret_geometry.lock() {
+                        geometry.apply(method_name, args);
+                    }
                 }
 
         if !message.response_handle.is_null() {
@@ -0,0 +1,150 @@
+//! Touch and pen contacts from `WM_POINTER*` messages.
+//!
+//! Flutter tells simultaneous pointers apart by device id and expects each one
+//! to go add, down, move..., up, remove. The mouse is device 0 and trackpad
+//! gestures device 1, so every live contact gets the lowest free id from
+//! [`FIRST_CONTACT_DEVICE_ID`] up. [`TouchContacts`] maps the Windows pointer
+//! id to that device and turns each message into the phases the engine needs,
+//! filling in the `kAdd` / `kRemove` the Windows messages don't have.
+//!
+//! A finger leaves range when it lifts, so touch contacts are removed on
+//! `WM_POINTERUP`. A pen stays in range and keeps hovering until
+//! `WM_POINTERLEAVE`.
+
+use std::collections::HashMap;
+
+use crate::bindings::embedder::{
+    FlutterPointerDeviceKind, FlutterPointerPhase, FlutterPointerPhase_kAdd,
+    FlutterPointerPhase_kCancel, FlutterPointerPhase_kDown, FlutterPointerPhase_kHover,
+    FlutterPointerPhase_kMove, FlutterPointerPhase_kRemove, FlutterPointerPhase_kUp,
+};
+
+/// Device id of the first touch or pen contact.
+pub(crate) const FIRST_CONTACT_DEVICE_ID: i32 = 2;
+
+/// What a `WM_POINTER*` message says about one contact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ContactEvent {
+    /// `WM_POINTERDOWN`.
+    Down,
+    /// `WM_POINTERUPDATE`; `in_contact` is `POINTER_FLAG_INCONTACT`.
+    Update { in_contact: bool },
+    /// `WM_POINTERUP`. `canceled` is `POINTER_FLAG_CANCELED`; `in_range` keeps
+    /// the device added (a hovering pen).
+    Up { canceled: bool, in_range: bool },
+    /// `WM_POINTERLEAVE`.
+    Leave,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Contact {
+    pub(crate) device: i32,
+    pub(crate) device_kind: FlutterPointerDeviceKind,
+    down: bool,
+    /// Last position, in overlay pixels, for closing the contact on focus loss.
+    pub(crate) position: (f64, f64),
+}
+
+#[derive(Default)]
+pub(crate) struct TouchContacts {
+    contacts: HashMap<u32, Contact>,
+}
+
+impl TouchContacts {
+    /// Records `event` at `position` for Windows pointer `pointer_id`, a
+    /// pointer of `device_kind`. Returns the contact's device id and the
+    /// phases to send, in order; empty for a leave of a pointer that was never
+    /// seen.
+    pub(crate) fn on_event(
+        &mut self,
+        pointer_id: u32,
+        device_kind: FlutterPointerDeviceKind,
+        event: ContactEvent,
+        position: (f64, f64),
+    ) -> (i32, Vec<FlutterPointerPhase>) {
+        let mut phases = Vec::new();
+        let contact = match self.contacts.get(&pointer_id) {
+            Some(contact) => *contact,
+            None if event == ContactEvent::Leave => return (0, phases),
+            None => {
+                phases.push(FlutterPointerPhase_kAdd);
+                Contact {
+                    device: self.free_device(),
+                    device_kind,
+                    down: false,
+                    position,
+                }
+            }
+        };
+        let device = contact.device;
+        let (down, keep) = match event {
+            ContactEvent::Down | ContactEvent::Update { in_contact: true } => {
+                phases.push(if contact.down {
+                    FlutterPointerPhase_kMove
+                } else {
+                    FlutterPointerPhase_kDown
+                });
+                (true, true)
+            }
+            ContactEvent::Update { in_contact: false } => {
+                phases.push(if contact.down {
+                    FlutterPointerPhase_kUp
+                } else {
+                    FlutterPointerPhase_kHover
+                });
+                (false, true)
+            }
+            ContactEvent::Up { canceled, in_range } => {
+                if contact.down {
+                    phases.push(if canceled {
+                        FlutterPointerPhase_kCancel
+                    } else {
+                        FlutterPointerPhase_kUp
+                    });
+                }
+                (false, in_range)
+            }
+            ContactEvent::Leave => {
+                if contact.down {
+                    phases.push(FlutterPointerPhase_kCancel);
+                }
+                (false, false)
+            }
+        };
+        if keep {
+            self.contacts.insert(
+                pointer_id,
+                Contact {
+                    down,
+                    position,
+                    ..contact
+                },
+            );
+        } else {
+            phases.push(FlutterPointerPhase_kRemove);
+            self.contacts.remove(&pointer_id);
+        }
+        (device, phases)
+    }
+
+    /// Forgets every contact, returning the phases that close them: a cancel
+    /// for each one that is down, then a remove.
+    pub(crate) fn cancel_all(&mut self) -> Vec<(Contact, FlutterPointerPhase)> {
+        let mut phases = Vec::new();
+        for (_, contact) in self.contacts.drain() {
+            if contact.down {
+                phases.push((contact, FlutterPointerPhase_kCancel));
+            }
+            phases.push((contact, FlutterPointerPhase_kRemove));
+        }
+        phases
+    }
+
+    fn free_device(&self) -> i32 {
+        let mut device = FIRST_CONTACT_DEVICE_ID;
+        while self.contacts.values().any(|contact| contact.device == device) {
+            device += 1;
+        }
+        device
+    }
+}
@@ -0,0 +1,185 @@
+//! Turns pointer wheel messages into Flutter pan/zoom gestures.
+//!
+//! Precision touchpads (and hosts that call `EnableMouseInPointer`) deliver
+//! scrolling as `WM_POINTERWHEEL` / `WM_POINTERHWHEEL` with sub-notch deltas.
+//! Forwarding those as discrete wheel signals makes lists scroll in steps and
+//! skips Flutter's fling and overscroll physics, which only run for a gesture
+//! with an explicit end. This module tracks one such gesture per overlay: the
+//! first delta starts it, later deltas accumulate into the pan offset, and it
+//! ends after [`GESTURE_IDLE_TIMEOUT`] without input or when another pointer
+//! message arrives.
+//!
+//! Pinch and rotate join the same gesture. Precision touchpads report a pinch as
+//! a wheel delta with Ctrl held, and touch hardware that sends `WM_GESTURE`
+//! reports zoom as a finger distance and rotation as an angle. The plain
+//! `WM_MOUSEWHEEL` path stays a discrete scroll signal.
+
+use std::f64::consts::PI;
+use std::time::{Duration, Instant};
+
+use winapi::um::winuser::WHEEL_DELTA;
+
+/// Pixels scrolled per full wheel notch; matches the legacy `WM_MOUSEWHEEL` path.
+pub(crate) const PIXELS_PER_NOTCH: f64 = 20.0;
+
+/// How long a gesture stays open without new deltas.
+pub(crate) const GESTURE_IDLE_TIMEOUT: Duration = Duration::from_millis(120);
+
+/// Zoom factor per full wheel notch of a Ctrl+wheel pinch.
+pub(crate) const PINCH_SCALE_PER_NOTCH: f64 = 1.25;
+
+/// Converts a (possibly fractional-notch) wheel delta to scroll pixels.
+pub(crate) fn wheel_delta_to_pixels(delta: i16) -> f64 {
+    delta as f64 / WHEEL_DELTA as f64 * PIXELS_PER_NOTCH
+}
+
+/// Converts a Ctrl+wheel pinch delta to a zoom factor; positive deltas zoom in.
+pub(crate) fn wheel_delta_to_scale(delta: i16) -> f64 {
+    PINCH_SCALE_PER_NOTCH.powf(delta as f64 / WHEEL_DELTA as f64)
+}
+
+/// Angle in radians encoded in a `GID_ROTATE` argument; the Rust form of
+/// `GID_ROTATE_ANGLE_FROM_ARGUMENT`.
+pub(crate) fn rotate_angle_from_argument(argument: u64) -> f64 {
+    (argument as u16 as f64 / 65535.0) * 4.0 * PI - 2.0 * PI
+}
+
+/// Zoom or rotate step decoded from a `WM_GESTURE` message.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TouchGesture {
+    /// `GID_ZOOM`: distance in pixels between the two fingers.
+    Zoom { distance: f64 },
+    /// `GID_ROTATE`: radians turned since the gesture began, counterclockwise.
+    Rotate { angle: f64 },
+}
+
+/// What to send to the engine for one wheel message.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PanUpdate {
+    /// A `kPanZoomStart` must precede this update.
+    pub starts_gesture: bool,
+    /// Accumulated pan offset since the gesture started.
+    pub pan_x: f64,
+    pub pan_y: f64,
+    /// Zoom factor since the gesture started, `1.0` for none.
+    pub scale: f64,
+    /// Clockwise rotation in radians since the gesture started.
+    pub rotation: f64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct TrackpadScrollState {
+    /// Whether the trackpad device has been announced with `kAdd`.
+    pub device_added: bool,
+    /// Position where the current gesture started, `None` when idle.
+    origin: Option<(f64, f64)>,
+    pan_x: f64,
+    pan_y: f64,
+    scale: f64,
+    rotation: f64,
+    /// Finger distance and scale when the current `GID_ZOOM` began.
+    zoom_base: Option<(f64, f64)>,
+    /// Rotation when the current `GID_ROTATE` began.
+    rotation_base: f64,
+    last_delta_at: Option<Instant>,
+}
+
+impl TrackpadScrollState {
+    /// Opens a gesture at `position` if none is active. Returns whether it did.
+    fn begin(&mut self, position: (f64, f64)) -> bool {
+        let starts_gesture = self.origin.is_none();
+        if starts_gesture {
+            self.origin = Some(position);
+            self.pan_x = 0.0;
+            self.pan_y = 0.0;
+            self.scale = 1.0;
+            self.rotation = 0.0;
+            self.zoom_base = None;
+            self.rotation_base = 0.0;
+        }
+        starts_gesture
+    }
+
+    fn update(&mut self, starts_gesture: bool, now: Instant) -> PanUpdate {
+        self.last_delta_at = Some(now);
+        PanUpdate {
+            starts_gesture,
+            pan_x: self.pan_x,
+            pan_y: self.pan_y,
+            scale: self.scale,
+            rotation: self.rotation,
+        }
+    }
+
+    /// Records a wheel delta at `position`. `vertical` selects the axis.
+    ///
+    /// A positive `WM_POINTERWHEEL` delta scrolls content up, which is a
+    /// downward pan in Flutter's coordinates; a positive `WM_POINTERHWHEEL`
+    /// delta scrolls right, a leftward pan.
+    pub(crate) fn on_wheel(
+        &mut self,
+        delta: i16,
+        vertical: bool,
+        position: (f64, f64),
+        now: Instant,
+    ) -> PanUpdate {
+        let starts_gesture = self.begin(position);
+        let pixels = wheel_delta_to_pixels(delta);
+        if vertical {
+            self.pan_y += pixels;
+        } else {
+            self.pan_x -= pixels;
+        }
+        self.update(starts_gesture, now)
+    }
+
+    /// Records a Ctrl+wheel pinch delta at `position`.
+    pub(crate) fn on_pinch(&mut self, delta: i16, position: (f64, f64), now: Instant) -> PanUpdate {
+        let starts_gesture = self.begin(position);
+        self.scale *= wheel_delta_to_scale(delta);
+        self.update(starts_gesture, now)
+    }
+
+    /// Records a `WM_GESTURE` step at `position`. `begins` is set for the
+    /// message carrying `GF_BEGIN`, whose value becomes the reference the
+    /// following steps are measured against.
+    pub(crate) fn on_gesture(
+        &mut self,
+        gesture: TouchGesture,
+        begins: bool,
+        position: (f64, f64),
+        now: Instant,
+    ) -> PanUpdate {
+        let starts_gesture = self.begin(position);
+        match gesture {
+            TouchGesture::Zoom { distance } => match self.zoom_base {
+                Some((base_distance, base_scale)) if !begins && base_distance > 0.0 => {
+                    self.scale = base_scale * distance / base_distance;
+                }
+                _ => self.zoom_base = Some((distance, self.scale)),
+            },
+            TouchGesture::Rotate { angle } => {
+                if begins {
+                    self.rotation_base = self.rotation;
+                } else {
+                    self.rotation = self.rotation_base - angle;
+                }
+            }
+        }
+        self.update(starts_gesture, now)
+    }
+
+    /// Ends the gesture if it has been idle for [`GESTURE_IDLE_TIMEOUT`], or
+    /// unconditionally when `force` is set. Returns the start position to send
+    /// `kPanZoomEnd` at, or `None` if nothing was active.
+    pub(crate) fn take_end(&mut self, now: Instant, force: bool) -> Option<(f64, f64)> {
+        let idle = self
+            .last_delta_at
+            .is_some_and(|at| now.duration_since(at) >= GESTURE_IDLE_TIMEOUT);
+        if !(force || idle) {
+            return None;
+        }
+        self.last_delta_at = None;
+        self.origin.take()
+    }
+}
@@ -0,0 +1,55 @@
+//! Recycled CPU pixel buffers for software-rendered overlays.
+//!
+//! Dragging the host window edge resizes every overlay many times per second.
+//! Without pooling each resize reallocates the software pixel buffer; with it,
+//! buffers are allocated at power-of-two capacities and handed back for reuse.
+
+/// Upper bound on idle buffers kept around. A handful covers the sizes seen
+/// during a drag without holding on to much memory afterwards.
+const MAX_IDLE_BUFFERS: usize = 4;
+
+#[derive(Default)]
+pub(crate) struct PixelBufferPool {
+    idle: Vec<Vec<u8>>,
+}
+
+impl PixelBufferPool {
+    /// Returns a zeroed buffer of exactly `len` bytes, reusing the smallest idle
+    /// buffer that is large enough or allocating one rounded up to a power of two.
+    pub(crate) fn acquire(&mut self, len: usize) -> Vec<u8> {
+        let best = self
+            .idle
+            .iter()
+            .enumerate()
+            .filter(|(_, buf)| buf.capacity() >= len)
+            .min_by_key(|(_, buf)| buf.capacity())
+            .map(|(index, _)| index);
+
+        let mut buffer = match best {
+            Some(index) => self.idle.swap_remove(index),
+            None => Vec::with_capacity(len.next_power_of_two()),
+        };
+        buffer.clear();
+        buffer.resize(len, 0);
+        buffer
+    }
+
+    /// Hands a buffer back for reuse. When the pool is full the smallest buffer
+    /// is dropped, since larger ones can satisfy more requests.
+    pub(crate) fn release(&mut self, buffer: Vec<u8>) {
+        if buffer.capacity() == 0 {
+            return;
+        }
+        self.idle.push(buffer);
+        if self.idle.len() > MAX_IDLE_BUFFERS
+            && let Some(smallest) = self
+                .idle
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, buf)| buf.capacity())
+                .map(|(index, _)| index)
+        {
+            self.idle.swap_remove(smallest);
+        }
+    }
+}
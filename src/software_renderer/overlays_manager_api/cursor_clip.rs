@@ -0,0 +1,71 @@
+//! Confining the OS cursor to an overlay, set with `set_cursor_clip`.
+//!
+//! `ClipCursor` is process-wide, and Windows drops the clip whenever the
+//! window loses activation. So the clip is not set once: the manager re-syncs
+//! it every tick and after resizes, focus changes and shutdowns. Each sync
+//! compares the wanted rect with `GetClipCursor` and only calls `ClipCursor`
+//! when they differ. A clip the host set itself is left alone unless an
+//! overlay takes over.
+
+use log::warn;
+use windows::Win32::Foundation::{HWND, POINT, RECT};
+use windows::Win32::Graphics::Gdi::ClientToScreen;
+use windows::Win32::UI::WindowsAndMessaging::{ClipCursor, GetClipCursor, GetForegroundWindow};
+
+#[derive(Default)]
+pub(crate) struct CursorClip {
+    /// Overlay the cursor is confined to while it is focused and visible.
+    pub(crate) overlay_id: Option<String>,
+    /// Whether the clip currently in effect was set here.
+    clipped: bool,
+}
+
+impl CursorClip {
+    /// Confines the cursor to `rect` (screen coordinates), or releases a clip
+    /// set here when `rect` is `None`.
+    pub(crate) fn apply(&mut self, rect: Option<RECT>) {
+        match rect {
+            Some(rect) => {
+                let mut current = RECT::default();
+                let unchanged = unsafe { GetClipCursor(&mut current) }.is_ok() && current == rect;
+                if !unchanged && let Err(e) = unsafe { ClipCursor(Some(&rect)) } {
+                    warn!("[OverlayManager] ClipCursor failed: {e}");
+                    return;
+                }
+                self.clipped = true;
+            }
+            None if self.clipped => {
+                if let Err(e) = unsafe { ClipCursor(None) } {
+                    warn!("[OverlayManager] Releasing the cursor clip failed: {e}");
+                }
+                self.clipped = false;
+            }
+            None => {}
+        }
+    }
+}
+
+/// The screen rect of an overlay at client `position` with `size`, for a
+/// client area whose top-left corner is at screen `client_origin`.
+pub(crate) fn clip_rect(client_origin: (i32, i32), position: (i32, i32), size: (u32, u32)) -> RECT {
+    let left = client_origin.0 + position.0;
+    let top = client_origin.1 + position.1;
+    RECT {
+        left,
+        top,
+        right: left + size.0 as i32,
+        bottom: top + size.1 as i32,
+    }
+}
+
+/// Screen position of `hwnd`'s client area, or `None` while another window
+/// is in the foreground, so the host never clips the cursor of another app.
+pub(crate) fn foreground_client_origin(hwnd: HWND) -> Option<(i32, i32)> {
+    if unsafe { GetForegroundWindow() } != hwnd {
+        return None;
+    }
+    let mut origin = POINT::default();
+    unsafe { ClientToScreen(hwnd, &mut origin) }
+        .as_bool()
+        .then_some((origin.x, origin.y))
+}
@@ -0,0 +1,52 @@
+//! Pure routing decisions for pointer input across stacked overlays.
+
+/// Returns the topmost overlay in `overlay_order` that claims the pointer.
+///
+/// `claims` is asked top-down and should hit-test the overlay at the event
+/// position. It is evaluated for every overlay above the consumer (plus the
+/// consumer itself) and never for those below, so it may also update per-overlay
+/// hover state as a side effect. Overlays that miss let the event fall through.
+pub(crate) fn pointer_consumer(
+    overlay_order: &[String],
+    mut claims: impl FnMut(&str) -> bool,
+) -> Option<&String> {
+    overlay_order.iter().rev().find(|id| claims(id))
+}
+
+/// Reorders `overlay_order` (bottom to top) for pointer routing: by ascending
+/// `priority`, so the highest priority ends up on top, keeping the Z-order
+/// among overlays of equal priority.
+pub(crate) fn input_order(
+    overlay_order: &[String],
+    priority: impl Fn(&str) -> i32,
+) -> Vec<String> {
+    let mut order = overlay_order.to_vec();
+    order.sort_by_key(|id| priority(id));
+    order
+}
+
+/// Moves `identifier` to position `index` of `overlay_order` (0 is the
+/// bottom). An index past the end moves it to the top. Returns `false`, leaving
+/// the order as is, if `identifier` isn't in it.
+pub(crate) fn move_to_index(
+    overlay_order: &mut Vec<String>,
+    identifier: &str,
+    index: usize,
+) -> bool {
+    let Some(from) = overlay_order.iter().position(|id| id == identifier) else {
+        return false;
+    };
+    let entry = overlay_order.remove(from);
+    let index = index.min(overlay_order.len());
+    overlay_order.insert(index, entry);
+    true
+}
+
+/// Returns the topmost overlay in `overlay_order` for which `is_modal` holds.
+/// While one exists it receives all input and nothing beneath it does.
+pub(crate) fn modal_owner(
+    overlay_order: &[String],
+    is_modal: impl FnMut(&str) -> bool,
+) -> Option<&String> {
+    pointer_consumer(overlay_order, is_modal)
+}
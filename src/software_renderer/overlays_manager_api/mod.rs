@@ -41,6 +41,7 @@
 //!     "main_ui",
 //!     None,              // dart_entrypoint_args
 //!     None,              // engine_args
+//!     None,              // preferred_renderer: OpenGL with software fallback
 //! );
 //! assert!(ok);
 //! # }
@@ -129,47 +130,78 @@
 //! [`FlutterOverlay::get_texture_srv`]: crate::software_renderer::api::FlutterOverlay::get_texture_srv
 
 use parking_lot::Mutex;
+use serde_json::Value;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Once;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Global flag indicating that the overlay system is fully initialized and ready.
 static OVERLAY_SYSTEM_READY: AtomicBool = AtomicBool::new(false);
 
+/// How long `render_one_frame` waits for the engine before giving up.
+const RENDER_ONE_FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
 use directx_math::{XMMatrix, XMMatrixIdentity};
-use log::{error, info, warn};
-use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+use log::{debug, error, info, warn};
+use windows::Win32::Foundation::{HANDLE, HWND, LPARAM, LRESULT, RECT, WPARAM};
 use windows::Win32::Graphics::Direct3D11::{
-    ID3D11DepthStencilView, ID3D11Device, ID3D11DeviceContext, ID3D11SamplerState,
-    ID3D11ShaderResourceView,
+    D3D11_TEXTURE2D_DESC, D3D11_VIEWPORT, D3D11_VIEWPORT_AND_SCISSORRECT_OBJECT_COUNT_PER_PIPELINE,
+    ID3D11DepthStencilView, ID3D11Device,
+    ID3D11DeviceContext, ID3D11RenderTargetView, ID3D11SamplerState, ID3D11ShaderResourceView,
+    ID3D11Texture2D,
 };
 use windows::Win32::Graphics::Dxgi::{DXGI_SWAP_CHAIN_DESC, IDXGISwapChain};
 use windows::Win32::UI::WindowsAndMessaging::{
-    WM_CHAR, WM_KEYDOWN, WM_KEYUP, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP,
-    WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCMOUSELEAVE, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SYSKEYDOWN,
-    WM_SYSKEYUP,
+    WM_ACTIVATEAPP, WM_CHAR, WM_GESTURE, WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION,
+    WM_IME_STARTCOMPOSITION, WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS, WM_LBUTTONDOWN, WM_LBUTTONUP,
+    WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCMOUSELEAVE,
+    WM_POINTERDOWN, WM_POINTERHWHEEL, WM_POINTERLEAVE, WM_POINTERUP, WM_POINTERUPDATE,
+    WM_POINTERWHEEL, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SYSKEYDOWN, WM_SYSKEYUP,
 };
 use windows::core::Result as WindowsResult;
 
 use crate::init_logging;
-use crate::software_renderer::api::{FlutterEmbedderError, OverlayCreateParams};
+use crate::path_utils::resolve_bundle_dir;
+use crate::software_renderer::api::{
+    AlphaMode, EngineFlags, FlutterEmbedderError, FpsStats, OverlayCreateParams, RendererType,
+    RuntimeMode, StartupTimings, TaskRunnerComApartment,
+};
 use crate::software_renderer::d3d11_compositor::effects::{
-    EffectConfig, EffectParams, EffectTarget, HologramParams, PostEffect, WarpFieldParams,
+    ColorGradeParams, EffectConfig, EffectParams, EffectTarget, HologramParams, PostEffect,
+    WarpFieldParams,
 };
+use crate::software_renderer::d3d11_compositor::post_processing_renderer::AddressMode;
 use crate::software_renderer::overlay::overlay_impl::PendingPlatformMessage;
 
 use crate::software_renderer::d3d11_compositor::primitive_3d_renderer::{
     BlendMode, PrimitiveOptions, PrimitiveType, Vertex3D,
 };
+use crate::software_renderer::d3d11_compositor::shader_manifest::ShaderManifestEntry;
 use crate::software_renderer::d3d11_compositor::text_3d_renderer::{FontAtlas, TexturedVertex3D};
 use crate::software_renderer::d3d11_compositor::traits::{FrameParams, Renderer};
 use crate::software_renderer::dynamic_flutter_engine_dll_loader::FlutterEngineDll;
-use crate::software_renderer::gl_renderer::angle_interop::preload_angle_dlls;
+#[cfg(feature = "d3d-debug")]
+use crate::software_renderer::gl_renderer::d3d_debug;
+use crate::software_renderer::gl_renderer::angle_interop::{AngleOptions, preload_angle_dlls};
+use crate::software_renderer::gl_renderer::d3d_backup::D3D11StateBackup;
 use crate::software_renderer::multiview::window::{SatelliteWindow, WindowSpec};
+use crate::software_renderer::multiview::view_surface::SyncMode;
+use crate::software_renderer::overlay::anchor::Anchor;
+use crate::software_renderer::overlay::d3d::TextureShareOptions;
+use crate::software_renderer::overlay::heartbeat::send_heartbeat;
 use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
-use crate::software_renderer::overlay::semantics_handler::update_interactive_widget_hover_state;
+use crate::software_renderer::overlay::project_args;
+use crate::software_renderer::overlay::input::{ScrollConfig, pointer_event_client_position};
+use crate::software_renderer::overlay::lifecycle::{LifecycleState, set_lifecycle_state};
+use crate::software_renderer::overlay::locale::{Locale, update_locales};
+use crate::software_renderer::overlay::system_settings::{SystemSettings, push_system_settings};
+use crate::software_renderer::overlay::textinput::EditingState;
+use crate::software_renderer::overlay::semantics_handler::{
+    HitTestMode, ProcessedSemanticsNode, update_interactive_widget_hover_state,
+    update_interactive_widget_hover_state_at,
+};
 
 /// A thread-safe, clonable handle for interacting with the global OverlayManager.
 #[derive(Clone, Copy)]
@@ -213,13 +245,28 @@ fn get_overlay_manager() -> Option<&'static Mutex<OverlayManager>> {
     }
 }
 
+pub(crate) mod buffer_pool;
+pub(crate) mod cursor_clip;
+mod dispatch;
 mod keybind;
+mod resize_debounce;
+mod rtv_cache;
 mod types;
+mod watchdog;
 #[cfg(test)]
 mod tests;
+use buffer_pool::PixelBufferPool;
+use cursor_clip::{CursorClip, clip_rect, foreground_client_origin};
+use dispatch::{input_order, modal_owner, move_to_index, pointer_consumer};
 use keybind::{Keybind, parse_keybind};
+use resize_debounce::{ResizeDebounce, ResizeRequest};
+use watchdog::{OverlayLaunch, Watchdog};
+pub use crate::software_renderer::overlay::project_args::EngineLogHandler;
 pub use keybind::{KeybindCallback, VisibilityToggleCallback};
-pub use types::{FlutterRenderPass, FontAtlasSpec};
+pub use types::{
+    FlutterRenderPass, FontAtlasSpec, KeyFallback, KeyRouting, OverlayBounds, OverlayOptions,
+};
+pub use watchdog::{OverlayRestartCallback, RestartPolicy, RestartReason};
 
 /// Manages all active Flutter overlay instances.
 ///
@@ -240,6 +287,8 @@ pub struct OverlayManager {
     screen_width: u32,
     /// The height of the screen in pixels.
     screen_height: u32,
+    /// Top-left of the screen rect from the last `handle_resize`.
+    screen_origin: (i32, i32),
     /// The time when the `OverlayManager` was created or resumed.
     start_time: Instant,
     /// Indicates whether the `OverlayManager` is currently paused.
@@ -262,6 +311,45 @@ pub struct OverlayManager {
         Option<KeybindCallback>,
         bool,
     )>,
+    /// Where key events go when `focused_overlay_id` doesn't name a live overlay.
+    key_fallback: KeyFallback,
+    /// Which overlay key events go to; see `set_key_routing`.
+    key_routing: KeyRouting,
+    /// Recycled software pixel buffers used by `handle_resize`. `None` unless
+    /// enabled with `set_pixel_buffer_pooling`.
+    pixel_buffer_pool: Option<PixelBufferPool>,
+    /// Size from the last `resize_flutter_overlays` call not yet passed to
+    /// `handle_resize`; see `set_resize_debounce_ms`.
+    resize_debounce: ResizeDebounce,
+    /// `icudtl.dat` used by overlays created after `set_icu_data_override`.
+    icu_override: Option<PathBuf>,
+    /// Runtime mode for overlays created after `set_runtime_mode`.
+    runtime_mode: Option<RuntimeMode>,
+    /// Engine switches for overlays created after `set_engine_flags`.
+    engine_flags: Option<EngineFlags>,
+    /// COM apartment for the task runners of overlays created after
+    /// `set_task_runner_com_apartment`.
+    task_runner_com: TaskRunnerComApartment,
+    /// Whether overlays created after `set_custom_render_task_runner` render on
+    /// their task-runner thread. `true` by default.
+    custom_render_task_runner: bool,
+    /// Longest gap between frames `is_presenting` accepts.
+    presenting_timeout: Duration,
+    /// ANGLE display attributes for overlays created after `set_angle_options`.
+    angle_options: AngleOptions,
+    /// Bundle and arguments each overlay was started with, for restarts.
+    launches: HashMap<String, OverlayLaunch>,
+    /// Overlays with `enable_auto_restart`, keyed by identifier.
+    watchdogs: HashMap<String, Watchdog>,
+    /// Called after each watchdog restart.
+    restart_callback: Option<OverlayRestartCallback>,
+    /// Cursor confinement requested with `set_cursor_clip`.
+    cursor_clip: CursorClip,
+    /// Frames between automatic live object reports; 0 disables them.
+    #[cfg(feature = "d3d-debug")]
+    live_report_interval: u32,
+    #[cfg(feature = "d3d-debug")]
+    frames_since_live_report: u32,
 }
 
 impl OverlayManager {
@@ -275,12 +363,32 @@ impl OverlayManager {
             swap_chain: None,
             screen_width: 0,
             screen_height: 0,
+            screen_origin: (0, 0),
             start_time: Instant::now(),
             is_paused: false,
             time_at_pause: 0.0,
             recovery_cooldown: 0,
             visibility_toggles: Vec::new(),
             keybind_actions: Vec::new(),
+            key_fallback: KeyFallback::default(),
+            key_routing: KeyRouting::default(),
+            pixel_buffer_pool: None,
+            resize_debounce: ResizeDebounce::default(),
+            icu_override: None,
+            runtime_mode: None,
+            engine_flags: None,
+            task_runner_com: TaskRunnerComApartment::None,
+            custom_render_task_runner: true,
+            presenting_timeout: Duration::from_secs(5),
+            angle_options: AngleOptions::default(),
+            launches: HashMap::new(),
+            watchdogs: HashMap::new(),
+            restart_callback: None,
+            cursor_clip: CursorClip::default(),
+            #[cfg(feature = "d3d-debug")]
+            live_report_interval: 0,
+            #[cfg(feature = "d3d-debug")]
+            frames_since_live_report: 0,
         }
     }
 
@@ -387,9 +495,7 @@ impl OverlayManager {
                 if !overlay.is_visible() {
                     continue;
                 }
-                let (ox, oy) = overlay.get_position();
-                let (ow, oh) = overlay.get_dimensions();
-                if x >= ox && x < ox + (ow as i32) && y >= oy && y < oy + (oh as i32) {
+                if overlay.contains_point(x as f64, y as f64) {
                     return Some(identifier.clone());
                 }
             }
@@ -445,10 +551,8 @@ impl OverlayManager {
     fn init(
         &mut self,
         swap_chain: &IDXGISwapChain,
-        flutter_asset_dir: &Path,
         identifier: &str,
-        dart_args_for_this_instance: Option<Vec<String>>,
-        engine_args_opt: Option<Vec<String>>,
+        launch: OverlayLaunch,
     ) -> bool {
         if self.active_instances.contains_key(identifier) {
             self.bring_to_front(Some(identifier));
@@ -490,31 +594,47 @@ impl OverlayManager {
             }
         };
 
-        let width = desc.BufferDesc.Width;
-        let height = desc.BufferDesc.Height;
-
-        self.screen_width = width;
-        self.screen_height = height;
+        self.screen_width = desc.BufferDesc.Width;
+        self.screen_height = desc.BufferDesc.Height;
         self.swap_chain = Some(swap_chain.clone());
+        rtv_cache::invalidate();
+
+        let options = &launch.options;
+        let bounds = options.bounds.unwrap_or(OverlayBounds {
+            x: 0,
+            y: 0,
+            width: self.screen_width,
+            height: self.screen_height,
+        });
 
         init_logging();
 
         match FlutterOverlay::create(
             OverlayCreateParams {
                 name: identifier.to_string(),
-                x: 0,
-                y: 0,
-                width,
-                height,
-                flutter_data_dir: flutter_asset_dir.to_path_buf(),
-                dart_entrypoint_args: dart_args_for_this_instance,
-                engine_args: engine_args_opt,
+                x: bounds.x,
+                y: bounds.y,
+                width: bounds.width,
+                height: bounds.height,
+                flutter_data_dir: launch.asset_dir.clone(),
+                dart_entrypoint_args: options.dart_args.clone(),
+                engine_args: options.engine_args.clone(),
+                icu_override: self.icu_override.clone(),
+                runtime_mode: self.runtime_mode,
+                engine_flags: self.engine_flags.clone(),
+                task_runner_com: self.task_runner_com,
+                custom_render_task_runner: self.custom_render_task_runner,
+                angle_options: self.angle_options,
+                preferred_renderer: options.preferred_renderer.clone(),
+                flutter_paths: None,
             },
             &device,
             swap_chain,
         ) {
-            Ok(overlay_box) => {
+            Ok(mut overlay_box) => {
+                overlay_box.fixed_size = options.bounds.map(|b| (b.width, b.height));
                 self.add_overlay_instance(identifier.to_string(), overlay_box);
+                self.launches.insert(identifier.to_string(), launch);
                 info!(
                     "[OverlayManager:{identifier}] Flutter overlay initialized and added to manager."
                 );
@@ -541,6 +661,33 @@ impl OverlayManager {
             return (false, None);
         }
 
+        // Keys and buttons released while the window is inactive never send
+        // their up message here, so let go of everything on focus loss. The
+        // message is left for the host to handle as well.
+        if msg == WM_KILLFOCUS || (msg == WM_ACTIVATEAPP && wparam.0 == 0) {
+            for overlay in self.active_instances.values() {
+                overlay.release_held_input(hwnd);
+            }
+            return (false, None);
+        }
+
+        // IME composition belongs to whichever overlay has a text field
+        // focused, wherever the mouse is.
+        if matches!(
+            msg,
+            WM_IME_STARTCOMPOSITION | WM_IME_COMPOSITION | WM_IME_ENDCOMPOSITION
+        ) {
+            let consumed = self
+                .input_routing_order()
+                .iter()
+                .rev()
+                .filter_map(|id| self.active_instances.get(id))
+                .any(|overlay| {
+                    overlay.accepts_input() && overlay.handle_ime_event(hwnd, msg, lparam)
+                });
+            return (consumed, None);
+        }
+
         // Visibility toggle keybinds — processed BEFORE the visibility gate
         // so that hidden overlays can be toggled back on. Only fires on key-down.
         if matches!(msg, WM_KEYDOWN | WM_SYSKEYDOWN) {
@@ -576,13 +723,11 @@ impl OverlayManager {
                             && !was_visible
                             && let Some(sc) = &self.swap_chain
                         {
-                            overlay.handle_window_resize_force(
-                                0,
-                                0,
-                                self.screen_width,
-                                self.screen_height,
-                                sc,
-                            );
+                            let (x, y, width, height) = match overlay.fixed_size {
+                                Some((w, h)) => (overlay.x, overlay.y, w, h),
+                                None => (0, 0, self.screen_width, self.screen_height),
+                            };
+                            overlay.handle_window_resize_force(x, y, width, height, sc);
                         }
 
                         let msg_payload = if new_visible {
@@ -630,14 +775,51 @@ impl OverlayManager {
                 | WM_MBUTTONUP
                 | WM_NCMOUSELEAVE
                 | WM_MOUSEWHEEL
+                | WM_MOUSEHWHEEL
+                | WM_POINTERWHEEL
+                | WM_POINTERHWHEEL
+                | WM_POINTERDOWN
+                | WM_POINTERUPDATE
+                | WM_POINTERUP
+                | WM_POINTERLEAVE
+                | WM_GESTURE
         );
 
         let is_key_event = matches!(
             msg,
             WM_KEYDOWN | WM_SYSKEYDOWN | WM_KEYUP | WM_SYSKEYUP | WM_CHAR
         );
+        let is_press = matches!(
+            msg,
+            WM_LBUTTONDOWN | WM_RBUTTONDOWN | WM_MBUTTONDOWN | WM_POINTERDOWN
+        );
+
+        if (is_pointer_event || is_key_event)
+            && let Some(modal_id) = modal_owner(&self.overlay_order, |id| {
+                self.active_instances
+                    .get(id)
+                    .is_some_and(|overlay| overlay.visible && overlay.modal)
+            })
+        {
+            let overlay = &self.active_instances[modal_id];
+            if !overlay.input_enabled {
+                return (true, None);
+            }
+            if is_pointer_event {
+                if is_press {
+                    overlay.send_view_focus(0, true);
+                }
+                if let Some((x, y)) = pointer_event_client_position(hwnd, msg, lparam) {
+                    update_interactive_widget_hover_state_at(overlay, x, y);
+                }
+                overlay.handle_pointer_event(hwnd, msg, wparam, lparam);
+            } else {
+                overlay.handle_keyboard_event(msg, wparam, lparam);
+            }
+            return (true, None);
+        }
 
-        if matches!(msg, WM_LBUTTONDOWN | WM_RBUTTONDOWN | WM_MBUTTONDOWN)
+        if is_press
             && let Some(instance) = self
                 .focused_overlay_id
                 .as_ref()
@@ -648,36 +830,102 @@ impl OverlayManager {
         }
 
         if is_pointer_event {
-            let overlay_order_copy: Vec<String> = self.overlay_order.clone();
+            // Hit-test at the event's own position instead of trusting the
+            // per-frame hover flag, so a click on a transparent region of the
+            // top overlay falls through to the one beneath in the same message.
+            // Observe-only overlays are hit-tested for their hover state but
+            // never become the consumer. Opaque overlays also claim events
+            // that miss every interactive widget but land on their bounds.
+            let position = pointer_event_client_position(hwnd, msg, lparam);
+            let routing_order = self.input_routing_order();
+            let consumer = pointer_consumer(&routing_order, |id| {
+                self.active_instances.get(id).is_some_and(|overlay| {
+                    overlay.accepts_input()
+                        && position.is_some_and(|(x, y)| {
+                            let interactive =
+                                update_interactive_widget_hover_state_at(overlay, x, y);
+                            overlay.hit_test_mode.claims(interactive, || {
+                                let (x, y) = overlay.input_transform.client_to_overlay(x, y);
+                                overlay.contains_point(x, y)
+                            })
+                        })
+                        && overlay.consumes_input
+                })
+            })
+            .cloned();
 
-            for identifier in overlay_order_copy.iter().rev() {
+            for identifier in routing_order.iter().rev() {
                 if let Some(overlay_instance) = self.active_instances.get(identifier) {
-                    if !overlay_instance.is_visible() {
+                    if !overlay_instance.accepts_input() {
                         continue;
                     }
 
                     overlay_instance.handle_pointer_event(hwnd, msg, wparam, lparam);
 
-                    if overlay_instance
-                        .is_interactive_widget_hovered
-                        .load(Ordering::SeqCst)
+                    if consumer.as_ref() == Some(identifier) {
+                        if overlay_instance.auto_raise_on_hover {
+                            self.bring_to_front(Some(identifier));
+                        }
+                        return (true, None);
+                    }
+                }
+            }
+        } else if is_key_event {
+            let routed = self.key_routing.target(
+                &self.overlay_order,
+                |id| {
+                    self.active_instances
+                        .get(id)
+                        .is_some_and(|overlay| overlay.accepts_input())
+                },
+                |id| {
+                    self.active_instances.get(id).is_some_and(|overlay| {
+                        overlay.is_interactive_widget_hovered.load(Ordering::SeqCst)
+                    })
+                },
+            );
+            if let Some(overlay_instance) = routed.and_then(|id| self.active_instances.get(id)) {
+                let consumed = overlay_instance.handle_keyboard_event(msg, wparam, lparam);
+                return (consumed, None);
+            }
+            if let Some(overlay_instance) = self
+                .focused_overlay_id
+                .as_ref()
+                .and_then(|id| self.active_instances.get(id))
+            {
+                if overlay_instance.input_enabled
+                    && overlay_instance.handle_keyboard_event(msg, wparam, lparam)
+                {
+                    return (true, None);
+                }
+            } else {
+                let candidates = self.key_fallback.candidates(&self.overlay_order, |id| {
+                    self.active_instances
+                        .get(id)
+                        .is_some_and(|overlay| overlay.accepts_input())
+                });
+                for identifier in candidates {
+                    if let Some(overlay_instance) = self.active_instances.get(identifier)
+                        && overlay_instance.handle_keyboard_event(msg, wparam, lparam)
                     {
-                        self.bring_to_front(Some(identifier));
                         return (true, None);
                     }
                 }
             }
-        } else if is_key_event
-            && let Some(focused_id) = &self.focused_overlay_id
-            && let Some(overlay_instance) = self.active_instances.get(focused_id)
-            && overlay_instance.handle_keyboard_event(msg, wparam, lparam)
-        {
-            return (true, None);
         }
 
         (false, None)
     }
 
+    /// `overlay_order` sorted by each overlay's `input_priority`.
+    fn input_routing_order(&self) -> Vec<String> {
+        input_order(&self.overlay_order, |id| {
+            self.active_instances
+                .get(id)
+                .map_or(0, |overlay| overlay.input_priority)
+        })
+    }
+
     /// Handles WM_SETCURSOR, respecting Z-order and hover states.
     fn handle_set_cursor(
         &self,
@@ -685,9 +933,31 @@ impl OverlayManager {
         lparam_from_message: LPARAM,
         main_app_hwnd: HWND,
     ) -> Option<LRESULT> {
-        for identifier in self.overlay_order.iter().rev() {
+        if let Some(modal_id) = modal_owner(&self.overlay_order, |id| {
+            self.active_instances
+                .get(id)
+                .is_some_and(|overlay| overlay.visible && overlay.modal)
+        }) {
+            let overlay = &self.active_instances[modal_id];
+            if !overlay.input_enabled
+                || !overlay
+                    .is_interactive_widget_hovered
+                    .load(std::sync::atomic::Ordering::SeqCst)
+            {
+                return None;
+            }
+            return overlay.handle_set_cursor(
+                hwnd_for_setcursor_message,
+                lparam_from_message,
+                main_app_hwnd,
+            );
+        }
+
+        for identifier in self.input_routing_order().iter().rev() {
             // Topmost first
             if let Some(overlay_instance) = self.active_instances.get(identifier)
+                && overlay_instance.input_enabled
+                && overlay_instance.consumes_input
                 && overlay_instance
                     .is_interactive_widget_hovered
                     .load(std::sync::atomic::Ordering::SeqCst)
@@ -703,6 +973,28 @@ impl OverlayManager {
         None
     }
 
+    /// Reports live objects of the host device and of every overlay's ANGLE device.
+    /// Returns how many devices produced a report.
+    #[cfg(feature = "d3d-debug")]
+    fn dump_live_d3d_objects(&self) -> usize {
+        let mut reported = 0;
+        if let Some(swap_chain) = &self.swap_chain
+            && let Ok(device) = unsafe { swap_chain.GetDevice::<ID3D11Device>() }
+            && d3d_debug::report_live_objects(&device, "host")
+        {
+            reported += 1;
+        }
+        for (id, overlay) in &self.active_instances {
+            if let Some(angle) = &overlay.angle_state
+                && let Ok(device) = angle.0.get_d3d_device()
+                && d3d_debug::report_live_objects(&device, &format!("ANGLE '{id}'"))
+            {
+                reported += 1;
+            }
+        }
+        reported
+    }
+
     /// Handles resizing for all active overlays.
     fn handle_resize(
         &mut self,
@@ -712,9 +1004,13 @@ impl OverlayManager {
         width: u32,
         height: u32,
     ) {
+        let old_origin = self.screen_origin;
+        let old_screen = (self.screen_width, self.screen_height);
         self.screen_width = width;
         self.screen_height = height;
+        self.screen_origin = (x_pos, y_pos);
         self.swap_chain = Some(swap_chain.clone());
+        rtv_cache::invalidate();
 
         if self.active_instances.is_empty() {
             return;
@@ -722,11 +1018,67 @@ impl OverlayManager {
 
         for (id, overlay_instance) in self.active_instances.iter_mut() {
             if !overlay_instance.engine.0.is_null() {
-                overlay_instance.handle_window_resize(x_pos, y_pos, width, height, swap_chain);
+                // A zero size (minimized window) still goes through so the
+                // overlay suspends rendering.
+                let (w, h) = match overlay_instance.fixed_size {
+                    Some(size) if width > 0 && height > 0 => size,
+                    _ => (width, height),
+                };
+                let (x, y) = match overlay_instance.anchor {
+                    Anchor::TopLeft if overlay_instance.fixed_size.is_none() => (x_pos, y_pos),
+                    anchor => {
+                        let offset = anchor.offset(
+                            (overlay_instance.x, overlay_instance.y),
+                            (overlay_instance.width, overlay_instance.height),
+                            old_origin,
+                            old_screen,
+                        );
+                        anchor.position(offset, (w, h), (x_pos, y_pos), (width, height))
+                    }
+                };
+                match self.pixel_buffer_pool.as_mut() {
+                    Some(pool) => {
+                        overlay_instance.handle_window_resize_pooled(x, y, w, h, swap_chain, pool)
+                    }
+                    None => overlay_instance.handle_window_resize(x, y, w, h, swap_chain),
+                }
             } else {
                 warn!("[OverlayManager:{id}] Engine handle is null, cannot resize.");
             }
         }
+        self.sync_cursor_clip();
+    }
+
+    /// Applies the debounced resize once it has settled. Returns whether one
+    /// was applied.
+    fn apply_settled_resize(&mut self) -> bool {
+        let Some(request) = self.resize_debounce.take_ready(Instant::now()) else {
+            return false;
+        };
+        let Some(swap_chain) = self.swap_chain.clone() else {
+            return false;
+        };
+        let ResizeRequest { x, y, width, height } = request;
+        self.handle_resize(&swap_chain, x, y, width, height);
+        true
+    }
+
+    /// Confines the cursor to the overlay set with `set_cursor_clip` while it
+    /// is focused and visible and the host window is in the foreground, and
+    /// releases it otherwise.
+    fn sync_cursor_clip(&mut self) {
+        let bounds = self.cursor_clip.overlay_id.as_ref().and_then(|id| {
+            let overlay = self.active_instances.get(id)?;
+            let focused = self.focused_overlay_id.as_ref() == Some(id);
+            (focused && overlay.is_visible())
+                .then(|| (overlay.get_position(), overlay.get_scaled_dimensions()))
+        });
+        let rect = bounds.and_then(|(position, size)| {
+            let desc = unsafe { self.swap_chain.as_ref()?.GetDesc() }.ok()?;
+            let origin = foreground_client_origin(desc.OutputWindow)?;
+            Some(clip_rect(origin, position, size))
+        });
+        self.cursor_clip.apply(rect);
     }
 
     /// Shuts down a specific Flutter overlay instance.
@@ -734,10 +1086,16 @@ impl OverlayManager {
         if let Some(overlay_box) = self.active_instances.remove(identifier) {
             info!("[OverlayManager:{identifier}] Shutting down overlay instance.");
             self.overlay_order.retain(|id| id != identifier);
+            self.launches.remove(identifier);
+            self.watchdogs.remove(identifier);
 
             if self.focused_overlay_id.as_deref() == Some(identifier) {
                 self.focused_overlay_id = self.overlay_order.last().cloned();
             }
+            if self.cursor_clip.overlay_id.as_deref() == Some(identifier) {
+                self.cursor_clip.overlay_id = None;
+            }
+            self.sync_cursor_clip();
             overlay_box.shutdown()
         } else {
             warn!(
@@ -747,6 +1105,48 @@ impl OverlayManager {
         }
     }
 
+    /// Shuts an overlay down and starts it again from the bundle it was created
+    /// with. Position, Z-order, visibility, focus and the effect config carry
+    /// over; Dart state does not.
+    fn restart_instance(&mut self, identifier: &str) -> Result<(), FlutterEmbedderError> {
+        let (Some(launch), Some(swap_chain)) =
+            (self.launches.get(identifier).cloned(), self.swap_chain.clone())
+        else {
+            return Err(FlutterEmbedderError::InvalidHandle);
+        };
+        let Some(old) = self.active_instances.remove(identifier) else {
+            return Err(FlutterEmbedderError::InvalidHandle);
+        };
+        let z_index = self.overlay_order.iter().position(|id| id == identifier);
+        self.overlay_order.retain(|id| id != identifier);
+        let (x, y, visible, effect_config) = (old.x, old.y, old.visible, old.effect_config);
+        if let Err(e) = old.shutdown() {
+            warn!("[OverlayManager:{identifier}] Shutdown before restart failed: {e}");
+        }
+
+        let started = self.init(&swap_chain, identifier, launch);
+        if !started {
+            self.watchdogs.remove(identifier);
+            return Err(FlutterEmbedderError::InitializationFailed(format!(
+                "overlay '{identifier}' failed to restart"
+            )));
+        }
+        if let Some(index) = z_index {
+            self.overlay_order.retain(|id| id != identifier);
+            let index = index.min(self.overlay_order.len());
+            self.overlay_order.insert(index, identifier.to_string());
+        }
+        if let Ok(overlay) = self.get_instance_mut(Some(identifier)) {
+            overlay.set_position(x, y);
+            overlay.visible = visible;
+            overlay.effect_config = effect_config;
+        }
+        if let Some(watchdog) = self.watchdogs.get_mut(identifier) {
+            *watchdog = Watchdog::new(watchdog.policy);
+        }
+        Ok(())
+    }
+
     /// Shuts down all active Flutter overlay instances.
     pub fn shutdown_all_instances(&mut self) {
         let all_ids: Vec<String> = self.active_instances.keys().cloned().collect();
@@ -1024,6 +1424,49 @@ impl OverlayManager {
             })
     }
 
+    /// Runs `post` on every overlay whose user data tag equals `tag` and
+    /// returns how many accepted the message. Overlays that haven't registered
+    /// a Dart port yet are skipped.
+    fn post_to_group(
+        &self,
+        tag: u64,
+        post: impl Fn(&FlutterOverlay) -> Result<(), FlutterEmbedderError>,
+    ) -> usize {
+        let mut delivered = 0;
+        for (id, overlay) in self.active_instances.iter() {
+            if overlay.user_data != Some(tag) {
+                continue;
+            }
+            if overlay.dart_send_port.load(Ordering::SeqCst) == 0 {
+                debug!("[OverlayManager] Skipping '{id}' in group {tag}: no Dart port registered");
+                continue;
+            }
+            match post(overlay) {
+                Ok(()) => delivered += 1,
+                Err(e) => warn!("[OverlayManager] Group post to '{id}' failed: {e:?}"),
+            }
+        }
+        delivered
+    }
+
+    /// Posts a byte buffer to every overlay tagged with `tag`.
+    ///
+    /// # Arguments
+    /// * `tag` - The user data tag set with `set_user_data`.
+    /// * `buffer` - The byte buffer to post.
+    pub fn post_buffer_to_group(&self, tag: u64, buffer: &[u8]) -> usize {
+        self.post_to_group(tag, |overlay| overlay.post_buffer(buffer))
+    }
+
+    /// Posts a string to every overlay tagged with `tag`.
+    ///
+    /// # Arguments
+    /// * `tag` - The user data tag set with `set_user_data`.
+    /// * `value` - The string value to post.
+    pub fn post_string_to_group(&self, tag: u64, value: &str) -> usize {
+        self.post_to_group(tag, |overlay| overlay.post_string(value))
+    }
+
     /// Sets the screen-space position for a specific overlay.
     pub fn set_overlay_position(&mut self, identifier: Option<&str>, x: i32, y: i32) {
         match self.get_instance_mut(identifier) {
@@ -1047,6 +1490,36 @@ impl OverlayManager {
         }
     }
 
+    /// Registers a fallible channel handler for a specific overlay instance.
+    pub fn register_channel_handler_result_for_instance<F>(
+        &mut self,
+        identifier: Option<&str>,
+        channel: &str,
+        handler: F,
+    ) where
+        F: Fn(Vec<u8>) -> Result<Vec<u8>, (String, String)> + Send + Sync + 'static,
+    {
+        match self.get_instance_mut(identifier) {
+            Ok(overlay) => overlay.register_channel_handler_result(channel, handler),
+            Err(e) => warn!("[OverlayManager] register_channel_handler_result failed: {e}"),
+        }
+    }
+
+    /// Registers a `JSONMethodCodec` handler for a specific overlay instance.
+    pub fn register_json_method_handler_for_instance<F>(
+        &mut self,
+        identifier: Option<&str>,
+        channel: &str,
+        handler: F,
+    ) where
+        F: Fn(&str, Value) -> Value + Send + Sync + 'static,
+    {
+        match self.get_instance_mut(identifier) {
+            Ok(overlay) => overlay.register_json_method_handler(channel, handler),
+            Err(e) => warn!("[OverlayManager] register_json_method_handler failed: {e}"),
+        }
+    }
+
     /// Brings the specified overlay to the top of the Z-order.
     pub fn bring_to_front(&mut self, identifier: Option<&str>) {
         if let Ok(id_str) = self.get_instance(identifier).map(|ov| ov.name.clone()) {
@@ -1055,11 +1528,23 @@ impl OverlayManager {
         }
     }
 
+    /// Moves the specified overlay to `index` in the Z-order (0 is the bottom).
+    /// An index past the end brings it to the front.
+    pub fn set_z_index(&mut self, identifier: Option<&str>, index: usize) {
+        match self.get_instance(identifier).map(|ov| ov.name.clone()) {
+            Ok(id_str) => {
+                move_to_index(&mut self.overlay_order, &id_str, index);
+            }
+            Err(e) => warn!("[OverlayManager] set_z_index failed: {e}"),
+        }
+    }
+
     /// Sets keyboard focus to the specified overlay and brings it to the front.
     pub fn set_keyboard_focus(&mut self, identifier: Option<&str>) {
         if let Ok(id_str) = self.get_instance(identifier).map(|ov| ov.name.clone()) {
             self.focused_overlay_id = Some(id_str.clone());
             self.bring_to_front(Some(&id_str));
+            self.sync_cursor_clip();
         }
     }
 }
@@ -1078,14 +1563,19 @@ impl FlutterOverlayManagerHandle {
     ///
     /// # Renderer Selection
     ///
-    /// This function automatically determines the best available renderer. It will first
-    /// attempt to initialize a hardware-accelerated **OpenGL** renderer via ANGLE.
+    /// With `preferred_renderer: None` this function automatically determines the best
+    /// available renderer. It will first attempt to initialize a hardware-accelerated
+    /// **OpenGL** renderer via ANGLE.
     ///
     /// If OpenGL initialization fails for any reason (e.g., `libEGL.dll` or `libGLESv2.dll`
     /// are not found, or a graphics driver issue occurs), it will log an error and
     /// automatically fall back to a **Software** renderer. This ensures that the overlay
     /// can be displayed even on systems without proper OpenGL support.
     ///
+    /// `Some(RendererType::Software)` skips ANGLE entirely, e.g. for drivers where ANGLE
+    /// initializes but renders corrupt frames. `Some(RendererType::OpenGL)` makes the call
+    /// fail instead of falling back.
+    ///
     /// # Arguments
     ///
     /// * `swap_chain`: A reference to the host application's `IDXGISwapChain`.
@@ -1105,6 +1595,8 @@ impl FlutterOverlayManagerHandle {
     /// * `dart_args`: Optional. A vector of string arguments for the Dart `main()` function.
     /// * `engine_args`: Optional. A vector of command-line switches for the Flutter Engine,
     ///   typically used in debug builds.
+    /// * `preferred_renderer`: Optional. Forces a renderer instead of the automatic
+    ///   selection described above.
     ///
     /// # Returns
     ///
@@ -1122,6 +1614,7 @@ impl FlutterOverlayManagerHandle {
     ///     "main_hud",
     ///     None, // No special Dart arguments
     ///     None, // No special engine arguments
+    ///     None, // Pick the renderer automatically
     /// );
     /// ```
     pub fn init_instance(
@@ -1131,125 +1624,502 @@ impl FlutterOverlayManagerHandle {
         identifier: &str,
         dart_args: Option<Vec<String>>,
         engine_args: Option<Vec<String>>,
+        preferred_renderer: Option<RendererType>,
     ) -> bool {
-        if let Some(mut manager) = self.manager.try_lock() {
-            manager.init(
-                swap_chain,
-                flutter_asset_build_dir,
-                identifier,
-                dart_args,
-                engine_args,
-            )
-        } else {
-            false
-        }
+        let options = OverlayOptions {
+            dart_args,
+            engine_args,
+            preferred_renderer,
+            bounds: None,
+        };
+        self.init_instance_with_options(swap_chain, flutter_asset_build_dir, identifier, options)
     }
 
-    /// Renders all latched 3D primitives for all visible overlays.
-    ///
-    /// This function is the primary method for drawing 3D geometry (e.g., entity highlights,
-    /// debug lines, world-space widgets) that needs to correctly interact with the host
-    /// application's depth buffer. It handles the transformation of vertices using the
-    /// game's camera, rendering to the entire available surface.
+    /// Like `init_instance`, with every optional setting in one `OverlayOptions`.
     ///
-    /// ## Usage
-    /// This should be called once per frame from your main render hook (e.g., `new_present`).
-    /// For correct depth testing and integration, it must be called *before* any
-    /// post-processing effects that flatten or scale the game's render target.
+    /// # What it solves
+    /// Dart and engine arguments, a forced renderer and the overlay's bounds
+    /// can be combined freely, and `init_instance_with_dirs` takes the same
+    /// struct. A settings popup or a chat box only needs a few hundred pixels;
+    /// with `bounds`, the view, its texture and hit testing use `width` x
+    /// `height`, and the size is kept when the swap chain is resized. The
+    /// screen size that effect shaders see still follows the swap chain.
     ///
-    /// ## Arguments
+    /// # Arguments
+    /// * `swap_chain`: A reference to the host application's `IDXGISwapChain`.
+    /// * `flutter_asset_build_dir`: Path to the Flutter bundle, as for `init_instance`.
+    /// * `identifier`: A unique string that identifies this overlay instance.
+    /// * `options`: Arguments, renderer and bounds; `Default` behaves like
+    ///   `init_instance` with `None` everywhere.
     ///
-    /// * `view_projection_matrix`: The combined view and projection matrix from the host camera.
-    /// * `depth_stencil_view`: The host application's active `ID3D11DepthStencilView` to use
-    ///   for depth testing.
+    /// # Returns
+    /// `true` if the overlay was initialized successfully.
     ///
-    /// ## Example
+    /// # Example
     /// ```rust, no_run
-    /// // In your main render hook (e.g., dxgi_present_hook.rs)
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// let game_dsv = get_current_game_dsv();
-    /// let game_matrix = get_current_game_matrix();
-    ///
-    /// manager.render_primitives(
-    ///     &game_matrix,
-    ///     &Some(game_dsv)
-    /// );
-    ///
-    /// // ... now run post-processing on the combined scene ...
+    /// let options = OverlayOptions {
+    ///     bounds: Some(OverlayBounds { x: 760, y: 390, width: 400, height: 300 }),
+    ///     preferred_renderer: Some(RendererType::Software),
+    ///     ..Default::default()
+    /// };
+    /// manager.init_instance_with_options(&my_swap_chain, &path, "settings", options);
     /// ```
-    pub fn render_primitives(
+    pub fn init_instance_with_options(
         &self,
-        view_projection_matrix: &XMMatrix,
-        depth_stencil_view: &Option<ID3D11DepthStencilView>,
-    ) {
+        swap_chain: &IDXGISwapChain,
+        flutter_asset_build_dir: &Path,
+        identifier: &str,
+        options: OverlayOptions,
+    ) -> bool {
         let Some(mut manager) = self.manager.try_lock() else {
-            return;
-        };
-
-        let context = match manager.shared_d3d_context.clone() {
-            Some(ctx) => ctx,
-            None => {
-                return;
-            }
+            return false;
         };
-
-        let time = manager.start_time.elapsed().as_secs_f32();
-
-        let frame_params = FrameParams {
-            context: &context,
-            view_projection_matrix,
-            depth_stencil_view,
-            screen_width: 0.0,
-            screen_height: 0.0,
-            time,
+        let launch = OverlayLaunch {
+            asset_dir: flutter_asset_build_dir.to_path_buf(),
+            options,
         };
-
-        for overlay in manager.active_instances.values_mut() {
-            {
-                overlay.primitive_renderer.draw(&frame_params);
-                overlay.text_renderer.draw(&frame_params);
-            }
-        }
+        manager.init(swap_chain, identifier, launch)
     }
 
-    /// Ticks the Flutter engine and composites the final 2D UI for all visible overlays.
+    /// Like `init_instance`, but picks the bundle from a list of candidate
+    /// directories.
     ///
-    /// This function handles two critical tasks: it drives the Flutter engine's internal
-    /// state and animations (`tick`), and then it draws the resulting UI texture onto the
-    /// screen. It should be called at the end of your render loop to ensure the UI
-    /// appears on top of all other game and primitive rendering.
+    /// # What it solves
+    /// During development the bundle may live in `build/windows/x64/runner/Debug`,
+    /// `.../Release` or an `assemble` output, depending on the last build. Each
+    /// directory is probed in order for `flutter_assets`, `icudtl.dat` (unless
+    /// `set_icu_data_override` is set) and `flutter_engine.dll`, and the first
+    /// complete one is used.
     ///
-    /// ## Usage
-    /// Call this once per frame in your main render hook after all 3D scene rendering
-    /// and post-processing is complete.
+    /// # Arguments
+    /// * `swap_chain`: A reference to the host application's `IDXGISwapChain`.
+    /// * `dirs`: Candidate bundle directories, in order of preference.
+    /// * `identifier`: A unique string that identifies this overlay instance.
+    /// * `options`: Arguments, renderer and bounds, as for `init_instance_with_options`.
     ///
-    /// ## Example
+    /// # Returns
+    /// `Ok(())` once the overlay is running. `InitializationFailed` lists what was
+    /// missing in each directory when none was complete, or reports that the
+    /// overlay itself failed to start (details in the log).
+    ///
+    /// # Example
     /// ```rust, no_run
-    /// // In your main render hook (e.g., dxgi_present_hook.rs)
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    ///
-    /// // ... render game world and 3D primitives ...
-    /// // ... run post-processing ...
-    ///
-    /// // Finally, draw the UI on top of everything.
-    /// manager.render_ui();
+    /// let dirs = [
+    ///     PathBuf::from("ui/build/windows/x64/runner/Debug"),
+    ///     PathBuf::from("ui/build/windows/x64/runner/Release"),
+    /// ];
+    /// let options = OverlayOptions::default();
+    /// if let Err(e) = manager.init_instance_with_dirs(&my_swap_chain, &dirs, "hud", options) {
+    ///     error!("hud failed to start: {e}");
+    /// }
     /// ```
-    pub fn render_ui(&self) {
+    pub fn init_instance_with_dirs(
+        &self,
+        swap_chain: &IDXGISwapChain,
+        dirs: &[PathBuf],
+        identifier: &str,
+        options: OverlayOptions,
+    ) -> Result<(), FlutterEmbedderError> {
         let Some(mut manager) = self.manager.try_lock() else {
-            return;
+            return Err(FlutterEmbedderError::OperationFailed(
+                "overlay manager is busy".to_string(),
+            ));
         };
-
-        let context = match manager.shared_d3d_context.clone() {
-            Some(ctx) => ctx,
-            None => return,
+        let bundle_dir = resolve_bundle_dir(dirs, manager.icu_override.as_deref()).map_err(|e| {
+            error!("[OverlayManager:{identifier}] {e}");
+            FlutterEmbedderError::InitializationFailed(e)
+        })?;
+        let launch = OverlayLaunch {
+            asset_dir: bundle_dir.clone(),
+            options,
         };
+        if manager.init(swap_chain, identifier, launch) {
+            Ok(())
+        } else {
+            Err(FlutterEmbedderError::InitializationFailed(format!(
+                "overlay '{identifier}' failed to start from `{}`",
+                bundle_dir.display()
+            )))
+        }
+    }
 
-        let time = manager.start_time.elapsed().as_secs_f32();
-        let identity_matrix = XMMatrix(XMMatrixIdentity());
-
-        let frame_params = FrameParams {
-            context: &context,
-            view_projection_matrix: &identity_matrix,
+    /// Points overlays created from now on at a shared `icudtl.dat`.
+    ///
+    /// # What it solves
+    /// Every Flutter bundle normally ships its own ~10MB `icudtl.dat`. With an
+    /// override, each overlay keeps its own assets folder but all of them load
+    /// ICU from one place, and the bundles can omit the file. Existing overlays
+    /// are unaffected. A missing file makes `init_instance` fail before the
+    /// engine starts. Pass `None` to go back to the per-bundle file.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_icu_data_override(Some(PathBuf::from("./shared/icudtl.dat")));
+    /// manager.init_instance(&my_swap_chain, &hud_path, "hud", None, None, None);
+    /// manager.init_instance(&my_swap_chain, &map_path, "map", None, None, None);
+    /// ```
+    pub fn set_icu_data_override(&self, icu_path: Option<PathBuf>) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.icu_override = icu_path;
+        }
+    }
+
+    /// Enables the D3D11 debug layer on the device ANGLE creates for OpenGL overlays.
+    ///
+    /// # What it solves
+    /// Use-after-free style corruption (for example the host releasing its device
+    /// while overlays still reference it) shows up as debug layer errors instead
+    /// of random crashes. Call it before the first `init_instance`: the ANGLE
+    /// display is shared by the process and keeps the flags it was created with.
+    /// Requires the Graphics Tools optional Windows feature. Only available with
+    /// the `d3d-debug` cargo feature.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.enable_d3d_debug_layer();
+    /// manager.init_instance(&my_swap_chain, &assets_path, "hud", None, None, None);
+    /// ```
+    #[cfg(feature = "d3d-debug")]
+    pub fn enable_d3d_debug_layer(&self) {
+        d3d_debug::request_debug_layers();
+    }
+
+    /// Writes all live D3D11 objects of the host device and each overlay's ANGLE
+    /// device to the debugger output.
+    ///
+    /// # What it solves
+    /// Shows which textures, views and buffers are still alive, e.g. right after
+    /// shutting down an overlay, to find leaked GPU resources. Devices created
+    /// without the debug layer are skipped. Returns the number of devices
+    /// reported. Only available with the `d3d-debug` cargo feature.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.shutdown_instance("hud");
+    /// manager.dump_live_d3d_objects();
+    /// ```
+    #[cfg(feature = "d3d-debug")]
+    pub fn dump_live_d3d_objects(&self) -> usize {
+        self.manager
+            .try_lock()
+            .map_or(0, |manager| manager.dump_live_d3d_objects())
+    }
+
+    /// Calls `dump_live_d3d_objects` automatically every `frames` calls to
+    /// `tick_overlays`. `0` (default) turns the periodic report off. Only
+    /// available with the `d3d-debug` cargo feature.
+    #[cfg(feature = "d3d-debug")]
+    pub fn set_live_object_report_interval(&self, frames: u32) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.live_report_interval = frames;
+            manager.frames_since_live_report = 0;
+        }
+    }
+
+    /// Forces the Flutter runtime mode for overlays created from now on.
+    ///
+    /// # What it solves
+    /// By default the mode is inferred from the bundle: release if `app.so` is
+    /// present, debug otherwise, so a profile build can't be told apart from a
+    /// release one. `Some(RuntimeMode::Profile)` enables Dart profiling and keeps
+    /// the whole timeline so DevTools can attach. The bundle must ship the
+    /// matching `flutter_engine.dll`. `None` restores inference.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_runtime_mode(Some(RuntimeMode::Profile));
+    /// manager.init_instance(&my_swap_chain, &profile_bundle, "hud", None, None, None);
+    /// ```
+    pub fn set_runtime_mode(&self, mode: Option<RuntimeMode>) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.runtime_mode = mode;
+        }
+    }
+
+    /// Sets typed engine switches for overlays created from now on.
+    ///
+    /// # What it solves
+    /// Raw engine args are passed to the engine as-is, and a debug-only switch
+    /// such as `--disable-dart-asserts` makes a release engine refuse to start.
+    /// `EngineFlags` are checked against each overlay's runtime mode when it is
+    /// created; unsupported switches are dropped with a warning. `None` clears
+    /// them.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_engine_flags(Some(EngineFlags::new().trace_startup(true).verbose_logging(true)));
+    /// manager.init_instance(&my_swap_chain, &bundle, "hud", None, None, None);
+    /// ```
+    pub fn set_engine_flags(&self, flags: Option<EngineFlags>) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.engine_flags = flags;
+        }
+    }
+
+    /// Makes the task-runner thread of overlays created from now on join a COM
+    /// apartment.
+    ///
+    /// # What it solves
+    /// Platform message handlers run on the task-runner thread, which never
+    /// initializes COM, so handlers that open shell dialogs or use WIC fail with
+    /// `CO_E_NOTINITIALIZED`. With an apartment set, the thread calls
+    /// `CoInitializeEx` before its message loop and `CoUninitialize` when it
+    /// exits. `TaskRunnerComApartment::None`, the default, keeps COM untouched.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_task_runner_com_apartment(TaskRunnerComApartment::SingleThreaded);
+    /// manager.init_instance(&my_swap_chain, &bundle, "hud", None, None, None);
+    /// ```
+    pub fn set_task_runner_com_apartment(&self, apartment: TaskRunnerComApartment) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.task_runner_com = apartment;
+        }
+    }
+
+    /// Chooses whether overlays created from now on render on their own
+    /// task-runner thread or on the engine's internal raster thread.
+    ///
+    /// # What it solves
+    /// Each overlay hands the engine one task runner for the platform, UI and
+    /// render roles. On some engine versions a custom render runner forces
+    /// rasterization onto that single thread, which costs frame time for busy
+    /// UIs. Passing `false` leaves the render runner unset so the engine starts
+    /// its own raster thread; the platform runner stays custom either way.
+    ///
+    /// # Threading
+    /// The ANGLE `make_current` callback pins the GL context to the first
+    /// thread that calls it and refuses calls from any other. With `false`
+    /// that thread is the engine's raster thread, so the resize and device
+    /// recovery work done inside the callback runs there, concurrently with
+    /// platform tasks instead of between them. The shared D3D11 device is
+    /// multithread-protected, so this is safe, but host code that assumed
+    /// rendering and platform messages never overlap should not rely on it.
+    ///
+    /// # Arguments
+    /// * `enabled` - `true` (the default) to render on the task-runner thread.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_custom_render_task_runner(false);
+    /// manager.init_instance(&my_swap_chain, &bundle, "hud", None, None, None);
+    /// ```
+    pub fn set_custom_render_task_runner(&self, enabled: bool) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.custom_render_task_runner = enabled;
+        }
+    }
+
+    /// Sets the ANGLE display attributes for overlays created from now on.
+    ///
+    /// # What it solves
+    /// ANGLE is asked for the experimental fast present path and automatic
+    /// trimming by default. A few older Intel drivers render corrupted frames
+    /// with them. Turning either off is a lighter workaround than falling back
+    /// to the software renderer. The options are also used when the display
+    /// is recreated after a device loss.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_angle_options(AngleOptions {
+    ///     experimental_present_path: false,
+    ///     ..AngleOptions::default()
+    /// });
+    /// manager.init_instance(&my_swap_chain, &bundle, "hud", None, None, None);
+    /// ```
+    pub fn set_angle_options(&self, options: AngleOptions) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.angle_options = options;
+        }
+    }
+
+    /// Renders all latched 3D primitives for all visible overlays.
+    ///
+    /// This function is the primary method for drawing 3D geometry (e.g., entity highlights,
+    /// debug lines, world-space widgets) that needs to correctly interact with the host
+    /// application's depth buffer. It handles the transformation of vertices using the
+    /// game's camera, rendering to the entire available surface.
+    ///
+    /// ## Usage
+    /// This should be called once per frame from your main render hook (e.g., `new_present`).
+    /// For correct depth testing and integration, it must be called *before* any
+    /// post-processing effects that flatten or scale the game's render target.
+    ///
+    /// ## Arguments
+    ///
+    /// * `view_projection_matrix`: The combined view and projection matrix from the host camera.
+    /// * `depth_stencil_view`: The host application's active `ID3D11DepthStencilView` to use
+    ///   for depth testing.
+    ///
+    /// ## Example
+    /// ```rust, no_run
+    /// // In your main render hook (e.g., dxgi_present_hook.rs)
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// let game_dsv = get_current_game_dsv();
+    /// let game_matrix = get_current_game_matrix();
+    ///
+    /// manager.render_primitives(
+    ///     &game_matrix,
+    ///     &Some(game_dsv)
+    /// );
+    ///
+    /// // ... now run post-processing on the combined scene ...
+    /// ```
+    pub fn render_primitives(
+        &self,
+        view_projection_matrix: &XMMatrix,
+        depth_stencil_view: &Option<ID3D11DepthStencilView>,
+    ) {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return;
+        };
+
+        let context = match manager.shared_d3d_context.clone() {
+            Some(ctx) => ctx,
+            None => {
+                return;
+            }
+        };
+
+        let time = manager.start_time.elapsed().as_secs_f32();
+
+        let frame_params = FrameParams {
+            context: &context,
+            view_projection_matrix,
+            depth_stencil_view,
+            screen_width: 0.0,
+            screen_height: 0.0,
+            time,
+        };
+
+        for overlay in manager.active_instances.values_mut() {
+            {
+                overlay.primitive_renderer.draw(&frame_params);
+                overlay.text_renderer.draw(&frame_params);
+            }
+        }
+    }
+
+    /// Renders all latched 3D primitives once per clip rect.
+    ///
+    /// # What it solves
+    /// `render_primitives` clips to whatever scissor rect the host has bound, which
+    /// is a single rect. In split-screen, markers have to stay inside each player's
+    /// half. This sets each rect as the scissor rect in turn and draws the
+    /// primitives and 3D text into it, then restores the host's scissor rects.
+    ///
+    /// # Arguments
+    /// * `view_projection_matrix` - The combined view and projection matrix used for all rects.
+    ///   With a separate camera per player, call this once per player with that
+    ///   player's matrix and rect.
+    /// * `depth_stencil_view` - The host's depth buffer for depth testing, as in `render_primitives`.
+    /// * `clip_rects` - Scissor rects in render-target pixels. Empty rects are skipped,
+    ///   so an empty slice draws nothing.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// let halves = [
+    ///     RECT { left: 0, top: 0, right: 960, bottom: 1080 },
+    ///     RECT { left: 960, top: 0, right: 1920, bottom: 1080 },
+    /// ];
+    /// manager.render_primitives_clipped(&game_matrix, &Some(game_dsv), &halves);
+    /// ```
+    pub fn render_primitives_clipped(
+        &self,
+        view_projection_matrix: &XMMatrix,
+        depth_stencil_view: &Option<ID3D11DepthStencilView>,
+        clip_rects: &[RECT],
+    ) {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return;
+        };
+        let Some(context) = manager.shared_d3d_context.clone() else {
+            return;
+        };
+
+        let frame_params = FrameParams {
+            context: &context,
+            view_projection_matrix,
+            depth_stencil_view,
+            screen_width: 0.0,
+            screen_height: 0.0,
+            time: manager.start_time.elapsed().as_secs_f32(),
+        };
+
+        let mut original_rects =
+            [RECT::default(); D3D11_VIEWPORT_AND_SCISSORRECT_OBJECT_COUNT_PER_PIPELINE as usize];
+        let mut original_count = original_rects.len() as u32;
+        unsafe {
+            context.RSGetScissorRects(&mut original_count, Some(original_rects.as_mut_ptr()))
+        };
+
+        for rect in clip_rects
+            .iter()
+            .filter(|rect| rect.right > rect.left && rect.bottom > rect.top)
+        {
+            unsafe { context.RSSetScissorRects(Some(&[*rect])) };
+            for overlay in manager.active_instances.values_mut() {
+                overlay.primitive_renderer.draw(&frame_params);
+                overlay.text_renderer.draw(&frame_params);
+            }
+        }
+
+        unsafe {
+            context.RSSetScissorRects(
+                (original_count > 0).then(|| &original_rects[..original_count as usize]),
+            )
+        };
+    }
+
+    /// Ticks the Flutter engine and composites the final 2D UI for all visible overlays.
+    ///
+    /// This function handles two critical tasks: it drives the Flutter engine's internal
+    /// state and animations (`tick`), and then it draws the resulting UI texture onto the
+    /// screen. It should be called at the end of your render loop to ensure the UI
+    /// appears on top of all other game and primitive rendering.
+    ///
+    /// ## Usage
+    /// Call this once per frame in your main render hook after all 3D scene rendering
+    /// and post-processing is complete.
+    ///
+    /// ## Example
+    /// ```rust, no_run
+    /// // In your main render hook (e.g., dxgi_present_hook.rs)
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    ///
+    /// // ... render game world and 3D primitives ...
+    /// // ... run post-processing ...
+    ///
+    /// // Finally, draw the UI on top of everything.
+    /// manager.render_ui();
+    /// ```
+    pub fn render_ui(&self) {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return;
+        };
+
+        let context = match manager.shared_d3d_context.clone() {
+            Some(ctx) => ctx,
+            None => return,
+        };
+
+        let time = manager.start_time.elapsed().as_secs_f32();
+        let identity_matrix = XMMatrix(XMMatrixIdentity());
+
+        let frame_params = FrameParams {
+            context: &context,
+            view_projection_matrix: &identity_matrix,
             depth_stencil_view: &None,
             screen_width: manager.screen_width as f32,
             screen_height: manager.screen_height as f32,
@@ -1257,8 +2127,13 @@ impl FlutterOverlayManagerHandle {
         };
 
         let mut rendered_any = false;
+        let now = Instant::now();
 
         for (_id, overlay) in manager.active_instances.iter_mut() {
+            overlay.advance_fade(now);
+            if overlay.is_render_suspended() {
+                continue;
+            }
             if overlay.is_visible() && overlay.has_first_frame() {
                 overlay.reopen_shared_texture_if_needed(&context);
                 overlay.tick(&context);
@@ -1280,13 +2155,14 @@ impl FlutterOverlayManagerHandle {
                     }
                 }
 
+                let (width, height) = overlay.get_scaled_dimensions();
                 overlay.post_processor.queue_texture_render(
                     &overlay.srv,
                     &overlay.effect_config,
                     overlay.x,
                     overlay.y,
-                    overlay.width,
-                    overlay.height,
+                    width,
+                    height,
                 );
                 overlay.post_processor.draw(&frame_params);
                 rendered_any = true;
@@ -1316,6 +2192,10 @@ impl FlutterOverlayManagerHandle {
     /// render pipelines, this gives you a chance to work with the updated texture
     /// before it's drawn to the screen.
     ///
+    /// Restarts from `enable_auto_restart` run synchronously inside the tick: the
+    /// old engine is shut down and the new one initialized while the manager lock
+    /// is held, so a tick that restarts an overlay takes as long as its startup.
+    ///
     /// # Example
     /// ```rust, no_run
     /// // In your main game loop
@@ -1325,24 +2205,98 @@ impl FlutterOverlayManagerHandle {
     /// manager.composite_overlays();
     /// ```
     pub fn tick_overlays(&self) {
-        let Some(mut manager) = self.manager.try_lock() else {
-            return;
-        };
-        if let Some(context) = manager.shared_d3d_context.clone() {
-            for overlay in manager.active_instances.values_mut() {
-                if overlay.is_visible() && overlay.has_first_frame() {
-                    overlay.reopen_shared_texture_if_needed(&context);
-                    overlay.tick(&context);
-                }
-                for view_id in overlay.secondary_view_ids() {
-                    overlay.tick_view(view_id, &context);
-                }
-                let _ = overlay.request_frame();
-            }
-        }
+        self.tick_overlays_dirty();
     }
 
-    /// Composites (draws) all visible overlays onto the screen in their specified Z-order.
+    /// Ticks all overlays like `tick_overlays` and reports whether anything changed.
+    ///
+    /// # What it solves
+    /// A host that renders on demand otherwise has to composite and present every
+    /// frame just in case. When this returns `false`, no overlay texture (nor any
+    /// secondary view) got new content and no fade moved, so the previous
+    /// composite is still correct and the present can be skipped.
+    ///
+    /// # Returns
+    /// `true` if at least one overlay produced a new frame this tick.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if manager.tick_overlays_dirty() || scene_changed {
+    ///     render_my_3d_world();
+    ///     manager.composite_overlays(&view_projection);
+    ///     present();
+    /// }
+    /// ```
+    pub fn tick_overlays_dirty(&self) -> bool {
+        let Some(mut guard) = self.manager.try_lock() else {
+            return false;
+        };
+        let manager = &mut *guard;
+        let mut dirty = manager.apply_settled_resize();
+        let mut restarts = Vec::new();
+        if let Some(context) = manager.shared_d3d_context.clone() {
+            let now = Instant::now();
+            for (id, overlay) in manager.active_instances.iter_mut() {
+                // A running fade changes the composite even without a new frame.
+                dirty |= overlay.fade.is_some();
+                overlay.advance_fade(now);
+                let paused = overlay.lifecycle_state == LifecycleState::Paused;
+                if overlay.is_render_suspended() || paused {
+                    continue;
+                }
+                if overlay.is_visible() && overlay.has_first_frame() {
+                    overlay.reopen_shared_texture_if_needed(&context);
+                    dirty |= overlay.tick(&context);
+                } else if !overlay.is_visible() {
+                    overlay.reset_fps_stats();
+                }
+                for view_id in overlay.secondary_view_ids() {
+                    dirty |= overlay.tick_view(view_id, &context);
+                }
+                let tick_ok = overlay.request_frame().is_ok() && !overlay.is_device_lost();
+                if overlay.has_first_frame()
+                    && let Some(watchdog) = manager.watchdogs.get_mut(id)
+                    && let Some(reason) = watchdog.observe(overlay, tick_ok, now)
+                {
+                    restarts.push((id.clone(), reason));
+                }
+            }
+        }
+
+        let mut restarted = Vec::new();
+        for (id, reason) in restarts {
+            warn!("[OverlayManager:{id}] Watchdog restarting overlay: {reason:?}");
+            let result = manager.restart_instance(&id);
+            if let Err(e) = &result {
+                error!("[OverlayManager:{id}] Watchdog restart failed: {e}");
+            }
+            dirty = true;
+            restarted.push((id, reason, result.is_ok()));
+        }
+
+        #[cfg(feature = "d3d-debug")]
+        if manager.live_report_interval > 0 {
+            manager.frames_since_live_report += 1;
+            if manager.frames_since_live_report >= manager.live_report_interval {
+                manager.frames_since_live_report = 0;
+                manager.dump_live_d3d_objects();
+            }
+        }
+
+        manager.sync_cursor_clip();
+
+        let callback = manager.restart_callback.clone();
+        drop(guard);
+        if let Some(callback) = callback {
+            for (id, reason, started) in &restarted {
+                callback(id, *reason, *started);
+            }
+        }
+        dirty
+    }
+
+    /// Composites (draws) all visible overlays onto the screen in their specified Z-order.
     ///
     /// # What it solves
     /// This function handles the final drawing of the user interfaces. It should be
@@ -1358,6 +2312,35 @@ impl FlutterOverlayManagerHandle {
     /// manager.composite_overlays(); // Draws the UI on top of the world
     /// ```
     pub fn composite_overlays(&self, view_projection_matrix: &XMMatrix) {
+        self.composite_overlays_ex(view_projection_matrix, &None);
+    }
+
+    /// Composites all visible overlays like `composite_overlays`, depth testing
+    /// their 3D primitives and text against the host's depth buffer.
+    ///
+    /// # What it solves
+    /// `composite_overlays` draws primitives with no depth buffer bound, so an
+    /// entity highlight stays visible through the wall in front of it. Passing
+    /// the game's depth buffer lets the scene occlude primitives whose options
+    /// don't set `ignore_depth_stencil`. The 2D Flutter UI is never depth
+    /// tested. Passing `None` keeps the always-on-top behavior.
+    ///
+    /// # Arguments
+    /// * `view_projection_matrix` - The camera matrix the depth buffer was rendered with.
+    /// * `depth_stencil_view` - The host's depth buffer, matching the back buffer's size.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.tick_overlays();
+    /// render_my_3d_world();
+    /// manager.composite_overlays_ex(&view_projection, &Some(game_dsv));
+    /// ```
+    pub fn composite_overlays_ex(
+        &self,
+        view_projection_matrix: &XMMatrix,
+        depth_stencil_view: &Option<ID3D11DepthStencilView>,
+    ) {
         let Some(mut manager) = self.manager.try_lock() else {
             return;
         };
@@ -1371,7 +2354,7 @@ impl FlutterOverlayManagerHandle {
             let frame_params = FrameParams {
                 context: &context,
                 view_projection_matrix,
-                depth_stencil_view: &None,
+                depth_stencil_view,
                 screen_width: manager.screen_width as f32,
                 screen_height: manager.screen_height as f32,
                 time,
@@ -1380,6 +2363,7 @@ impl FlutterOverlayManagerHandle {
             for id in manager.overlay_order.clone() {
                 if let Some(overlay) = manager.active_instances.get_mut(&id)
                     && overlay.is_visible()
+                    && !overlay.is_render_suspended()
                 {
                     update_interactive_widget_hover_state(overlay);
 
@@ -1388,13 +2372,14 @@ impl FlutterOverlayManagerHandle {
                     overlay.text_renderer.draw(&frame_params);
 
                     // Queue and draw the 2D Flutter UI
+                    let (width, height) = overlay.get_scaled_dimensions();
                     overlay.post_processor.queue_texture_render(
                         &overlay.srv,
                         &overlay.effect_config,
                         overlay.x,
                         overlay.y,
-                        overlay.width,
-                        overlay.height,
+                        width,
+                        height,
                     );
                     overlay.post_processor.draw(&frame_params);
                 }
@@ -1402,6 +2387,108 @@ impl FlutterOverlayManagerHandle {
         }
     }
 
+    /// Ticks one overlay and composites it, with its effect, 3D primitives and
+    /// text, into `target` instead of the back buffer.
+    ///
+    /// # What it solves
+    /// For UI shown inside the world (an in-game monitor, a wrist panel) the
+    /// overlay shouldn't be drawn to the screen at all. `get_texture_srv` only
+    /// exposes the raw Flutter texture; this renders the same result
+    /// `composite_overlays` would, stretched over the whole target, so the host
+    /// can sample it like any other texture. The target must have been created
+    /// on the host device with `D3D11_BIND_RENDER_TARGET`; its contents are
+    /// blended over, not cleared. All pipeline state is restored afterwards.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `target` - Render target texture to draw into.
+    /// * `view_projection_matrix` - Camera matrix for the overlay's 3D primitives and text.
+    ///
+    /// # Returns
+    /// `false` if the overlay doesn't exist, has no frame yet, or `target` can't be rendered to.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.render_overlay_to_texture(Some("monitor"), &monitor_texture, &view_proj);
+    /// // ... then draw the monitor mesh sampling `monitor_texture`.
+    /// ```
+    pub fn render_overlay_to_texture(
+        &self,
+        identifier: Option<&str>,
+        target: &ID3D11Texture2D,
+        view_projection_matrix: &XMMatrix,
+    ) -> bool {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return false;
+        };
+        let Some(context) = manager.shared_d3d_context.clone() else {
+            return false;
+        };
+        let time = if manager.is_paused {
+            manager.time_at_pause
+        } else {
+            manager.start_time.elapsed().as_secs_f32()
+        };
+        let Ok(overlay) = manager.get_instance_mut(identifier) else {
+            return false;
+        };
+        if !overlay.has_first_frame() || overlay.is_render_suspended() {
+            return false;
+        }
+
+        let mut desc = D3D11_TEXTURE2D_DESC::default();
+        unsafe { target.GetDesc(&mut desc) };
+        let mut rtv: Option<ID3D11RenderTargetView> = None;
+        let created = unsafe {
+            context
+                .GetDevice()
+                .and_then(|device| device.CreateRenderTargetView(target, None, Some(&mut rtv)))
+        };
+        if let Err(e) = created {
+            error!("[OverlayManager] render_overlay_to_texture: cannot render to target: {e:?}");
+            return false;
+        }
+
+        overlay.advance_fade(Instant::now());
+        overlay.reopen_shared_texture_if_needed(&context);
+        overlay.tick(&context);
+
+        let _state_backup = D3D11StateBackup::new(&context);
+        unsafe {
+            context.OMSetRenderTargets(Some(&[rtv]), None);
+            context.RSSetViewports(Some(&[D3D11_VIEWPORT {
+                TopLeftX: 0.0,
+                TopLeftY: 0.0,
+                Width: desc.Width as f32,
+                Height: desc.Height as f32,
+                MinDepth: 0.0,
+                MaxDepth: 1.0,
+            }]));
+        }
+
+        let frame_params = FrameParams {
+            context: &context,
+            view_projection_matrix,
+            depth_stencil_view: &None,
+            screen_width: desc.Width as f32,
+            screen_height: desc.Height as f32,
+            time,
+        };
+        overlay.primitive_renderer.draw(&frame_params);
+        overlay.text_renderer.draw(&frame_params);
+        overlay.post_processor.queue_texture_render(
+            &overlay.srv,
+            &overlay.effect_config,
+            0,
+            0,
+            desc.Width,
+            desc.Height,
+        );
+        overlay.post_processor.draw(&frame_params);
+        true
+    }
+
     /// Updates the screen dimensions used by the overlays.
     /// # Example
     /// ```rust, no_run
@@ -1480,566 +2567,2787 @@ impl FlutterOverlayManagerHandle {
         }
     }
 
-    /// Clears all submitted 3D primitives from all groups and all active overlays.
+    /// Sets an indexed group of 3D primitives in an overlay.
+    ///
+    /// A wireframe box drawn with `set_primitives` repeats each corner for
+    /// every edge that meets there. Here the corners are passed once and the
+    /// edges (or triangles) refer to them by index, which keeps more geometry
+    /// within the 65536-vertex buffer. Clearing works as for other groups.
+    ///
+    /// # Arguments
+    /// * `identifier`: The unique name of the target overlay. `None` targets the single active overlay.
+    /// * `group_id`: A string slice that identifies this group of primitives.
+    /// * `vertices`: The distinct vertices of the group.
+    /// * `indices`: Indices into `vertices`, three per triangle or two per line.
+    ///   Empty removes the group.
+    /// * `topology`: A `PrimitiveType` enum that specifies how the indices are connected.
+    ///   Only `Triangles` and `Lines` can be indexed.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// let corners: [Vertex3D; 8] = box_corners(min, max, [0.0, 1.0, 0.0, 1.0]);
+    /// let edges: [u32; 24] = [
+    ///     0, 1, 1, 2, 2, 3, 3, 0, // bottom
+    ///     4, 5, 5, 6, 6, 7, 7, 4, // top
+    ///     0, 4, 1, 5, 2, 6, 3, 7, // sides
+    /// ];
+    /// manager.set_indexed_primitives(
+    ///     Some("hud"),
+    ///     "bounds",
+    ///     &corners,
+    ///     &edges,
+    ///     PrimitiveType::Lines,
+    /// );
+    /// ```
+    pub fn set_indexed_primitives(
+        &self,
+        identifier: Option<&str>,
+        group_id: &str,
+        vertices: &[Vertex3D],
+        indices: &[u32],
+        topology: PrimitiveType,
+    ) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.set_indexed_primitives(group_id, vertices, indices, topology);
+        }
+    }
+
+    /// Sets 3D primitives for many groups of an overlay under one lock.
+    ///
+    /// Each `set_primitives` call takes the manager lock on its own, so a scene
+    /// that updates hundreds of groups per frame pays for as many lock cycles
+    /// (and risks losing some to a busy `try_lock`). This applies every group
+    /// in one critical section. A group that is already set is replaced.
+    ///
+    /// # Arguments
+    /// * `identifier`: The unique name of the target overlay. `None` targets the single active overlay.
+    /// * `groups`: `(group_id, vertices, topology)` for each group, as for `set_primitives`.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// let highlights: Vec<(String, Vec<Vertex3D>)> = entity_highlights();
+    /// let groups: Vec<_> = highlights
+    ///     .iter()
+    ///     .map(|(id, vertices)| (id.as_str(), vertices.as_slice(), PrimitiveType::Lines))
+    ///     .collect();
+    /// manager.set_primitive_groups(Some("hud"), &groups);
+    /// ```
+    pub fn set_primitive_groups(
+        &self,
+        identifier: Option<&str>,
+        groups: &[(&str, &[Vertex3D], PrimitiveType)],
+    ) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            for (group_id, vertices, topology) in groups {
+                overlay.set_primitives(group_id, vertices, *topology);
+            }
+        }
+    }
+
+    /// Sets a group of 3D lines drawn with a fixed width in pixels.
+    ///
+    /// # What it solves
+    /// Lines set with `PrimitiveType::Lines` are always one pixel wide, which is
+    /// hard to see on high-DPI displays. Each segment here is drawn as a quad
+    /// facing the camera, expanded with the frame's view-projection matrix, so
+    /// it keeps its width at any distance. Thick lines are not pickable.
+    ///
+    /// # Arguments
+    /// * `identifier`: The unique name of the target overlay. `None` targets the single active overlay.
+    /// * `group_id`: A string slice that identifies this group of primitives.
+    /// * `segments`: Start and end vertex of each line. Empty removes the group.
+    /// * `width_px`: Line width in render-target pixels. Must be positive.
+    /// * `options`: Rendering options like depth stencil, blend mode, etc.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// let red = [1.0, 0.0, 0.0, 1.0];
+    /// let at = |position| Vertex3D { position, color: red };
+    /// let path: Vec<(Vertex3D, Vertex3D)> =
+    ///     waypoints.windows(2).map(|w| (at(w[0]), at(w[1]))).collect();
+    /// manager.set_thick_lines(None, "route", &path, 3.0, PrimitiveOptions::default());
+    /// ```
+    pub fn set_thick_lines(
+        &self,
+        identifier: Option<&str>,
+        group_id: &str,
+        segments: &[(Vertex3D, Vertex3D)],
+        width_px: f32,
+        options: PrimitiveOptions,
+    ) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.set_thick_lines(group_id, segments, width_px, options);
+        }
+    }
+
+    /// Clears all submitted 3D primitives from all groups and all active overlays.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.clear_all_primitives();
+    /// ```
+    pub fn clear_all_primitives(&self) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            for overlay in manager.active_instances.values_mut() {
+                overlay.clear_all_queued_primitives();
+            }
+        }
+    }
+
+    /// Clears primitives from a specific group for a specific overlay.
+    ///
+    /// # Arguments
+    /// * `identifier`: The unique name of the target overlay. `None` targets the single active overlay.
+    /// * `group_id`: The ID of the group to clear.
+    pub fn clear_primitives(&self, identifier: Option<&str>, group_id: &str) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.clear_primitives(group_id);
+        }
+    }
+
+    /// Takes a snapshot of all submitted 3D primitive data, making it ready for rendering.
+    ///
+    /// This function is the core of the "Submit & Latch" system, which resolves rendering flickers and
+    /// disappearing primitives. The problem occurs because the game's update logic (which submits new data)
+    /// and the render hook (`new_present`, which draws the data) can run at different rates.
+    ///
+    /// By calling this function once at the beginning of a render frame, we "latch" a stable, complete
+    /// copy of the data into a dedicated render buffer. This guarantees that all draw calls within the
+    /// same frame use the exact same data, eliminating the race condition that causes  visual artifacts.
+    ///
+    /// ## Usage
+    /// Call this function at the very beginning of your `new_present` render hook.
+    ///
+    /// ```rust
+    /// // in dxgi_present_hook.rs
+    /// pub(crate) extern "system" fn new_present(...) -> HRESULT {
+    ///     // Latch the data at the start of the frame.
+    ///     if let Some(om) = get_flutter_overlay_manager_handle() {
+    ///         om.latch_all_queued_primitives();
+    ///     }
+    ///
+    ///     // ... rest of the render hook ...
+    /// }
+    /// ```
+    pub fn latch_all_queued_primitives(&self) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.latch_all_queued_primitives();
+        }
+    }
+
+    /// Latches all queued 3D text for all visible overlays.
+    ///
+    /// Call this once per frame, typically alongside `latch_all_queued_primitives()`,
+    /// to prepare text geometry for rendering.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// // in dxgi_present_hook.rs
+    /// pub(crate) extern "system" fn new_present(...) -> HRESULT {
+    ///     if let Some(om) = get_flutter_overlay_manager_handle() {
+    ///         om.latch_all_queued_primitives();
+    ///         om.latch_all_queued_text();
+    ///     }
+    ///     // ... rest of the render hook ...
+    /// }
+    /// ```
+    pub fn latch_all_queued_text(&self) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.latch_all_queued_text();
+        }
+    }
+
+    /// Registers a font atlas for 3D text rendering on a specific overlay.
+    ///
+    /// A font atlas is a texture containing all the glyphs for a font, along with
+    /// metadata about each glyph's position, size, and spacing. Must be called before
+    /// any `set_text` calls that use this font.
+    ///
+    /// # Arguments
+    /// * `identifier` - The target overlay. `None` targets the single active overlay.
+    /// * `spec` - The font atlas to register ([`FontAtlasSpec`]).
+    pub fn register_font_atlas(&self, identifier: Option<&str>, spec: FontAtlasSpec) {
+        let FontAtlasSpec {
+            font_id,
+            texture,
+            sampler,
+            glyphs,
+            line_height,
+            base_font_size,
+        } = spec;
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.register_font_atlas(
+                &font_id,
+                texture,
+                sampler,
+                glyphs,
+                line_height,
+                base_font_size,
+            );
+        }
+    }
+
+    /// Unregisters a font atlas and clears all text using it.
+    ///
+    /// # Arguments
+    /// * `identifier` - The target overlay. `None` targets the single active overlay.
+    /// * `font_id` - The font identifier to unregister.
+    pub fn unregister_font_atlas(&self, identifier: Option<&str>, font_id: &str) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.unregister_font_atlas(font_id);
+        }
+    }
+
+    /// Sets pre-built 3D text vertices for rendering.
+    ///
+    /// Use the `text_presets::generate_text_vertices` helper to create the vertices
+    /// from a text string and font atlas.
+    ///
+    /// # Arguments
+    /// * `identifier` - The target overlay. `None` targets the single active overlay.
+    /// * `font_id` - The font atlas to use (must be registered first)
+    /// * `group_id` - Unique identifier for this text group (for updates/removal)
+    /// * `vertices` - Pre-built text vertices (from `generate_text_vertices`)
+    /// * `options` - Rendering options (depth, blend mode, etc.)
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// use flutter_embedder::software_renderer::d3d11_compositor::text_presets;
+    /// use flutter_embedder::software_renderer::d3d11_compositor::primitive_3d_renderer::PrimitiveOptions;
+    ///
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// // Generate vertices using the text_presets module (requires font atlas reference)
+    /// manager.set_text(None, "my_font", "label_1", &vertices, PrimitiveOptions::default());
+    /// ```
+    pub fn set_text(
+        &self,
+        identifier: Option<&str>,
+        font_id: &str,
+        group_id: &str,
+        vertices: &[TexturedVertex3D],
+        options: PrimitiveOptions,
+    ) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.set_text(font_id, group_id, vertices, options);
+        }
+    }
+
+    /// Clears text for a specific group.
+    ///
+    /// # Arguments
+    /// * `identifier` - The target overlay. `None` targets the single active overlay.
+    /// * `font_id` - The font the text was registered with.
+    /// * `group_id` - The group identifier to clear.
+    pub fn clear_text(&self, identifier: Option<&str>, font_id: &str, group_id: &str) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.clear_text(font_id, group_id);
+        }
+    }
+
+    /// Clears all text for a specific font on an overlay.
+    ///
+    /// # Arguments
+    /// * `identifier` - The target overlay. `None` targets the single active overlay.
+    /// * `font_id` - The font identifier whose text should be cleared.
+    pub fn clear_font_text(&self, identifier: Option<&str>, font_id: &str) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.clear_font_text(font_id);
+        }
+    }
+
+    /// Clears all text from all fonts on all overlays.
+    pub fn clear_all_text(&self) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            for overlay in manager.active_instances.values_mut() {
+                overlay.clear_all_text();
+            }
+        }
+    }
+
+    /// Draws a short debug label with the crate's built-in bitmap font.
+    ///
+    /// No font atlas has to be registered; the label lives in `group_id` under
+    /// the [`DEBUG_FONT_ID`](crate::software_renderer::d3d11_compositor::debug_font::DEBUG_FONT_ID)
+    /// font and follows the normal submit / latch cycle.
+    /// Call again with the same `group_id` to move or re-text a label, or with an
+    /// empty `text` to remove it.
+    ///
+    /// # Arguments
+    /// * `identifier` - The target overlay. `None` targets the single active overlay.
+    /// * `group_id` - Unique identifier for this label.
+    /// * `text` - The label text (printable ASCII).
+    /// * `world_pos` - The 3D position the label is centered on.
+    /// * `color` - RGBA color for the text.
+    /// * `size` - Height of one character cell in world units.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.draw_text_3d(None, "marker_1", "Spawn", [0.0, 3.0, 0.0], [1.0, 1.0, 0.0, 1.0], 0.4);
+    /// ```
+    pub fn draw_text_3d(
+        &self,
+        identifier: Option<&str>,
+        group_id: &str,
+        text: &str,
+        world_pos: [f32; 3],
+        color: [f32; 4],
+        size: f32,
+    ) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+            && let Err(e) = overlay.draw_text_3d(group_id, text, world_pos, color, size)
+        {
+            warn!("[OverlayManager] draw_text_3d failed: {e}");
+        }
+    }
+
+    /// Returns a reference to a registered font atlas, if it exists.
+    ///
+    /// This is useful for generating text vertices using the `text_presets` helpers.
+    /// Note: This acquires the manager lock briefly to clone the font atlas.
+    ///
+    /// # Arguments
+    /// * `identifier` - The target overlay. `None` targets the single active overlay.
+    /// * `font_id` - The font identifier to retrieve.
+    ///
+    /// # Returns
+    /// A cloned `FontAtlas` if found, otherwise `None`.
+    pub fn get_font_atlas(&self, identifier: Option<&str>, font_id: &str) -> Option<FontAtlas> {
+        if let Some(manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance(identifier)
+        {
+            return overlay.get_font_atlas(font_id).cloned();
+        }
+        None
+    }
+
+    /// Resumes all shader animations for all overlays.
+    ///
+    /// Unfreezes the `time` uniform sent to custom shaders, allowing visual effects
+    /// to resume from where they left off.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// // When the game is unpaused:
+    /// manager.resume_animations();
+    /// ```
+    pub fn resume_animations(&self) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && manager.is_paused
+        {
+            manager.start_time =
+                Instant::now() - std::time::Duration::from_secs_f32(manager.time_at_pause);
+            manager.is_paused = false;
+        }
+    }
+
+    /// Sets a post-processing effect for the **entire** overlay.
+    ///
+    /// Applies a full-screen shader effect to an overlay's texture, allowing for
+    /// dynamic visual styles like holograms, warp fields, or color grading,
+    /// controlled directly from your Rust code.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `effect` - The `PostEffect` enum variant to apply.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// // Make the main menu look like a hologram
+    /// manager.set_fullscreen_effect(Some("main_menu"), PostEffect::Hologram);
+    /// ```
+    pub fn set_fullscreen_effect(&self, identifier: Option<&str>, effect: PostEffect) {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return;
+        };
+        if let Ok(overlay) = manager.get_instance_mut(identifier) {
+            overlay.effect_config.params = match effect {
+                PostEffect::Passthrough => EffectParams::None,
+                PostEffect::Hologram => EffectParams::Hologram(HologramParams::default()),
+                PostEffect::WarpField => EffectParams::WarpField(WarpFieldParams::default()),
+                PostEffect::Glitch => EffectParams::Glitch(HologramParams::default()),
+                PostEffect::ColorGrade => EffectParams::ColorGrade(ColorGradeParams::default()),
+            };
+            overlay.effect_config.target = EffectTarget::Fullscreen;
+        }
+    }
+
+    /// Returns an overlay's effect configuration as bytes for saving.
+    ///
+    /// # What it solves
+    /// Effect tuning done in a settings UI is lost on restart. The bytes (JSON)
+    /// round-trip through `import_effect_config`, including custom params set
+    /// beyond the `PostEffect` presets.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Returns
+    /// The encoded config, or an empty `Vec` if the overlay doesn't exist.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// std::fs::write("hud_effect.json", manager.export_effect_config(Some("hud")))?;
+    /// ```
+    pub fn export_effect_config(&self, identifier: Option<&str>) -> Vec<u8> {
+        if let Some(manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance(identifier)
+        {
+            return overlay.effect_config.to_bytes();
+        }
+        Vec::new()
+    }
+
+    /// Restores an effect configuration saved with `export_effect_config`.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `bytes` - The encoded config.
+    ///
+    /// # Returns
+    /// `false` if the overlay doesn't exist or the bytes don't decode; the
+    /// current effect is kept in that case.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if let Ok(saved) = std::fs::read("hud_effect.json") {
+    ///     manager.import_effect_config(Some("hud"), &saved);
+    /// }
+    /// ```
+    pub fn import_effect_config(&self, identifier: Option<&str>, bytes: &[u8]) -> bool {
+        let config = match EffectConfig::from_bytes(bytes) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("[OverlayManager] import_effect_config: invalid config: {e}");
+                return false;
+            }
+        };
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.effect_config = config;
+            return true;
+        }
+        false
+    }
+
+    /// Applies a post-processing effect to a **specific area** of an overlay.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `effect` - The `PostEffect` enum variant to apply.
+    /// * `bounds` - An array `[x, y, width, height]` defining the target rectangle in logical pixels.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// // Make a specific button on the HUD glow with a warp effect
+    /// let button_bounds = [100.0, 200.0, 150.0, 50.0];
+    /// manager.set_widget_effect(Some("main_hud"), PostEffect::WarpField, button_bounds);
+    /// ```
+    pub fn set_widget_effect(
+        &self,
+        identifier: Option<&str>,
+        effect: PostEffect,
+        bounds: [f32; 4],
+    ) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.effect_config.params = match effect {
+                PostEffect::Passthrough => EffectParams::None,
+                PostEffect::Hologram => EffectParams::Hologram(HologramParams::default()),
+                PostEffect::WarpField => EffectParams::WarpField(WarpFieldParams::default()),
+                PostEffect::Glitch => EffectParams::Glitch(HologramParams::default()),
+                PostEffect::ColorGrade => EffectParams::ColorGrade(ColorGradeParams::default()),
+            };
+            overlay.effect_config.target = EffectTarget::Widget(bounds);
+        }
+    }
+
+    /// Removes any active effect from an overlay, reverting it to the default passthrough shader.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.clear_effect(Some("main_menu"));
+    /// ```
+    pub fn set_keep_alive(&self, identifier: Option<&str>, keep_alive: bool) {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return;
+        };
+        if let Ok(overlay) = manager.get_instance_mut(identifier) {
+            overlay.keep_alive = keep_alive;
+        }
+    }
+
+    /// Controls whether hovering an interactive widget brings the overlay to the front.
+    ///
+    /// # What it solves
+    /// Input routing raises a hovered overlay above the others by default, which
+    /// overrides explicit Z-ordering. Disable it for pinned overlays (e.g. a
+    /// background HUD) that must keep their place. Input is still delivered.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `enabled` - `true` (default) to raise on hover, `false` to keep the Z-order.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_auto_raise_on_hover(Some("hud"), false);
+    /// ```
+    pub fn set_auto_raise_on_hover(&self, identifier: Option<&str>, enabled: bool) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.auto_raise_on_hover = enabled;
+        }
+    }
+
+    /// Makes an overlay modal, or back to normal.
+    ///
+    /// # What it solves
+    /// A dialog should block everything beneath it, but input normally falls
+    /// through transparent regions to lower overlays and the host. While a
+    /// modal overlay is visible, `forward_input_to_flutter` sends all pointer
+    /// and keyboard input to it, wherever the pointer is, and reports it as
+    /// consumed. With several visible modals, the topmost one in the Z-order
+    /// wins. Visibility toggle and keybind actions still fire.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `modal` - `true` to capture all input while visible, `false` (default) for normal routing.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_modal(Some("confirm_dialog"), true);
+    /// manager.bring_to_front(Some("confirm_dialog"));
+    /// manager.set_visibility(Some("confirm_dialog"), true);
+    /// ```
+    pub fn set_modal(&self, identifier: Option<&str>, modal: bool) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.modal = modal;
+        }
+    }
+
+    /// Ranks an overlay in pointer routing independently of its Z-order.
+    ///
+    /// # What it solves
+    /// Input normally goes to the topmost overlay under the pointer, so what is
+    /// drawn on top always wins. A layer that is drawn below, such as an
+    /// interactive HUD under a decorative frame, can be given a higher
+    /// priority so it is hit-tested first. Equal priorities keep the Z-order.
+    /// Modal overlays still take all input regardless of priority.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `priority` - Higher is routed first; defaults to `0`.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_input_priority(Some("hud"), 10);
+    /// ```
+    pub fn set_input_priority(&self, identifier: Option<&str>, priority: i32) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.input_priority = priority;
+        }
+    }
+
+    /// Makes an overlay observe pointer input without consuming it.
+    ///
+    /// # What it solves
+    /// A tooltip or highlight layer needs hover and move events to follow the
+    /// pointer, but a click on it must still reach the interactive overlay or
+    /// the game underneath. With `consumes` set to `false` the overlay gets
+    /// every pointer event and its hover state as usual, but the event carries
+    /// on to the overlays below and is not reported as consumed on its account.
+    /// It also doesn't pick the cursor shape.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `consumes` - `true` (default) to stop events on a hit, `false` to only observe.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_consumes_input(Some("tooltips"), false);
+    /// ```
+    pub fn set_consumes_input(&self, identifier: Option<&str>, consumes: bool) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.consumes_input = consumes;
+        }
+    }
+
+    /// Sets which pointer events an overlay claims.
+    ///
+    /// # What it solves
+    /// By default a pointer event only stops at an overlay when it lands on an
+    /// interactive widget, so a full-screen HUD lets clicks on its empty areas
+    /// through to the game. A panel with a solid background should swallow
+    /// those clicks instead; `HitTestMode::Opaque` claims every event inside
+    /// the overlay's bounds. `consumes_input = false` still wins over both.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `mode` - `HitTestMode::SemanticsOnly` (default) or `HitTestMode::Opaque`.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_hit_test_mode(Some("inventory"), HitTestMode::Opaque);
+    /// ```
+    pub fn set_hit_test_mode(&self, identifier: Option<&str>, mode: HitTestMode) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.hit_test_mode = mode;
+        }
+    }
+
+    /// Sets the screen point an overlay stays relative to when the screen is
+    /// resized.
+    ///
+    /// # What it solves
+    /// `resize_flutter_overlays` moves every overlay back to the screen origin,
+    /// so a panel placed with `set_overlay_position` near the right or bottom
+    /// edge lands somewhere else after a resize. With an anchor, the overlay's
+    /// distance to that edge, corner or the center is measured before the
+    /// resize and kept afterwards. `Anchor::TopLeft`, the default, keeps the
+    /// old behavior.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `anchor` - The screen point to keep the distance to.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_anchor(Some("minimap"), Anchor::BottomRight);
+    /// ```
+    pub fn set_anchor(&self, identifier: Option<&str>, anchor: Anchor) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.anchor = anchor;
+        }
+    }
+
+    /// Returns the anchor set with `set_anchor`.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Returns
+    /// `None` if the overlay doesn't exist or the manager is busy.
+    pub fn get_anchor(&self, identifier: Option<&str>) -> Option<Anchor> {
+        let manager = self.manager.try_lock()?;
+        Some(manager.get_instance(identifier).ok()?.anchor)
+    }
+
+    /// Attaches a host-defined `u64` tag to an overlay.
+    ///
+    /// # What it solves
+    /// Associates host state (entity id, panel type, ...) with an overlay without
+    /// a parallel map that can desync when overlays are created or destroyed.
+    /// The tag lives on the overlay and goes away with `shutdown_instance`.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `data` - The tag to store, replacing any previous one.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_user_data(Some("inventory"), entity_id);
+    /// ```
+    pub fn set_user_data(&self, identifier: Option<&str>, data: u64) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.user_data = Some(data);
+        }
+    }
+
+    /// Returns the tag set with `set_user_data`, or `None` if none was set or the
+    /// overlay doesn't exist.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if let Some(entity_id) = manager.get_user_data(Some("inventory")) {
+    ///     despawn(entity_id);
+    /// }
+    /// ```
+    pub fn get_user_data(&self, identifier: Option<&str>) -> Option<u64> {
+        let manager = self.manager.try_lock()?;
+        manager.get_instance(identifier).ok()?.user_data
+    }
+
+    /// Triggers a frame-based glitch effect that auto-fades and auto-clears.
+    /// NOTE: Currently hardcoded to the Glitch shader. Should be refactored
+    /// to accept a dynamic EffectParams for any effect type.
+    pub fn trigger_glitch_effect(&self, identifier: Option<&str>, frames: u32) {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return;
+        };
+        if let Ok(overlay) = manager.get_instance_mut(identifier) {
+            overlay.effect_frames_remaining = frames;
+            overlay.effect_total_frames = frames;
+            overlay.effect_config.params = EffectParams::Glitch(HologramParams::default());
+            overlay.effect_config.target = EffectTarget::Fullscreen;
+        }
+    }
+
+    pub fn clear_effect(&self, identifier: Option<&str>) {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return;
+        };
+        if let Ok(overlay) = manager.get_instance_mut(identifier) {
+            overlay.effect_config = EffectConfig::default();
+        }
+    }
+
+    /// Updates the entire effect configuration for a specific overlay.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `config`: The complete `EffectConfig` struct.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// let config = EffectConfig {
+    ///     target: EffectTarget::Fullscreen,
+    ///     params: EffectParams::Hologram(HologramParams { intensity: 0.8, ..Default::default() }),
+    /// };
+    /// manager.update_effect_config(Some("main_menu"), config);
+    /// ```
+    pub fn update_effect_config(&self, identifier: Option<&str>, config: EffectConfig) {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return;
+        };
+        if let Ok(overlay) = manager.get_instance_mut(identifier) {
+            overlay.effect_config = config;
+        }
+    }
+
+    /// Forwards a raw Windows message to the manager for input processing.
+    ///
+    /// This is the critical function for making UIs interactive. It translates Windows
+    /// input messages (mouse moves, clicks, key presses) into events that Flutter
+    /// can understand and deliver to the appropriate widgets. Without this, your
+    /// UI will be visible but completely non-interactive.
+    ///
+    /// Forward `WM_KILLFOCUS` and `WM_ACTIVATEAPP` too: on focus loss every
+    /// key and mouse button the overlays still hold is released, so Alt isn't
+    /// left stuck down after Alt-Tab. These are never reported as consumed.
+    ///
+    /// Touch and pen input arrives as `WM_POINTERDOWN` / `UPDATE` / `UP` /
+    /// `LEAVE`, with each finger sent to Flutter as its own pointer. When one
+    /// of these is consumed, don't pass it to `DefWindowProc`, or Windows
+    /// also promotes it to a mouse click.
+    ///
+    /// Forward `WM_IME_STARTCOMPOSITION`, `WM_IME_COMPOSITION` and
+    /// `WM_IME_ENDCOMPOSITION` for CJK input. While a Flutter text field is
+    /// focused the composing text is shown inside the field and the candidate
+    /// window opens at its caret. Don't pass consumed IME messages to
+    /// `DefWindowProc`, or the confirmed text arrives a second time as
+    /// `WM_CHAR`.
+    ///
+    /// # Returns
+    /// `true` if a Flutter overlay consumed the event. The host application can
+    /// use this to suppress further processing of the input (e.g., stop the game
+    /// camera from moving when the mouse is over a UI button).
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// // Inside your application's WndProc
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if manager.forward_input_to_flutter(hwnd, msg, wparam, lparam) {
+    ///     return LRESULT(0); // Flutter handled it, so we stop processing.
+    /// }
+    /// // ... continue with normal message processing for the game ...
+    /// ```
+    pub fn forward_input_to_flutter(
+        &self,
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> bool {
+        type DeferredCb = (String, bool, VisibilityToggleCallback);
+        let (result, deferred_cb): (bool, Option<DeferredCb>) =
+            if let Some(mut manager) = self.manager.try_lock() {
+                manager.handle_input_event(hwnd, msg, wparam, lparam)
+            } else {
+                (false, None)
+            };
+        if let Some((overlay_id, visible, cb)) = deferred_cb {
+            cb(&overlay_id, visible);
+        }
+        result
+    }
+
+    /// Injects a synthetic key event, bypassing the OS keyboard state.
+    ///
+    /// # What it solves
+    /// Lets accessibility tools and automated tests drive keyboard-focused Flutter
+    /// widgets without sending real Win32 messages. Press/release pairing follows
+    /// the same rules as real input, so always send a matching release.
+    ///
+    /// # Arguments
+    /// * `identifier` - The target overlay. If `None`, targets the focused overlay
+    ///   (or the single active overlay when nothing is focused).
+    /// * `logical_key` - Flutter logical key id.
+    /// * `physical_key` - Flutter physical (USB HID) key id.
+    /// * `down` - `true` for a press, `false` for a release.
+    /// * `character` - Optional text for the press; committed to an active text field.
+    ///
+    /// # Returns
+    /// `true` if the event was queued for an overlay.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// // Type "a" into the focused overlay.
+    /// manager.inject_key(None, 0x61, 0x7_0004, true, Some('a'));
+    /// manager.inject_key(None, 0x61, 0x7_0004, false, None);
+    /// ```
+    pub fn inject_key(
+        &self,
+        identifier: Option<&str>,
+        logical_key: u64,
+        physical_key: u64,
+        down: bool,
+        character: Option<char>,
+    ) -> bool {
+        let Some(manager) = self.manager.try_lock() else {
+            return false;
+        };
+        let target = match identifier {
+            Some(id) => Some(id.to_string()),
+            None => manager.focused_overlay_id.clone(),
+        };
+        match manager.get_instance(target.as_deref()) {
+            Ok(overlay) => overlay.inject_key(logical_key, physical_key, down, character),
+            Err(e) => {
+                warn!("[OverlayManager] inject_key failed: {e}");
+                false
+            }
+        }
+    }
+
+    /// Reads the text and selection of the focused text field in an overlay.
+    ///
+    /// # What it solves
+    /// The editing state lives inside the embedder's text input model and
+    /// was only ever sent to Flutter. This returns a copy so the host can
+    /// mirror it, e.g. to preview what the user is typing into a command
+    /// palette. The state is not modified.
+    ///
+    /// # Arguments
+    /// * `identifier` - The target overlay. If `None`, targets the focused overlay
+    ///   (or the single active overlay when nothing is focused).
+    ///
+    /// # Returns
+    /// `None` if the overlay doesn't exist or none of its text fields has focus.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if let Some(state) = manager.get_editing_state(Some("palette")) {
+    ///     show_preview(&state.text);
+    /// }
+    /// ```
+    pub fn get_editing_state(&self, identifier: Option<&str>) -> Option<EditingState> {
+        let manager = self.manager.try_lock()?;
+        let target = match identifier {
+            Some(id) => Some(id.to_string()),
+            None => manager.focused_overlay_id.clone(),
+        };
+        manager.get_instance(target.as_deref()).ok()?.get_editing_state()
+    }
+
+    /// Pushes the current Windows theme and accessibility settings to every overlay.
+    ///
+    /// # What it solves
+    /// Overlays render into the host's swap chain and never see
+    /// `WM_SETTINGCHANGE`, so switching Windows to dark mode or turning on high
+    /// contrast leaves the Flutter UI as it was. Call this from the host's
+    /// `WndProc` on `WM_SETTINGCHANGE` and `WM_THEMECHANGED`. It reads the app
+    /// theme (`AppsUseLightTheme`), the text size, high contrast and the
+    /// animation setting, then sends them over `flutter/settings` and
+    /// `FlutterEngineUpdateAccessibilityFeatures`.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// // In your WndProc
+    /// // case WM_SETTINGCHANGE | WM_THEMECHANGED:
+    /// if let Some(manager) = get_flutter_overlay_manager_handle() {
+    ///     manager.notify_system_theme_changed();
+    /// }
+    /// ```
+    pub fn notify_system_theme_changed(&self) {
+        let settings = SystemSettings::read();
+        if let Some(manager) = self.manager.try_lock() {
+            for (id, overlay) in &manager.active_instances {
+                if overlay.engine.0.is_null() {
+                    continue;
+                }
+                if let Err(e) = push_system_settings(overlay, &settings) {
+                    warn!("[OverlayManager:{id}] Failed to push system settings: {e}");
+                }
+            }
+        }
+    }
+
+    /// Switches an overlay's UI to a single locale.
+    ///
+    /// # What it solves
+    /// The engine starts with no locale information from the host, so a
+    /// language picker in the host app has no way to reach
+    /// `Localizations` in Dart. This sends the locale through
+    /// `FlutterEngineUpdateLocales`. Use `set_locales` to pass fallbacks too.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `language` - Language subtag, e.g. `"de"`.
+    /// * `country` - Region subtag, e.g. `"AT"`. An empty string sends none.
+    /// * `script` - Optional script subtag, e.g. `Some("Latn")`.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_locale(Some("hud"), "pt", "BR", None)?;
+    /// ```
+    pub fn set_locale(
+        &self,
+        identifier: Option<&str>,
+        language: &str,
+        country: &str,
+        script: Option<&str>,
+    ) -> Result<(), FlutterEmbedderError> {
+        let mut locale = Locale::new(language);
+        if !country.is_empty() {
+            locale = locale.with_country(country);
+        }
+        if let Some(script) = script {
+            locale = locale.with_script(script);
+        }
+        self.set_locales(identifier, &[locale])
+    }
+
+    /// Replaces an overlay's preferred locales, most preferred first.
+    ///
+    /// # What it solves
+    /// Flutter resolves `supportedLocales` against the whole preference list,
+    /// and each update replaces the previous list rather than adding to it.
+    /// Passing every fallback here lets the UI pick the best match it ships.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `locales` - Preferred locales, highest priority first. Must not be empty.
+    ///
+    /// # Returns
+    /// `EngineNotRunning` if the overlay's engine isn't running,
+    /// `InvalidHandle` if the overlay doesn't exist, or an `OperationFailed`
+    /// for an invalid list or an engine error.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_locales(
+    ///     Some("hud"),
+    ///     &[Locale::new("zh").with_script("Hant").with_country("TW"), Locale::new("en")],
+    /// )?;
+    /// ```
+    pub fn set_locales(
+        &self,
+        identifier: Option<&str>,
+        locales: &[Locale],
+    ) -> Result<(), FlutterEmbedderError> {
+        let Some(manager) = self.manager.try_lock() else {
+            return Err(FlutterEmbedderError::OperationFailed(
+                "overlay manager is busy".to_string(),
+            ));
+        };
+        match manager.get_instance(identifier) {
+            Ok(overlay) => update_locales(overlay, locales),
+            Err(e) => {
+                warn!("[OverlayManager] set_locales failed: {e}");
+                Err(FlutterEmbedderError::InvalidHandle)
+            }
+        }
+    }
+
+    /// Tells an overlay which `AppLifecycleState` the app is in.
+    ///
+    /// # What it solves
+    /// An overlay keeps running its animations and tickers while the host
+    /// window is minimized or in the background, burning CPU on frames nobody
+    /// sees. Sending the lifecycle state lets Dart stop tickers and pause work
+    /// through `AppLifecycleListener`, and while an overlay is `Paused`
+    /// `tick_overlays` skips it entirely. Send `Resumed` to start it again.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `state` - The state to report, sent as `AppLifecycleState.<state>` on
+    ///   `flutter/lifecycle`.
+    ///
+    /// # Returns
+    /// `EngineNotRunning` if the overlay's engine isn't running, `InvalidHandle`
+    /// if the overlay doesn't exist, or the engine's error.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// // In your WndProc, on WM_SIZE:
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// let state = if wparam.0 as u32 == SIZE_MINIMIZED {
+    ///     LifecycleState::Paused
+    /// } else {
+    ///     LifecycleState::Resumed
+    /// };
+    /// manager.set_lifecycle_state(Some("hud"), state)?;
+    /// ```
+    pub fn set_lifecycle_state(
+        &self,
+        identifier: Option<&str>,
+        state: LifecycleState,
+    ) -> Result<(), FlutterEmbedderError> {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return Err(FlutterEmbedderError::OperationFailed(
+                "overlay manager is busy".to_string(),
+            ));
+        };
+        match manager.get_instance_mut(identifier) {
+            Ok(overlay) => set_lifecycle_state(overlay, state),
+            Err(e) => {
+                warn!("[OverlayManager] set_lifecycle_state failed: {e}");
+                Err(FlutterEmbedderError::InvalidHandle)
+            }
+        }
+    }
+
+    /// Turns an overlay's semantics (accessibility) tree on or off.
+    ///
+    /// # What it solves
+    /// Flutter only builds and sends semantics nodes while semantics are
+    /// enabled. Overlays enable them at startup because hover detection over
+    /// interactive widgets is a hit test on that tree; this call lets the host
+    /// follow a screen reader being started or stopped. While disabled the
+    /// tree is empty, so there is no hover detection, hand cursor or
+    /// hover-based auto-raise.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `enabled` - Whether the engine should keep the semantics tree up to date.
+    ///
+    /// # Returns
+    /// `false` if the overlay doesn't exist, its engine isn't running, or the
+    /// engine rejected the change.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_accessibility_enabled(Some("hud"), true);
+    /// ```
+    pub fn set_accessibility_enabled(&self, identifier: Option<&str>, enabled: bool) -> bool {
+        let Some(manager) = self.manager.try_lock() else {
+            return false;
+        };
+        match manager.get_instance(identifier) {
+            Ok(overlay) => match overlay.set_semantics_enabled(enabled) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("[OverlayManager] set_accessibility_enabled: {e}");
+                    false
+                }
+            },
+            Err(e) => {
+                warn!("[OverlayManager] set_accessibility_enabled failed: {e}");
+                false
+            }
+        }
+    }
+
+    /// Returns a snapshot of an overlay's semantics tree, ordered by node id.
+    ///
+    /// # What it solves
+    /// The tree Flutter sends for accessibility is otherwise only used for
+    /// hover hit testing. With the snapshot a host can feed its own screen
+    /// reader bridge or automation. Each node's `rect` is in its own
+    /// coordinate space; `transform_to_parent` maps it into the parent's.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Returns
+    /// The nodes, root (id `0`) first. Empty if the overlay doesn't exist, has
+    /// semantics disabled, or hasn't sent a tree yet.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// for node in manager.get_semantics_tree(Some("hud")) {
+    ///     println!("{}: {:?}", node.id, node.label);
+    /// }
+    /// ```
+    pub fn get_semantics_tree(&self, identifier: Option<&str>) -> Vec<ProcessedSemanticsNode> {
+        let Some(manager) = self.manager.try_lock() else {
+            return Vec::new();
+        };
+        manager
+            .get_instance(identifier)
+            .map(|overlay| overlay.semantics_snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Makes an overlay's engine reload the installed system fonts.
+    ///
+    /// # What it solves
+    /// The engine reads the system font collection once at startup, so fonts
+    /// installed or changed while the host runs don't show up until a restart.
+    /// Call this (or `reload_all_fonts`) on `WM_FONTCHANGE`.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Returns
+    /// `EngineNotRunning` if the overlay's engine isn't running, `InvalidHandle`
+    /// if the overlay doesn't exist, or the engine's error.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.reload_system_fonts(Some("hud"))?;
+    /// ```
+    pub fn reload_system_fonts(
+        &self,
+        identifier: Option<&str>,
+    ) -> Result<(), FlutterEmbedderError> {
+        let Some(manager) = self.manager.try_lock() else {
+            return Err(FlutterEmbedderError::OperationFailed(
+                "overlay manager is busy".to_string(),
+            ));
+        };
+        match manager.get_instance(identifier) {
+            Ok(overlay) => overlay.reload_system_fonts(),
+            Err(e) => {
+                warn!("[OverlayManager] reload_system_fonts failed: {e}");
+                Err(FlutterEmbedderError::InvalidHandle)
+            }
+        }
+    }
+
+    /// Makes every running overlay reload the installed system fonts.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// // In your WndProc
+    /// // case WM_FONTCHANGE:
+    /// if let Some(manager) = get_flutter_overlay_manager_handle() {
+    ///     manager.reload_all_fonts();
+    /// }
+    /// ```
+    pub fn reload_all_fonts(&self) {
+        if let Some(manager) = self.manager.try_lock() {
+            for (id, overlay) in &manager.active_instances {
+                if overlay.engine.0.is_null() {
+                    continue;
+                }
+                if let Err(e) = overlay.reload_system_fonts() {
+                    warn!("[OverlayManager:{id}] Failed to reload system fonts: {e}");
+                }
+            }
+        }
+    }
+
+    /// Tells an overlay's engine that the system is low on memory.
+    ///
+    /// # What it solves
+    /// A Flutter overlay keeps decoded images and rasterized layers cached for
+    /// as long as it runs. When the OS reports memory pressure, this makes the
+    /// engine drop those caches and run a Dart GC instead of waiting for the
+    /// process to get trimmed.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Returns
+    /// `EngineNotRunning` if the overlay's engine isn't running, `InvalidHandle`
+    /// if the overlay doesn't exist, or the engine's error.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.notify_low_memory(Some("inventory"))?;
+    /// ```
+    pub fn notify_low_memory(&self, identifier: Option<&str>) -> Result<(), FlutterEmbedderError> {
+        let Some(manager) = self.manager.try_lock() else {
+            return Err(FlutterEmbedderError::OperationFailed(
+                "overlay manager is busy".to_string(),
+            ));
+        };
+        match manager.get_instance(identifier) {
+            Ok(overlay) => overlay.notify_low_memory(),
+            Err(e) => {
+                warn!("[OverlayManager] notify_low_memory failed: {e}");
+                Err(FlutterEmbedderError::InvalidHandle)
+            }
+        }
+    }
+
+    /// Sends the low-memory notification to every running overlay.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// // When a CreateMemoryResourceNotification handle signals low memory:
+    /// if let Some(manager) = get_flutter_overlay_manager_handle() {
+    ///     manager.notify_all_low_memory();
+    /// }
+    /// ```
+    pub fn notify_all_low_memory(&self) {
+        if let Some(manager) = self.manager.try_lock() {
+            for (id, overlay) in &manager.active_instances {
+                if overlay.engine.0.is_null() {
+                    continue;
+                }
+                if let Err(e) = overlay.notify_low_memory() {
+                    warn!("[OverlayManager:{id}] Failed to send the low-memory warning: {e}");
+                }
+            }
+        }
+    }
+
+    /// Requests that the topmost active overlay under the cursor set the mouse cursor style.
+    /// Call this from your `WndProc` when handling `WM_SETCURSOR`.
+    ///
+    /// Allows the Flutter UI to control the appearance of the mouse cursor, for example,
+    /// changing it from an arrow to a text-input I-beam when hovering over a text field,
+    /// or to a hand pointer over a button. This provides essential visual feedback to the user.
+    ///
+    /// # Returns
+    /// * `Some(LRESULT(1))` if a Flutter overlay handled the cursor request.
+    /// * `None` if no overlay handled the request.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// // In your WndProc
+    /// // case WM_SETCURSOR:
+    /// if let Some(manager) = get_flutter_overlay_manager_handle() {
+    ///     if let Some(result) = manager.set_flutter_cursor(hwnd, lparam, original_hwnd) {
+    ///         return result; // Flutter handled the cursor
+    ///     }
+    /// }
+    /// // Default handling...
+    /// ```
+    pub fn set_flutter_cursor(
+        &self,
+        hwnd_for_setcursor_message: HWND,
+        lparam: LPARAM,
+        original_hwnd: HWND,
+    ) -> Option<LRESULT> {
+        if let Some(manager) = self.manager.try_lock() {
+            manager.handle_set_cursor(hwnd_for_setcursor_message, lparam, original_hwnd)
+        } else {
+            None
+        }
+    }
+
+    /// Notifies all active overlays of a window or render area resize.
+    ///
+    /// Informs all Flutter instances about the new size of the window, allowing them
+    /// to recalculate layouts and adapt to the new resolution. It also ensures the
+    /// underlying GPU textures are resized correctly to prevent stretching or clipping.
+    ///
+    /// The overlays are resized on the first `tick_overlays` after the size has
+    /// stopped changing for the delay set with `set_resize_debounce_ms`
+    /// (100 ms by default). The render-target view cache is dropped at once.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.resize_flutter_overlays(&my_swap_chain, 0, 0, new_width, new_height);
+    /// ```
+    pub fn resize_flutter_overlays(
+        &self,
+        swap_chain: &IDXGISwapChain,
+        x_pos: i32,
+        y_pos: i32,
+        width: u32,
+        height: u32,
+    ) {
+        rtv_cache::invalidate();
+        if let Some(mut manager) = self.manager.try_lock() {
+            let request = ResizeRequest {
+                x: x_pos,
+                y: y_pos,
+                width,
+                height,
+            };
+            match manager.resize_debounce.push(request, Instant::now()) {
+                Some(_) => manager.handle_resize(swap_chain, x_pos, y_pos, width, height),
+                None => manager.swap_chain = Some(swap_chain.clone()),
+            }
+        }
+    }
+
+    /// Sets how long the size passed to `resize_flutter_overlays` must stay the
+    /// same before the overlays are resized.
+    ///
+    /// # What it solves
+    /// Dragging the window edge sends a resize per mouse move, and each one
+    /// recreates the shared textures and EGL surfaces of every overlay, which
+    /// stutters. With a debounce only the size the drag settles on is applied;
+    /// until then the overlays keep their old textures. The settled size is
+    /// applied from `tick_overlays`, so keep ticking while the user holds the
+    /// edge still. A zero size (minimized window) is always applied at once.
+    ///
+    /// # Arguments
+    /// * `ms`: Delay in milliseconds. `0` resizes on every call, as before.
+    ///   Defaults to 100.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_resize_debounce_ms(250);
+    /// ```
+    pub fn set_resize_debounce_ms(&self, ms: u32) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.resize_debounce.set_delay(Duration::from_millis(ms.into()));
+            manager.apply_settled_resize();
+        }
+    }
+
+    /// Enables or disables pooling of software-renderer pixel buffers across resizes.
+    ///
+    /// # What it solves
+    /// Dragging the window edge resizes overlays every frame, and each growing
+    /// resize reallocates the CPU pixel buffer. With pooling on, buffers are
+    /// allocated at power-of-two capacities and recycled, so most resizes reuse
+    /// an existing allocation. Costs up to 2x the buffer memory. Disabling it
+    /// frees the idle buffers. Has no effect on OpenGL overlays.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_pixel_buffer_pooling(true);
+    /// ```
+    pub fn set_pixel_buffer_pooling(&self, enabled: bool) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            if !enabled {
+                manager.pixel_buffer_pool = None;
+            } else if manager.pixel_buffer_pool.is_none() {
+                manager.pixel_buffer_pool = Some(PixelBufferPool::default());
+            }
+        }
+    }
+
+    /// Caches the render-target view returned by `get_back_buffer_rtv`.
+    ///
+    /// # What it solves
+    /// A host that composites overlays itself needs an RTV of the back buffer
+    /// every frame. With the cache on, `get_back_buffer_rtv` creates it once
+    /// and then hands out the same view without calling `GetBuffer` or
+    /// `CreateRenderTargetView`, until the next resize. Off by default.
+    ///
+    /// The cached view holds a reference to the back buffer, and
+    /// `ResizeBuffers` fails with `DXGI_ERROR_INVALID_CALL` while it exists. So
+    /// with the cache on, call `resize_flutter_overlays` or
+    /// `invalidate_rtv_cache` before the original `ResizeBuffers`, and release
+    /// your own copies of the view. Disabling the cache drops the view.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_rtv_cache_enabled(true);
+    /// ```
+    pub fn set_rtv_cache_enabled(&self, enabled: bool) {
+        rtv_cache::set_enabled(enabled);
+    }
+
+    /// Returns a render-target view of the host back buffer.
+    ///
+    /// # What it solves
+    /// Lets the host bind the back buffer the overlays draw into. A new view is
+    /// created on each call unless `set_rtv_cache_enabled` turned the cache on,
+    /// in which case the view is reused until the next
+    /// `resize_flutter_overlays` or `invalidate_rtv_cache`.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if let Some(rtv) = manager.get_back_buffer_rtv() {
+    ///     unsafe { context.OMSetRenderTargets(Some(&[Some(rtv)]), None) };
+    /// }
+    /// ```
+    pub fn get_back_buffer_rtv(&self) -> Option<ID3D11RenderTargetView> {
+        let swap_chain = self.manager.try_lock()?.swap_chain.clone()?;
+        rtv_cache::back_buffer_rtv(&swap_chain)
+    }
+
+    /// Drops the cached back buffer render-target view.
+    ///
+    /// # What it solves
+    /// The cache is cleared automatically on `resize_flutter_overlays`. Call this
+    /// when the host recreates or resizes its swap chain some other way (for
+    /// example `ResizeBuffers` without notifying the manager), since the cached
+    /// view holds a reference to the old back buffer and keeps it alive. Works
+    /// while the manager is busy. Does nothing unless the cache is enabled.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.invalidate_rtv_cache();
+    /// unsafe { swap_chain.ResizeBuffers(0, w, h, DXGI_FORMAT_UNKNOWN, flags)? };
+    /// ```
+    pub fn invalidate_rtv_cache(&self) {
+        rtv_cache::invalidate();
+    }
+
+    /// Shuts down a specific Flutter overlay instance, releasing all its resources.
+    /// # Arguments
+    /// * `identifier`: The unique identifier of the overlay to shut down.
+    pub fn shutdown_instance(&self, identifier: &str) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Err(e) = manager.shutdown_instance(identifier)
+        {
+            error!("[OverlayManagerHandle] Error during shutdown of instance {identifier}: {e}");
+        }
+    }
+
+    /// Shuts down all currently active Flutter overlay instances.
+    /// # Example
+    /// ```rust, no_run
+    /// // In your application's exit/cleanup logic:
+    /// if let Some(manager) = get_flutter_overlay_manager_handle() {
+    ///     manager.shutdown_all_instances();
+    /// }
+    /// ```
+    pub fn shutdown_all_instances(&self) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.shutdown_all_instances();
+        }
+    }
+
+    /// Brings the specified overlay to the top of the rendering order (Z-order).
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// // When a popup is shown
+    /// manager.bring_to_front(Some("popup_dialog"));
+    /// ```
+    pub fn bring_to_front(&self, identifier: Option<&str>) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.bring_to_front(identifier);
+        }
+    }
+
+    /// Confines the OS cursor to an overlay while it has focus.
+    ///
+    /// # What it solves
+    /// A chat box or menu that captures the mouse should keep the cursor from
+    /// wandering onto the game or a second monitor, like a modal window. While
+    /// enabled, the cursor is clipped to the overlay's screen rect whenever the
+    /// overlay is focused and visible and the host window is in the
+    /// foreground. The clip follows moves and resizes and is released when
+    /// the overlay loses focus, is hidden or shut down, or when this is called
+    /// with `false`. Only one overlay can clip at a time; enabling another
+    /// moves the clip. Rotation is ignored.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `enabled` - `true` to confine the cursor to this overlay, `false` to release it.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_focus(Some("chat_ui"));
+    /// manager.set_cursor_clip(Some("chat_ui"), true);
+    /// ```
+    pub fn set_cursor_clip(&self, identifier: Option<&str>, enabled: bool) {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return;
+        };
+        let id = match manager.get_instance(identifier) {
+            Ok(overlay) => overlay.name.clone(),
+            Err(e) => {
+                warn!("[OverlayManager] set_cursor_clip: {e}");
+                return;
+            }
+        };
+        if enabled {
+            manager.cursor_clip.overlay_id = Some(id);
+        } else if manager.cursor_clip.overlay_id.as_ref() == Some(&id) {
+            manager.cursor_clip.overlay_id = None;
+        }
+        manager.sync_cursor_clip();
+    }
+
+    /// Moves an overlay to a position in the rendering order (Z-order).
+    ///
+    /// # What it solves
+    /// `bring_to_front` can only put an overlay on top. A debug panel or a
+    /// layered HUD that reorders overlays needs to place one in the middle of
+    /// the stack. Focus is not changed. See `get_overlay_order` for the
+    /// current order.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `index` - New position, 0 being drawn first (bottom). Past the end puts it on top.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// // Keep the minimap just above the world markers.
+    /// manager.set_z_index(Some("minimap"), 1);
+    /// ```
+    pub fn set_z_index(&self, identifier: Option<&str>, index: usize) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.set_z_index(identifier, index);
+        }
+    }
+
+    /// Moves an overlay to the bottom of the rendering order (Z-order), so
+    /// every other overlay is drawn over it. Focus is not changed.
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.send_to_back(Some("background_hud"));
+    /// ```
+    pub fn send_to_back(&self, identifier: Option<&str>) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.set_z_index(identifier, 0);
+        }
+    }
+
+    /// Sets keyboard focus to the specified overlay, which also brings it to the front.
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// // When the user clicks on the chat input field
+    /// manager.set_focus(Some("chat_ui"));
+    /// ```
+    pub fn set_focus(&self, identifier: Option<&str>) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.set_keyboard_focus(identifier);
+        }
+    }
+
+    /// Routes Flutter engine and Dart log output to `handler` instead of the `log` crate.
+    ///
+    /// # What it solves
+    /// Lets the host surface Dart `print` / `debugPrint` output in its own
+    /// systems, such as an in-game dev console or a crash reporter. The handler
+    /// receives `(tag, message)`, applies to every overlay in the process, and
+    /// may run on any engine thread, so keep it cheap and non-blocking. If it
+    /// panics, that line is logged through `log` instead.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_engine_log_handler(Arc::new(|tag, message| {
+    ///     dev_console::push(format!("[{tag}] {message}"));
+    /// }));
+    /// ```
+    pub fn set_engine_log_handler(&self, handler: EngineLogHandler) {
+        project_args::set_engine_log_handler(Some(handler));
+    }
+
+    /// Removes the handler set by `set_engine_log_handler`; engine logs go
+    /// through the `log` crate again.
+    pub fn clear_engine_log_handler(&self) {
+        project_args::set_engine_log_handler(None);
+    }
+
+    /// Sets how key events are routed while no overlay has keyboard focus.
+    ///
+    /// # What it solves
+    /// A freshly shown menu can start receiving keys before the host gets around
+    /// to calling [`set_focus`](Self::set_focus). The default,
+    /// [`KeyFallback::Drop`], leaves unfocused key events to the host.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_key_fallback_policy(KeyFallback::TopmostVisible);
+    /// ```
+    pub fn set_key_fallback_policy(&self, policy: KeyFallback) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.key_fallback = policy;
+        }
+    }
+
+    /// Sets which overlay key events are delivered to.
+    ///
+    /// # What it solves
+    /// By default only the focused overlay gets keys, so shortcuts meant for an
+    /// editor are dropped while focus sits on another overlay or nowhere.
+    /// [`KeyRouting::TopmostVisible`] sends keys to the topmost overlay that
+    /// accepts input, and [`KeyRouting::UnderCursor`] to the topmost one with
+    /// an interactive widget under the cursor, using focus when the cursor is
+    /// over none. Key-up goes wherever the policy points at that moment, so
+    /// moving the cursor between overlays while a key is held can leave the
+    /// first one seeing the key as still down. Modal overlays still take all
+    /// keys.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_key_routing(KeyRouting::UnderCursor);
+    /// ```
+    pub fn set_key_routing(&self, routing: KeyRouting) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.key_routing = routing;
+        }
+    }
+
+    /// Checks if the specified overlay currently has keyboard focus.
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if manager.is_focused(Some("chat_ui")) {
+    ///     // Don't process game movement keys
+    /// }
+    /// ```
+    pub fn is_focused(&self, identifier: Option<&str>) -> bool {
+        if let Some(manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance(identifier)
+        {
+            return manager.focused_overlay_id.as_deref() == Some(overlay.name.as_str());
+        }
+        false
+    }
+
+    pub fn has_rendered_frame(&self) -> bool {
+        if let Some(manager) = self.manager.try_lock() {
+            return manager
+                .active_instances
+                .values()
+                .any(|o| o.has_first_frame());
+        }
+        false
+    }
+
+    /// Blocks until an overlay has produced its first frame or `timeout` passes.
+    ///
+    /// # What it solves
+    /// Showing a host window or menu right after `init_instance` composites an
+    /// empty texture for the first few frames. Waiting here first means the UI
+    /// appears already painted. The manager is only locked briefly between
+    /// polls, so other threads keep working; don't call this from the thread
+    /// that owns the only message pump the overlay depends on.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `timeout` - How long to wait at most.
+    ///
+    /// # Returns
+    /// `true` once the first frame is there, `false` on timeout or if the
+    /// overlay doesn't exist.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.init_instance(&my_swap_chain, &bundle, "hud", None, None, None);
+    /// if !manager.wait_first_frame(Some("hud"), Duration::from_secs(2)) {
+    ///     warn!("hud is slow to start");
+    /// }
+    /// ```
+    pub fn wait_first_frame(&self, identifier: Option<&str>, timeout: Duration) -> bool {
+        const POLL_INTERVAL: Duration = Duration::from_millis(5);
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(manager) = self.manager.try_lock() {
+                match manager.get_instance(identifier) {
+                    Ok(overlay) if overlay.has_first_frame() => return true,
+                    Ok(_) => {}
+                    Err(_) => return false,
+                }
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            std::thread::sleep(POLL_INTERVAL.min(deadline - now));
+        }
+    }
+
+    /// Checks that an overlay's Dart isolate still answers platform messages.
+    ///
+    /// # What it solves
+    /// A hung isolate keeps its engine handle and last frame, so nothing else
+    /// reports the freeze. This calls a method `flutter/navigation` doesn't
+    /// implement and waits for the framework's empty reply, so the Dart app
+    /// needs no code for it as long as it starts with `runApp`. The manager is
+    /// unlocked while waiting.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `timeout` - How long to wait for the reply.
+    ///
+    /// # Returns
+    /// `true` if the reply arrived in time, `false` on timeout, if the engine
+    /// isn't running or if the overlay doesn't exist.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if !manager.is_engine_responsive(Some("hud"), Duration::from_millis(500)) {
+    ///     warn!("hud stopped responding");
+    /// }
+    /// ```
+    pub fn is_engine_responsive(&self, identifier: Option<&str>, timeout: Duration) -> bool {
+        let heartbeat = {
+            let Some(manager) = self.manager.try_lock() else {
+                return false;
+            };
+            let Ok(overlay) = manager.get_instance(identifier) else {
+                return false;
+            };
+            match send_heartbeat(overlay) {
+                Ok(heartbeat) => heartbeat,
+                Err(e) => {
+                    warn!("[OverlayManager] Heartbeat for '{:?}' not sent: {e:?}", identifier);
+                    return false;
+                }
+            }
+        };
+        heartbeat.wait(timeout)
+    }
+
+    /// Restarts an overlay automatically when it keeps failing or stops
+    /// responding.
+    ///
+    /// # What it solves
+    /// A long-running HUD can get stuck after a driver hiccup or a hung isolate,
+    /// and nothing restarts it unless the host polls `is_engine_responsive`
+    /// itself. With a policy set, `tick_overlays` counts ticks whose frame
+    /// request fails and keeps a heartbeat in flight without blocking. The
+    /// heartbeat check only arms once a first heartbeat has been answered, which
+    /// needs an app started with `runApp`. If the first heartbeat goes
+    /// unanswered, a warning is logged and no further heartbeats are sent until
+    /// it is answered, so a silent app is neither restarted nor flooded with
+    /// probes. When either check trips, the overlay is shut
+    /// down and started again from the same bundle and arguments, keeping its
+    /// position, Z-order, visibility and effect config. The callback from
+    /// `set_on_overlay_restarted` is told afterwards.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `policy` - Thresholds for the two checks.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.enable_auto_restart(
+    ///     Some("hud"),
+    ///     RestartPolicy {
+    ///         max_consecutive_errors: 120,
+    ///         unresponsive_timeout: Some(Duration::from_secs(3)),
+    ///     },
+    /// )?;
+    /// ```
+    pub fn enable_auto_restart(
+        &self,
+        identifier: Option<&str>,
+        policy: RestartPolicy,
+    ) -> Result<(), FlutterEmbedderError> {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return Err(FlutterEmbedderError::OperationFailed(
+                "overlay manager is busy".to_string(),
+            ));
+        };
+        let name = match manager.get_instance(identifier) {
+            Ok(overlay) => overlay.name.clone(),
+            Err(e) => {
+                warn!("[OverlayManager] enable_auto_restart failed: {e}");
+                return Err(FlutterEmbedderError::InvalidHandle);
+            }
+        };
+        manager.watchdogs.insert(name, Watchdog::new(policy));
+        Ok(())
+    }
+
+    /// Stops watching an overlay set up with `enable_auto_restart`.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    pub fn disable_auto_restart(&self, identifier: Option<&str>) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(name) = manager.get_instance(identifier).map(|ov| ov.name.clone())
+        {
+            manager.watchdogs.remove(&name);
+        }
+    }
+
+    /// Sets the callback run after each watchdog restart, or removes it with
+    /// `None`.
+    ///
+    /// # What it solves
+    /// A restarted overlay loses its Dart state, so the host usually needs to
+    /// resend whatever it pushed at startup, or report the crash. The callback
+    /// gets the overlay id, the `RestartReason` and whether the new instance
+    /// started. It runs after the manager is unlocked, so it may call back into
+    /// the handle.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_on_overlay_restarted(Some(Arc::new(|id, reason, started| {
+    ///     warn!("{id} restarted ({reason:?}), running: {started}");
+    /// })));
+    /// ```
+    pub fn set_on_overlay_restarted(&self, callback: Option<OverlayRestartCallback>) {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.restart_callback = callback;
+        }
+    }
+
+    /// Returns the startup phase timings of an overlay.
+    ///
+    /// # What it solves
+    /// Shows where overlay startup time goes (DLL load, renderer setup, engine
+    /// run, first frame) so slow loads can be narrowed down.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if let Some(t) = manager.get_startup_timings(Some("hud")) {
+    ///     println!("{t:?}");
+    /// }
+    /// ```
+    pub fn get_startup_timings(&self, identifier: Option<&str>) -> Option<StartupTimings> {
+        if let Some(manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance(identifier)
+        {
+            return Some(overlay.get_startup_timings());
+        }
+        None
+    }
+
+    /// Returns per-overlay frame upload timings.
+    ///
+    /// # What it solves
+    /// `tick_overlays` updates every overlay in one call, so the host can't see
+    /// which one is expensive. Each overlay times the part of its `tick` that
+    /// copies a new frame into its texture, the CPU upload on the software
+    /// renderer or the copy out of the ANGLE texture on OpenGL, and keeps a
+    /// rolling average over the last 60 frames. Hidden overlays report zeroes.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Returns
+    /// `None` if the overlay doesn't exist or the manager is busy.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if let Some(stats) = manager.get_fps_stats(Some("hud")) {
+    ///     debug_text(&format!("hud {:.2} ms avg", stats.avg_frame_ms));
+    /// }
+    /// ```
+    pub fn get_fps_stats(&self, identifier: Option<&str>) -> Option<FpsStats> {
+        let manager = self.manager.try_lock()?;
+        Some(manager.get_instance(identifier).ok()?.get_fps_stats())
+    }
+
+    /// Renders one fresh frame of an overlay synchronously.
+    ///
+    /// # What it solves
+    /// Golden-image tests need to know the texture holds the frame for the
+    /// state they just set up, which a free-running present loop can't
+    /// promise. This waits for the overlay's task runner to finish every task
+    /// that is already due, schedules a frame, waits for the engine to present
+    /// it and copies it into the texture, so `save_overlay_png` right after
+    /// sees exactly that frame. It blocks for up to five seconds and holds the
+    /// manager meanwhile; it is meant for tests and tooling, not the game loop.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `context` - Device context to copy the frame with, as for `tick_overlays`.
+    ///
+    /// # Returns
+    /// `Err(EngineNotRunning)` if the engine isn't running, or `OperationFailed`
+    /// if the manager is busy or no frame arrived in time.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// let context = manager.get_d3d_context().unwrap();
+    /// manager.post_string(Some("hud"), "loaded");
+    /// manager.render_one_frame(Some("hud"), &context)?;
+    /// manager.save_overlay_png(Some("hud"), "hud_loaded.png", false, AlphaMode::Straight)?;
+    /// ```
+    pub fn render_one_frame(
+        &self,
+        identifier: Option<&str>,
+        context: &ID3D11DeviceContext,
+    ) -> Result<(), FlutterEmbedderError> {
+        let Some(manager) = self.manager.try_lock() else {
+            return Err(FlutterEmbedderError::OperationFailed(
+                "overlay manager is busy".to_string(),
+            ));
+        };
+        let overlay = manager.get_instance(identifier).map_err(|e| {
+            warn!("[OverlayManager] render_one_frame failed: {e}");
+            FlutterEmbedderError::InvalidHandle
+        })?;
+        overlay.render_one_frame(context, RENDER_ONE_FRAME_TIMEOUT)
+    }
+
+    /// Returns the renderer an overlay ended up with.
+    ///
+    /// # What it solves
+    /// With automatic selection `init_instance` falls back to software when
+    /// ANGLE fails, and only says so in the log. This tells the host which
+    /// renderer is actually in use, e.g. to show it on a performance HUD.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Returns
+    /// `None` if the overlay doesn't exist or the manager is busy.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if manager.get_renderer_type(Some("hud")) == Some(RendererType::Software) {
+    ///     warn!("hud is running on the software renderer");
+    /// }
+    /// ```
+    pub fn get_renderer_type(&self, identifier: Option<&str>) -> Option<RendererType> {
+        let manager = self.manager.try_lock()?;
+        Some(manager.get_instance(identifier).ok()?.renderer_type.clone())
+    }
+
+    /// Returns when an overlay last put a new frame into its texture.
+    ///
+    /// # What it solves
+    /// Input latency is the time from an input event to the frame that shows
+    /// its effect. Comparing this against the `Instant` an input was sent gives
+    /// that delay, down to the tick that copied the frame. It is a single
+    /// atomic load, so it can be polled every frame.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Returns
+    /// `None` if the overlay doesn't exist or hasn't produced a frame yet.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// let clicked_at = Instant::now();
+    /// // ... later frames ...
+    /// if let Some(shown_at) = manager.get_last_present_time(Some("hud"))
+    ///     && shown_at > clicked_at
+    /// {
+    ///     println!("click to frame: {:?}", shown_at - clicked_at);
+    /// }
+    /// ```
+    pub fn get_last_present_time(&self, identifier: Option<&str>) -> Option<Instant> {
+        let manager = self.manager.try_lock()?;
+        manager.get_instance(identifier).ok()?.get_last_present_time()
+    }
+
+    /// Checks whether an overlay's engine is running and presenting frames.
+    ///
+    /// # What it solves
+    /// A crashed engine leaves the last frame on screen, so it looks the same
+    /// as a working one. This returns `true` when the engine handle is live and
+    /// a frame was presented within the timeout set with
+    /// `set_presenting_timeout` (5 seconds by default). A new overlay gets that
+    /// long for its first frame.
+    ///
+    /// It only tells whether frames arrive, not whether the engine is healthy:
+    /// Flutter presents nothing while the UI is idle, so an idle overlay looks
+    /// exactly like a hung one here. Use `is_engine_responsive`, which waits for
+    /// a heartbeat reply, to tell the two apart.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Returns
+    /// `false` if the overlay doesn't exist, the manager is busy, or no frame
+    /// arrived in time.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if !manager.is_presenting(Some("hud")) {
+    ///     debug!("hud is idle or stuck");
+    /// }
+    /// ```
+    pub fn is_presenting(&self, identifier: Option<&str>) -> bool {
+        let Some(manager) = self.manager.try_lock() else {
+            return false;
+        };
+        let timeout = manager.presenting_timeout;
+        manager
+            .get_instance(identifier)
+            .is_ok_and(|overlay| overlay.is_presenting(timeout))
+    }
+
+    /// Sets how long `is_presenting` waits for a frame before reporting an
+    /// overlay as not presenting.
+    ///
+    /// # Arguments
+    /// * `timeout` - Longest accepted gap between presented frames.
     ///
     /// # Example
-    /// ```rust,no_run
+    /// ```rust, no_run
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// manager.clear_all_primitives();
+    /// manager.set_presenting_timeout(Duration::from_secs(2));
     /// ```
-    pub fn clear_all_primitives(&self) {
+    pub fn set_presenting_timeout(&self, timeout: Duration) {
         if let Some(mut manager) = self.manager.try_lock() {
-            for overlay in manager.active_instances.values_mut() {
-                overlay.clear_all_queued_primitives();
-            }
+            manager.presenting_timeout = timeout;
         }
     }
 
-    /// Clears primitives from a specific group for a specific overlay.
+    /// Saves an overlay's current frame as a PNG file.
+    ///
+    /// # What it solves
+    /// Bug reports and visual regression tests need a picture of exactly what
+    /// an overlay shows. This reads the overlay texture back from the GPU,
+    /// converts it from BGRA with its row padding to RGBA and encodes it, in
+    /// one call. With `include_effects` the overlay is first drawn with its
+    /// effect, opacity and stencil mask into an offscreen target, without the
+    /// game behind it. Call it on the render thread; it waits for the GPU.
+    ///
+    /// The engine renders premultiplied alpha, while PNG stores straight
+    /// alpha, so pass `AlphaMode::Straight` for a file that image viewers
+    /// show correctly. `AlphaMode::Premultiplied` writes the engine's bytes
+    /// unchanged.
     ///
     /// # Arguments
-    /// * `identifier`: The unique name of the target overlay. `None` targets the single active overlay.
-    /// * `group_id`: The ID of the group to clear.
-    pub fn clear_primitives(&self, identifier: Option<&str>, group_id: &str) {
-        if let Some(mut manager) = self.manager.try_lock()
-            && let Ok(overlay) = manager.get_instance_mut(identifier)
-        {
-            overlay.clear_primitives(group_id);
-        }
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `path` - Destination file, overwritten if it exists.
+    /// * `include_effects` - Capture the composited look instead of the raw frame.
+    /// * `alpha_mode` - Alpha convention of the written pixels.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// let result = manager.save_overlay_png(Some("hud"), "hud.png", true, AlphaMode::Straight);
+    /// if let Err(e) = result {
+    ///     eprintln!("snapshot failed: {e}");
+    /// }
+    /// ```
+    pub fn save_overlay_png(
+        &self,
+        identifier: Option<&str>,
+        path: impl AsRef<Path>,
+        include_effects: bool,
+        alpha_mode: AlphaMode,
+    ) -> Result<(), FlutterEmbedderError> {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return Err(FlutterEmbedderError::OperationFailed(
+                "overlay manager is busy".to_string(),
+            ));
+        };
+        let Some(context) = manager.shared_d3d_context.clone() else {
+            return Err(FlutterEmbedderError::OperationFailed(
+                "no D3D11 context; initialize the manager first".to_string(),
+            ));
+        };
+        let time = manager.start_time.elapsed().as_secs_f32();
+        let overlay = manager.get_instance_mut(identifier).map_err(|e| {
+            warn!("[OverlayManager] save_overlay_png failed: {e}");
+            FlutterEmbedderError::InvalidHandle
+        })?;
+        overlay.save_png(&context, path.as_ref(), include_effects, alpha_mode, time)
     }
 
-    /// Takes a snapshot of all submitted 3D primitive data, making it ready for rendering.
-    ///
-    /// This function is the core of the "Submit & Latch" system, which resolves rendering flickers and
-    /// disappearing primitives. The problem occurs because the game's update logic (which submits new data)
-    /// and the render hook (`new_present`, which draws the data) can run at different rates.
+    /// Reads an overlay's current frame back into CPU memory.
     ///
-    /// By calling this function once at the beginning of a render frame, we "latch" a stable, complete
-    /// copy of the data into a dedicated render buffer. This guarantees that all draw calls within the
-    /// same frame use the exact same data, eliminating the race condition that causes  visual artifacts.
+    /// # What it solves
+    /// Automated UI tests want to compare pixels directly instead of going
+    /// through a PNG file. This returns the raw Flutter frame, without
+    /// effects, as tightly packed BGRA8 rows of `width * 4` bytes, whatever
+    /// row padding the GPU copy had. Software overlays copy their CPU frame
+    /// buffer; OpenGL overlays go through a staging texture that is created for
+    /// the call and released afterwards, so call it on the render thread and
+    /// expect it to wait for the GPU.
+    ///
+    /// The engine renders premultiplied alpha. `AlphaMode::Premultiplied`
+    /// returns those bytes unchanged; `AlphaMode::Straight` divides the color
+    /// channels by alpha first, for comparing against straight-alpha
+    /// reference images.
     ///
-    /// ## Usage
-    /// Call this function at the very beginning of your `new_present` render hook.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `alpha_mode` - Alpha convention of the returned pixels.
     ///
-    /// ```rust
-    /// // in dxgi_present_hook.rs
-    /// pub(crate) extern "system" fn new_present(...) -> HRESULT {
-    ///     // Latch the data at the start of the frame.
-    ///     if let Some(om) = get_flutter_overlay_manager_handle() {
-    ///         om.latch_all_queued_primitives();
-    ///     }
+    /// # Returns
+    /// `(width, height, pixels)`, or `None` if the overlay doesn't exist, the
+    /// manager is busy or the read-back failed (logged).
     ///
-    ///     // ... rest of the render hook ...
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// let capture = manager.capture_overlay_pixels(Some("hud"), AlphaMode::Premultiplied);
+    /// if let Some((width, height, bgra)) = capture {
+    ///     let center = ((height / 2 * width + width / 2) * 4) as usize;
+    ///     assert_eq!(bgra[center + 3], 255, "hud center should be opaque");
     /// }
     /// ```
-    pub fn latch_all_queued_primitives(&self) {
-        if let Some(mut manager) = self.manager.try_lock() {
-            manager.latch_all_queued_primitives();
+    pub fn capture_overlay_pixels(
+        &self,
+        identifier: Option<&str>,
+        alpha_mode: AlphaMode,
+    ) -> Option<(u32, u32, Vec<u8>)> {
+        let manager = self.manager.try_lock()?;
+        let context = manager.shared_d3d_context.as_ref()?;
+        let overlay = manager.get_instance(identifier).ok()?;
+        match overlay.capture_pixels(context, alpha_mode) {
+            Ok(pixels) => Some((overlay.width, overlay.height, pixels)),
+            Err(e) => {
+                warn!("[OverlayManager:{}] capture_overlay_pixels failed: {e}", overlay.name);
+                None
+            }
         }
     }
 
-    /// Latches all queued 3D text for all visible overlays.
+    /// Returns the Dart VM service URI of an overlay's engine.
     ///
-    /// Call this once per frame, typically alongside `latch_all_queued_primitives()`,
-    /// to prepare text geometry for rendering.
+    /// # What it solves
+    /// Attaching DevTools to a profile build needs the VM service URI, which
+    /// includes a random auth code, so the port alone isn't enough. The engine
+    /// logs the URI once the service is listening; it is captured from that
+    /// line and also logged with the overlay's name. Debug builds start the
+    /// service on their own; profile builds need
+    /// `EngineFlags::new().enable_vm_service(port)` (`0` picks a free port)
+    /// passed to `set_engine_flags` before the overlay is created. Release
+    /// builds have no VM service.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Returns
+    /// `None` if the overlay doesn't exist or the service hasn't reported in yet.
     ///
     /// # Example
     /// ```rust, no_run
-    /// // in dxgi_present_hook.rs
-    /// pub(crate) extern "system" fn new_present(...) -> HRESULT {
-    ///     if let Some(om) = get_flutter_overlay_manager_handle() {
-    ///         om.latch_all_queued_primitives();
-    ///         om.latch_all_queued_text();
-    ///     }
-    ///     // ... rest of the render hook ...
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if let Some(uri) = manager.get_vm_service_uri(Some("hud")) {
+    ///     println!("attach DevTools to {uri}");
     /// }
     /// ```
-    pub fn latch_all_queued_text(&self) {
-        if let Some(mut manager) = self.manager.try_lock() {
-            manager.latch_all_queued_text();
-        }
+    pub fn get_vm_service_uri(&self, identifier: Option<&str>) -> Option<String> {
+        let manager = self.manager.try_lock()?;
+        manager.get_instance(identifier).ok()?.get_vm_service_uri()
     }
 
-    /// Registers a font atlas for 3D text rendering on a specific overlay.
-    ///
-    /// A font atlas is a texture containing all the glyphs for a font, along with
-    /// metadata about each glyph's position, size, and spacing. Must be called before
-    /// any `set_text` calls that use this font.
-    ///
+    /// Sets the visibility of a Flutter overlay. An invisible overlay is not rendered and does not receive input.
     /// # Arguments
-    /// * `identifier` - The target overlay. `None` targets the single active overlay.
-    /// * `spec` - The font atlas to register ([`FontAtlasSpec`]).
-    pub fn register_font_atlas(&self, identifier: Option<&str>, spec: FontAtlasSpec) {
-        let FontAtlasSpec {
-            font_id,
-            texture,
-            sampler,
-            glyphs,
-            line_height,
-            base_font_size,
-        } = spec;
-        if let Some(mut manager) = self.manager.try_lock()
-            && let Ok(overlay) = manager.get_instance_mut(identifier)
-        {
-            overlay.register_font_atlas(
-                &font_id,
-                texture,
-                sampler,
-                glyphs,
-                line_height,
-                base_font_size,
-            );
-        }
-    }
-
-    /// Unregisters a font atlas and clears all text using it.
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `is_visible` - Whether the overlay should be visible.
     ///
-    /// # Arguments
-    /// * `identifier` - The target overlay. `None` targets the single active overlay.
-    /// * `font_id` - The font identifier to unregister.
-    pub fn unregister_font_atlas(&self, identifier: Option<&str>, font_id: &str) {
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// // When the player presses the escape key:
+    /// manager.set_visibility(Some("pause_menu"), true);
+    /// ```
+    pub fn set_visibility(&self, identifier: Option<&str>, is_visible: bool) {
         if let Some(mut manager) = self.manager.try_lock()
             && let Ok(overlay) = manager.get_instance_mut(identifier)
         {
-            overlay.unregister_font_atlas(font_id);
+            overlay.set_visibility(is_visible);
         }
     }
 
-    /// Sets pre-built 3D text vertices for rendering.
-    ///
-    /// Use the `text_presets::generate_text_vertices` helper to create the vertices
-    /// from a text string and font atlas.
+    /// Returns the state restoration data an overlay's framework last stored.
     ///
+    /// # What it solves
+    /// Widgets using `RestorationMixin` (scroll positions, text fields, tabs)
+    /// write their state to `flutter/restoration`. Read it before destroying
+    /// the overlay or on shutdown, save it, and pass it to
+    /// `set_restoration_data` on the next run to bring that state back.
     /// # Arguments
-    /// * `identifier` - The target overlay. `None` targets the single active overlay.
-    /// * `font_id` - The font atlas to use (must be registered first)
-    /// * `group_id` - Unique identifier for this text group (for updates/removal)
-    /// * `vertices` - Pre-built text vertices (from `generate_text_vertices`)
-    /// * `options` - Rendering options (depth, blend mode, etc.)
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
     ///
     /// # Example
     /// ```rust, no_run
-    /// use flutter_embedder::software_renderer::d3d11_compositor::text_presets;
-    /// use flutter_embedder::software_renderer::d3d11_compositor::primitive_3d_renderer::PrimitiveOptions;
-    ///
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// // Generate vertices using the text_presets module (requires font atlas reference)
-    /// manager.set_text(None, "my_font", "label_1", &vertices, PrimitiveOptions::default());
+    /// if let Some(data) = manager.get_restoration_data(Some("inventory")) {
+    ///     std::fs::write("inventory.restore", data).ok();
+    /// }
     /// ```
-    pub fn set_text(
-        &self,
-        identifier: Option<&str>,
-        font_id: &str,
-        group_id: &str,
-        vertices: &[TexturedVertex3D],
-        options: PrimitiveOptions,
-    ) {
-        if let Some(mut manager) = self.manager.try_lock()
-            && let Ok(overlay) = manager.get_instance_mut(identifier)
+    pub fn get_restoration_data(&self, identifier: Option<&str>) -> Option<Vec<u8>> {
+        if let Some(manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance(identifier)
         {
-            overlay.set_text(font_id, group_id, vertices, options);
+            return overlay.get_restoration_data();
         }
+        None
     }
 
-    /// Clears text for a specific group.
+    /// Hands previously saved state restoration data to an overlay.
     ///
+    /// Call it right after `init_instance`: the framework reads the data on its
+    /// first `get`, and an app that is already running receives it as a push.
     /// # Arguments
-    /// * `identifier` - The target overlay. `None` targets the single active overlay.
-    /// * `font_id` - The font the text was registered with.
-    /// * `group_id` - The group identifier to clear.
-    pub fn clear_text(&self, identifier: Option<&str>, font_id: &str, group_id: &str) {
-        if let Some(mut manager) = self.manager.try_lock()
-            && let Ok(overlay) = manager.get_instance_mut(identifier)
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `data` - Bytes returned by an earlier `get_restoration_data`.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.init_instance(&my_swap_chain, &bundle, "inventory", None, None, None);
+    /// if let Ok(data) = std::fs::read("inventory.restore") {
+    ///     manager.set_restoration_data(Some("inventory"), data);
+    /// }
+    /// ```
+    pub fn set_restoration_data(&self, identifier: Option<&str>, data: Vec<u8>) {
+        if let Some(manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance(identifier)
         {
-            overlay.clear_text(font_id, group_id);
+            overlay.set_restoration_data(data);
         }
     }
 
-    /// Clears all text for a specific font on an overlay.
+    /// Sets how opaque an overlay's UI is drawn, from `0.0` (invisible) to `1.0`.
     ///
+    /// A fully transparent overlay still ticks and receives input; use
+    /// `set_visibility` or `fade_out` to dismiss it. Cancels a running fade.
     /// # Arguments
-    /// * `identifier` - The target overlay. `None` targets the single active overlay.
-    /// * `font_id` - The font identifier whose text should be cleared.
-    pub fn clear_font_text(&self, identifier: Option<&str>, font_id: &str) {
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `opacity` - Opacity of the overlay, clamped to `0.0..=1.0`.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_opacity(Some("minimap"), 0.6);
+    /// ```
+    pub fn set_opacity(&self, identifier: Option<&str>, opacity: f32) {
         if let Some(mut manager) = self.manager.try_lock()
             && let Ok(overlay) = manager.get_instance_mut(identifier)
         {
-            overlay.clear_font_text(font_id);
+            overlay.set_opacity(opacity);
         }
     }
 
-    /// Clears all text from all fonts on all overlays.
-    pub fn clear_all_text(&self) {
-        if let Some(mut manager) = self.manager.try_lock() {
-            for overlay in manager.active_instances.values_mut() {
-                overlay.clear_all_text();
-            }
-        }
+    /// Returns the opacity an overlay is currently drawn with, including the
+    /// progress of a running fade. `None` if the overlay doesn't exist.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if manager.get_opacity(Some("menu")).is_some_and(|o| o < 1.0) {
+    ///     // still fading in
+    /// }
+    /// ```
+    pub fn get_opacity(&self, identifier: Option<&str>) -> Option<f32> {
+        let manager = self.manager.try_lock()?;
+        Some(manager.get_instance(identifier).ok()?.opacity())
     }
 
-    /// Returns a reference to a registered font atlas, if it exists.
-    ///
-    /// This is useful for generating text vertices using the `text_presets` helpers.
-    /// Note: This acquires the manager lock briefly to clone the font atlas.
+    /// Draws an overlay larger or smaller without resizing its engine surface.
     ///
+    /// # What it solves
+    /// `resize_instance` changes the size Flutter lays out at, so a HUD
+    /// authored for 1920x1080 reflows when shrunk. The render scale only
+    /// changes the size of the composited quad, anchored at the overlay's
+    /// position. Hit testing and pointer input follow the scaled bounds, so
+    /// clicks still land on the widget under the cursor.
     /// # Arguments
-    /// * `identifier` - The target overlay. `None` targets the single active overlay.
-    /// * `font_id` - The font identifier to retrieve.
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `scale` - Size multiplier for compositing; must be positive. `1.0` is native size.
     ///
     /// # Returns
-    /// A cloned `FontAtlas` if found, otherwise `None`.
-    pub fn get_font_atlas(&self, identifier: Option<&str>, font_id: &str) -> Option<FontAtlas> {
-        if let Some(manager) = self.manager.try_lock()
-            && let Ok(overlay) = manager.get_instance(identifier)
+    /// `false` if the overlay doesn't exist or `scale` is invalid.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_position(Some("hud"), 1400, 20);
+    /// manager.set_render_scale(Some("hud"), 0.5);
+    /// ```
+    pub fn set_render_scale(&self, identifier: Option<&str>, scale: f32) -> bool {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
         {
-            return overlay.get_font_atlas(font_id).cloned();
+            return match overlay.set_render_scale(scale) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("[OverlayManager] set_render_scale: {e}");
+                    false
+                }
+            };
         }
-        None
+        false
     }
 
-    /// Resumes all shader animations for all overlays.
+    /// Rotates an overlay's composited UI, e.g. for a screen mounted at an
+    /// angle in the scene.
     ///
-    /// Unfreezes the `time` uniform sent to custom shaders, allowing visual effects
-    /// to resume from where they left off.
+    /// # What it solves
+    /// Rotating in Flutter with a `Transform` widget keeps the engine surface
+    /// axis-aligned and clips the corners. Here the composited quad itself is
+    /// rotated clockwise around its center. Hit testing and pointer input are
+    /// rotated back, so clicks register where the rotated pixels appear.
+    /// `0.0` restores the default.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `degrees` - Clockwise rotation in degrees; must be finite.
+    ///
+    /// # Returns
+    /// `false` if the overlay doesn't exist or `degrees` is invalid.
     ///
     /// # Example
     /// ```rust, no_run
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// // When the game is unpaused:
-    /// manager.resume_animations();
+    /// manager.set_rotation(Some("terminal"), -12.5);
     /// ```
-    pub fn resume_animations(&self) {
+    pub fn set_rotation(&self, identifier: Option<&str>, degrees: f32) -> bool {
         if let Some(mut manager) = self.manager.try_lock()
-            && manager.is_paused
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
         {
-            manager.start_time =
-                Instant::now() - std::time::Duration::from_secs_f32(manager.time_at_pause);
-            manager.is_paused = false;
+            return match overlay.set_rotation(degrees) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("[OverlayManager] set_rotation: {e}");
+                    false
+                }
+            };
         }
+        false
     }
 
-    /// Sets a post-processing effect for the **entire** overlay.
-    ///
-    /// Applies a full-screen shader effect to an overlay's texture, allowing for
-    /// dynamic visual styles like holograms, warp fields, or color grading,
-    /// controlled directly from your Rust code.
+    /// Returns an overlay's render scale, or `None` if it doesn't exist.
+    pub fn get_render_scale(&self, identifier: Option<&str>) -> Option<f32> {
+        let manager = self.manager.try_lock()?;
+        Some(manager.get_instance(identifier).ok()?.render_scale())
+    }
+
+    /// Sets how mouse wheel notches scroll an overlay.
     ///
+    /// # What it solves
+    /// `WM_MOUSEWHEEL` is translated at a fixed 20 pixels per notch, which is
+    /// too fast for trackpads that report many small deltas, and some users
+    /// expect the direction reversed. The config applies to this overlay only,
+    /// to both `WM_MOUSEWHEEL` and the horizontal `WM_MOUSEHWHEEL`;
+    /// `ScrollConfig::default()` restores the standard behavior.
     /// # Arguments
     /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
-    /// * `effect` - The `PostEffect` enum variant to apply.
+    /// * `config` - Pixels per notch and whether to invert the direction.
     ///
     /// # Example
     /// ```rust, no_run
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// // Make the main menu look like a hologram
-    /// manager.set_fullscreen_effect(Some("main_menu"), PostEffect::Hologram);
+    /// manager.set_scroll_config(
+    ///     Some("hud"),
+    ///     ScrollConfig {
+    ///         pixels_per_notch: 8.0,
+    ///         invert: true,
+    ///     },
+    /// );
     /// ```
-    pub fn set_fullscreen_effect(&self, identifier: Option<&str>, effect: PostEffect) {
-        let Some(mut manager) = self.manager.try_lock() else {
-            return;
-        };
-        if let Ok(overlay) = manager.get_instance_mut(identifier) {
-            overlay.effect_config.params = match effect {
-                PostEffect::Passthrough => EffectParams::None,
-                PostEffect::Hologram => EffectParams::Hologram(HologramParams::default()),
-                PostEffect::WarpField => EffectParams::WarpField(WarpFieldParams::default()),
-                PostEffect::Glitch => EffectParams::Glitch(HologramParams::default()),
-            };
-            overlay.effect_config.target = EffectTarget::Fullscreen;
+    pub fn set_scroll_config(&self, identifier: Option<&str>, config: ScrollConfig) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.scroll_config = config;
         }
     }
 
-    /// Applies a post-processing effect to a **specific area** of an overlay.
+    /// Maps pointer input into an overlay that is drawn offset or scaled.
+    ///
+    /// # What it solves
+    /// Pointer events carry raw window client coordinates. When the host
+    /// renders into a letterboxed or scaled viewport and composites the overlay
+    /// there, clicks land off by the letterbox margins. With the overlay drawn
+    /// at `(offset_x, offset_y)` and scaled by `scale`, input (including the
+    /// hover hit test) is translated back into the overlay's own pixels.
+    /// `set_input_offset(id, 0.0, 0.0, 1.0)` restores the default.
     /// # Arguments
     /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
-    /// * `effect` - The `PostEffect` enum variant to apply.
-    /// * `bounds` - An array `[x, y, width, height]` defining the target rectangle in logical pixels.
+    /// * `offset_x`, `offset_y` - Client-space position of the overlay's top-left corner.
+    /// * `scale` - Client pixels per overlay pixel; must be positive.
+    ///
+    /// # Returns
+    /// `false` if the overlay doesn't exist or `scale` is invalid.
     ///
     /// # Example
     /// ```rust, no_run
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// // Make a specific button on the HUD glow with a warp effect
-    /// let button_bounds = [100.0, 200.0, 150.0, 50.0];
-    /// manager.set_widget_effect(Some("main_hud"), PostEffect::WarpField, button_bounds);
+    /// // 1280x720 game image centered in a 1920x1080 window.
+    /// manager.set_input_offset(Some("hud"), 320.0, 180.0, 1.0);
     /// ```
-    pub fn set_widget_effect(
+    pub fn set_input_offset(
         &self,
         identifier: Option<&str>,
-        effect: PostEffect,
-        bounds: [f32; 4],
-    ) {
+        offset_x: f64,
+        offset_y: f64,
+        scale: f64,
+    ) -> bool {
         if let Some(mut manager) = self.manager.try_lock()
             && let Ok(overlay) = manager.get_instance_mut(identifier)
         {
-            overlay.effect_config.params = match effect {
-                PostEffect::Passthrough => EffectParams::None,
-                PostEffect::Hologram => EffectParams::Hologram(HologramParams::default()),
-                PostEffect::WarpField => EffectParams::WarpField(WarpFieldParams::default()),
-                PostEffect::Glitch => EffectParams::Glitch(HologramParams::default()),
+            return match overlay.set_input_offset(offset_x, offset_y, scale) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("[OverlayManager] set_input_offset: {e}");
+                    false
+                }
             };
-            overlay.effect_config.target = EffectTarget::Widget(bounds);
         }
+        false
     }
 
-    /// Removes any active effect from an overlay, reverting it to the default passthrough shader.
+    /// Chooses how an overlay's satellite windows sync GL before presenting.
+    ///
+    /// # What it solves
+    /// Satellite views have no keyed mutex, so the present callback must wait
+    /// for the frame's GL work before the window samples the shared texture.
+    /// `SyncMode::Finish` (default) calls `glFinish`, which stalls the raster
+    /// thread until the whole pipeline drains. `SyncMode::Fence` waits on a
+    /// fence placed after the frame instead, yielding between polls and giving
+    /// up after `PRESENT_FENCE_TIMEOUT`. Contexts without fence support keep
+    /// using `glFinish`.
+    ///
     /// # Arguments
     /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `mode` - The sync to use from the next presented frame on.
     ///
     /// # Example
     /// ```rust, no_run
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// manager.clear_effect(Some("main_menu"));
+    /// manager.set_present_sync_mode(Some("main"), SyncMode::Fence);
     /// ```
-    pub fn set_keep_alive(&self, identifier: Option<&str>, keep_alive: bool) {
-        let Some(mut manager) = self.manager.try_lock() else {
-            return;
-        };
-        if let Ok(overlay) = manager.get_instance_mut(identifier) {
-            overlay.keep_alive = keep_alive;
+    pub fn set_present_sync_mode(&self, identifier: Option<&str>, mode: SyncMode) {
+        if let Some(manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance(identifier)
+        {
+            overlay.set_present_sync_mode(mode);
         }
     }
 
-    /// Triggers a frame-based glitch effect that auto-fades and auto-clears.
-    /// NOTE: Currently hardcoded to the Glitch shader. Should be refactored
-    /// to accept a dynamic EffectParams for any effect type.
-    pub fn trigger_glitch_effect(&self, identifier: Option<&str>, frames: u32) {
+    /// Shares a copy of an overlay's texture with other devices or processes.
+    ///
+    /// # What it solves
+    /// The overlay texture itself is private to the host device (and dynamic on
+    /// the software path), so an encoder or capture tool can't open it. With
+    /// sharing on, each new frame is also copied into a texture created with
+    /// `D3D11_RESOURCE_MISC_SHARED`, or with an NT handle and/or keyed mutex as
+    /// requested in `options`, for zero-copy handoff.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `options` - How to share the copy, or `None` to stop sharing.
+    ///
+    /// # Returns
+    /// `InvalidHandle` if the overlay doesn't exist, `OperationFailed` if the
+    /// texture couldn't be created.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// let options = TextureShareOptions { nt_handle: true, keyed_mutex: true };
+    /// if manager.set_texture_sharing(Some("hud"), Some(options)).is_ok() {
+    ///     let handle = manager.get_shared_texture_handle(Some("hud"));
+    /// }
+    /// ```
+    pub fn set_texture_sharing(
+        &self,
+        identifier: Option<&str>,
+        options: Option<TextureShareOptions>,
+    ) -> Result<(), FlutterEmbedderError> {
         let Some(mut manager) = self.manager.try_lock() else {
-            return;
+            return Err(FlutterEmbedderError::OperationFailed(
+                "overlay manager is busy".to_string(),
+            ));
         };
-        if let Ok(overlay) = manager.get_instance_mut(identifier) {
-            overlay.effect_frames_remaining = frames;
-            overlay.effect_total_frames = frames;
-            overlay.effect_config.params = EffectParams::Glitch(HologramParams::default());
-            overlay.effect_config.target = EffectTarget::Fullscreen;
-        }
+        let overlay = manager.get_instance_mut(identifier).map_err(|e| {
+            warn!("[OverlayManager] set_texture_sharing failed: {e}");
+            FlutterEmbedderError::InvalidHandle
+        })?;
+        overlay.set_texture_sharing(options)
     }
 
-    pub fn clear_effect(&self, identifier: Option<&str>) {
-        let Some(mut manager) = self.manager.try_lock() else {
-            return;
-        };
-        if let Ok(overlay) = manager.get_instance_mut(identifier) {
-            overlay.effect_config = EffectConfig::default();
-        }
+    /// Returns the handle of the texture shared with `set_texture_sharing`.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
+    /// # Returns
+    /// `None` if the overlay doesn't exist or isn't sharing. The handle changes
+    /// when the overlay is resized; an NT handle stays owned by the overlay, so
+    /// duplicate it to keep it past that.
+    pub fn get_shared_texture_handle(&self, identifier: Option<&str>) -> Option<HANDLE> {
+        let manager = self.manager.try_lock()?;
+        manager.get_instance(identifier).ok()?.shared_texture_handle()
     }
 
-    /// Updates the entire effect configuration for a specific overlay.
+    /// Debounces an overlay's hover state to stop cursor flicker at widget edges.
+    ///
+    /// # What it solves
+    /// The per-frame hover hit test can flip between hovered and not hovered on
+    /// consecutive frames while the cursor rests on a widget boundary, which makes
+    /// the cursor shape and auto-raise flicker. With a debounce, a change only
+    /// takes effect after it has held for more than `frames` frames. Pointer
+    /// routing still hit-tests each event directly. Defaults to `0` (off).
     /// # Arguments
     /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
-    /// * `config`: The complete `EffectConfig` struct.
+    /// * `frames` - Frames a hover change must persist before it applies.
     ///
     /// # Example
     /// ```rust, no_run
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// let config = EffectConfig {
-    ///     target: EffectTarget::Fullscreen,
-    ///     params: EffectParams::Hologram(HologramParams { intensity: 0.8, ..Default::default() }),
-    /// };
-    /// manager.update_effect_config(Some("main_menu"), config);
+    /// manager.set_hover_debounce(Some("hud"), 2);
     /// ```
-    pub fn update_effect_config(&self, identifier: Option<&str>, config: EffectConfig) {
-        let Some(mut manager) = self.manager.try_lock() else {
-            return;
-        };
-        if let Ok(overlay) = manager.get_instance_mut(identifier) {
-            overlay.effect_config = config;
+    pub fn set_hover_debounce(&self, identifier: Option<&str>, frames: u32) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.set_hover_debounce(frames);
         }
     }
 
-    /// Forwards a raw Windows message to the manager for input processing.
+    /// Limits how often an overlay rebuilds its semantics tree.
     ///
-    /// This is the critical function for making UIs interactive. It translates Windows
-    /// input messages (mouse moves, clicks, key presses) into events that Flutter
-    /// can understand and deliver to the appropriate widgets. Without this, your
-    /// UI will be visible but completely non-interactive.
+    /// # What it solves
+    /// The engine sends a semantics update whenever the UI changes, so an
+    /// animated UI replaces the tree many times a second. With an interval,
+    /// updates are coalesced: at most one is applied per `interval`, always the
+    /// latest, and a held-back one is applied on a later frame. Hover hit
+    /// testing then sees a tree up to `interval` old. Defaults to
+    /// `Duration::ZERO` (every update).
     ///
-    /// # Returns
-    /// `true` if a Flutter overlay consumed the event. The host application can
-    /// use this to suppress further processing of the input (e.g., stop the game
-    /// camera from moving when the mouse is over a UI button).
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `interval` - Minimum time between two applied updates.
     ///
     /// # Example
     /// ```rust, no_run
-    /// // Inside your application's WndProc
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// if manager.forward_input_to_flutter(hwnd, msg, wparam, lparam) {
-    ///     return LRESULT(0); // Flutter handled it, so we stop processing.
-    /// }
-    /// // ... continue with normal message processing for the game ...
+    /// manager.set_semantics_update_interval(Some("hud"), Duration::from_millis(100));
     /// ```
-    pub fn forward_input_to_flutter(
-        &self,
-        hwnd: HWND,
-        msg: u32,
-        wparam: WPARAM,
-        lparam: LPARAM,
-    ) -> bool {
-        type DeferredCb = (String, bool, VisibilityToggleCallback);
-        let (result, deferred_cb): (bool, Option<DeferredCb>) =
-            if let Some(mut manager) = self.manager.try_lock() {
-                manager.handle_input_event(hwnd, msg, wparam, lparam)
-            } else {
-                (false, None)
-            };
-        if let Some((overlay_id, visible, cb)) = deferred_cb {
-            cb(&overlay_id, visible);
+    pub fn set_semantics_update_interval(&self, identifier: Option<&str>, interval: Duration) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.set_semantics_update_interval(interval);
         }
-        result
     }
 
-    /// Requests that the topmost active overlay under the cursor set the mouse cursor style.
-    /// Call this from your `WndProc` when handling `WM_SETCURSOR`.
-    ///
-    /// Allows the Flutter UI to control the appearance of the mouse cursor, for example,
-    /// changing it from an arrow to a text-input I-beam when hovering over a text field,
-    /// or to a hand pointer over a button. This provides essential visual feedback to the user.
+    /// Scales the cursors an overlay requests by its drawn size.
     ///
-    /// # Returns
-    /// * `Some(LRESULT(1))` if a Flutter overlay handled the cursor request.
-    /// * `None` if no overlay handled the request.
+    /// # What it solves
+    /// Flutter's cursor kinds map to fixed-size system cursors, which look
+    /// oversized over an overlay that is drawn downscaled. With scaling on, the
+    /// cursor is loaded at the system size times the `scale` passed to
+    /// `set_input_offset`. Off by default.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `enabled` - Whether to scale the cursor.
     ///
     /// # Example
     /// ```rust, no_run
-    /// // In your WndProc
-    /// // case WM_SETCURSOR:
-    /// if let Some(manager) = get_flutter_overlay_manager_handle() {
-    ///     if let Some(result) = manager.set_flutter_cursor(hwnd, lparam, original_hwnd) {
-    ///         return result; // Flutter handled the cursor
-    ///     }
-    /// }
-    /// // Default handling...
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_input_offset(Some("hud"), 0.0, 0.0, 0.5);
+    /// manager.set_cursor_scaling(Some("hud"), true);
     /// ```
-    pub fn set_flutter_cursor(
-        &self,
-        hwnd_for_setcursor_message: HWND,
-        lparam: LPARAM,
-        original_hwnd: HWND,
-    ) -> Option<LRESULT> {
-        if let Some(manager) = self.manager.try_lock() {
-            manager.handle_set_cursor(hwnd_for_setcursor_message, lparam, original_hwnd)
-        } else {
-            None
+    pub fn set_cursor_scaling(&self, identifier: Option<&str>, enabled: bool) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.set_cursor_scaling(enabled);
         }
     }
 
-    /// Notifies all active overlays of a window or render area resize.
+    /// Masks the composited overlay to an arbitrary shape.
     ///
-    /// Informs all Flutter instances about the new size of the window, allowing them
-    /// to recalculate layouts and adapt to the new resolution. It also ensures the
-    /// underlying GPU textures are resized correctly to prevent stretching or clipping.
+    /// # What it solves
+    /// Non-rectangular HUD elements (a round minimap) otherwise need a `ClipPath`
+    /// in Flutter, which costs raster time on every frame. Here the alpha of
+    /// `mask_srv` is multiplied into the overlay's alpha when it is composited.
+    /// The mask is stretched over the overlay's bounds: UV `(0, 0)` is the
+    /// overlay's top-left corner and `(1, 1)` its bottom-right, whatever the
+    /// mask's resolution. Masked overlays are drawn in three passes and use the
+    /// render target's alpha channel as scratch space inside their bounds.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `mask_srv` - Shader resource view of the mask texture, created on the host device.
     ///
     /// # Example
     /// ```rust, no_run
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// manager.resize_flutter_overlays(&my_swap_chain, 0, 0, new_width, new_height);
+    /// manager.set_overlay_stencil_mask(Some("minimap"), &circle_mask_srv);
     /// ```
-    pub fn resize_flutter_overlays(
+    pub fn set_overlay_stencil_mask(
         &self,
-        swap_chain: &IDXGISwapChain,
-        x_pos: i32,
-        y_pos: i32,
-        width: u32,
-        height: u32,
+        identifier: Option<&str>,
+        mask_srv: &ID3D11ShaderResourceView,
     ) {
-        if let Some(mut manager) = self.manager.try_lock() {
-            manager.handle_resize(swap_chain, x_pos, y_pos, width, height);
-        }
-    }
-
-    /// Shuts down a specific Flutter overlay instance, releasing all its resources.
-    /// # Arguments
-    /// * `identifier`: The unique identifier of the overlay to shut down.
-    pub fn shutdown_instance(&self, identifier: &str) {
         if let Some(mut manager) = self.manager.try_lock()
-            && let Err(e) = manager.shutdown_instance(identifier)
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
         {
-            error!("[OverlayManagerHandle] Error during shutdown of instance {identifier}: {e}");
+            overlay.set_stencil_mask(Some(mask_srv.clone()));
         }
     }
 
-    /// Shuts down all currently active Flutter overlay instances.
+    /// Removes a mask set with `set_overlay_stencil_mask`.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
     /// # Example
     /// ```rust, no_run
-    /// // In your application's exit/cleanup logic:
-    /// if let Some(manager) = get_flutter_overlay_manager_handle() {
-    ///     manager.shutdown_all_instances();
-    /// }
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.clear_overlay_stencil_mask(Some("minimap"));
     /// ```
-    pub fn shutdown_all_instances(&self) {
-        if let Some(mut manager) = self.manager.try_lock() {
-            manager.shutdown_all_instances();
+    pub fn clear_overlay_stencil_mask(&self, identifier: Option<&str>) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.set_stencil_mask(None);
         }
     }
 
-    /// Brings the specified overlay to the top of the rendering order (Z-order).
+    /// Chooses how the overlay's texture is sampled past its edges.
+    ///
+    /// # What it solves
+    /// With the default `AddressMode::Clamp`, the edge texels are repeated
+    /// when the composite pass samples just outside the texture, which shows as
+    /// a smeared fringe on rotated, scaled or partly clipped overlays.
+    /// `AddressMode::Border` with a transparent `border_color` makes those
+    /// samples transparent instead.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `mode` - `AddressMode::Clamp` or `AddressMode::Border`.
+    /// * `border_color` - RGBA returned outside the texture in `Border` mode.
+    ///
     /// # Example
     /// ```rust, no_run
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// // When a popup is shown
-    /// manager.bring_to_front(Some("popup_dialog"));
+    /// manager.set_sampler_address_mode(Some("minimap"), AddressMode::Border, [0.0; 4]);
     /// ```
-    pub fn bring_to_front(&self, identifier: Option<&str>) {
-        if let Some(mut manager) = self.manager.try_lock() {
-            manager.bring_to_front(identifier);
-        }
+    pub fn set_sampler_address_mode(
+        &self,
+        identifier: Option<&str>,
+        mode: AddressMode,
+        border_color: [f32; 4],
+    ) -> Result<(), FlutterEmbedderError> {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return Err(FlutterEmbedderError::OperationFailed(
+                "overlay manager is busy".to_string(),
+            ));
+        };
+        let overlay = manager.get_instance_mut(identifier).map_err(|e| {
+            warn!("[OverlayManager] set_sampler_address_mode failed: {e}");
+            FlutterEmbedderError::InvalidHandle
+        })?;
+        overlay.set_sampler_address_mode(mode, border_color)
     }
 
-    /// Sets keyboard focus to the specified overlay, which also brings it to the front.
+    /// Resets an overlay's position and composite state in one call.
+    ///
+    /// # What it solves
+    /// Undoing a session of tweaking otherwise takes a call per setting, and
+    /// it's easy to miss one. This moves the overlay back to `(0, 0)`, clears
+    /// its effect and stencil mask, restores full opacity (cancelling a fade),
+    /// removes the input offset and switches back to clamped sampling.
+    /// Visibility, input routing flags and the 3D primitives are left alone.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    ///
     /// # Example
     /// ```rust, no_run
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// // When the user clicks on the chat input field
-    /// manager.set_focus(Some("chat_ui"));
+    /// manager.reset_overlay_state(Some("minimap"));
     /// ```
-    pub fn set_focus(&self, identifier: Option<&str>) {
-        if let Some(mut manager) = self.manager.try_lock() {
-            manager.set_keyboard_focus(identifier);
+    pub fn reset_overlay_state(&self, identifier: Option<&str>) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.reset_display_state();
         }
     }
 
-    /// Checks if the specified overlay currently has keyboard focus.
+    /// Fades an overlay out and hides it at the end.
+    ///
+    /// # What it solves
+    /// An animated dismiss needs input to stop the moment it starts, so a click
+    /// during the animation can't hit a half-faded button, and the overlay to
+    /// stop ticking once it is gone. `fade_out` does both: input is disabled
+    /// immediately and the overlay becomes invisible when the fade completes.
+    /// A later `set_visibility(true)` shows it at full opacity again.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `duration` - Length of the fade; `Duration::ZERO` hides immediately.
+    ///
     /// # Example
     /// ```rust, no_run
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// if manager.is_focused(Some("chat_ui")) {
-    ///     // Don't process game movement keys
-    /// }
+    /// manager.fade_out(Some("pause_menu"), Duration::from_millis(250));
     /// ```
-    pub fn is_focused(&self, identifier: Option<&str>) -> bool {
-        if let Some(manager) = self.manager.try_lock()
-            && let Ok(overlay) = manager.get_instance(identifier)
+    pub fn fade_out(&self, identifier: Option<&str>, duration: Duration) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
         {
-            return manager.focused_overlay_id.as_deref() == Some(overlay.name.as_str());
-        }
-        false
-    }
-
-    pub fn has_rendered_frame(&self) -> bool {
-        if let Some(manager) = self.manager.try_lock() {
-            return manager
-                .active_instances
-                .values()
-                .any(|o| o.has_first_frame());
+            overlay.fade_out(duration);
         }
-        false
     }
 
-    /// Sets the visibility of a Flutter overlay. An invisible overlay is not rendered and does not receive input.
+    /// Shows an overlay and fades it in.
+    ///
+    /// The counterpart of `fade_out`: the overlay becomes visible immediately
+    /// and accepts input once it is fully opaque. Calling it during a fade-out
+    /// reverses the fade from the current opacity.
     /// # Arguments
     /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
-    /// * `is_visible` - Whether the overlay should be visible.
+    /// * `duration` - Length of the fade; `Duration::ZERO` shows immediately.
     ///
     /// # Example
     /// ```rust, no_run
     /// let manager = get_flutter_overlay_manager_handle().unwrap();
-    /// // When the player presses the escape key:
-    /// manager.set_visibility(Some("pause_menu"), true);
+    /// manager.fade_in(Some("pause_menu"), Duration::from_millis(250));
     /// ```
-    pub fn set_visibility(&self, identifier: Option<&str>, is_visible: bool) {
+    pub fn fade_in(&self, identifier: Option<&str>, duration: Duration) {
         if let Some(mut manager) = self.manager.try_lock()
             && let Ok(overlay) = manager.get_instance_mut(identifier)
         {
-            overlay.set_visibility(is_visible);
+            overlay.fade_in(duration);
         }
     }
 
@@ -2166,6 +5474,66 @@ impl FlutterOverlayManagerHandle {
         }
     }
 
+    /// Sends a platform message to one overlay and receives Dart's reply.
+    ///
+    /// # What it solves
+    /// `broadcast_message` is fire-and-forget, so Rust can't call a Dart
+    /// method and use its result. Here a response handle is attached to the
+    /// message and `callback` gets the reply bytes, or `None` if Dart replied
+    /// `null`. For a `MethodChannel`, encode the call and decode the reply
+    /// envelope with the channel's codec.
+    ///
+    /// The callback may never run: a message on a channel with no Dart handler
+    /// is buffered until one is registered, not answered.
+    ///
+    /// The callback runs later, during a tick, possibly while the manager is
+    /// locked, so handle methods called from it may find the manager busy.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `channel` - The channel to send the message on.
+    /// * `message` - The message payload.
+    /// * `callback` - Called at most once, with the reply.
+    ///
+    /// # Returns
+    /// `EngineNotRunning` if the overlay's engine isn't running, `InvalidHandle`
+    /// if the overlay doesn't exist, or the engine's error. The callback can
+    /// only run when this returns `Ok`.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// // Dart: BasicMessageChannel('game/query', StringCodec()).setMessageHandler(
+    /// //     (message) async => 'pong');
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.send_platform_message_with_response(Some("hud"), "game/query", b"ping", |reply| {
+    ///     let reply = reply.map(String::from_utf8_lossy);
+    ///     info!("Dart replied {reply:?}");
+    /// })?;
+    /// ```
+    pub fn send_platform_message_with_response<F>(
+        &self,
+        identifier: Option<&str>,
+        channel: &str,
+        message: &[u8],
+        callback: F,
+    ) -> Result<(), FlutterEmbedderError>
+    where
+        F: FnOnce(Option<&[u8]>) + Send + 'static,
+    {
+        let Some(manager) = self.manager.try_lock() else {
+            return Err(FlutterEmbedderError::OperationFailed(
+                "overlay manager is busy".to_string(),
+            ));
+        };
+        match manager.get_instance(identifier) {
+            Ok(overlay) => overlay.send_platform_message_with_response(channel, message, callback),
+            Err(e) => {
+                warn!("[OverlayManager] send_platform_message_with_response failed: {e}");
+                Err(FlutterEmbedderError::InvalidHandle)
+            }
+        }
+    }
+
     /// Registers a custom message handler for a specific channel on an overlay.
     ///
     /// # Arguments
@@ -2193,6 +5561,135 @@ impl FlutterOverlayManagerHandle {
         }
     }
 
+    /// Registers a channel handler that can answer with an error.
+    ///
+    /// # What it solves
+    /// A plain handler always answers with its bytes, so Dart can't tell a
+    /// failed request from a successful one. When this handler returns
+    /// `Err((code, message))`, Dart receives a standard method codec error
+    /// envelope and `MethodChannel.invokeMethod` throws a `PlatformException`
+    /// with that code and message. `Ok` bytes are sent as they are, like with
+    /// `register_channel_handler`.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `channel` - The name of the channel the handler will listen to.
+    /// * `handler` - A closure that processes an incoming `Vec<u8>` and returns the response
+    ///   bytes or an error code and message.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.register_channel_handler_result(Some("settings_menu"), "settings/save", |payload| {
+    ///     save_settings(&payload)
+    ///         .map(|()| vec![0, 0]) // success envelope with a null result
+    ///         .map_err(|e| ("SAVE_FAILED".to_string(), e.to_string()))
+    /// });
+    /// ```
+    pub fn register_channel_handler_result<F>(
+        &self,
+        identifier: Option<&str>,
+        channel: &str,
+        handler: F,
+    ) where
+        F: Fn(Vec<u8>) -> Result<Vec<u8>, (String, String)> + Send + Sync + 'static,
+    {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.register_channel_handler_result_for_instance(identifier, channel, handler);
+        }
+    }
+
+    /// Registers a handler for a `MethodChannel` that uses `JSONMethodCodec`.
+    ///
+    /// # What it solves
+    /// Raw handlers have to pull the method name and arguments out of the bytes
+    /// themselves. Here the `{"method": .., "args": ..}` call is decoded first
+    /// and the handler's return value is wrapped in the `[result]` success
+    /// envelope. Malformed JSON is answered with an error envelope, which Dart
+    /// throws as a `PlatformException`. Missing `args` arrive as `Value::Null`.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `channel` - The name of the channel the handler will listen to.
+    /// * `handler` - A closure taking the method name and arguments and returning the result.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// // Dart: MethodChannel('game/player', JSONMethodCodec())
+    /// //     .invokeMethod('heal', {'amount': 5});
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.register_json_method_handler(Some("hud"), "game/player", |method, args| {
+    ///     match method {
+    ///         "heal" => json!(heal_player(args["amount"].as_i64().unwrap_or(0))),
+    ///         _ => Value::Null,
+    ///     }
+    /// });
+    /// ```
+    pub fn register_json_method_handler<F>(
+        &self,
+        identifier: Option<&str>,
+        channel: &str,
+        handler: F,
+    ) where
+        F: Fn(&str, Value) -> Value + Send + Sync + 'static,
+    {
+        if let Some(mut manager) = self.manager.try_lock() {
+            manager.register_json_method_handler_for_instance(identifier, channel, handler);
+        }
+    }
+
+    /// Removes a handler added with `register_channel_handler`.
+    ///
+    /// # What it solves
+    /// A handler lives as long as its overlay, together with whatever state its
+    /// closure captured. UIs that navigate between screens register handlers
+    /// per screen and need to drop them again when the screen goes away.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `channel` - The channel the handler was registered for.
+    ///
+    /// # Returns
+    /// `true` if a handler was registered and has been removed. `false` also
+    /// when the overlay doesn't exist, which is logged.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.unregister_channel_handler(Some("settings_menu"), "settings/setVolume");
+    /// ```
+    pub fn unregister_channel_handler(&self, identifier: Option<&str>, channel: &str) -> bool {
+        let Some(mut manager) = self.manager.try_lock() else {
+            return false;
+        };
+        match manager.get_instance_mut(identifier) {
+            Ok(overlay) => overlay.unregister_channel_handler(channel),
+            Err(e) => {
+                warn!("[OverlayManager] unregister_channel_handler: {e}");
+                false
+            }
+        }
+    }
+
+    /// Returns whether an overlay has a custom handler for `channel`.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `channel` - The channel to look up.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if !manager.has_channel_handler(Some("settings_menu"), "settings/setVolume") {
+    ///     manager.register_channel_handler(Some("settings_menu"), "settings/setVolume", on_volume);
+    /// }
+    /// ```
+    pub fn has_channel_handler(&self, identifier: Option<&str>, channel: &str) -> bool {
+        if let Some(manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance(identifier)
+        {
+            return overlay.has_channel_handler(channel);
+        }
+        false
+    }
+
     /// Gets the dimensions (width, height) of all active overlays.
     ///
     /// Allows the host application to get the size of all UIs, which can be useful
@@ -2214,6 +5711,39 @@ impl FlutterOverlayManagerHandle {
         }
     }
 
+    /// Returns the identifiers of all overlays in draw order, bottom first.
+    ///
+    /// # What it solves
+    /// The Z-order changes with `bring_to_front`, focus and auto-raise on
+    /// hover, so a debug panel can't track it from the host side. The last
+    /// entry is drawn on top. Pointer routing can differ when overlays have an
+    /// `input_priority`. Empty if the manager is busy.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// for (depth, id) in manager.get_overlay_order().iter().rev().enumerate() {
+    ///     ui.label(format!("{depth}: {id}"));
+    /// }
+    /// ```
+    pub fn get_overlay_order(&self) -> Vec<String> {
+        self.manager
+            .try_lock()
+            .map(|manager| manager.overlay_order.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the identifiers of all active overlays, sorted by name.
+    /// Empty if the manager is busy.
+    pub fn get_active_identifiers(&self) -> Vec<String> {
+        let Some(manager) = self.manager.try_lock() else {
+            return Vec::new();
+        };
+        let mut identifiers: Vec<String> = manager.active_instances.keys().cloned().collect();
+        identifiers.sort();
+        identifiers
+    }
+
     /// Gets a clone of the shared Direct3D device context used by the manager.
     ///
     /// Provides direct access to the D3D11 context for advanced, custom rendering
@@ -2374,6 +5904,111 @@ impl FlutterOverlayManagerHandle {
         false
     }
 
+    /// Posts the same byte buffer to every overlay tagged with `tag`.
+    ///
+    /// # What it solves
+    /// Feeds one per-frame data stream to a set of related overlays (all HUD
+    /// panels of a vehicle, say) in a single locked pass, without looping over
+    /// identifiers in host code or broadcasting to every overlay. Tags come
+    /// from `set_user_data`; overlays without a registered Dart port are skipped.
+    ///
+    /// # Arguments
+    /// * `tag` - The user data tag that selects the overlays.
+    /// * `buffer` - The byte buffer to post.
+    ///
+    /// # Returns
+    /// The number of overlays the buffer was posted to.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_user_data(Some("speedometer"), VEHICLE_HUD);
+    /// manager.set_user_data(Some("fuel_gauge"), VEHICLE_HUD);
+    /// manager.post_buffer_to_group(VEHICLE_HUD, &telemetry_bytes);
+    /// ```
+    pub fn post_buffer_to_group(&self, tag: u64, buffer: &[u8]) -> usize {
+        if let Some(manager) = self.manager.try_lock() {
+            return manager.post_buffer_to_group(tag, buffer);
+        }
+        0
+    }
+
+    /// Posts the same string to every overlay tagged with `tag`. See
+    /// `post_buffer_to_group`.
+    ///
+    /// # Arguments
+    /// * `tag` - The user data tag that selects the overlays.
+    /// * `value` - The string value to post.
+    ///
+    /// # Returns
+    /// The number of overlays the string was posted to.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.post_string_to_group(VEHICLE_HUD, "engine_off");
+    /// ```
+    pub fn post_string_to_group(&self, tag: u64, value: &str) -> usize {
+        if let Some(manager) = self.manager.try_lock() {
+            return manager.post_string_to_group(tag, value);
+        }
+        0
+    }
+
+    /// Turns GPU picking of 3D primitive groups on or off for an overlay.
+    ///
+    /// # What it solves
+    /// 3D markers drawn with `set_primitives` can't be hit-tested from the CPU
+    /// side without redoing the projection. With picking on, every group is also
+    /// drawn into an offscreen id buffer each frame so `pick_at` can tell which
+    /// group is under a pixel. That costs a second draw of all primitives, so
+    /// it is off by default.
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `enabled` - Whether to render the id buffer.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// manager.set_picking_enabled(Some("world_markers"), true);
+    /// ```
+    pub fn set_picking_enabled(&self, identifier: Option<&str>, enabled: bool) {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            overlay.set_picking_enabled(enabled);
+        }
+    }
+
+    /// Returns the primitive group drawn at a pixel in the last frame.
+    ///
+    /// The nearest group wins, tested against the primitives' own depth only,
+    /// not the host scene's. Reading the pixel waits for the GPU to finish the
+    /// frame, so call it once per frame at most (e.g. on mouse move).
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `x`, `y` - Pixel in the render target the primitives are drawn to.
+    ///
+    /// # Returns
+    /// The group id, or `None` if nothing is drawn there, picking is off or the
+    /// overlay doesn't exist.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// if let Some(group) = manager.pick_at(Some("world_markers"), mouse_x, mouse_y) {
+    ///     println!("hovering {group}");
+    /// }
+    /// ```
+    pub fn pick_at(&self, identifier: Option<&str>, x: u32, y: u32) -> Option<String> {
+        let manager = self.manager.try_lock()?;
+        let context = manager.shared_d3d_context.clone()?;
+        manager
+            .get_instance(identifier)
+            .ok()?
+            .pick_primitive_at(&context, x, y)
+    }
+
     /// Registers a custom shader effect from compiled byte code.
     ///
     /// This allows for extending the rendering capabilities with custom visual effects for 3D primitives.
@@ -2422,6 +6057,55 @@ impl FlutterOverlayManagerHandle {
         }
     }
 
+    /// Registers a list of custom shader effects in one call.
+    ///
+    /// # What it solves
+    /// Registering a dozen effects one by one is repetitive, and a bad `.cso`
+    /// only shows up as a log line. A manifest registers all entries under one
+    /// lock and reports each entry's outcome, naming the shader that failed to
+    /// load or create. Failed entries don't stop the others.
+    ///
+    /// # Arguments
+    /// * `identifier` - The unique identifier of the overlay. If `None`, targets the single active overlay.
+    /// * `entries` - The effects to register. `.cso` paths are read at call time.
+    ///
+    /// # Returns
+    /// One result per entry, in order. Every entry gets `InvalidHandle` if the
+    /// overlay can't be found.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// let manager = get_flutter_overlay_manager_handle().unwrap();
+    /// let entries = [
+    ///     ShaderManifestEntry::new("glow", ShaderSource::Path("shaders/glow.cso".into())),
+    ///     ShaderManifestEntry {
+    ///         constant_buffer_size: Some(16),
+    ///         ..ShaderManifestEntry::new("pulse", ShaderSource::Bytes(include_bytes!("pulse.cso").to_vec()))
+    ///     },
+    /// ];
+    /// for (entry, result) in entries.iter().zip(manager.register_custom_shaders_from_manifest(None, &entries)) {
+    ///     if let Err(e) = result {
+    ///         eprintln!("{}: {e}", entry.effect_id);
+    ///     }
+    /// }
+    /// ```
+    pub fn register_custom_shaders_from_manifest(
+        &self,
+        identifier: Option<&str>,
+        entries: &[ShaderManifestEntry],
+    ) -> Vec<Result<(), FlutterEmbedderError>> {
+        if let Some(mut manager) = self.manager.try_lock()
+            && let Ok(overlay) = manager.get_instance_mut(identifier)
+        {
+            let device = unsafe { overlay.srv.GetDevice().unwrap() };
+            return overlay.register_custom_shaders_from_manifest(&device, entries);
+        }
+        entries
+            .iter()
+            .map(|_| Err(FlutterEmbedderError::InvalidHandle))
+            .collect()
+    }
+
     /// Sets a texture at a specific shader resource slot for a custom effect.
     /// This allows binding textures to non-sequential slots, enabling optional textures
     /// like normal maps, specular maps, etc.
@@ -2704,6 +6388,7 @@ impl FlutterOverlayManagerHandle {
         let Some(mut manager) = self.manager.try_lock() else {
             return false;
         };
+        rtv_cache::invalidate();
         let mut all_recovered = true;
 
         for (id, overlay) in manager.active_instances.iter_mut() {
@@ -0,0 +1,67 @@
+//! Coalescing window resizes during a drag.
+//!
+//! Every `resize_flutter_overlays` call recreates the overlay textures and
+//! EGL surfaces, and dragging the window edge sends a WM_SIZE per mouse move.
+//! [`ResizeDebounce`] holds on to the latest size and releases it only once it
+//! has stopped changing for the configured delay. The manager polls it every
+//! tick, so the last size of a drag is applied even if no further WM_SIZE
+//! arrives.
+
+use std::time::{Duration, Instant};
+
+/// Delay used until `set_resize_debounce_ms` is called.
+pub(crate) const DEFAULT_RESIZE_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Arguments of one `resize_flutter_overlays` call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ResizeRequest {
+    pub(crate) x: i32,
+    pub(crate) y: i32,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+pub(crate) struct ResizeDebounce {
+    delay: Duration,
+    /// Latest request and when it last changed.
+    pending: Option<(ResizeRequest, Instant)>,
+}
+
+impl Default for ResizeDebounce {
+    fn default() -> Self {
+        Self {
+            delay: DEFAULT_RESIZE_DEBOUNCE,
+            pending: None,
+        }
+    }
+}
+
+impl ResizeDebounce {
+    pub(crate) fn set_delay(&mut self, delay: Duration) {
+        self.delay = delay;
+    }
+
+    /// Records `request`. Returns it if it should be applied right away: when
+    /// there is no delay, or for a zero size (minimized window), which only
+    /// suspends rendering and allocates nothing.
+    pub(crate) fn push(&mut self, request: ResizeRequest, now: Instant) -> Option<ResizeRequest> {
+        if self.delay.is_zero() || request.width == 0 || request.height == 0 {
+            self.pending = None;
+            return Some(request);
+        }
+        match &self.pending {
+            Some((pending, _)) if *pending == request => {}
+            _ => self.pending = Some((request, now)),
+        }
+        None
+    }
+
+    /// Takes the pending request once it has been stable for the delay.
+    pub(crate) fn take_ready(&mut self, now: Instant) -> Option<ResizeRequest> {
+        let (_, since) = self.pending?;
+        if now.saturating_duration_since(since) < self.delay {
+            return None;
+        }
+        self.pending.take().map(|(request, _)| request)
+    }
+}
@@ -0,0 +1,86 @@
+//! Optional cached render-target view for the host swap chain's back buffer.
+//!
+//! A host that composites overlays itself needs an RTV of the back buffer
+//! every frame, and `GetBuffer` + `CreateRenderTargetView` each time is wasted
+//! work when the back buffer is stable. With the cache enabled, one view is
+//! kept per swap chain and a lookup is a pointer compare: in D3D11, buffer 0
+//! always names the current back buffer, so the view stays valid until the
+//! buffers are resized or the swap chain is replaced.
+//!
+//! The cached view holds a reference to the back buffer, and `ResizeBuffers`
+//! fails while one is alive. That is why the cache is off by default, and why
+//! it lives outside the manager lock: `resize_flutter_overlays` and
+//! `invalidate_rtv_cache` can always drop it, even while the manager is busy.
+
+use log::warn;
+use parking_lot::Mutex;
+use windows::Win32::Graphics::Direct3D11::{ID3D11Device, ID3D11RenderTargetView, ID3D11Texture2D};
+use windows::Win32::Graphics::Dxgi::IDXGISwapChain;
+use windows::core::Interface;
+
+struct RenderTargetViewCache {
+    enabled: bool,
+    /// `(swap chain pointer, view)`.
+    entry: Option<(usize, ID3D11RenderTargetView)>,
+}
+
+static CACHE: Mutex<RenderTargetViewCache> = Mutex::new(RenderTargetViewCache {
+    enabled: false,
+    entry: None,
+});
+
+/// Turns the cache on or off. Turning it off drops the cached view.
+pub(crate) fn set_enabled(enabled: bool) {
+    let mut cache = CACHE.lock();
+    cache.enabled = enabled;
+    if !enabled {
+        cache.entry = None;
+    }
+}
+
+/// Drops the cached view, if any. The cache stays enabled.
+pub(crate) fn invalidate() {
+    CACHE.lock().entry = None;
+}
+
+/// Returns an RTV of `swap_chain`'s back buffer: the cached one when it was
+/// made for this swap chain, otherwise a new one, kept only while the cache is
+/// enabled.
+pub(crate) fn back_buffer_rtv(swap_chain: &IDXGISwapChain) -> Option<ID3D11RenderTargetView> {
+    let swap_chain_key = swap_chain.as_raw() as usize;
+    let mut cache = CACHE.lock();
+    if let Some((key, rtv)) = &cache.entry
+        && *key == swap_chain_key
+    {
+        return Some(rtv.clone());
+    }
+    let rtv = create_back_buffer_rtv(swap_chain)?;
+    if cache.enabled {
+        cache.entry = Some((swap_chain_key, rtv.clone()));
+    }
+    Some(rtv)
+}
+
+fn create_back_buffer_rtv(swap_chain: &IDXGISwapChain) -> Option<ID3D11RenderTargetView> {
+    let back_buffer: ID3D11Texture2D = match unsafe { swap_chain.GetBuffer(0) } {
+        Ok(buffer) => buffer,
+        Err(e) => {
+            warn!("[OverlayManager] GetBuffer for the back buffer RTV failed: {e}");
+            return None;
+        }
+    };
+    let device: ID3D11Device = match unsafe { back_buffer.GetDevice() } {
+        Ok(device) => device,
+        Err(e) => {
+            warn!("[OverlayManager] GetDevice for the back buffer RTV failed: {e}");
+            return None;
+        }
+    };
+
+    let mut rtv: Option<ID3D11RenderTargetView> = None;
+    if let Err(e) = unsafe { device.CreateRenderTargetView(&back_buffer, None, Some(&mut rtv)) } {
+        warn!("[OverlayManager] CreateRenderTargetView for the back buffer failed: {e}");
+        return None;
+    }
+    rtv
+}
@@ -0,0 +1,51 @@
+use crate::software_renderer::overlays_manager_api::buffer_pool::PixelBufferPool;
+
+#[test]
+fn acquire_rounds_capacity_to_power_of_two() {
+    let mut pool = PixelBufferPool::default();
+    let buf = pool.acquire(1000);
+    assert_eq!(buf.len(), 1000);
+    assert_eq!(buf.capacity(), 1024);
+    assert!(buf.iter().all(|&b| b == 0));
+}
+
+#[test]
+fn released_buffer_is_reused_and_zeroed() {
+    let mut pool = PixelBufferPool::default();
+    let mut buf = pool.acquire(1000);
+    buf.fill(0xAB);
+    let ptr = buf.as_ptr();
+    pool.release(buf);
+
+    let again = pool.acquire(900);
+    assert_eq!(again.as_ptr(), ptr);
+    assert_eq!(again.len(), 900);
+    assert!(again.iter().all(|&b| b == 0));
+}
+
+#[test]
+fn smallest_fitting_buffer_is_picked() {
+    let mut pool = PixelBufferPool::default();
+    let big = pool.acquire(4000);
+    let small = pool.acquire(1000);
+    let small_ptr = small.as_ptr();
+    pool.release(big);
+    pool.release(small);
+
+    let reused = pool.acquire(500);
+    assert_eq!(reused.as_ptr(), small_ptr);
+}
+
+#[test]
+fn pool_is_bounded_and_keeps_larger_buffers() {
+    let mut pool = PixelBufferPool::default();
+    let buffers: Vec<Vec<u8>> = [16usize, 32, 64, 128, 256]
+        .iter()
+        .map(|&len| pool.acquire(len))
+        .collect();
+    for buf in buffers {
+        pool.release(buf);
+    }
+    // The 16-byte buffer was evicted, so the smallest reusable one is 32.
+    assert_eq!(pool.acquire(16).capacity(), 32);
+}
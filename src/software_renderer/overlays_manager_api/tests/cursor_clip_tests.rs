@@ -0,0 +1,23 @@
+use windows::Win32::Foundation::RECT;
+
+use crate::software_renderer::overlays_manager_api::cursor_clip::clip_rect;
+
+#[test]
+fn clip_rect_offsets_the_overlay_by_the_client_origin() {
+    let rect = clip_rect((100, 50), (20, 30), (400, 300));
+    assert_eq!(
+        rect,
+        RECT {
+            left: 120,
+            top: 80,
+            right: 520,
+            bottom: 380,
+        }
+    );
+}
+
+#[test]
+fn clip_rect_handles_negative_positions() {
+    let rect = clip_rect((0, 0), (-10, -20), (50, 60));
+    assert_eq!((rect.left, rect.top, rect.right, rect.bottom), (-10, -20, 40, 40));
+}
@@ -0,0 +1,158 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::bindings::embedder::{FlutterRect, FlutterTransformation};
+use crate::software_renderer::overlay::semantics_handler::{
+    ProcessedSemanticsNode, RustSemanticsFlag, hit_test_interactive,
+};
+use crate::software_renderer::overlays_manager_api::dispatch::{
+    input_order, modal_owner, move_to_index, pointer_consumer,
+};
+
+const IDENTITY: FlutterTransformation = FlutterTransformation {
+    scaleX: 1.0,
+    skewX: 0.0,
+    transX: 0.0,
+    skewY: 0.0,
+    scaleY: 1.0,
+    transY: 0.0,
+    pers0: 0.0,
+    pers1: 0.0,
+    pers2: 1.0,
+};
+
+fn node(
+    id: i32,
+    rect: (f64, f64, f64, f64),
+    button: bool,
+    children: Vec<i32>,
+) -> ProcessedSemanticsNode {
+    let mut flags = HashSet::new();
+    if button {
+        flags.insert(RustSemanticsFlag::IsButton);
+    }
+    ProcessedSemanticsNode {
+        id,
+        flags,
+        rect: FlutterRect {
+            left: rect.0,
+            top: rect.1,
+            right: rect.2,
+            bottom: rect.3,
+        },
+        transform_to_parent: IDENTITY,
+        children_in_hit_test_order: children,
+        label: String::new(),
+    }
+}
+
+/// Full-screen root with buttons on the left and right and a hole in between.
+fn top_tree() -> HashMap<i32, ProcessedSemanticsNode> {
+    HashMap::from([
+        (0, node(0, (0.0, 0.0, 800.0, 600.0), false, vec![1, 2])),
+        (1, node(1, (0.0, 0.0, 200.0, 600.0), true, vec![])),
+        (2, node(2, (600.0, 0.0, 800.0, 600.0), true, vec![])),
+    ])
+}
+
+/// A single button covering the whole screen.
+fn bottom_tree() -> HashMap<i32, ProcessedSemanticsNode> {
+    HashMap::from([
+        (0, node(0, (0.0, 0.0, 800.0, 600.0), false, vec![1])),
+        (1, node(1, (0.0, 0.0, 800.0, 600.0), true, vec![])),
+    ])
+}
+
+fn consumer_at(x: f64, y: f64) -> Option<String> {
+    let order = vec!["bottom".to_string(), "top".to_string()];
+    let trees = HashMap::from([("bottom", bottom_tree()), ("top", top_tree())]);
+    pointer_consumer(&order, |id| hit_test_interactive(&trees[id], x, y)).cloned()
+}
+
+#[test]
+fn top_overlay_consumes_over_its_widgets() {
+    assert_eq!(consumer_at(100.0, 300.0).as_deref(), Some("top"));
+    assert_eq!(consumer_at(700.0, 300.0).as_deref(), Some("top"));
+}
+
+#[test]
+fn hole_in_top_overlay_falls_through() {
+    assert_eq!(consumer_at(400.0, 300.0).as_deref(), Some("bottom"));
+}
+
+#[test]
+fn miss_everywhere_is_unconsumed() {
+    assert_eq!(consumer_at(900.0, 300.0), None);
+}
+
+#[test]
+fn empty_tree_never_hits() {
+    assert!(!hit_test_interactive(&HashMap::new(), 10.0, 10.0));
+}
+
+#[test]
+fn claims_not_evaluated_below_consumer() {
+    let order = vec!["bottom".to_string(), "middle".to_string(), "top".to_string()];
+    let mut asked = Vec::new();
+    let hit = pointer_consumer(&order, |id| {
+        asked.push(id.to_string());
+        id == "middle"
+    });
+    assert_eq!(hit.map(String::as_str), Some("middle"));
+    assert_eq!(asked, ["top", "middle"]);
+}
+
+#[test]
+fn topmost_modal_owns_input() {
+    let order = vec!["dialog".to_string(), "hud".to_string(), "confirm".to_string()];
+    let modal = |id: &str| id == "dialog" || id == "confirm";
+    assert_eq!(modal_owner(&order, modal).map(String::as_str), Some("confirm"));
+}
+
+#[test]
+fn no_modal_means_normal_routing() {
+    let order = vec!["bottom".to_string(), "top".to_string()];
+    assert_eq!(modal_owner(&order, |_| false), None);
+}
+
+#[test]
+fn input_priority_reorders_routing_but_keeps_ties_in_z_order() {
+    let order: Vec<String> = ["a", "b", "c", "d"].iter().map(|s| s.to_string()).collect();
+    let priorities = HashMap::from([("a", 5), ("b", 0), ("c", 0), ("d", -1)]);
+    assert_eq!(input_order(&order, |id| priorities[id]), ["d", "b", "c", "a"]);
+}
+
+#[test]
+fn higher_priority_overlay_below_is_hit_first() {
+    let order = vec!["bottom".to_string(), "top".to_string()];
+    let trees = HashMap::from([("bottom", bottom_tree()), ("top", top_tree())]);
+    let routed = input_order(&order, |id| if id == "bottom" { 1 } else { 0 });
+    let consumer = pointer_consumer(&routed, |id| hit_test_interactive(&trees[id], 100.0, 300.0));
+    assert_eq!(consumer.map(String::as_str), Some("bottom"));
+}
+
+fn stack_of(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(|id| id.to_string()).collect()
+}
+
+#[test]
+fn move_to_index_places_an_overlay_mid_stack() {
+    let mut stack = stack_of(&["a", "b", "c", "d"]);
+    assert!(move_to_index(&mut stack, "d", 1));
+    assert_eq!(stack, stack_of(&["a", "d", "b", "c"]));
+    assert!(move_to_index(&mut stack, "a", 2));
+    assert_eq!(stack, stack_of(&["d", "b", "a", "c"]));
+}
+
+#[test]
+fn move_to_index_past_the_end_moves_to_the_top() {
+    let mut stack = stack_of(&["a", "b", "c"]);
+    assert!(move_to_index(&mut stack, "a", 99));
+    assert_eq!(stack, stack_of(&["b", "c", "a"]));
+}
+
+#[test]
+fn move_to_index_ignores_unknown_overlays() {
+    let mut stack = stack_of(&["a", "b"]);
+    assert!(!move_to_index(&mut stack, "x", 0));
+    assert_eq!(stack, stack_of(&["a", "b"]));
+}
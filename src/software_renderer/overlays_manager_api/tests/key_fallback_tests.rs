@@ -0,0 +1,30 @@
+use crate::software_renderer::overlays_manager_api::KeyFallback;
+
+fn order() -> Vec<String> {
+    ["bottom", "middle", "top"].iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn drop_has_no_candidates() {
+    let order = order();
+    assert!(KeyFallback::Drop.candidates(&order, |_| true).is_empty());
+}
+
+#[test]
+fn topmost_visible_skips_hidden_overlays() {
+    let order = order();
+    let picked = KeyFallback::TopmostVisible.candidates(&order, |id| id != "top");
+    assert_eq!(picked, vec!["middle"]);
+}
+
+#[test]
+fn broadcast_is_topmost_first() {
+    let order = order();
+    let picked = KeyFallback::Broadcast.candidates(&order, |id| id != "middle");
+    assert_eq!(picked, vec!["top", "bottom"]);
+}
+
+#[test]
+fn default_is_drop() {
+    assert_eq!(KeyFallback::default(), KeyFallback::Drop);
+}
@@ -0,0 +1,31 @@
+use crate::software_renderer::overlays_manager_api::KeyRouting;
+
+fn order() -> Vec<String> {
+    ["bottom", "middle", "top"].iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn focused_only_never_overrides_focus() {
+    let order = order();
+    assert_eq!(KeyRouting::FocusedOnly.target(&order, |_| true, |_| true), None);
+}
+
+#[test]
+fn topmost_visible_skips_overlays_without_input() {
+    let order = order();
+    let picked = KeyRouting::TopmostVisible.target(&order, |id| id != "top", |_| false);
+    assert_eq!(picked.map(String::as_str), Some("middle"));
+}
+
+#[test]
+fn under_cursor_picks_the_topmost_hovered_overlay() {
+    let order = order();
+    let picked = KeyRouting::UnderCursor.target(&order, |_| true, |id| id != "top");
+    assert_eq!(picked.map(String::as_str), Some("middle"));
+    assert_eq!(KeyRouting::UnderCursor.target(&order, |_| true, |_| false), None);
+}
+
+#[test]
+fn default_is_focused_only() {
+    assert_eq!(KeyRouting::default(), KeyRouting::FocusedOnly);
+}
@@ -1 +1,8 @@
+mod buffer_pool_tests;
+mod cursor_clip_tests;
+mod dispatch_tests;
+mod key_fallback_tests;
+mod key_routing_tests;
 mod keybind_tests;
+mod resize_debounce_tests;
+mod watchdog_tests;
@@ -0,0 +1,64 @@
+use std::time::{Duration, Instant};
+
+use crate::software_renderer::overlays_manager_api::resize_debounce::{
+    ResizeDebounce, ResizeRequest,
+};
+
+fn size(width: u32, height: u32) -> ResizeRequest {
+    ResizeRequest {
+        x: 0,
+        y: 0,
+        width,
+        height,
+    }
+}
+
+#[test]
+fn resize_is_held_until_the_size_settles() {
+    let mut debounce = ResizeDebounce::default();
+    let start = Instant::now();
+    assert_eq!(debounce.push(size(800, 600), start), None);
+    assert_eq!(debounce.take_ready(start + Duration::from_millis(50)), None);
+    assert_eq!(debounce.take_ready(start + Duration::from_millis(100)), Some(size(800, 600)));
+    assert_eq!(debounce.take_ready(start + Duration::from_millis(200)), None);
+}
+
+#[test]
+fn a_new_size_restarts_the_delay() {
+    let mut debounce = ResizeDebounce::default();
+    let start = Instant::now();
+    debounce.push(size(800, 600), start);
+    debounce.push(size(820, 600), start + Duration::from_millis(80));
+    assert_eq!(debounce.take_ready(start + Duration::from_millis(120)), None);
+    assert_eq!(debounce.take_ready(start + Duration::from_millis(180)), Some(size(820, 600)));
+}
+
+#[test]
+fn repeating_the_pending_size_keeps_its_timestamp() {
+    let mut debounce = ResizeDebounce::default();
+    let start = Instant::now();
+    debounce.push(size(800, 600), start);
+    debounce.push(size(800, 600), start + Duration::from_millis(90));
+    assert_eq!(debounce.take_ready(start + Duration::from_millis(100)), Some(size(800, 600)));
+}
+
+#[test]
+fn zero_delay_and_zero_size_apply_immediately() {
+    let mut debounce = ResizeDebounce::default();
+    let now = Instant::now();
+    debounce.push(size(800, 600), now);
+    assert_eq!(debounce.push(size(0, 0), now), Some(size(0, 0)));
+    assert_eq!(debounce.take_ready(now + Duration::from_secs(1)), None);
+
+    debounce.set_delay(Duration::ZERO);
+    assert_eq!(debounce.push(size(640, 480), now), Some(size(640, 480)));
+}
+
+#[test]
+fn shortening_the_delay_releases_a_pending_size() {
+    let mut debounce = ResizeDebounce::default();
+    let now = Instant::now();
+    debounce.push(size(800, 600), now);
+    debounce.set_delay(Duration::ZERO);
+    assert_eq!(debounce.take_ready(now), Some(size(800, 600)));
+}
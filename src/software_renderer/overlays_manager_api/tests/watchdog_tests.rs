@@ -0,0 +1,99 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::software_renderer::overlay::heartbeat::Heartbeat;
+use crate::software_renderer::overlays_manager_api::watchdog::{
+    RestartPolicy, RestartReason, Watchdog,
+};
+
+fn policy(max_consecutive_errors: u32, timeout_ms: Option<u64>) -> RestartPolicy {
+    RestartPolicy {
+        max_consecutive_errors,
+        unresponsive_timeout: timeout_ms.map(Duration::from_millis),
+    }
+}
+
+#[test]
+fn restarts_after_error_streak() {
+    let mut watchdog = Watchdog::new(policy(3, None));
+    assert_eq!(watchdog.record_tick(false), None);
+    assert_eq!(watchdog.record_tick(false), None);
+    assert_eq!(watchdog.record_tick(false), Some(RestartReason::TickErrors(3)));
+}
+
+#[test]
+fn successful_tick_resets_streak() {
+    let mut watchdog = Watchdog::new(policy(2, None));
+    watchdog.record_tick(false);
+    watchdog.record_tick(true);
+    assert_eq!(watchdog.record_tick(false), None);
+}
+
+#[test]
+fn zero_error_limit_never_restarts() {
+    let mut watchdog = Watchdog::new(policy(0, None));
+    for _ in 0..100 {
+        assert_eq!(watchdog.record_tick(false), None);
+    }
+}
+
+#[test]
+fn unanswered_heartbeat_times_out() {
+    let mut watchdog = Watchdog::new(policy(0, Some(500)));
+    let start = Instant::now();
+    assert_eq!(watchdog.poll_heartbeat(start), Ok(true));
+    let first = Arc::new(Heartbeat::default());
+    watchdog.ping_sent(first.clone(), start);
+    first.mark_answered();
+    let resent = start + Duration::from_millis(500);
+    assert_eq!(watchdog.poll_heartbeat(resent), Ok(true));
+    watchdog.ping_sent(Arc::new(Heartbeat::default()), resent);
+    assert_eq!(watchdog.poll_heartbeat(resent + Duration::from_millis(100)), Ok(false));
+    assert_eq!(
+        watchdog.poll_heartbeat(resent + Duration::from_millis(600)),
+        Err(RestartReason::Unresponsive(Duration::from_millis(600)))
+    );
+}
+
+#[test]
+fn heartbeat_never_answered_is_not_resent_or_restarted() {
+    let mut watchdog = Watchdog::new(policy(0, Some(500)));
+    let start = Instant::now();
+    watchdog.ping_sent(Arc::new(Heartbeat::default()), start);
+    assert_eq!(watchdog.poll_heartbeat(start + Duration::from_secs(10)), Ok(false));
+    assert_eq!(watchdog.poll_heartbeat(start + Duration::from_secs(20)), Ok(false));
+}
+
+#[test]
+fn late_answer_resumes_probing_and_arms_the_check() {
+    let mut watchdog = Watchdog::new(policy(0, Some(500)));
+    let start = Instant::now();
+    let first = Arc::new(Heartbeat::default());
+    watchdog.ping_sent(first.clone(), start);
+    assert_eq!(watchdog.poll_heartbeat(start + Duration::from_secs(10)), Ok(false));
+    first.mark_answered();
+    let resent = start + Duration::from_secs(11);
+    assert_eq!(watchdog.poll_heartbeat(resent), Ok(true));
+    watchdog.ping_sent(Arc::new(Heartbeat::default()), resent);
+    assert_eq!(
+        watchdog.poll_heartbeat(resent + Duration::from_millis(600)),
+        Err(RestartReason::Unresponsive(Duration::from_millis(600)))
+    );
+}
+
+#[test]
+fn answered_heartbeat_is_resent_after_interval() {
+    let mut watchdog = Watchdog::new(policy(0, Some(5_000)));
+    let start = Instant::now();
+    let heartbeat = Arc::new(Heartbeat::default());
+    watchdog.ping_sent(heartbeat.clone(), start);
+    heartbeat.mark_answered();
+    assert_eq!(watchdog.poll_heartbeat(start + Duration::from_millis(200)), Ok(false));
+    assert_eq!(watchdog.poll_heartbeat(start + Duration::from_secs(2)), Ok(true));
+}
+
+#[test]
+fn no_timeout_sends_no_heartbeat() {
+    let mut watchdog = Watchdog::new(policy(10, None));
+    assert_eq!(watchdog.poll_heartbeat(Instant::now()), Ok(false));
+}
@@ -5,6 +5,7 @@ use std::collections::HashMap;
 
 use windows::Win32::Graphics::Direct3D11::{ID3D11SamplerState, ID3D11ShaderResourceView};
 
+use crate::software_renderer::api::RendererType;
 use crate::software_renderer::d3d11_compositor::text_3d_renderer::GlyphInfo;
 
 /// A font atlas to register on an overlay for 3D text rendering.
@@ -23,6 +24,31 @@ pub struct FontAtlasSpec {
     pub base_font_size: f32,
 }
 
+/// Position and size of an overlay, in client pixels of the host window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OverlayBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Optional settings for starting an overlay, taken by
+/// `init_instance_with_options` and `init_instance_with_dirs`. The default
+/// covers the whole swap chain and picks the renderer automatically.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OverlayOptions {
+    /// Arguments for the Dart `main()` function.
+    pub dart_args: Option<Vec<String>>,
+    /// Command-line switches for the Flutter Engine.
+    pub engine_args: Option<Vec<String>>,
+    /// Forces a renderer instead of trying OpenGL and falling back to software.
+    pub preferred_renderer: Option<RendererType>,
+    /// Position and size of the overlay. `None` covers the swap chain and
+    /// follows it on resize; a fixed size is kept across resizes.
+    pub bounds: Option<OverlayBounds>,
+}
+
 /// Specifies which rendering pass to execute, allowing for separation of 3D
 /// primitives and 2D UI.
 pub enum FlutterRenderPass {
@@ -31,3 +57,66 @@ pub enum FlutterRenderPass {
     /// Render only the 2D Flutter UI.
     UiOnly,
 }
+
+/// What to do with a key event when no overlay has keyboard focus.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyFallback {
+    /// Drop the event (the host keeps it).
+    #[default]
+    Drop,
+    /// Deliver to the topmost visible overlay.
+    TopmostVisible,
+    /// Offer to every visible overlay, topmost first, until one consumes it.
+    Broadcast,
+}
+
+impl KeyFallback {
+    /// Overlays to offer an unfocused key event to, in delivery order.
+    ///
+    /// `overlay_order` is bottom-to-top, as stored on the manager.
+    pub(crate) fn candidates(
+        self,
+        overlay_order: &[String],
+        is_visible: impl Fn(&str) -> bool,
+    ) -> Vec<&String> {
+        let visible = overlay_order.iter().rev().filter(|id| is_visible(id));
+        match self {
+            KeyFallback::Drop => Vec::new(),
+            KeyFallback::TopmostVisible => visible.take(1).collect(),
+            KeyFallback::Broadcast => visible.collect(),
+        }
+    }
+}
+
+/// Which overlay receives key events.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyRouting {
+    /// The focused overlay, or the [`KeyFallback`] policy when none is.
+    #[default]
+    FocusedOnly,
+    /// The topmost overlay that accepts input, regardless of focus.
+    TopmostVisible,
+    /// The topmost overlay with an interactive widget under the cursor. Falls
+    /// back to [`KeyRouting::FocusedOnly`] when the cursor is over none.
+    UnderCursor,
+}
+
+impl KeyRouting {
+    /// The overlay this policy sends a key event to instead of the focused
+    /// one, or `None` to use focus.
+    ///
+    /// `overlay_order` is bottom-to-top, as stored on the manager.
+    pub(crate) fn target(
+        self,
+        overlay_order: &[String],
+        accepts_input: impl Fn(&str) -> bool,
+        under_cursor: impl Fn(&str) -> bool,
+    ) -> Option<&String> {
+        let mut eligible = overlay_order.iter().rev().filter(|id| accepts_input(id));
+        match self {
+            KeyRouting::FocusedOnly => None,
+            KeyRouting::TopmostVisible => eligible.next(),
+            KeyRouting::UnderCursor => eligible.find(|id| under_cursor(id)),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Opt-in per-overlay watchdog. Each tick the manager reports whether the
+//! overlay could schedule a frame, and the watchdog keeps one non-blocking
+//! heartbeat in flight. Too many failed ticks in a row, or a heartbeat that
+//! goes unanswered for too long, makes the manager restart the overlay in place.
+//! The heartbeat check only arms after a first reply, so an app that never
+//! answers the probe is not restarted over and over. Until that reply comes no
+//! second heartbeat is sent, as each one holds a reply callback in the engine.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::warn;
+
+use crate::software_renderer::overlays_manager_api::types::OverlayOptions;
+use crate::software_renderer::overlay::heartbeat::{Heartbeat, send_heartbeat};
+use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
+
+/// Minimum time between two heartbeats that were both answered.
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// When the watchdog restarts an overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartPolicy {
+    /// Ticks in a row whose frame request fails, or whose device is lost,
+    /// before a restart. 0 disables the check.
+    pub max_consecutive_errors: u32,
+    /// How long a heartbeat may stay unanswered before a restart. `None`
+    /// disables the check. It applies once a heartbeat has been answered, as
+    /// the probe needs the app to run `WidgetsBinding`.
+    pub unresponsive_timeout: Option<Duration>,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_consecutive_errors: 60,
+            unresponsive_timeout: Some(Duration::from_secs(5)),
+        }
+    }
+}
+
+/// Why the watchdog restarted an overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartReason {
+    /// This many ticks in a row failed.
+    TickErrors(u32),
+    /// A heartbeat went unanswered for this long.
+    Unresponsive(Duration),
+}
+
+/// Called after the watchdog restarted an overlay. Receives the overlay id,
+/// the reason and whether the new instance started. Runs on the thread that
+/// called `tick_overlays`, after the manager is unlocked.
+pub type OverlayRestartCallback = Arc<dyn Fn(&str, RestartReason, bool) + Send + Sync + 'static>;
+
+/// What `init` was given for an overlay, kept so it can be started again.
+#[derive(Clone)]
+pub(crate) struct OverlayLaunch {
+    pub(crate) asset_dir: PathBuf,
+    pub(crate) options: OverlayOptions,
+}
+
+pub(crate) struct Watchdog {
+    pub(crate) policy: RestartPolicy,
+    consecutive_errors: u32,
+    ping: Option<(Arc<Heartbeat>, Instant)>,
+    /// Whether any heartbeat was answered since the watchdog was created.
+    echo_confirmed: bool,
+    /// The first heartbeat outlived the timeout without an answer; probing is
+    /// paused until it is answered.
+    probe_stalled: bool,
+}
+
+impl Watchdog {
+    pub(crate) fn new(policy: RestartPolicy) -> Self {
+        Self {
+            policy,
+            consecutive_errors: 0,
+            ping: None,
+            echo_confirmed: false,
+            probe_stalled: false,
+        }
+    }
+
+    /// Counts one tick. Returns the reason to restart once the error streak
+    /// reaches the policy's limit.
+    pub(crate) fn record_tick(&mut self, ok: bool) -> Option<RestartReason> {
+        if ok {
+            self.consecutive_errors = 0;
+            return None;
+        }
+        self.consecutive_errors += 1;
+        let limit = self.policy.max_consecutive_errors;
+        (limit > 0 && self.consecutive_errors >= limit)
+            .then_some(RestartReason::TickErrors(self.consecutive_errors))
+    }
+
+    /// Checks the heartbeat in flight. `Ok(true)` means the caller should send
+    /// a new one and pass it to `ping_sent`.
+    pub(crate) fn poll_heartbeat(&mut self, now: Instant) -> Result<bool, RestartReason> {
+        let Some(timeout) = self.policy.unresponsive_timeout else {
+            return Ok(false);
+        };
+        match &self.ping {
+            None => Ok(true),
+            Some((heartbeat, sent_at)) => {
+                let waited = now.saturating_duration_since(*sent_at);
+                if heartbeat.is_answered() {
+                    self.echo_confirmed = true;
+                    self.probe_stalled = false;
+                    Ok(waited >= PING_INTERVAL.min(timeout))
+                } else if waited < timeout {
+                    Ok(false)
+                } else if self.echo_confirmed {
+                    self.ping = None;
+                    Err(RestartReason::Unresponsive(waited))
+                } else {
+                    // Nothing ever answered, so a hang can't be told apart from
+                    // an app without the handler. Keep waiting on this ping
+                    // rather than piling up new ones.
+                    self.probe_stalled = true;
+                    Ok(false)
+                }
+            }
+        }
+    }
+
+    pub(crate) fn ping_sent(&mut self, heartbeat: Arc<Heartbeat>, now: Instant) {
+        self.ping = Some((heartbeat, now));
+    }
+
+    /// Runs both checks for one tick of `overlay`.
+    pub(crate) fn observe(
+        &mut self,
+        overlay: &FlutterOverlay,
+        tick_ok: bool,
+        now: Instant,
+    ) -> Option<RestartReason> {
+        if let Some(reason) = self.record_tick(tick_ok) {
+            return Some(reason);
+        }
+        let was_stalled = self.probe_stalled;
+        match self.poll_heartbeat(now) {
+            Err(reason) => Some(reason),
+            Ok(true) => {
+                match send_heartbeat(overlay) {
+                    Ok(heartbeat) => self.ping_sent(heartbeat, now),
+                    Err(e) => warn!("[Watchdog:{}] Heartbeat not sent: {e:?}", overlay.name),
+                }
+                None
+            }
+            Ok(false) => {
+                if self.probe_stalled && !was_stalled {
+                    warn!(
+                        "[Watchdog:{}] First heartbeat unanswered; the unresponsive check is \
+                         off until it is answered",
+                        overlay.name
+                    );
+                }
+                None
+            }
+        }
+    }
+}
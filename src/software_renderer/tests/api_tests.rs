@@ -1,4 +1,11 @@
-use crate::software_renderer::api::{FlutterEmbedderError, should_skip_resize};
+use crate::software_renderer::api::{
+    AlphaMode, EngineFlags, FRAME_TIMER_WINDOW, FlutterEmbedderError, FlutterPaths, FpsStats,
+    FrameTimer, RuntimeMode, convert_alpha, premultiply, present_instant, presented_recently,
+    resize_suspend_change, should_skip_resize, unpremultiply,
+};
+use std::error::Error;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 #[test]
 fn skip_resize_when_unchanged_and_not_forced() {
@@ -20,6 +27,38 @@ fn no_skip_when_position_changed() {
     assert!(!should_skip_resize((0, 0, 800, 600), (10, 20, 800, 600), false));
 }
 
+#[test]
+fn zero_width_suspends_rendering() {
+    assert_eq!(resize_suspend_change(false, 0, 600), Some(true));
+}
+
+#[test]
+fn zero_height_suspends_rendering() {
+    assert_eq!(resize_suspend_change(false, 800, 0), Some(true));
+}
+
+#[test]
+fn non_zero_size_resumes_rendering() {
+    assert_eq!(resize_suspend_change(true, 800, 600), Some(false));
+    assert_eq!(resize_suspend_change(false, 800, 600), None);
+}
+
+#[test]
+fn repeated_zero_size_keeps_rendering_suspended() {
+    assert_eq!(resize_suspend_change(true, 0, 0), None);
+    assert_eq!(resize_suspend_change(true, 0, 600), None);
+}
+
+#[test]
+fn present_instant_is_none_before_first_present() {
+    let start = Instant::now();
+    assert_eq!(present_instant(start, 0), None);
+    assert_eq!(
+        present_instant(start, 1_500),
+        Some(start + Duration::from_nanos(1_500))
+    );
+}
+
 #[test]
 fn error_display_messages() {
     assert_eq!(
@@ -39,3 +78,195 @@ fn error_display_messages() {
         "Invalid Flutter overlay handle provided."
     );
 }
+
+#[test]
+fn structured_init_errors_display_their_fields() {
+    assert_eq!(
+        FlutterEmbedderError::AssetMissing {
+            which: "icudtl.dat",
+            path: PathBuf::from("bundle/icudtl.dat"),
+        }
+        .to_string(),
+        "Missing icudtl.dat: `bundle/icudtl.dat` does not exist"
+    );
+    assert_eq!(
+        FlutterEmbedderError::RendererInitFailed {
+            renderer: "ANGLE",
+            egl_code: Some(0x3001),
+            message: "eglInitialize failed".to_string(),
+        }
+        .to_string(),
+        "ANGLE renderer failed: eglInitialize failed (EGL 0x3001)"
+    );
+    assert_eq!(
+        FlutterEmbedderError::EngineRunFailed { result_code: 2 }.to_string(),
+        "Flutter engine failed to start: FlutterEngineResult 2"
+    );
+}
+
+#[test]
+fn dll_load_failed_chains_its_source() {
+    let err = FlutterEmbedderError::DllLoadFailed {
+        path: PathBuf::from("bundle"),
+        source: "LoadLibraryExW failed".into(),
+    };
+    assert_eq!(
+        err.source().map(|s| s.to_string()).as_deref(),
+        Some("LoadLibraryExW failed")
+    );
+    assert!(FlutterEmbedderError::EngineNotRunning.source().is_none());
+}
+
+#[test]
+fn premultiply_scales_color_by_alpha() {
+    let mut px = [200, 100, 50, 128, 10, 20, 30, 255];
+    premultiply(&mut px);
+    assert_eq!(px, [100, 50, 25, 128, 10, 20, 30, 255]);
+}
+
+#[test]
+fn unpremultiply_restores_color_and_zeroes_transparent() {
+    let mut px = [100, 50, 25, 128, 9, 9, 9, 0];
+    unpremultiply(&mut px);
+    assert_eq!(px, [199, 100, 50, 128, 0, 0, 0, 0]);
+}
+
+#[test]
+fn alpha_round_trip_is_close() {
+    let original = [240u8, 120, 8, 200];
+    let mut px = original;
+    convert_alpha(&mut px, AlphaMode::Straight, AlphaMode::Premultiplied);
+    convert_alpha(&mut px, AlphaMode::Premultiplied, AlphaMode::Straight);
+    for (a, b) in px.iter().zip(original.iter()) {
+        assert!((*a as i32 - *b as i32).abs() <= 1);
+    }
+}
+
+#[test]
+fn convert_alpha_same_mode_is_noop() {
+    let mut px = [1, 2, 3, 4];
+    convert_alpha(&mut px, AlphaMode::Straight, AlphaMode::Straight);
+    assert_eq!(px, [1, 2, 3, 4]);
+}
+
+#[test]
+fn engine_flags_emit_all_switches_in_debug() {
+    let flags = EngineFlags::new()
+        .disable_dart_asserts(true)
+        .trace_startup(true)
+        .enable_vm_service(8181)
+        .verbose_logging(true);
+    assert_eq!(
+        flags.switches_for(RuntimeMode::Debug),
+        [
+            "--disable-dart-asserts",
+            "--trace-startup",
+            "--vm-service-port=8181",
+            "--verbose-logging"
+        ]
+    );
+}
+
+#[test]
+fn engine_flags_drop_switches_unsupported_by_mode() {
+    let flags = EngineFlags::new()
+        .disable_dart_asserts(true)
+        .trace_startup(true)
+        .enable_vm_service(0)
+        .verbose_logging(true);
+    assert_eq!(
+        flags.switches_for(RuntimeMode::Profile),
+        ["--trace-startup", "--vm-service-port=0", "--verbose-logging"]
+    );
+    assert_eq!(flags.switches_for(RuntimeMode::Release), ["--verbose-logging"]);
+}
+
+#[test]
+fn engine_flags_default_is_empty() {
+    assert!(EngineFlags::new().switches_for(RuntimeMode::Debug).is_empty());
+}
+
+#[test]
+fn frame_timer_starts_zeroed() {
+    assert_eq!(FrameTimer::default().stats(), FpsStats::default());
+}
+
+#[test]
+fn frame_timer_averages_recent_frames() {
+    let mut timer = FrameTimer::default();
+    timer.record(2.0);
+    timer.record(4.0);
+    let stats = timer.stats();
+    assert_eq!(stats.last_frame_ms, 4.0);
+    assert_eq!(stats.avg_frame_ms, 3.0);
+    assert_eq!(stats.present_count, 2);
+}
+
+#[test]
+fn frame_timer_window_drops_oldest() {
+    let mut timer = FrameTimer::default();
+    timer.record(100.0);
+    for _ in 0..FRAME_TIMER_WINDOW {
+        timer.record(1.0);
+    }
+    let stats = timer.stats();
+    assert!((stats.avg_frame_ms - 1.0).abs() < 1e-4);
+    assert_eq!(stats.present_count, FRAME_TIMER_WINDOW as u64 + 1);
+}
+
+#[test]
+fn frame_timer_reset_clears_history() {
+    let mut timer = FrameTimer::default();
+    timer.record(5.0);
+    timer.reset();
+    assert_eq!(timer.stats(), FpsStats::default());
+}
+
+#[test]
+fn responsive_when_presented_within_timeout() {
+    let start = Instant::now();
+    let timeout = Duration::from_secs(5);
+    let presented = start + Duration::from_secs(10);
+    assert!(presented_recently(Some(presented), start, presented + timeout, timeout));
+    assert!(!presented_recently(
+        Some(presented),
+        start,
+        presented + Duration::from_secs(6),
+        timeout
+    ));
+}
+
+#[test]
+fn first_frame_gets_the_timeout_from_startup() {
+    let start = Instant::now();
+    let timeout = Duration::from_secs(5);
+    assert!(presented_recently(None, start, start + Duration::from_secs(4), timeout));
+    assert!(!presented_recently(None, start, start + Duration::from_secs(6), timeout));
+}
+
+#[test]
+fn flutter_paths_name_the_missing_path() {
+    let dir = std::env::temp_dir();
+    let icu = dir.join("flutter_paths_test_missing_icudtl.dat");
+    let paths = FlutterPaths {
+        assets: dir.clone(),
+        icu: icu.clone(),
+        aot: None,
+    };
+    match paths.validate() {
+        Err(FlutterEmbedderError::InitializationFailed(message)) => {
+            assert!(message.contains("icudtl.dat"));
+            assert!(message.contains(&icu.display().to_string()));
+        }
+        other => panic!("expected InitializationFailed, got {other:?}"),
+    }
+
+    let no_assets = FlutterPaths {
+        assets: dir.join("flutter_paths_test_missing_assets"),
+        ..paths
+    };
+    assert!(matches!(
+        no_assets.validate(),
+        Err(FlutterEmbedderError::InitializationFailed(m)) if m.contains("flutter_assets")
+    ));
+}
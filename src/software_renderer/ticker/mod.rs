@@ -6,9 +6,10 @@
 //! timer between deadlines instead of busy-waiting.
 //!
 //! On the software path, the engine hands rendered pixels back through
-//! [`on_present`], which copies them into the overlay's pixel buffer; the next
-//! [`tick`](crate::software_renderer::api::FlutterOverlay::tick) uploads that
-//! buffer to the D3D11 texture.
+//! [`on_present`], which copies them into the overlay's pixel buffer and notes
+//! which rectangle changed; the next
+//! [`tick`](crate::software_renderer::api::FlutterOverlay::tick) uploads just
+//! that rectangle to the D3D11 texture.
 //!
 //! This module is an internal implementation detail and is not part of the public
 //! API; it is documented here for contributors.
@@ -40,6 +40,12 @@ pub extern "C" fn on_present(
         let src_len = rows.saturating_sub(1) * row_bytes_flutter + bytes;
         let dst_len = pixel_buffer.len();
         let src = unsafe { std::slice::from_raw_parts(allocation as *const u8, src_len) };
+        let damage = frame_damage(src, pixel_buffer, rows, row_bytes_flutter, pitch, bytes);
+        if let Some(rect) = damage
+            && let Ok(mut pending) = ov.software_damage.lock()
+        {
+            *pending = Some(pending.map_or(rect, |p| p.union(&rect)));
+        }
         copy_framebuffer(src, pixel_buffer, rows, row_bytes_flutter, pitch, bytes, dst_len);
 
         ov.software_frame_dirty.store(true, Ordering::Release);
@@ -49,6 +55,73 @@ pub extern "C" fn on_present(
     true
 }
 
+/// A pixel rectangle of the software frame that changed since the last upload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DirtyRect {
+    pub(crate) x: u32,
+    pub(crate) y: u32,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+impl DirtyRect {
+    /// Smallest rectangle covering both.
+    pub(crate) fn union(&self, other: &DirtyRect) -> DirtyRect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = (self.x + self.width).max(other.x + other.width);
+        let bottom = (self.y + self.height).max(other.y + other.height);
+        DirtyRect {
+            x,
+            y,
+            width: right - x,
+            height: bottom - y,
+        }
+    }
+}
+
+/// Bounding box of the 4-byte pixels that differ between the incoming frame and
+/// the pixel buffer, with the same layout arguments as [`copy_framebuffer`].
+/// The software present callback carries no damage region, so this diff is what
+/// lets `tick` upload only the changed part. `None` means the frame is identical.
+pub(crate) fn frame_damage(
+    src: &[u8],
+    dst: &[u8],
+    rows: usize,
+    src_pitch: usize,
+    dst_pitch: usize,
+    bytes: usize,
+) -> Option<DirtyRect> {
+    let mut bounds: Option<(usize, usize, usize, usize)> = None;
+    for y in 0..rows {
+        let s = y * src_pitch;
+        let d = y * dst_pitch;
+        if s + bytes > src.len() || d + bytes > dst.len() {
+            break;
+        }
+        let (new, old) = (&src[s..s + bytes], &dst[d..d + bytes]);
+        if new == old {
+            continue;
+        }
+        let differs = |i: &usize| new[i * 4..(i + 1) * 4] != old[i * 4..(i + 1) * 4];
+        let pixels = bytes / 4;
+        let Some(first) = (0..pixels).find(differs) else {
+            continue;
+        };
+        let last = (0..pixels).rev().find(differs).unwrap_or(first);
+        bounds = Some(match bounds {
+            None => (first, y, last, y),
+            Some((l, t, r, _)) => (l.min(first), t, r.max(last), y),
+        });
+    }
+    bounds.map(|(left, top, right, bottom)| DirtyRect {
+        x: left as u32,
+        y: top as u32,
+        width: (right - left + 1) as u32,
+        height: (bottom - top + 1) as u32,
+    })
+}
+
 /// Copies `rows` rows of `bytes` bytes each from a Flutter framebuffer (`src_pitch`
 /// stride) into a destination pixel buffer (`dst_pitch` stride). Bounds-checked so
 /// a short buffer cannot overrun. Pure and unit-testable.
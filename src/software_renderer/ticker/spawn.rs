@@ -5,15 +5,19 @@ use crate::bindings::embedder::{
     FlutterViewFocusState_kFocused, FlutterViewFocusState_kUnfocused,
 };
 
+use crate::software_renderer::api::TaskRunnerComApartment;
 use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
 use crate::software_renderer::ticker::task_runner_window::{Timer, run_message_loop};
 
-use log::error;
+use log::{error, warn};
 use std::ffi::{CString, c_void};
 use std::ptr;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
 use std::{thread, time::Duration};
+use windows::Win32::System::Com::{
+    COINIT_APARTMENTTHREADED, COINIT_MULTITHREADED, CoInitializeEx, CoUninitialize,
+};
 
 extern "C" fn key_event_callback(_handled: bool, user_data: *mut c_void) {
     unsafe {
@@ -25,6 +29,22 @@ extern "C" fn key_event_callback(_handled: bool, user_data: *mut c_void) {
     }
 }
 
+/// Enters `apartment` on the calling thread. Returns whether a matching
+/// `CoUninitialize` is owed.
+fn init_thread_com(apartment: TaskRunnerComApartment) -> bool {
+    let coinit = match apartment {
+        TaskRunnerComApartment::None => return false,
+        TaskRunnerComApartment::SingleThreaded => COINIT_APARTMENTTHREADED,
+        TaskRunnerComApartment::MultiThreaded => COINIT_MULTITHREADED,
+    };
+    let hr = unsafe { CoInitializeEx(None, coinit) };
+    if hr.is_err() {
+        warn!("[TaskRunner] CoInitializeEx({apartment:?}) failed: {hr:?}");
+        return false;
+    }
+    true
+}
+
 pub fn start_task_runner(overlay: &mut FlutterOverlay) {
     if overlay.task_runner_thread.is_some() {
         return;
@@ -37,6 +57,7 @@ pub fn start_task_runner(overlay: &mut FlutterOverlay) {
     let pending_messages_for_thread = overlay.pending_platform_messages.clone();
     let pending_keys_for_thread = overlay.pending_key_events.clone();
     let pending_view_focus_for_thread = overlay.pending_view_focus.clone();
+    let com_apartment = overlay.task_runner_com;
 
     let waker_for_thread = overlay.task_queue_state.waker.clone();
     let timer = Timer::new();
@@ -47,6 +68,8 @@ pub fn start_task_runner(overlay: &mut FlutterOverlay) {
     let handle = thread::Builder::new()
         .name(format!("task_runner_{name_for_thread}"))
         .spawn(move || {
+            let com_initialized = init_thread_com(com_apartment);
+
             let process = move || {
                 let engine = engine_atomic_ptr.load(Ordering::SeqCst);
                 if engine.is_null() {
@@ -170,6 +193,10 @@ pub fn start_task_runner(overlay: &mut FlutterOverlay) {
 
             run_message_loop(&waker_for_thread, process);
             let _ = &waker_for_thread;
+
+            if com_initialized {
+                unsafe { CoUninitialize() };
+            }
         })
         .expect("Failed to spawn task runner thread");
 
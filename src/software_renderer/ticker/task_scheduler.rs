@@ -95,6 +95,14 @@ impl TaskQueueState {
     pub fn set_timer(&self, timer: Arc<Timer>) {
         *self.timer.lock().unwrap() = Some(timer);
     }
+
+    /// Whether a task is queued with a target time at or before `now`
+    /// (engine time, nanoseconds).
+    pub fn has_due_task(&self, now: u64) -> bool {
+        self.queue
+            .lock()
+            .is_ok_and(|queue| queue.peek().is_some_and(|task| task.target_time <= now))
+    }
 }
 
 pub struct TaskQueueState {
@@ -1,4 +1,7 @@
-use crate::software_renderer::ticker::present::copy_framebuffer;
+use crate::software_renderer::ticker::present::{
+    DirtyRect, copy_framebuffer, frame_damage, on_present,
+};
+use crate::software_renderer::ticker::ticker::clip_to;
 
 #[test]
 fn copies_all_rows_same_pitch() {
@@ -43,3 +46,56 @@ fn short_src_does_not_overrun() {
     assert_eq!(&dst[0..4], &[1, 2, 3, 4]);
     assert_eq!(&dst[4..8], &[0, 0, 0, 0]);
 }
+
+fn rect(x: u32, y: u32, width: u32, height: u32) -> DirtyRect {
+    DirtyRect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+#[test]
+fn identical_frame_has_no_damage() {
+    let frame = vec![7u8; 4 * 4 * 3];
+    assert_eq!(frame_damage(&frame, &frame, 3, 16, 16, 16), None);
+}
+
+#[test]
+fn damage_covers_changed_pixels_only() {
+    // 4x3 pixels; change pixel (1, 1) and (2, 2).
+    let old = vec![0u8; 4 * 4 * 3];
+    let mut new = old.clone();
+    new[16 + 4] = 1;
+    new[32 + 8 + 3] = 1;
+    assert_eq!(frame_damage(&new, &old, 3, 16, 16, 16), Some(rect(1, 1, 2, 2)));
+}
+
+#[test]
+fn damage_respects_source_pitch() {
+    // Source rows are padded to 12 bytes; the padding must not count as a change.
+    let old = vec![0u8; 8 * 2];
+    let mut new = vec![9u8; 12 * 2];
+    new[..8].fill(0);
+    new[12..20].fill(0);
+    new[12] = 5;
+    assert_eq!(frame_damage(&new, &old, 2, 12, 8, 8), Some(rect(0, 1, 1, 1)));
+}
+
+#[test]
+fn union_spans_both_rects() {
+    assert_eq!(rect(2, 3, 4, 1).union(&rect(0, 0, 1, 1)), rect(0, 0, 6, 4));
+}
+
+#[test]
+fn damage_is_clipped_after_shrink() {
+    assert_eq!(clip_to(rect(50, 10, 100, 100), 80, 60), rect(50, 10, 30, 50));
+    assert_eq!(clip_to(rect(90, 10, 5, 5), 80, 60), rect(80, 10, 0, 5));
+}
+
+#[test]
+fn present_after_shutdown_is_ignored() {
+    let frame = [0u8; 16];
+    assert!(on_present(std::ptr::null_mut(), frame.as_ptr().cast(), 8, 2));
+}
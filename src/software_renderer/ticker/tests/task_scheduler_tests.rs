@@ -1,7 +1,11 @@
 use crate::bindings::embedder::FlutterTask;
-use crate::software_renderer::ticker::task_scheduler::{SafeFlutterTask, ScheduledTask};
+use crate::software_renderer::ticker::task_runner_window::Waker;
+use crate::software_renderer::ticker::task_scheduler::{
+    SafeFlutterTask, ScheduledTask, TaskQueueState,
+};
 use std::collections::BinaryHeap;
 use std::ptr;
+use std::sync::Arc;
 
 fn task(target_time: u64, id: u64) -> ScheduledTask {
     ScheduledTask {
@@ -48,3 +52,14 @@ fn single_task_round_trips() {
     assert_eq!(popped.task.0.task, 7);
     assert!(heap.is_empty());
 }
+
+#[test]
+fn has_due_task_looks_only_at_the_earliest_task() {
+    let state = TaskQueueState::new(Arc::new(Waker::new()));
+    assert!(!state.has_due_task(100));
+    state.queue.lock().unwrap().push(task(150, 1));
+    assert!(!state.has_due_task(100));
+    state.queue.lock().unwrap().push(task(80, 2));
+    assert!(state.has_due_task(100));
+    assert!(state.has_due_task(80));
+}
@@ -1,69 +1,92 @@
 use log::error;
 use std::sync::atomic::Ordering;
-use std::{mem, ptr};
-use windows::Win32::Graphics::Direct3D11::{
-    D3D11_MAP_WRITE_DISCARD, D3D11_MAPPED_SUBRESOURCE, ID3D11DeviceContext,
-};
+use windows::Win32::Graphics::Direct3D11::{D3D11_BOX, ID3D11DeviceContext};
 
 use crate::software_renderer::overlay::overlay_impl::FlutterOverlay;
+use crate::software_renderer::ticker::present::DirtyRect;
 
-pub fn tick(overlay: &FlutterOverlay, context: &ID3D11DeviceContext) {
+/// Uploads the latest software frame. Returns `true` if the texture changed.
+///
+/// Only the rectangle `on_present` found changed is uploaded, and a frame
+/// identical to the last one uploads nothing. The whole frame goes up after
+/// creation or a resize (`full_repaint_needed`), or when the damage of the
+/// pending frame can't be read.
+pub fn tick(overlay: &FlutterOverlay, context: &ID3D11DeviceContext) -> bool {
     if !overlay.software_frame_dirty.swap(false, Ordering::Acquire) {
-        return;
+        return false;
     }
 
     if let Some(pixel_buffer) = &overlay.pixel_buffer {
         if overlay.width == 0 || overlay.height == 0 {
-            return;
+            return false;
         }
 
-        unsafe {
-            let mut mapped: D3D11_MAPPED_SUBRESOURCE = mem::zeroed();
-            if context
-                .Map(
-                    &overlay.texture,
-                    0,
-                    D3D11_MAP_WRITE_DISCARD,
-                    0,
-                    Some(&mut mapped),
-                )
-                .is_ok()
-            {
-                let data = mapped.pData;
-                if data.is_null() {
-                    error!("[tick] mapped pData is null");
-                    context.Unmap(&overlay.texture, 0);
-                    return;
-                }
-
-                let rp_tex = mapped.RowPitch as usize;
-                let rp_buf = (overlay.width as usize) * 4;
-
-                if rp_tex < rp_buf {
-                    error!("[tick] tex_pitch {rp_tex} < buf_pitch {rp_buf}");
-                    context.Unmap(&overlay.texture, 0);
-                    return;
-                }
-
-                if pixel_buffer.len() < rp_buf * (overlay.height as usize) {
-                    error!(
-                        "[tick] buffer too small ({} req, {} have)",
-                        rp_buf * (overlay.height as usize),
-                        pixel_buffer.len()
-                    );
-                    context.Unmap(&overlay.texture, 0);
-                    return;
-                }
-
-                let src = pixel_buffer.as_ptr();
-                for y in 0..overlay.height as usize {
-                    let dst = (data as *mut u8).add(y * rp_tex);
-                    let row = src.add(y * rp_buf);
-                    ptr::copy_nonoverlapping(row, dst, rp_buf);
-                }
+        let pitch = (overlay.width as usize) * 4;
+        if pixel_buffer.len() < pitch * (overlay.height as usize) {
+            error!(
+                "[tick] buffer too small ({} req, {} have)",
+                pitch * (overlay.height as usize),
+                pixel_buffer.len()
+            );
+            return false;
+        }
 
-                context.Unmap(&overlay.texture, 0);
+        let full = DirtyRect {
+            x: 0,
+            y: 0,
+            width: overlay.width,
+            height: overlay.height,
+        };
+        // A poisoned lock has lost the damage, so upload the whole frame
+        // rather than dropping it.
+        let damage = match overlay.software_damage.lock() {
+            Ok(mut pending) => pending.take(),
+            Err(_) => Some(full),
+        };
+        let rect = if overlay.full_repaint_needed.swap(false, Ordering::AcqRel) {
+            full
+        } else {
+            match damage {
+                Some(rect) => clip_to(rect, overlay.width, overlay.height),
+                None => return false,
             }
+        };
+        if rect.width == 0 || rect.height == 0 {
+            return false;
         }
+
+        let dst_box = D3D11_BOX {
+            left: rect.x,
+            top: rect.y,
+            front: 0,
+            right: rect.x + rect.width,
+            bottom: rect.y + rect.height,
+            back: 1,
+        };
+        let offset = rect.y as usize * pitch + rect.x as usize * 4;
+        unsafe {
+            context.UpdateSubresource(
+                &overlay.texture,
+                0,
+                Some(&dst_box),
+                pixel_buffer[offset..].as_ptr() as *const _,
+                pitch as u32,
+                0,
+            );
+        }
+        return true;
+    }
+    false
+}
+
+/// Clips `rect` to a `width` x `height` texture. Damage can outlive a resize.
+pub(crate) fn clip_to(rect: DirtyRect, width: u32, height: u32) -> DirtyRect {
+    let x = rect.x.min(width);
+    let y = rect.y.min(height);
+    DirtyRect {
+        x,
+        y,
+        width: rect.width.min(width - x),
+        height: rect.height.min(height - y),
     }
 }
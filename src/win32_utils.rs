@@ -10,6 +10,7 @@
 //!    child window so it sits inside your native frame.
 //! 4. **Message handling** – Intercept and handle all the key Win32 messages:
 //!    - **Sizing**: Resize both native child and Flutter viewport on `WM_SIZE`.  
+//!    - **Size limits**: Enforce the `AppState` size constraints on `WM_GETMINMAXINFO`.
 //!    - **Focus**: Forward activation and focus changes (`WM_ACTIVATE` / `WM_SETFOCUS`)
 //!      to the Flutter child.  
 //!    - **Non-client drawing**: Give plugins (e.g. `window_manager`) first look at
@@ -20,8 +21,30 @@
 //!    - **DPI changes**: Reposition and resize on `WM_DPICHANGED` for per-monitor DPI.  
 //!    - **Clean shutdown**: Destroy window on `WM_CLOSE` and post `WM_QUIT` on
 //!      `WM_DESTROY`.
+//!    - **Deferred plugins**: Register plugins loaded in the background when
+//!      `WM_PLUGINS_PREPARED` arrives.
+//!    - **System settings**: Optionally pass `WM_SETTINGCHANGE` / `WM_THEMECHANGED`
+//!      on to the Flutter child, which Windows only sends to top-level windows.
+//!    - **Deferred show**: Show a window waiting for its first frame once the
+//!      `FIRST_FRAME_SHOW_TIMER` fallback fires.
 //! 5. **Message loop** – Run `GetMessage`/`DispatchMessage` until `WM_QUIT`.
 //!
+//! # Keyboard input
+//!
+//! Keys normally go straight to the Flutter child, which has focus:
+//!
+//! 1. `GetMessageW` returns `WM_KEYDOWN` addressed to the focused child.
+//! 2. `TranslateMessage` posts the matching `WM_CHAR` (for Ctrl+C that is
+//!    `0x03`) to the same window.
+//! 3. `DispatchMessageW` hands `WM_KEYDOWN` to Flutter's window procedure, which
+//!    peeks for the `WM_CHAR` to build a single key event; shortcuts such as
+//!    Ctrl+C/V are resolved from it by the framework.
+//!
+//! When the parent holds focus instead (after activation races, or a click on
+//! the frame), the message loop re-addresses keyboard messages to the child
+//! *before* translating them, so the `WM_CHAR` lands there too rather than in
+//! `DefWindowProcW`, and gives the child focus back.
+//!
 //! # How it works
 //!
 //! - All unhandled messages are first offered to Flutter’s **engine** (so that
@@ -41,12 +64,15 @@
 #![allow(dead_code)]
 
 use crate::{
+    WindowSizeConstraints,
     app_state::AppState,
     bindings::windows::{
-        HWND as RawHWND, LPARAM as RawLPARAM, LRESULT as RawLRESULT, UINT as RawUINT,
-        WPARAM as RawWPARAM,
+        FlutterDesktopEngineRef, HWND as RawHWND, LPARAM as RawLPARAM, LRESULT as RawLRESULT,
+        UINT as RawUINT, WPARAM as RawWPARAM,
     },
     constants,
+    dynamic_flutter_windows_dll_loader::FlutterDll,
+    plugin_loader,
 };
 use log::{debug, error, info, warn};
 use std::{
@@ -57,20 +83,23 @@ use std::{
 };
 use windows::{
     Win32::{
-        Foundation::{GetLastError, HWND, LPARAM, LRESULT, RECT, WPARAM},
+        Foundation::{GetLastError, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM},
         Graphics::Gdi::HBRUSH,
         System::Com::CoUninitialize,
         System::LibraryLoader::GetModuleHandleW,
         UI::WindowsAndMessaging::{
             CREATESTRUCTW, CS_HREDRAW, CS_VREDRAW, CreateWindowExW, DefWindowProcW, DestroyWindow,
             DispatchMessageW, GWL_STYLE, GWLP_USERDATA, GetClientRect, GetMessageW,
-            GetWindowLongPtrW, HMENU, HTCAPTION, HTCLIENT, IDC_ARROW, LoadCursorW, MSG, MoveWindow,
-            PostMessageW, PostQuitMessage, RegisterClassW, SWP_ASYNCWINDOWPOS, SWP_FRAMECHANGED,
-            SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SendMessageW, SetParent,
-            SetWindowLongPtrW, SetWindowPos, TranslateMessage, WINDOW_EX_STYLE, WM_ACTIVATE,
-            WM_CLOSE, WM_DESTROY, WM_DPICHANGED, WM_KILLFOCUS, WM_NCACTIVATE, WM_NCCALCSIZE,
-            WM_NCCREATE, WM_NCHITTEST, WM_NCPAINT, WM_PAINT, WM_SETFOCUS, WM_SIZE, WNDCLASSW,
-            WS_CHILD, WS_CLIPCHILDREN, WS_OVERLAPPEDWINDOW, WS_POPUP, WS_VISIBLE,
+            GetWindowLongPtrW, GetWindowRect, HMENU, HTCAPTION, HTCLIENT, IDC_ARROW,
+            IsWindowVisible, KillTimer, LoadCursorW, MINMAXINFO, MSG, MoveWindow, PostMessageW,
+            PostQuitMessage, RegisterClassW, SW_SHOWNORMAL, SWP_ASYNCWINDOWPOS, SWP_FRAMECHANGED,
+            SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SendMessageW, SetForegroundWindow,
+            SetParent, SetTimer, SetWindowLongPtrW, SetWindowPos, ShowWindow, TranslateMessage,
+            WA_INACTIVE, WINDOW_EX_STYLE, WM_ACTIVATE, WM_APP, WM_CLOSE, WM_DESTROY, WM_DPICHANGED,
+            WM_GETMINMAXINFO, WM_KEYFIRST, WM_KEYLAST, WM_KILLFOCUS, WM_NCACTIVATE, WM_NCCALCSIZE,
+            WM_NCCREATE, WM_NCHITTEST, WM_NCPAINT, WM_PAINT, WM_SETFOCUS, WM_SETTINGCHANGE, WM_SIZE,
+            WM_THEMECHANGED, WM_TIMER, WNDCLASSW, WS_CHILD, WS_CLIPCHILDREN, WS_OVERLAPPEDWINDOW,
+            WS_POPUP, WS_VISIBLE,
         },
     },
     core::{Error, PCWSTR},
@@ -80,6 +109,19 @@ use windows::{
 const WM_NCUAHDRAWCAPTION: u32 = 0x00AE;
 const WM_NCUAHDRAWFRAME: u32 = 0x00AF;
 
+/// Posted by the plugin loader's worker thread once deferred plugins are
+/// loaded; `lparam` owns a `Box<plugin_loader::DeferredPlugins>`.
+pub const WM_PLUGINS_PREPARED: u32 = WM_APP + 1;
+
+/// Sent by `set_window_size_constraints`; `lparam` points to the new
+/// `WindowSizeConstraints`, valid for the duration of the call.
+const WM_SET_SIZE_CONSTRAINTS: u32 = WM_APP + 2;
+
+/// `WM_TIMER` id that shows a window waiting for its first frame if the engine
+/// never reports one.
+pub const FIRST_FRAME_SHOW_TIMER: usize = 1;
+const FIRST_FRAME_SHOW_TIMEOUT_MS: u32 = 2000;
+
 #[link(name = "user32")]
 unsafe extern "system" {
     /// Forward keyboard focus to a child HWND.
@@ -100,6 +142,15 @@ pub extern "system" fn wnd_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LP
     unsafe {
         let state_ptr = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as *mut AppState;
 
+        // Theme and accessibility broadcasts only reach top-level windows; the
+        // message still goes to the engine and view below.
+        if matches!(msg, WM_SETTINGCHANGE | WM_THEMECHANGED)
+            && let Some(state) = state_ptr.as_ref()
+            && state.forward_system_settings
+        {
+            SendMessageW(state.child_hwnd, msg, Some(wparam), Some(lparam));
+        }
+
         match msg {
             // 1) Non-client create: stash AppState
             WM_NCCREATE => {
@@ -191,7 +242,10 @@ pub extern "system" fn wnd_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LP
 
             // 4) Activation & focus → child
             WM_ACTIVATE | WM_SETFOCUS => {
-                if let Some(state) = state_ptr.as_mut() {
+                let deactivating = msg == WM_ACTIVATE && (wparam.0 & 0xFFFF) as u32 == WA_INACTIVE;
+                if let Some(state) = state_ptr.as_mut()
+                    && !deactivating
+                {
                     debug!("[WndProc] focus event: {msg}");
                     SetFocus(state.child_hwnd);
                 }
@@ -285,7 +339,76 @@ pub extern "system" fn wnd_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LP
             // 10) Paint
             WM_PAINT => DefWindowProcW(hwnd, msg, wparam, lparam),
 
-            // 11) All others → engine → view → default
+            // 11) Deferred plugins loaded → register on this (platform) thread
+            WM_PLUGINS_PREPARED => {
+                let payload = *Box::from_raw(lparam.0 as *mut plugin_loader::DeferredPlugins);
+                if let Some(state) = state_ptr.as_mut() {
+                    let dll = &state.dll;
+                    let engine = (dll.FlutterDesktopViewControllerGetEngine)(state.controller);
+                    plugin_loader::complete_deferred_registration(payload, engine, dll);
+                } else {
+                    warn!("[WndProc] deferred plugins arrived after AppState was released");
+                }
+                LRESULT(0)
+            }
+
+            // 12) First frame never arrived → show anyway
+            WM_TIMER if wparam.0 == FIRST_FRAME_SHOW_TIMER => {
+                warn!(
+                    "[WndProc] no first frame after {FIRST_FRAME_SHOW_TIMEOUT_MS} ms, showing window"
+                );
+                show_main_window(hwnd);
+                LRESULT(0)
+            }
+
+            // 13) Resize limits. This arrives before WM_NCCREATE, when there is
+            // no state yet and Windows' defaults apply.
+            WM_GETMINMAXINFO => {
+                let info = lparam.0 as *mut MINMAXINFO;
+                if let Some(state) = state_ptr.as_ref()
+                    && let Some(info) = info.as_mut()
+                {
+                    let constraints = state.size_constraints;
+                    info.ptMinTrackSize = POINT {
+                        x: constraints.min.0,
+                        y: constraints.min.1,
+                    };
+                    if let Some((max_w, max_h)) = constraints.max {
+                        info.ptMaxTrackSize = POINT { x: max_w, y: max_h };
+                    }
+                    return LRESULT(0);
+                }
+                DefWindowProcW(hwnd, msg, wparam, lparam)
+            }
+
+            // 14) New resize limits → store, then fit the current size
+            WM_SET_SIZE_CONSTRAINTS => {
+                let constraints = lparam.0 as *const WindowSizeConstraints;
+                let (Some(state), Some(constraints)) = (state_ptr.as_mut(), constraints.as_ref())
+                else {
+                    return LRESULT(0);
+                };
+                state.size_constraints = *constraints;
+                let mut rc = RECT::default();
+                if GetWindowRect(hwnd, &mut rc).is_ok() {
+                    let size = (rc.right - rc.left, rc.bottom - rc.top);
+                    let (width, height) = constraints.clamp(size);
+                    if (width, height) != size {
+                        let _ = SetWindowPos(
+                            hwnd,
+                            Some(HWND(null_mut())),
+                            0,
+                            0,
+                            width,
+                            height,
+                            SWP_NOMOVE | SWP_NOZORDER | SWP_NOACTIVATE,
+                        );
+                    }
+                }
+                LRESULT(1)
+            }
+
+            // 15) All others → engine → view → default
             other => {
                 if let Some(state) = state_ptr.as_mut() {
                     let dll = &state.dll;
@@ -439,12 +562,86 @@ pub fn set_flutter_window_as_child(parent: HWND, child: HWND) {
     }
 }
 
+/// Show and focus the main window. Idempotent, so the first-frame callback
+/// and its timeout fallback can both call it.
+pub fn show_main_window(hwnd: HWND) {
+    unsafe {
+        let _ = KillTimer(Some(hwnd), FIRST_FRAME_SHOW_TIMER);
+        if IsWindowVisible(hwnd).as_bool() {
+            return;
+        }
+        let _ = ShowWindow(hwnd, SW_SHOWNORMAL);
+        let _ = SetForegroundWindow(hwnd);
+    }
+    info!("[Win32 Utils] Main window shown");
+}
+
+unsafe extern "C" fn show_main_window_callback(user_data: *mut c_void) {
+    show_main_window(HWND(user_data));
+}
+
+/// Keep `parent` hidden until the engine has drawn its first frame, so the
+/// window never shows the unpainted (white) child. Falls back to showing it
+/// after `FIRST_FRAME_SHOW_TIMEOUT_MS`.
+///
+/// Returns `false` if this engine can't report frames; the caller should show
+/// the window itself.
+pub fn show_main_window_on_first_frame(
+    parent: HWND,
+    engine: FlutterDesktopEngineRef,
+    dll: &FlutterDll,
+) -> bool {
+    let Some(set_next_frame_callback) = dll.FlutterDesktopEngineSetNextFrameCallback.as_ref()
+    else {
+        return false;
+    };
+    unsafe {
+        set_next_frame_callback(engine, Some(show_main_window_callback), parent.0);
+        if SetTimer(Some(parent), FIRST_FRAME_SHOW_TIMER, FIRST_FRAME_SHOW_TIMEOUT_MS, None) == 0 {
+            warn!("[Win32 Utils] SetTimer failed: {:?}", Error::from_win32());
+        }
+    }
+    true
+}
+
+/// Window a queued message should be delivered to: keyboard messages the
+/// parent received because it had focus go to the Flutter child instead.
+fn keyboard_target(msg: u32, target: HWND, parent: HWND, child: HWND) -> HWND {
+    if (WM_KEYFIRST..=WM_KEYLAST).contains(&msg) && target == parent && !child.is_invalid() {
+        child
+    } else {
+        target
+    }
+}
+
+/// Hands `constraints` to the window's thread. Returns `false` if `hwnd`
+/// didn't take them, i.e. is not a window created by `create_main_window`.
+pub fn set_window_size_constraints(hwnd: HWND, constraints: WindowSizeConstraints) -> bool {
+    let result = unsafe {
+        SendMessageW(
+            hwnd,
+            WM_SET_SIZE_CONSTRAINTS,
+            Some(WPARAM(0)),
+            Some(LPARAM(&constraints as *const WindowSizeConstraints as isize)),
+        )
+    };
+    result.0 == 1
+}
+
 /// Run the Win32 message loop until `WM_QUIT`, then drop any leftover `AppState`.
 pub fn run_message_loop(parent: HWND, _app_state_ptr: *mut AppState) {
     info!("[Win32 Utils] Entering message loop");
+    let child = unsafe { (GetWindowLongPtrW(parent, GWLP_USERDATA) as *const AppState).as_ref() }
+        .map_or(HWND(null_mut()), |state| state.child_hwnd);
     let mut msg = MSG::default();
     unsafe {
         while GetMessageW(&mut msg, Some(HWND(null_mut())), 0, 0).as_bool() {
+            let target = keyboard_target(msg.message, msg.hwnd, parent, child);
+            if target != msg.hwnd {
+                debug!("[Win32 Utils] keyboard message {:#x} redirected to child", msg.message);
+                SetFocus(target);
+                msg.hwnd = target;
+            }
             let _ = TranslateMessage(&msg);
             DispatchMessageW(&msg);
         }